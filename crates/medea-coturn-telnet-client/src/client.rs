@@ -2,7 +2,7 @@
 //!
 //! [Coturn]: https://github.com/coturn/coturn
 
-use std::io;
+use std::{io, time::Duration};
 
 use bytes::Bytes;
 use derive_more::{Display, From};
@@ -11,8 +11,8 @@ use tokio::net::{TcpStream, ToSocketAddrs};
 use tokio_util::codec::Framed;
 
 use crate::proto::{
-    CoturnCliCodec, CoturnCliCodecError, CoturnCliRequest, CoturnCliResponse,
-    CoturnResponseParseError,
+    ConfigOption, CoturnCliCodec, CoturnCliCodecError, CoturnCliRequest,
+    CoturnCliResponse, CoturnResponseParseError, CoturnSession, CoturnUser,
 };
 
 /// Errors that can be returned by [`CoturnTelnetConnection`].
@@ -51,6 +51,15 @@ pub enum CoturnTelnetError {
     /// This is unrecoverable error.
     #[display(fmt = "Coturn server rejected provided password")]
     WrongPassword,
+
+    /// [Coturn] server is considered unavailable after consecutive connection
+    /// failures, so no connection was attempted.
+    ///
+    /// Next attempt will be made after the contained [`Duration`].
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[display(fmt = "Coturn server is unavailable, retry in {:?}", _0)]
+    CircuitOpen(Duration),
 }
 
 impl From<CoturnCliCodecError> for CoturnTelnetError {
@@ -90,7 +99,7 @@ impl CoturnTelnetConnection {
         Ok(this)
     }
 
-    /// Returns session IDs for [Coturn] server associated with the provided
+    /// Returns sessions of [Coturn] server associated with the provided
     /// `username`.
     ///
     /// 1. Sends [`CoturnCliRequest::PrintSessions`] with the provided
@@ -108,18 +117,108 @@ impl CoturnTelnetConnection {
     pub async fn print_sessions(
         &mut self,
         username: String,
-    ) -> Result<Vec<String>, CoturnTelnetError> {
-        use CoturnTelnetError::{Disconnected, UnexpectedMessage};
+    ) -> Result<Vec<CoturnSession>, CoturnTelnetError> {
+        match self
+            .request(CoturnCliRequest::PrintSessions(username))
+            .await?
+        {
+            CoturnCliResponse::Sessions(sessions) => Ok(sessions),
+            response => Err(CoturnTelnetError::UnexpectedMessage(response)),
+        }
+    }
 
-        self.0
-            .send(CoturnCliRequest::PrintSessions(username))
-            .await?;
+    /// Returns users of [Coturn] server having sessions at the moment.
+    ///
+    /// 1. Sends [`CoturnCliRequest::PrintUsers`].
+    /// 2. Awaits for [`CoturnCliResponse::Users`].
+    ///
+    /// # Errors
+    ///
+    /// - Unable to send message to remote server.
+    /// - Transport error while waiting for server response.
+    /// - Received an unexpected (not [`CoturnCliResponse::Users`]) response
+    ///   from remote server.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn print_users(
+        &mut self,
+    ) -> Result<Vec<CoturnUser>, CoturnTelnetError> {
+        match self.request(CoturnCliRequest::PrintUsers).await? {
+            CoturnCliResponse::Users(users) => Ok(users),
+            response => Err(CoturnTelnetError::UnexpectedMessage(response)),
+        }
+    }
 
-        let response: CoturnCliResponse =
-            self.0.next().await.ok_or(Disconnected)??;
-        match response {
-            CoturnCliResponse::Sessions(sessions) => Ok(sessions),
-            _ => Err(UnexpectedMessage(response)),
+    /// Returns options of [Coturn] server.
+    ///
+    /// 1. Sends [`CoturnCliRequest::PrintConfig`].
+    /// 2. Awaits for [`CoturnCliResponse::Config`].
+    ///
+    /// # Errors
+    ///
+    /// - Unable to send message to remote server.
+    /// - Transport error while waiting for server response.
+    /// - Received an unexpected (not [`CoturnCliResponse::Config`]) response
+    ///   from remote server.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn print_config(
+        &mut self,
+    ) -> Result<Vec<ConfigOption>, CoturnTelnetError> {
+        match self.request(CoturnCliRequest::PrintConfig).await? {
+            CoturnCliResponse::Config(options) => Ok(options),
+            response => Err(CoturnTelnetError::UnexpectedMessage(response)),
+        }
+    }
+
+    /// Adds alternate server (`ip[:port]`) to [Coturn] server, which clients
+    /// are redirected to.
+    ///
+    /// 1. Sends [`CoturnCliRequest::AddAlternateServer`].
+    /// 2. Awaits for [`CoturnCliResponse::Ready`].
+    ///
+    /// # Errors
+    ///
+    /// - Unable to send message to remote server.
+    /// - Transport error while waiting for server response.
+    /// - Received an unexpected (not [`CoturnCliResponse::Ready`]) response
+    ///   from remote server.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn add_alternate_server(
+        &mut self,
+        addr: String,
+    ) -> Result<(), CoturnTelnetError> {
+        match self
+            .request(CoturnCliRequest::AddAlternateServer(addr))
+            .await?
+        {
+            CoturnCliResponse::Ready => Ok(()),
+            response => Err(CoturnTelnetError::UnexpectedMessage(response)),
+        }
+    }
+
+    /// Deletes alternate server (`ip[:port]`) from [Coturn] server.
+    ///
+    /// 1. Sends [`CoturnCliRequest::DeleteAlternateServer`].
+    /// 2. Awaits for [`CoturnCliResponse::Ready`].
+    ///
+    /// # Errors
+    ///
+    /// - Unable to send message to remote server.
+    /// - Transport error while waiting for server response.
+    /// - Received an unexpected (not [`CoturnCliResponse::Ready`]) response
+    ///   from remote server.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn delete_alternate_server(
+        &mut self,
+        addr: String,
+    ) -> Result<(), CoturnTelnetError> {
+        let req = CoturnCliRequest::DeleteAlternateServer(addr);
+        match self.request(req).await? {
+            CoturnCliResponse::Ready => Ok(()),
+            response => Err(CoturnTelnetError::UnexpectedMessage(response)),
         }
     }
 
@@ -181,6 +280,25 @@ impl CoturnTelnetConnection {
         Ok(())
     }
 
+    /// Sends the provided [`CoturnCliRequest`] and awaits for the response on
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// - Unable to send message to remote server.
+    /// - Transport error while waiting for server response.
+    async fn request(
+        &mut self,
+        req: CoturnCliRequest,
+    ) -> Result<CoturnCliResponse, CoturnTelnetError> {
+        self.0.send(req).await?;
+        Ok(self
+            .0
+            .next()
+            .await
+            .ok_or(CoturnTelnetError::Disconnected)??)
+    }
+
     /// Authenticates [`CoturnTelnetConnection`].
     ///
     /// 1. Awaits for [`CoturnCliResponse::EnterPassword`].
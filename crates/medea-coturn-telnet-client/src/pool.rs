@@ -1,7 +1,8 @@
 //! [deadpool]-based simple async pool for [`CoturnTelnetConnection`]s.
 //!
-//! You shouldn't use [deadpool] directly, just use the [`Pool`] type provided
-//! by this crate instead.
+//! [`Pool`] tracks health of its connections and stops connecting to a
+//! [Coturn] server after consecutive failures (opens its circuit), failing
+//! fast until the next reconnection attempt, which is delayed exponentially.
 //!
 //! # Example
 //!
@@ -21,18 +22,24 @@
 //! }
 //! ```
 //!
+//! [Coturn]: https://github.com/coturn/coturn
 //! [deadpool]: https://crates.io/crates/deadpool
 
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
 use bytes::Bytes;
-use deadpool::managed;
+use deadpool::managed::{self, PoolConfig, TimeoutType};
 
 use crate::client::{CoturnTelnetConnection, CoturnTelnetError};
 
-/// Type alias for using [`deadpool::managed::Pool`] with
-/// [`CoturnTelnetConnection`].
-pub type Pool = managed::Pool<CoturnTelnetConnection, CoturnTelnetError>;
-
 /// Type alias for using [`deadpool::managed::PoolError`] with
 /// [`CoturnTelnetConnection`].
 pub type Error = managed::PoolError<CoturnTelnetError>;
@@ -46,6 +53,343 @@ pub type Connection =
 /// [`CoturnTelnetError`].
 type RecycleResult = managed::RecycleResult<CoturnTelnetError>;
 
+/// Settings of [`Pool`] reconnection backoff and circuit breaker.
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    /// Number of consecutive connection failures after which the circuit of
+    /// [`Pool`] opens.
+    pub failure_threshold: u32,
+
+    /// Delay before the first reconnection attempt once the circuit is open.
+    ///
+    /// Doubles on each next failed attempt.
+    pub min_delay: Duration,
+
+    /// Maximum delay between reconnection attempts.
+    pub max_delay: Duration,
+}
+
+impl Default for Backoff {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            min_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Returns delay before the next reconnection attempt after the provided
+    /// number of consecutive `failures`.
+    fn delay(&self, failures: u32) -> Duration {
+        let exp = failures.saturating_sub(self.failure_threshold).min(31);
+        self.min_delay
+            .checked_mul(1 << exp)
+            .map_or(self.max_delay, |d| d.min(self.max_delay))
+    }
+}
+
+/// State of [`Pool`] circuit breaker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    /// Connections are established as usual.
+    Closed,
+
+    /// Connections are not established, and requests fail fast with
+    /// [`CoturnTelnetError::CircuitOpen`].
+    Open {
+        /// Delay before the next reconnection attempt.
+        retry_in: Duration,
+    },
+}
+
+/// Health status of [`Pool`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Status {
+    /// Maximum number of connections in the [`Pool`].
+    pub max_size: usize,
+
+    /// Current number of connections in the [`Pool`].
+    pub size: usize,
+
+    /// Number of idle connections in the [`Pool`].
+    pub available: usize,
+
+    /// Number of requests waiting for a connection.
+    pub waiting: usize,
+
+    /// Total number of failed attempts to establish connection.
+    pub failed_connects: u64,
+
+    /// Total number of connections which failed to be recycled.
+    pub failed_recycles: u64,
+
+    /// Total number of connections rejected by [Coturn] server because of
+    /// wrong password.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub auth_failures: u64,
+
+    /// Current state of the circuit breaker.
+    pub circuit: CircuitState,
+}
+
+/// Health of connections shared between [`Pool`] and its [`Manager`].
+#[derive(Debug, Default)]
+struct Health {
+    /// Total number of failed attempts to establish connection.
+    failed_connects: AtomicU64,
+
+    /// Total number of connections which failed to be recycled.
+    failed_recycles: AtomicU64,
+
+    /// Total number of connections rejected because of wrong password.
+    auth_failures: AtomicU64,
+
+    /// State of the circuit breaker.
+    breaker: Mutex<Breaker>,
+}
+
+/// State of [`Pool`] circuit breaker.
+#[derive(Debug, Default)]
+struct Breaker {
+    /// Number of consecutive failures to get a connection.
+    failures: u32,
+
+    /// [`Instant`] until which the circuit is open.
+    open_until: Option<Instant>,
+}
+
+impl Health {
+    /// Returns the current [`CircuitState`].
+    fn circuit(&self) -> CircuitState {
+        let now = Instant::now();
+        match self.breaker.lock().unwrap().open_until {
+            Some(until) if until > now => CircuitState::Open {
+                retry_in: until - now,
+            },
+            _ => CircuitState::Closed,
+        }
+    }
+
+    /// Records successfully retrieved connection, closing the circuit.
+    fn record_success(&self) {
+        let mut breaker = self.breaker.lock().unwrap();
+        breaker.failures = 0;
+        breaker.open_until = None;
+    }
+
+    /// Records the provided [`Error`] of retrieving connection, opening the
+    /// circuit if there are too many consecutive failures.
+    fn record_failure(&self, err: &Error, backoff: &Backoff) {
+        match err {
+            Error::Timeout(TimeoutType::Wait)
+            | Error::Backend(CoturnTelnetError::CircuitOpen(_)) => return,
+            Error::Timeout(TimeoutType::Recycle) => {
+                let _ = self.failed_recycles.fetch_add(1, Ordering::Relaxed);
+            }
+            Error::Backend(CoturnTelnetError::WrongPassword) => {
+                let _ = self.auth_failures.fetch_add(1, Ordering::Relaxed);
+                let _ = self.failed_connects.fetch_add(1, Ordering::Relaxed);
+            }
+            Error::Timeout(TimeoutType::Create) | Error::Backend(_) => {
+                let _ = self.failed_connects.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut breaker = self.breaker.lock().unwrap();
+        breaker.failures = breaker.failures.saturating_add(1);
+        if breaker.failures >= backoff.failure_threshold {
+            breaker.open_until =
+                Some(Instant::now() + backoff.delay(breaker.failures));
+        }
+    }
+}
+
+/// Aggregated result of [`Pool::delete_sessions()`].
+#[derive(Debug, Default)]
+pub struct SessionsDeletion {
+    /// Number of deleted sessions.
+    pub deleted: usize,
+
+    /// Users whose sessions failed to be deleted, along with the occurred
+    /// errors.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl SessionsDeletion {
+    /// Indicates whether sessions of all the users were deleted successfully.
+    #[inline]
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Pool of [`CoturnTelnetConnection`]s with health tracking.
+#[derive(Clone)]
+pub struct Pool {
+    /// Underlying [deadpool] pool.
+    ///
+    /// [deadpool]: https://crates.io/crates/deadpool
+    inner: managed::Pool<CoturnTelnetConnection, CoturnTelnetError>,
+
+    /// Health of connections in this [`Pool`].
+    health: Arc<Health>,
+
+    /// Settings of reconnection backoff and circuit breaker.
+    backoff: Backoff,
+}
+
+impl Pool {
+    /// Creates new [`Pool`] of the provided `max_size` with the given
+    /// [`Manager`].
+    #[inline]
+    #[must_use]
+    pub fn new(manager: Manager, max_size: usize) -> Self {
+        Self::from_config(manager, PoolConfig::new(max_size))
+    }
+
+    /// Creates new [`Pool`] with the given [`Manager`] and [`PoolConfig`].
+    #[must_use]
+    pub fn from_config(manager: Manager, config: PoolConfig) -> Self {
+        let health = Arc::clone(&manager.health);
+        Self {
+            inner: managed::Pool::from_config(manager, config),
+            health,
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Sets [`Backoff`] settings of this [`Pool`].
+    #[inline]
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Retrieves connection from this [`Pool`], establishing a new one if
+    /// there are no idle connections.
+    ///
+    /// # Errors
+    ///
+    /// With [`CoturnTelnetError::CircuitOpen`] if the circuit of this [`Pool`]
+    /// is open.
+    ///
+    /// With [`Error::Timeout`] if the configured timeouts are exceeded.
+    ///
+    /// With [`Error::Backend`] if establishing connection fails.
+    pub async fn get(&self) -> Result<Connection, Error> {
+        if let CircuitState::Open { retry_in } = self.health.circuit() {
+            return Err(CoturnTelnetError::CircuitOpen(retry_in).into());
+        }
+        match self.inner.get().await {
+            Ok(conn) => {
+                self.health.record_success();
+                Ok(conn)
+            }
+            Err(e) => {
+                self.health.record_failure(&e, &self.backoff);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the current health [`Status`] of this [`Pool`].
+    #[must_use]
+    pub fn status(&self) -> Status {
+        let status = self.inner.status();
+        #[allow(clippy::cast_sign_loss)]
+        let (available, waiting) = if status.available < 0 {
+            (0, (-status.available) as usize)
+        } else {
+            (status.available as usize, 0)
+        };
+        Status {
+            max_size: status.max_size,
+            size: status.size,
+            available,
+            waiting,
+            failed_connects: self
+                .health
+                .failed_connects
+                .load(Ordering::Relaxed),
+            failed_recycles: self
+                .health
+                .failed_recycles
+                .load(Ordering::Relaxed),
+            auth_failures: self.health.auth_failures.load(Ordering::Relaxed),
+            circuit: self.health.circuit(),
+        }
+    }
+
+    /// Forcibly closes all sessions of the provided `users` on [Coturn]
+    /// server, reusing a single connection while it's healthy.
+    ///
+    /// Doesn't stop on failures, but reports them in the returned
+    /// [`SessionsDeletion`]. Once the circuit of this [`Pool`] opens, the rest
+    /// of `users` fail fast.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn delete_sessions<I, U>(&self, users: I) -> SessionsDeletion
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<String>,
+    {
+        let mut result = SessionsDeletion::default();
+        let mut conn: Option<Connection> = None;
+        for user in users {
+            let user = user.into();
+            if conn.is_none() {
+                match self.get().await {
+                    Ok(c) => conn = Some(c),
+                    Err(e) => {
+                        result.failed.push((user, e));
+                        continue;
+                    }
+                }
+            }
+            if let Some(c) = conn.as_mut() {
+                match Self::delete_user_sessions(c, user.clone()).await {
+                    Ok(deleted) => result.deleted += deleted,
+                    Err(e) => {
+                        result.failed.push((user, e.into()));
+                        // Connection will be recycled or replaced.
+                        conn = None;
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Forcibly closes all sessions of the provided `user`, returning the
+    /// number of closed sessions.
+    async fn delete_user_sessions(
+        conn: &mut CoturnTelnetConnection,
+        user: String,
+    ) -> Result<usize, CoturnTelnetError> {
+        let sessions = conn.print_sessions(user).await?;
+        let count = sessions.len();
+        conn.delete_sessions(sessions.into_iter().map(|s| s.id))
+            .await?;
+        Ok(count)
+    }
+}
+
+impl fmt::Debug for Pool {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("status", &self.status())
+            .field("backoff", &self.backoff)
+            .finish()
+    }
+}
+
 /// Manager for creating and recycling [`CoturnTelnetConnection`]s.
 #[derive(Debug)]
 pub struct Manager {
@@ -54,6 +398,9 @@ pub struct Manager {
 
     /// Password to authenticate connections with.
     pass: Bytes,
+
+    /// Health of connections, shared with [`Pool`].
+    health: Arc<Health>,
 }
 
 impl Manager {
@@ -67,6 +414,7 @@ impl Manager {
         Self {
             addr: (host.into(), port),
             pass: pass.into(),
+            health: Arc::default(),
         }
     }
 }
@@ -89,6 +437,62 @@ impl managed::Manager<CoturnTelnetConnection, CoturnTelnetError> for Manager {
         &self,
         conn: &mut CoturnTelnetConnection,
     ) -> RecycleResult {
-        Ok(conn.ping().await?)
+        conn.ping().await.map_err(|e| {
+            let _ = self.health.failed_recycles.fetch_add(1, Ordering::Relaxed);
+            e.into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially() {
+        let backoff = Backoff {
+            failure_threshold: 2,
+            min_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+        };
+
+        assert_eq!(backoff.delay(2), Duration::from_secs(1));
+        assert_eq!(backoff.delay(3), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(4));
+        assert_eq!(backoff.delay(6), Duration::from_secs(10));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn opens_circuit_after_consecutive_failures() {
+        // Nothing listens on this port, so connections are refused.
+        let pool = Pool::new(Manager::new("127.0.0.1", 1, "turn"), 4)
+            .with_backoff(Backoff {
+                failure_threshold: 2,
+                min_delay: Duration::from_secs(60),
+                max_delay: Duration::from_secs(60),
+            });
+
+        for _ in 0..2 {
+            assert!(matches!(
+                pool.get().await,
+                Err(Error::Backend(CoturnTelnetError::IoFailed(_))),
+            ));
+        }
+        assert!(matches!(
+            pool.get().await,
+            Err(Error::Backend(CoturnTelnetError::CircuitOpen(_))),
+        ));
+
+        let status = pool.status();
+        assert_eq!(status.failed_connects, 2);
+        assert_eq!(status.auth_failures, 0);
+        assert!(matches!(status.circuit, CircuitState::Open { .. }));
+
+        let deletion = pool.delete_sessions(vec!["a", "b"]).await;
+        assert_eq!(deletion.deleted, 0);
+        assert_eq!(deletion.failed.len(), 2);
+        assert!(!deletion.is_ok());
+        assert_eq!(pool.status().failed_connects, 2);
     }
 }
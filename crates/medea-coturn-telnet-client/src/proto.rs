@@ -6,7 +6,10 @@
 use std::{
     convert::TryFrom,
     io,
-    str::{from_utf8, Utf8Error},
+    net::SocketAddr,
+    num::ParseIntError,
+    str::{from_utf8, FromStr, Utf8Error},
+    time::Duration,
 };
 
 use bytes::{BufMut as _, Bytes, BytesMut};
@@ -36,10 +39,33 @@ static UNKNOWN_COMMAND: &str = "Unknown command\r\n\r\n";
 static IS_SESSIONS_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"Total sessions: \d"#).unwrap());
 
-/// Regular expression to extract session IDs from
-/// [`CoturnCliResponse::Sessions`].
-static EXTRACT_SESSIONS_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"\d\) id=(.*),").unwrap());
+/// Regular expression to extract session ID and username from the first line
+/// of a [`CoturnSession`] description.
+static SESSION_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\d+\) id=(.*), user <(.*)>:$").unwrap());
+
+/// Regular expression to check if message can be parsed as
+/// [`CoturnCliResponse::Users`].
+static IS_USERS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"Total users: \d"#).unwrap());
+
+/// Regular expression to extract usernames and their sessions count from
+/// [`CoturnCliResponse::Users`].
+static EXTRACT_USERS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*user: <(.*)>, (\d+) sessions?\s*$").unwrap()
+});
+
+/// Regular expression to check if message can be parsed as
+/// [`CoturnCliResponse::Config`].
+///
+/// Output of [`CoturnCliRequest::PrintConfig`] always starts with `verbose`
+/// option.
+static IS_CONFIG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*verbose: (ON|OFF)\s*$").unwrap());
+
+/// Regular expression to extract an option of [`CoturnCliResponse::Config`].
+static CONFIG_OPTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s+([^:]+): ?(.*?)( \(\*\))?$").unwrap());
 
 /// Message that is received from [Coturn] server via [Telnet].
 ///
@@ -65,9 +91,19 @@ pub enum CoturnCliResponse {
     Ready,
 
     /// Answer to [`CoturnCliRequest::PrintSessions`], which contains list of
-    /// session IDs associated with the provided username in
+    /// sessions associated with the provided username in
     /// [`CoturnCliRequest::PrintSessions`] message.
-    Sessions(Vec<String>),
+    Sessions(Vec<CoturnSession>),
+
+    /// Answer to [`CoturnCliRequest::PrintUsers`], which contains list of
+    /// users having sessions at the moment.
+    Users(Vec<CoturnUser>),
+
+    /// Answer to [`CoturnCliRequest::PrintConfig`], which contains options of
+    /// [Coturn] server.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    Config(Vec<ConfigOption>),
 
     /// [Coturn] server hasn't recognized last [Telnet] command.
     ///
@@ -96,6 +132,10 @@ pub enum CoturnResponseParseError {
     /// Failed to represent provided bytes as [`String`].
     #[display(fmt = "Cannot convert to String: {}", _0)]
     NonUtf8String(Utf8Error),
+
+    /// Failed to parse number contained in response.
+    #[display(fmt = "Bad number in response: {}", _0)]
+    BadNumber(ParseIntError),
 }
 
 impl TryFrom<BytesMut> for CoturnCliResponse {
@@ -124,21 +164,259 @@ impl TryFrom<BytesMut> for CoturnCliResponse {
         }
 
         if IS_SESSIONS_REGEX.is_match(msg) {
-            let mut ids: Vec<String> = Vec::new();
-            for mat in EXTRACT_SESSIONS_REGEX.captures_iter(msg) {
-                if let Some(id) = mat.get(1) {
-                    ids.push(id.as_str().to_owned());
+            return CoturnSession::parse_all(msg)
+                .map(CoturnCliResponse::Sessions)
+                .ok_or_else(|| BadResponseFormat(msg.to_owned()));
+        }
+
+        if IS_USERS_REGEX.is_match(msg) {
+            let mut users = Vec::new();
+            for mat in EXTRACT_USERS_REGEX.captures_iter(msg) {
+                users.push(CoturnUser {
+                    username: mat[1].to_owned(),
+                    sessions: mat[2].parse()?,
+                });
+            }
+            return Ok(CoturnCliResponse::Users(users));
+        }
+
+        let options: Option<Vec<_>> = msg
+            .lines()
+            .filter_map(|l| {
+                if l.trim().is_empty() {
+                    None
                 } else {
-                    return Err(BadResponseFormat(msg.to_owned()));
+                    Some(CONFIG_OPTION_REGEX.captures(l).map(|c| {
+                        ConfigOption {
+                            name: c[1].to_owned(),
+                            value: c[2].to_owned(),
+                            changeable: c.get(3).is_some(),
+                        }
+                    }))
                 }
-            }
-            return Ok(CoturnCliResponse::Sessions(ids));
+            })
+            .collect();
+        let is_config = msg
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .map_or(false, |l| IS_CONFIG_REGEX.is_match(l));
+        if is_config {
+            return options
+                .map(CoturnCliResponse::Config)
+                .ok_or_else(|| BadResponseFormat(msg.to_owned()));
+        }
+        if options.filter(|o| !o.is_empty()).is_some() {
+            // Looks like options, but is not a known `pc` output.
+            return Err(BadResponseFormat(msg.to_owned()));
         }
 
         Err(BadResponseType(msg.to_owned()))
     }
 }
 
+/// Session of [Coturn] server, as printed by
+/// [`CoturnCliRequest::PrintSessions`].
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CoturnSession {
+    /// ID of this [`CoturnSession`].
+    pub id: String,
+
+    /// Name of the user this [`CoturnSession`] belongs to.
+    pub username: String,
+
+    /// Realm of the user this [`CoturnSession`] belongs to.
+    pub realm: String,
+
+    /// Time passed since this [`CoturnSession`] has been started.
+    pub age: Duration,
+
+    /// Time left until this [`CoturnSession`] expires, unless refreshed.
+    pub lifetime: Duration,
+
+    /// Transport protocol between client and [Coturn] server (`UDP`, `TCP`,
+    /// `TLS`, `DTLS`).
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub client_protocol: String,
+
+    /// Transport protocol of the allocated relay (`UDP`, `TCP`).
+    pub relay_protocol: String,
+
+    /// Address of client.
+    pub client_addr: Option<SocketAddr>,
+
+    /// Address of [Coturn] server the client is connected to.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub server_addr: Option<SocketAddr>,
+
+    /// Allocated relay addresses (one for each address family).
+    pub relay_addrs: Vec<SocketAddr>,
+
+    /// Traffic counters of this [`CoturnSession`].
+    pub usage: SessionUsage,
+
+    /// Addresses of peers the client has created permissions for.
+    ///
+    /// Either IP addresses or socket addresses for channel bindings.
+    pub peers: Vec<String>,
+}
+
+/// Traffic counters of a [`CoturnSession`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SessionUsage {
+    /// Number of packets received from client.
+    pub received_packets: u64,
+
+    /// Number of bytes received from client.
+    pub received_bytes: u64,
+
+    /// Number of packets sent to client.
+    pub sent_packets: u64,
+
+    /// Number of bytes sent to client.
+    pub sent_bytes: u64,
+
+    /// Current rate of receiving bytes from client, in bytes per second.
+    pub receive_rate: u64,
+
+    /// Current rate of sending bytes to client, in bytes per second.
+    pub send_rate: u64,
+}
+
+impl CoturnSession {
+    /// Parses all [`CoturnSession`]s from the provided output of
+    /// [`CoturnCliRequest::PrintSessions`].
+    ///
+    /// Lines which are not recognized are skipped, as their set depends on
+    /// [Coturn] version and session's protocol.
+    ///
+    /// Returns [`None`] if any recognized line has invalid format.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    fn parse_all(msg: &str) -> Option<Vec<Self>> {
+        let mut sessions: Vec<Self> = Vec::new();
+        let mut in_peers = false;
+        for line in msg.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(header) = SESSION_HEADER_REGEX.captures(line) {
+                in_peers = false;
+                sessions.push(Self {
+                    id: header[1].to_owned(),
+                    username: header[2].to_owned(),
+                    ..Self::default()
+                });
+                continue;
+            }
+            let session = match sessions.last_mut() {
+                Some(session) => session,
+                None => continue,
+            };
+            if line.starts_with("Total sessions:") {
+                break;
+            } else if line == "peers:" {
+                in_peers = true;
+            } else if let Some(realm) = line.strip_prefix("realm: ") {
+                session.realm = realm.to_owned();
+            } else if let Some(age) = line.strip_prefix("started ") {
+                session.age = parse_secs(age.trim_end_matches(" secs ago"))?;
+            } else if let Some(lifetime) = line.strip_prefix("expiring in ") {
+                session.lifetime =
+                    parse_secs(lifetime.trim_end_matches(" secs"))?;
+            } else if let Some(protocols) =
+                line.strip_prefix("client protocol ")
+            {
+                let (client, relay) = split_pair(protocols, "relay protocol ")?;
+                session.client_protocol = client.to_owned();
+                session.relay_protocol = relay.to_owned();
+            } else if let Some(addrs) = line.strip_prefix("client addr ") {
+                let (client, server) = split_pair(addrs, "server addr ")?;
+                session.client_addr = Some(client.parse().ok()?);
+                session.server_addr = Some(server.parse().ok()?);
+            } else if let Some(addr) = line.strip_prefix("relay addr ") {
+                session.relay_addrs.push(addr.parse().ok()?);
+            } else if let Some(usage) = line.strip_prefix("usage: ") {
+                let c = parse_counters(usage, &["rp", "rb", "sp", "sb"])?;
+                session.usage.received_packets = c[0];
+                session.usage.received_bytes = c[1];
+                session.usage.sent_packets = c[2];
+                session.usage.sent_bytes = c[3];
+            } else if let Some(rate) = line.strip_prefix("rate: ") {
+                let rate = rate.trim_end_matches(" (bytes per sec)");
+                let c = parse_counters(rate, &["r", "s", "total"])?;
+                session.usage.receive_rate = c[0];
+                session.usage.send_rate = c[1];
+            } else if in_peers {
+                session.peers.push(line.to_owned());
+            }
+        }
+        Some(sessions)
+    }
+}
+
+/// User of [Coturn] server, as printed by [`CoturnCliRequest::PrintUsers`].
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoturnUser {
+    /// Name of this [`CoturnUser`].
+    pub username: String,
+
+    /// Number of sessions this [`CoturnUser`] has at the moment.
+    pub sessions: usize,
+}
+
+/// Option of [Coturn] server, as printed by [`CoturnCliRequest::PrintConfig`].
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigOption {
+    /// Name of this [`ConfigOption`].
+    ///
+    /// May be repeated for multi-value options (like listener addresses).
+    pub name: String,
+
+    /// Value of this [`ConfigOption`].
+    pub value: String,
+
+    /// Indicator whether this [`ConfigOption`] can be changed in runtime.
+    pub changeable: bool,
+}
+
+/// Splits `{first}, {second_prefix}{second}` into `first` and `second`.
+fn split_pair<'a>(
+    line: &'a str,
+    second_prefix: &str,
+) -> Option<(&'a str, &'a str)> {
+    let mut parts = line.splitn(2, ", ");
+    let first = parts.next()?;
+    let second = parts.next()?.strip_prefix(second_prefix)?;
+    Some((first, second))
+}
+
+/// Parses [`Duration`] from the provided number of seconds.
+fn parse_secs(secs: &str) -> Option<Duration> {
+    secs.parse().ok().map(Duration::from_secs)
+}
+
+/// Parses `{name}={value}` counters separated with `, ` having the provided
+/// `names` in the provided order.
+fn parse_counters(line: &str, names: &[&str]) -> Option<Vec<u64>> {
+    let counters: Vec<_> = line.split(", ").collect();
+    if counters.len() != names.len() {
+        return None;
+    }
+    counters
+        .into_iter()
+        .zip(names)
+        .map(|(counter, name)| {
+            let value = counter.strip_prefix(name)?.strip_prefix('=')?;
+            u64::from_str(value).ok()
+        })
+        .collect()
+}
+
 /// Messages that can be sent to [Coturn] server via [Telnet].
 ///
 /// [Coturn]: https://github.com/coturn/coturn
@@ -149,28 +427,52 @@ pub enum CoturnCliRequest {
     /// [`CoturnCliResponse::EnterPassword`] is received.
     Auth(Bytes),
 
-    /// Request to retrieve [Coturn] session IDs by username.
+    /// Request to retrieve [Coturn] sessions by username.
     ///
     /// [Coturn]: https://github.com/coturn/coturn
     PrintSessions(String),
 
+    /// Request to retrieve [Coturn] users having sessions at the moment.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    PrintUsers,
+
+    /// Request to retrieve [Coturn] server options.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    PrintConfig,
+
     /// Close [Coturn] session by its ID.
     ///
     /// [Coturn]: https://github.com/coturn/coturn
     CloseSession(String),
 
+    /// Add alternate server (`ip[:port]`), which clients are redirected to
+    /// with `300 Try Alternate` response.
+    AddAlternateServer(String),
+
+    /// Delete alternate server (`ip[:port]`).
+    DeleteAlternateServer(String),
+
     /// Ping request.
     Ping,
 }
 
 impl From<CoturnCliRequest> for Bytes {
     fn from(req: CoturnCliRequest) -> Self {
-        use CoturnCliRequest::{Auth, CloseSession, Ping, PrintSessions};
+        use CoturnCliRequest::{
+            AddAlternateServer, Auth, CloseSession, DeleteAlternateServer,
+            Ping, PrintConfig, PrintSessions, PrintUsers,
+        };
 
         match req {
             Auth(pass) => pass,
             PrintSessions(username) => format!("ps {}", username).into(),
+            PrintUsers => "pu".into(),
+            PrintConfig => "pc".into(),
             CloseSession(session_id) => format!("cs {}", session_id).into(),
+            AddAlternateServer(addr) => format!("aas {}", addr).into(),
+            DeleteAlternateServer(addr) => format!("das {}", addr).into(),
             Ping => "ping".into(),
         }
     }
@@ -344,7 +646,7 @@ mod spec {
         {
             CoturnCliResponse::Sessions(sessions) => {
                 assert_eq!(
-                    sessions,
+                    sessions.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
                     vec![
                         "010000000000000001",
                         "001000000000000002",
@@ -352,8 +654,158 @@ mod spec {
                         "011000000000000003",
                     ],
                 );
+                assert_eq!(
+                    sessions[0],
+                    CoturnSession {
+                        id: "010000000000000001".to_owned(),
+                        username: "777_Mireya".to_owned(),
+                        realm: "medea".to_owned(),
+                        age: Duration::from_secs(545),
+                        lifetime: Duration::from_secs(171),
+                        client_protocol: "TCP".to_owned(),
+                        relay_protocol: "UDP".to_owned(),
+                        client_addr: Some("[::1]:56278".parse().unwrap()),
+                        server_addr: Some("[::1]:3478".parse().unwrap()),
+                        relay_addrs: vec!["[::1]:58490".parse().unwrap()],
+                        usage: SessionUsage {
+                            received_packets: 878_759,
+                            received_bytes: 704_147_763,
+                            sent_packets: 878_425,
+                            sent_bytes: 705_869_096,
+                            receive_rate: 1_299_165,
+                            send_rate: 1_302_341,
+                        },
+                        peers: vec!["::1".to_owned(), "[::1]:65282".to_owned()],
+                    },
+                );
+                assert!(sessions[1].peers.is_empty());
+                assert_eq!(sessions[3].peers, vec!["::1"]);
             }
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn parses_users() {
+        let mut codec = CoturnCliCodec::default();
+        let mut message = "
+    user: <777_Mireya>, 4 sessions
+    user: <778_Nick>, 1 session
+
+  Total users: 2
+
+> "
+        .into();
+
+        let decoded = codec
+            .decode(&mut message)
+            .expect("Failed to decode")
+            .unwrap();
+        assert_eq!(
+            decoded,
+            CoturnCliResponse::Users(vec![
+                CoturnUser {
+                    username: "777_Mireya".to_owned(),
+                    sessions: 4,
+                },
+                CoturnUser {
+                    username: "778_Nick".to_owned(),
+                    sessions: 1,
+                },
+            ]),
+        );
+    }
+
+    #[tokio::test]
+    async fn parses_config() {
+        let mut codec = CoturnCliCodec::default();
+        let mut message = "
+  verbose: ON
+  listener addr: 127.0.0.1
+  listener addr: ::1
+  total-quota: 0 (*)
+  realm: medea
+
+> "
+        .into();
+
+        match codec
+            .decode(&mut message)
+            .expect("Failed to decode")
+            .unwrap()
+        {
+            CoturnCliResponse::Config(options) => {
+                let options: Vec<_> = options
+                    .iter()
+                    .map(|o| (o.name.as_str(), o.value.as_str(), o.changeable))
+                    .collect();
+                assert_eq!(
+                    options,
+                    vec![
+                        ("verbose", "ON", false),
+                        ("listener addr", "127.0.0.1", false),
+                        ("listener addr", "::1", false),
+                        ("total-quota", "0", true),
+                        ("realm", "medea", false),
+                    ],
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_options_output() {
+        let mut codec = CoturnCliCodec::default();
+        let mut message = "
+  some key: some value
+  realm: medea
+
+> "
+        .into();
+
+        match codec.decode(&mut message) {
+            Err(CoturnCliCodecError::BadResponse(
+                CoturnResponseParseError::BadResponseFormat(_),
+            )) => {}
+            res => panic!("Unexpected decoding result: {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_config() {
+        let mut codec = CoturnCliCodec::default();
+        let mut message = "
+  verbose: ON
+  not an option
+
+> "
+        .into();
+
+        match codec.decode(&mut message) {
+            Err(CoturnCliCodecError::BadResponse(
+                CoturnResponseParseError::BadResponseFormat(_),
+            )) => {}
+            res => panic!("Unexpected decoding result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn encodes_requests() {
+        use CoturnCliRequest as R;
+
+        for (req, expected) in vec![
+            (R::PrintSessions("user".into()), "ps user"),
+            (R::PrintUsers, "pu"),
+            (R::PrintConfig, "pc"),
+            (R::CloseSession("1".into()), "cs 1"),
+            (
+                R::AddAlternateServer("1.1.1.1:3478".into()),
+                "aas 1.1.1.1:3478",
+            ),
+            (R::DeleteAlternateServer("1.1.1.1".into()), "das 1.1.1.1"),
+        ] {
+            assert_eq!(Bytes::from(req), Bytes::from(expected));
+        }
+    }
 }
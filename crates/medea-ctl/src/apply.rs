@@ -0,0 +1,285 @@
+//! Planning of changes required to make an existing `Room` match its spec.
+
+use std::collections::{BTreeSet, HashMap};
+
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::spec::{Member, Room, RoomElement};
+
+/// Change to be applied to a `Room`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Create the whole `Room` from its spec.
+    CreateRoom,
+
+    /// Delete the whole `Room` and create it again from its spec.
+    RecreateRoom,
+
+    /// Delete `Member` with the provided ID.
+    DeleteMember(String),
+
+    /// Create `Member` with the provided ID from its spec.
+    CreateMember(String),
+}
+
+/// Result of comparing a `Room` spec with an actual `Room`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Plan {
+    /// `Room` can be brought to its spec with the provided [`Action`]s, which
+    /// should be applied in order.
+    Actions(Vec<Action>),
+
+    /// `Room` settings (not its `Member`s) differ from the spec, so the `Room`
+    /// should be re-created, which drops all its connections.
+    RoomSettingsChanged,
+}
+
+/// Plans [`Action`]s required to bring the `actual` `Room` to the provided
+/// spec.
+///
+/// `Member`s are compared by the fields set in their specs only, so values
+/// generated by a media server (like random credentials or default timeouts)
+/// don't cause re-creation. `Member`s playing media from a re-created
+/// `Member` are re-created too, as their endpoints are dropped along with the
+/// source.
+#[must_use]
+pub fn plan(spec: &Room, actual: Option<&proto::Room>) -> Plan {
+    let actual = match actual {
+        Some(actual) => actual,
+        None => return Plan::Actions(vec![Action::CreateRoom]),
+    };
+    if !room_settings_match(spec, actual) {
+        return Plan::RoomSettingsChanged;
+    }
+
+    let spec_members: HashMap<&str, &Member> = spec
+        .spec
+        .pipeline
+        .iter()
+        .map(|(id, RoomElement::Member(member))| (id.as_str(), member))
+        .collect();
+    let actual_members: HashMap<&str, &proto::Member> = actual
+        .pipeline
+        .iter()
+        .filter_map(|(id, el)| match el.el.as_ref()? {
+            proto::room::element::El::Member(member) => {
+                Some((id.as_str(), member))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let deleted: BTreeSet<&str> = actual_members
+        .keys()
+        .copied()
+        .filter(|id| !spec_members.contains_key(id))
+        .collect();
+    let mut changed: BTreeSet<&str> = spec_members
+        .iter()
+        .filter_map(|(id, member)| {
+            actual_members
+                .get(id)
+                .filter(|actual| !member_matches(id, member, actual))
+                .map(|_| *id)
+        })
+        .collect();
+    loop {
+        let dependents: Vec<&str> = spec_members
+            .iter()
+            .filter_map(|(id, member)| {
+                Some(*id).filter(|id| {
+                    actual_members.contains_key(id)
+                        && !changed.contains(id)
+                        && member.play_src_members().any(|src| {
+                            changed.contains(src) || deleted.contains(src)
+                        })
+                })
+            })
+            .collect();
+        if dependents.is_empty() {
+            break;
+        }
+        changed.extend(dependents);
+    }
+    let mut created: BTreeSet<&str> = spec_members
+        .keys()
+        .copied()
+        .filter(|id| !actual_members.contains_key(id))
+        .collect();
+    created.extend(changed.iter().copied());
+
+    let mut actions: Vec<_> = deleted
+        .iter()
+        .chain(changed.iter())
+        .map(|id| Action::DeleteMember((*id).to_owned()))
+        .collect();
+
+    // `Member`s are created only after the `Member`s they play media from.
+    while !created.is_empty() {
+        let ready: Vec<&str> = created
+            .iter()
+            .filter(|id| {
+                spec_members[*id]
+                    .play_src_members()
+                    .all(|src| src == **id || !created.contains(src))
+            })
+            .copied()
+            .collect();
+        // Cyclic dependencies are resolved by a media server itself.
+        let ready = if ready.is_empty() {
+            created.iter().copied().collect()
+        } else {
+            ready
+        };
+        for id in ready {
+            created.remove(id);
+            actions.push(Action::CreateMember(id.to_owned()));
+        }
+    }
+
+    Plan::Actions(actions)
+}
+
+/// Indicates whether settings of the `actual` `Room` match the ones set in its
+/// spec.
+fn room_settings_match(spec: &Room, actual: &proto::Room) -> bool {
+    let empty_timeout = spec.empty_timeout.map(Into::into);
+    let max_duration = spec.max_duration.map(Into::into);
+    (empty_timeout.is_none() || empty_timeout == actual.empty_timeout)
+        && (max_duration.is_none() || max_duration == actual.max_duration)
+        && spec.on_close.as_deref().unwrap_or_default() == actual.on_close
+        && spec
+            .max_members
+            .map_or(true, |max| max == actual.max_members)
+        && spec
+            .max_publishers
+            .map_or(true, |max| max == actual.max_publishers)
+}
+
+/// Indicates whether the `actual` `Member` matches the fields set in its spec.
+fn member_matches(id: &str, spec: &Member, actual: &proto::Member) -> bool {
+    let expected = spec.clone().into_proto(id.to_owned());
+
+    (expected.credentials.is_none()
+        || expected.credentials == actual.credentials)
+        && expected.on_join == actual.on_join
+        && expected.on_leave == actual.on_leave
+        && expected.on_metrics == actual.on_metrics
+        && (expected.idle_timeout.is_none()
+            || expected.idle_timeout == actual.idle_timeout)
+        && (expected.reconnect_timeout.is_none()
+            || expected.reconnect_timeout == actual.reconnect_timeout)
+        && (expected.ping_interval.is_none()
+            || expected.ping_interval == actual.ping_interval)
+        && expected.remove_on_leave == actual.remove_on_leave
+        && expected.region == actual.region
+        && expected.external == actual.external
+        && expected.pipeline == actual.pipeline
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::Element;
+
+    use super::*;
+
+    /// Parses [`Room`] spec from the provided YAML.
+    fn room(yaml: &str) -> Room {
+        match Element::from_yaml(yaml).unwrap() {
+            Element::Room(room) => room,
+            _ => unreachable!(),
+        }
+    }
+
+    const ROOM: &str = r#"
+        kind: Room
+        id: test-call
+        spec:
+          pipeline:
+            caller:
+              kind: Member
+              credentials:
+                plain: test
+              spec:
+                pipeline:
+                  publish:
+                    kind: WebRtcPublishEndpoint
+                    spec:
+                      p2p: Always
+            responder:
+              kind: Member
+              credentials:
+                plain: test
+              spec:
+                pipeline:
+                  play:
+                    kind: WebRtcPlayEndpoint
+                    spec:
+                      src: "local://test-call/caller/publish"
+    "#;
+
+    #[test]
+    fn creates_missing_room() {
+        assert_eq!(
+            plan(&room(ROOM), None),
+            Plan::Actions(vec![Action::CreateRoom]),
+        );
+    }
+
+    #[test]
+    fn does_nothing_when_room_matches_spec() {
+        let spec = room(ROOM);
+        let mut actual = spec.clone().into_proto();
+        for el in actual.pipeline.values_mut() {
+            if let Some(proto::room::element::El::Member(member)) = &mut el.el {
+                member.idle_timeout =
+                    Some(std::time::Duration::from_secs(10).into());
+                member.connected = true;
+            }
+        }
+
+        assert_eq!(plan(&spec, Some(&actual)), Plan::Actions(Vec::new()));
+    }
+
+    #[test]
+    fn recreates_changed_members_with_dependents() {
+        let actual = room(ROOM).into_proto();
+        let spec = room(&ROOM.replace("p2p: Always", "p2p: Never"));
+
+        assert_eq!(
+            plan(&spec, Some(&actual)),
+            Plan::Actions(vec![
+                Action::DeleteMember("caller".to_owned()),
+                Action::DeleteMember("responder".to_owned()),
+                Action::CreateMember("caller".to_owned()),
+                Action::CreateMember("responder".to_owned()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn deletes_and_creates_members() {
+        let actual = room(ROOM).into_proto();
+        let mut spec = room(ROOM);
+        let caller = spec.spec.pipeline["caller"].clone();
+        spec.spec.pipeline.remove("responder");
+        spec.spec.pipeline.insert("watcher".to_owned(), caller);
+
+        assert_eq!(
+            plan(&spec, Some(&actual)),
+            Plan::Actions(vec![
+                Action::DeleteMember("responder".to_owned()),
+                Action::CreateMember("watcher".to_owned()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn detects_changed_room_settings() {
+        let actual = room(ROOM).into_proto();
+        let mut spec = room(ROOM);
+        spec.on_close = Some("grpc://127.0.0.1:9099".to_owned());
+
+        assert_eq!(plan(&spec, Some(&actual)), Plan::RoomSettingsChanged);
+    }
+}
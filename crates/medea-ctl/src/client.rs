@@ -0,0 +1,166 @@
+//! Thin wrapper around [`tonic`] client of [Medea]'s gRPC [Control API].
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::collections::HashMap;
+
+use derive_more::{Display, From};
+use medea_control_api_proto::grpc::api as proto;
+use proto::control_api_client::ControlApiClient;
+use tonic::transport::Channel;
+
+use crate::spec::SpecError;
+
+/// Code of [`proto::Error`] returned when the requested `Room` doesn't exist.
+pub const ROOM_NOT_FOUND: u32 = 1003;
+
+/// Errors which can happen while executing `medea-ctl` commands.
+#[derive(Debug, Display, From)]
+pub enum Error {
+    /// Connection to the [Control API] cannot be established.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Failed to connect to Control API: {}", _0)]
+    Transport(tonic::transport::Error),
+
+    /// gRPC request failed.
+    #[display(fmt = "gRPC request failed: {}", _0)]
+    Status(tonic::Status),
+
+    /// [Control API] responded with an error.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Control API error {}: {}", "_0.code", "_0.text")]
+    Api(proto::Error),
+
+    /// Spec cannot be loaded.
+    Spec(SpecError),
+
+    /// Invalid command usage.
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Usage(String),
+
+    /// Output cannot be serialized.
+    #[display(fmt = "Failed to serialize output: {}", _0)]
+    #[from(ignore)]
+    Output(String),
+}
+
+impl Error {
+    /// Indicates whether this [`Error`] is a [Control API] error with the
+    /// provided code.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[must_use]
+    pub fn is_api_error(&self, code: u32) -> bool {
+        matches!(self, Self::Api(e) if e.code == code)
+    }
+}
+
+/// Client of [Medea]'s gRPC [Control API].
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub struct ControlClient(ControlApiClient<Channel>);
+
+impl ControlClient {
+    /// Connects to the [Control API] at the provided address.
+    ///
+    /// # Errors
+    ///
+    /// Errors if connection cannot be established.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn connect(addr: String) -> Result<Self, Error> {
+        Ok(Self(ControlApiClient::connect(addr).await?))
+    }
+
+    /// Creates an element described by the provided [`proto::CreateRequest`].
+    ///
+    /// Returns `sid`s of the created `Member`s.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the request fails or [Control API] responds with an error.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn create(
+        &mut self,
+        req: proto::CreateRequest,
+    ) -> Result<HashMap<String, String>, Error> {
+        let resp = self.0.create(req).await?.into_inner();
+        match resp.error {
+            Some(e) => Err(Error::Api(e)),
+            None => Ok(resp.sid),
+        }
+    }
+
+    /// Deletes elements with the provided FIDs.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the request fails or [Control API] responds with an error.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn delete(&mut self, fids: Vec<String>) -> Result<(), Error> {
+        let resp = self
+            .0
+            .delete(proto::IdRequest { fid: fids })
+            .await?
+            .into_inner();
+        match resp.error {
+            Some(e) => Err(Error::Api(e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns elements with the provided FIDs.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the request fails or [Control API] responds with an error.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn get(
+        &mut self,
+        fids: Vec<String>,
+    ) -> Result<HashMap<String, proto::Element>, Error> {
+        let resp = self
+            .0
+            .get(proto::IdRequest { fid: fids })
+            .await?
+            .into_inner();
+        match resp.error {
+            Some(e) => Err(Error::Api(e)),
+            None => Ok(resp.elements),
+        }
+    }
+
+    /// Returns `Room` with the provided ID, or [`None`] if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the request fails or [Control API] responds with an error.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn get_room(
+        &mut self,
+        room_id: &str,
+    ) -> Result<Option<proto::Room>, Error> {
+        use proto::element::El;
+
+        match self.get(vec![room_id.to_owned()]).await {
+            Ok(mut elements) => Ok(elements
+                .remove(room_id)
+                .and_then(|el| el.el)
+                .and_then(|el| match el {
+                    El::Room(room) => Some(room),
+                    _ => None,
+                })),
+            Err(e) if e.is_api_error(ROOM_NOT_FOUND) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! Command-line client for [Medea]'s gRPC [Control API].
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+#![allow(clippy::module_name_repetitions)]
+
+mod apply;
+mod client;
+mod spec;
+mod tree;
+
+use std::process;
+
+use clap::{
+    app_from_crate, crate_authors, crate_description, crate_name,
+    crate_version, App, AppSettings, Arg, ArgMatches, SubCommand,
+};
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::{
+    apply::{Action, Plan},
+    client::{ControlClient, Error},
+    spec::{
+        Credentials, Element, Member, PlaySpec, PublishSpec, Room, RoomElement,
+        WebRtcPlayEndpoint, WebRtcPublishEndpoint,
+    },
+    tree::Node,
+};
+
+#[tokio::main]
+async fn main() {
+    let opts = cli().get_matches();
+    if let Err(e) = run(&opts).await {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Returns [`App`] describing `medea-ctl` CLI.
+fn cli() -> App<'static, 'static> {
+    app_from_crate!()
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("addr")
+                .help("Address of Medea's gRPC Control API.")
+                .default_value("http://127.0.0.1:6565")
+                .long("addr")
+                .short("a")
+                .global(true),
+        )
+        .subcommand(create_cmd())
+        .subcommand(
+            SubCommand::with_name("delete")
+                .about("Deletes elements.")
+                .arg(fids_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Prints elements.")
+                .arg(fids_arg())
+                .arg(
+                    Arg::with_name("output")
+                        .help("Output format.")
+                        .long("output")
+                        .short("o")
+                        .possible_values(&["tree", "yaml", "json"])
+                        .default_value("tree"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Prints connection status of Room's Members.")
+                .arg(Arg::with_name("room_id").required(true)),
+        )
+        .subcommand(apply_cmd())
+}
+
+/// Returns [`Arg`] of a spec file path.
+fn file_arg() -> Arg<'static, 'static> {
+    Arg::with_name("file")
+        .help("Path to YAML or JSON spec file.")
+        .long("file")
+        .short("f")
+        .takes_value(true)
+}
+
+/// Returns [`Arg`] of elements FIDs.
+fn fids_arg() -> Arg<'static, 'static> {
+    Arg::with_name("fid")
+        .help("FIDs of elements (e.g. `room`, `room/member`).")
+        .required(true)
+        .multiple(true)
+}
+
+/// Returns [`Arg`] of an endpoint FID.
+fn endpoint_fid_arg() -> Arg<'static, 'static> {
+    Arg::with_name("fid")
+        .help("FID in `room/member/endpoint` format.")
+        .required(true)
+}
+
+/// Returns [`Arg`] of forcibly relayed media of an endpoint.
+fn force_relay_arg() -> Arg<'static, 'static> {
+    Arg::with_name("force_relay")
+        .help("Relay all media through a TURN server forcibly.")
+        .long("force-relay")
+}
+
+/// Returns `create` [`SubCommand`].
+fn create_cmd() -> App<'static, 'static> {
+    SubCommand::with_name("create")
+        .about("Creates element from spec file or flags.")
+        .arg(file_arg())
+        .arg(
+            Arg::with_name("parent")
+                .help("FID of parent element for non-Room spec file.")
+                .long("parent")
+                .short("p")
+                .takes_value(true)
+                .requires("file"),
+        )
+        .subcommand(
+            SubCommand::with_name("room")
+                .about("Creates empty Room.")
+                .arg(Arg::with_name("fid").required(true)),
+        )
+        .subcommand(create_member_cmd())
+        .subcommand(
+            SubCommand::with_name("publish")
+                .about("Creates WebRtcPublishEndpoint.")
+                .arg(endpoint_fid_arg())
+                .arg(
+                    Arg::with_name("p2p")
+                        .help("P2P mode of endpoint.")
+                        .long("p2p")
+                        .possible_values(&["always", "never", "if-possible"])
+                        .default_value("always"),
+                )
+                .arg(force_relay_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("play")
+                .about("Creates WebRtcPlayEndpoint.")
+                .arg(endpoint_fid_arg())
+                .arg(
+                    Arg::with_name("src")
+                        .help(
+                            "Source URI in `local://room/member/endpoint` \
+                             format.",
+                        )
+                        .long("src")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(force_relay_arg()),
+        )
+}
+
+/// Returns `create member` [`SubCommand`].
+fn create_member_cmd() -> App<'static, 'static> {
+    SubCommand::with_name("member")
+        .about("Creates Member without endpoints.")
+        .arg(
+            Arg::with_name("fid")
+                .help("FID in `room/member` format.")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("credentials")
+                .help("Plain credentials of Member.")
+                .long("credentials")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("on_join")
+                .help("URL of OnJoin callback.")
+                .long("on-join")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("on_leave")
+                .help("URL of OnLeave callback.")
+                .long("on-leave")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("on_metrics")
+                .help("URL of metrics callbacks.")
+                .long("on-metrics")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remove_on_leave")
+                .help("Remove Member once it leaves Room.")
+                .long("remove-on-leave"),
+        )
+        .arg(
+            Arg::with_name("region")
+                .help("Region to select STUN/TURN servers by.")
+                .long("region")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("external")
+                .help("Don't reveal private ICE candidates of Member.")
+                .long("external"),
+        )
+}
+
+/// Returns `apply` [`SubCommand`].
+fn apply_cmd() -> App<'static, 'static> {
+    SubCommand::with_name("apply")
+        .about(
+            "Creates Room from spec file or brings existing Room to it by \
+             re-creating changed Members.",
+        )
+        .arg(file_arg().required(true))
+        .arg(
+            Arg::with_name("recreate")
+                .help("Re-create the whole Room if its settings changed.")
+                .long("recreate"),
+        )
+        .arg(
+            Arg::with_name("dry_run")
+                .help("Only print planned changes.")
+                .long("dry-run"),
+        )
+}
+
+/// Runs the command described by the provided [`ArgMatches`].
+async fn run(opts: &ArgMatches<'static>) -> Result<(), Error> {
+    let addr = opts.value_of("addr").unwrap().to_owned();
+    match opts.subcommand() {
+        ("create", Some(opts)) => create(addr, opts).await,
+        ("delete", Some(opts)) => {
+            let mut client = ControlClient::connect(addr).await?;
+            client.delete(values(opts, "fid")).await?;
+            println!("Deleted");
+            Ok(())
+        }
+        ("get", Some(opts)) => get(addr, opts).await,
+        ("status", Some(opts)) => status(addr, opts).await,
+        ("apply", Some(opts)) => apply(addr, opts).await,
+        _ => unreachable!("Subcommand is required by CLI settings"),
+    }
+}
+
+/// Creates an element from a spec file or from CLI flags.
+async fn create(addr: String, opts: &ArgMatches<'_>) -> Result<(), Error> {
+    let req = if let Some(path) = opts.value_of("file") {
+        let parent = opts.value_of("parent").map(ToOwned::to_owned);
+        spec::load_from_file(path)?.into_create_request(parent)?
+    } else {
+        let (element, parent) = match opts.subcommand() {
+            ("room", Some(opts)) => (
+                Element::Room(Room {
+                    id: opts.value_of("fid").unwrap().to_owned(),
+                    ..Room::default()
+                }),
+                None,
+            ),
+            ("member", Some(opts)) => {
+                let (parent, id) = split_fid(opts.value_of("fid").unwrap())?;
+                let member = Member {
+                    id: Some(id),
+                    credentials: opts
+                        .value_of("credentials")
+                        .map(|c| Credentials::Plain(c.to_owned())),
+                    on_join: opts.value_of("on_join").map(ToOwned::to_owned),
+                    on_leave: opts.value_of("on_leave").map(ToOwned::to_owned),
+                    on_metrics: opts
+                        .value_of("on_metrics")
+                        .map(ToOwned::to_owned),
+                    remove_on_leave: opts.is_present("remove_on_leave"),
+                    region: opts.value_of("region").map(ToOwned::to_owned),
+                    external: opts.is_present("external"),
+                    ..Member::default()
+                };
+                (Element::Member(member), Some(parent))
+            }
+            ("publish", Some(opts)) => {
+                use spec::P2pMode;
+
+                let (parent, id) = split_fid(opts.value_of("fid").unwrap())?;
+                let p2p = match opts.value_of("p2p").unwrap() {
+                    "never" => P2pMode::Never,
+                    "if-possible" => P2pMode::IfPossible,
+                    _ => P2pMode::Always,
+                };
+                let publish = WebRtcPublishEndpoint {
+                    id: Some(id),
+                    spec: PublishSpec {
+                        p2p,
+                        force_relay: opts.is_present("force_relay"),
+                        audio_settings: spec::MediaSettings::default(),
+                        video_settings: spec::MediaSettings::default(),
+                    },
+                };
+                (Element::WebRtcPublishEndpoint(publish), Some(parent))
+            }
+            ("play", Some(opts)) => {
+                let (parent, id) = split_fid(opts.value_of("fid").unwrap())?;
+                let play = WebRtcPlayEndpoint {
+                    id: Some(id),
+                    spec: PlaySpec {
+                        src: opts.value_of("src").unwrap().to_owned(),
+                        force_relay: opts.is_present("force_relay"),
+                    },
+                };
+                (Element::WebRtcPlayEndpoint(play), Some(parent))
+            }
+            _ => {
+                return Err(Error::Usage(
+                    "Either --file or element kind subcommand is required"
+                        .to_owned(),
+                ))
+            }
+        };
+        element.into_create_request(parent)?
+    };
+
+    let mut client = ControlClient::connect(addr).await?;
+    let mut sids: Vec<_> = client.create(req).await?.into_iter().collect();
+    sids.sort();
+    println!("Created");
+    for (member_id, sid) in sids {
+        println!("{}: {}", member_id, sid);
+    }
+    Ok(())
+}
+
+/// Prints the requested elements in the requested format.
+async fn get(addr: String, opts: &ArgMatches<'_>) -> Result<(), Error> {
+    let fids = values(opts, "fid");
+    let mut client = ControlClient::connect(addr).await?;
+    let mut elements = client.get(fids.clone()).await?;
+
+    for fid in fids {
+        let element = match elements.remove(&fid) {
+            Some(element) => element,
+            None => continue,
+        };
+        match opts.value_of("output").unwrap() {
+            "yaml" => {
+                let yaml = serde_yaml::to_string(&Element::from(element))
+                    .map_err(|e| Error::Output(e.to_string()))?;
+                println!("{}", yaml);
+            }
+            "json" => {
+                let json =
+                    serde_json::to_string_pretty(&Element::from(element))
+                        .map_err(|e| Error::Output(e.to_string()))?;
+                println!("{}", json);
+            }
+            _ => print!("{}", Node::from(element)),
+        }
+    }
+    Ok(())
+}
+
+/// Prints connection status of all `Member`s in the requested `Room`.
+async fn status(addr: String, opts: &ArgMatches<'_>) -> Result<(), Error> {
+    let room_id = opts.value_of("room_id").unwrap();
+    let mut client = ControlClient::connect(addr).await?;
+    let room = client.get_room(room_id).await?.ok_or_else(|| {
+        Error::Usage(format!("Room '{}' doesn't exist", room_id))
+    })?;
+
+    let mut members: Vec<_> = room
+        .pipeline
+        .into_iter()
+        .filter_map(|(_, el)| match el.el? {
+            proto::room::element::El::Member(member) => Some(member),
+            _ => None,
+        })
+        .collect();
+    members.sort_by(|a, b| a.id.cmp(&b.id));
+    let width = members.iter().map(|m| m.id.len()).max().unwrap_or_default();
+    for member in members {
+        println!(
+            "{:width$}  {}",
+            member.id,
+            tree::connection_status(&member),
+            width = width,
+        );
+    }
+    Ok(())
+}
+
+/// Creates a `Room` from the spec file or brings the existing one to it.
+async fn apply(addr: String, opts: &ArgMatches<'_>) -> Result<(), Error> {
+    let room = match spec::load_from_file(opts.value_of("file").unwrap())? {
+        Element::Room(room) => room,
+        el => {
+            return Err(Error::Usage(format!(
+                "Only Room specs can be applied, but {} is provided",
+                el.kind(),
+            )))
+        }
+    };
+    let dry_run = opts.is_present("dry_run");
+
+    let mut client = ControlClient::connect(addr).await?;
+    let actual = client.get_room(&room.id).await?;
+    let actions = match apply::plan(&room, actual.as_ref()) {
+        Plan::Actions(actions) => actions,
+        Plan::RoomSettingsChanged if opts.is_present("recreate") => {
+            vec![Action::RecreateRoom]
+        }
+        Plan::RoomSettingsChanged => {
+            return Err(Error::Usage(format!(
+                "Settings of Room '{}' differ from spec, use --recreate to \
+                 re-create it dropping all its connections",
+                room.id,
+            )))
+        }
+    };
+    if actions.is_empty() {
+        println!("Room '{}' is up to date", room.id);
+        return Ok(());
+    }
+
+    for action in actions {
+        println!("{:?}", action);
+        if dry_run {
+            continue;
+        }
+        match action {
+            Action::CreateRoom => {
+                let req =
+                    Element::Room(room.clone()).into_create_request(None)?;
+                client.create(req).await?;
+            }
+            Action::RecreateRoom => {
+                client.delete(vec![room.id.clone()]).await?;
+                let req =
+                    Element::Room(room.clone()).into_create_request(None)?;
+                client.create(req).await?;
+            }
+            Action::DeleteMember(id) => {
+                client.delete(vec![format!("{}/{}", room.id, id)]).await?;
+            }
+            Action::CreateMember(id) => {
+                let RoomElement::Member(mut member) =
+                    room.spec.pipeline[&id].clone();
+                member.id = Some(id);
+                let req = Element::Member(member)
+                    .into_create_request(Some(room.id.clone()))?;
+                client.create(req).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits the provided FID into its parent FID and the last element ID.
+fn split_fid(fid: &str) -> Result<(String, String), Error> {
+    match fid.rfind('/') {
+        Some(i) if i > 0 && i + 1 < fid.len() => {
+            Ok((fid[..i].to_owned(), fid[i + 1..].to_owned()))
+        }
+        _ => Err(Error::Usage(format!("'{}' is not a nested FID", fid))),
+    }
+}
+
+/// Returns all values of the provided argument.
+fn values(opts: &ArgMatches<'_>, name: &str) -> Vec<String> {
+    opts.values_of(name)
+        .map(|vals| vals.map(ToOwned::to_owned).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,885 @@
+//! Specs of [Control API] elements in the same format as [Medea]'s static
+//! specs, along with their conversions to and from protobuf.
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryInto as _,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use derive_more::Display;
+use medea_control_api_proto::grpc::api as proto;
+use serde::{Deserialize, Serialize};
+
+/// Errors which can happen while loading a spec.
+#[derive(Debug, Display)]
+pub enum SpecError {
+    /// Spec file cannot be read.
+    #[display(fmt = "Failed to read '{}': {}", "_0.display()", _1)]
+    Io(PathBuf, std::io::Error),
+
+    /// Spec file has an extension which is neither YAML, JSON nor TOML.
+    #[display(
+        fmt = "Unsupported spec format of '{}', expected .yml, .yaml, .json \
+               or .toml",
+        "_0.display()"
+    )]
+    UnsupportedFormat(PathBuf),
+
+    /// Spec file cannot be parsed as YAML.
+    #[display(fmt = "Failed to parse YAML spec: {}", _0)]
+    Yaml(serde_yaml::Error),
+
+    /// Spec file cannot be parsed as JSON.
+    #[display(fmt = "Failed to parse JSON spec: {}", _0)]
+    Json(serde_json::Error),
+
+    /// Spec file cannot be parsed as TOML.
+    #[display(fmt = "Failed to parse TOML spec: {}", _0)]
+    Toml(toml::de::Error),
+
+    /// Spec of a root element has no `id` field.
+    #[display(fmt = "Spec of {} has no 'id' field", _0)]
+    MissingId(&'static str),
+
+    /// Spec of a non-`Room` element is created without a parent FID.
+    #[display(fmt = "Parent FID is required to create {}", _0)]
+    MissingParent(&'static str),
+}
+
+/// Loads [`Element`] from the provided YAML, JSON or TOML file.
+///
+/// Format is detected by the file extension.
+///
+/// # Errors
+///
+/// Errors if the file cannot be read or parsed.
+pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Element, SpecError> {
+    let path = path.as_ref();
+    let ext = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase);
+    let parse: fn(&str) -> Result<Element, SpecError> = match ext.as_deref() {
+        Some("yml") | Some("yaml") => Element::from_yaml,
+        Some("json") => Element::from_json,
+        Some("toml") => Element::from_toml,
+        _ => return Err(SpecError::UnsupportedFormat(path.to_path_buf())),
+    };
+    let content = fs::read_to_string(path)
+        .map_err(|e| SpecError::Io(path.to_path_buf(), e))?;
+    parse(&content)
+}
+
+/// Root element of a spec file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Element {
+    Room(Room),
+    Member(Member),
+    WebRtcPublishEndpoint(WebRtcPublishEndpoint),
+    WebRtcPlayEndpoint(WebRtcPlayEndpoint),
+}
+
+impl Element {
+    /// Parses [`Element`] from the provided YAML.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided string is not a valid YAML spec.
+    pub fn from_yaml(yaml: &str) -> Result<Self, SpecError> {
+        serde_yaml::from_str(yaml).map_err(SpecError::Yaml)
+    }
+
+    /// Parses [`Element`] from the provided JSON.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided string is not a valid JSON spec.
+    pub fn from_json(json: &str) -> Result<Self, SpecError> {
+        serde_json::from_str(json).map_err(SpecError::Json)
+    }
+
+    /// Parses [`Element`] from the provided TOML.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided string is not a valid TOML spec.
+    pub fn from_toml(toml: &str) -> Result<Self, SpecError> {
+        toml::from_str(toml).map_err(SpecError::Toml)
+    }
+
+    /// Returns human-readable kind of this [`Element`].
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Room(_) => "Room",
+            Self::Member(_) => "Member",
+            Self::WebRtcPublishEndpoint(_) => "WebRtcPublishEndpoint",
+            Self::WebRtcPlayEndpoint(_) => "WebRtcPlayEndpoint",
+        }
+    }
+
+    /// Converts this [`Element`] into [`proto::CreateRequest`].
+    ///
+    /// `Room` is always created at the root, so `parent` is ignored for it,
+    /// while it's required for all other elements.
+    ///
+    /// # Errors
+    ///
+    /// Errors if this [`Element`] has no `id` or no `parent` is provided for a
+    /// non-`Room` element.
+    pub fn into_create_request(
+        self,
+        parent: Option<String>,
+    ) -> Result<proto::CreateRequest, SpecError> {
+        use proto::create_request::El;
+
+        let kind = self.kind();
+        let missing_id = || SpecError::MissingId(kind);
+        let (parent_fid, el) = match self {
+            Self::Room(room) => (String::new(), El::Room(room.into_proto())),
+            Self::Member(mut member) => {
+                let id = member.id.take().ok_or_else(missing_id)?;
+                let parent = parent.ok_or(SpecError::MissingParent(kind))?;
+                (parent, El::Member(member.into_proto(id)))
+            }
+            Self::WebRtcPublishEndpoint(mut publish) => {
+                let id = publish.id.take().ok_or_else(missing_id)?;
+                let parent = parent.ok_or(SpecError::MissingParent(kind))?;
+                (parent, El::WebrtcPub(publish.spec.into_proto(id)))
+            }
+            Self::WebRtcPlayEndpoint(mut play) => {
+                let id = play.id.take().ok_or_else(missing_id)?;
+                let parent = parent.ok_or(SpecError::MissingParent(kind))?;
+                (parent, El::WebrtcPlay(play.spec.into_proto(id)))
+            }
+        };
+        Ok(proto::CreateRequest {
+            parent_fid,
+            el: Some(el),
+        })
+    }
+}
+
+impl From<proto::Element> for Element {
+    fn from(proto: proto::Element) -> Self {
+        use proto::element::El;
+
+        match proto.el {
+            Some(El::Room(room)) => Self::Room(room.into()),
+            Some(El::Member(member)) => {
+                let id = member.id.clone();
+                let mut member = Member::from(member);
+                member.id = Some(id);
+                Self::Member(member)
+            }
+            Some(El::WebrtcPub(publish)) => {
+                Self::WebRtcPublishEndpoint(WebRtcPublishEndpoint {
+                    id: Some(publish.id.clone()),
+                    spec: publish.into(),
+                })
+            }
+            Some(El::WebrtcPlay(play)) => {
+                Self::WebRtcPlayEndpoint(WebRtcPlayEndpoint {
+                    id: Some(play.id.clone()),
+                    spec: play.into(),
+                })
+            }
+            None => Self::Room(Room::default()),
+        }
+    }
+}
+
+/// Pipeline of a spec element.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Pipeline<T> {
+    /// Child elements of this [`Pipeline`] by their IDs.
+    #[serde(default = "BTreeMap::new")]
+    pub pipeline: BTreeMap<String, T>,
+}
+
+impl<T> Default for Pipeline<T> {
+    fn default() -> Self {
+        Self {
+            pipeline: BTreeMap::new(),
+        }
+    }
+}
+
+/// Spec of [Control API] `Room` element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Room {
+    /// ID of this `Room`.
+    pub id: String,
+
+    /// Pipeline of this `Room`.
+    #[serde(default)]
+    pub spec: Pipeline<RoomElement>,
+
+    /// Timeout of the `Room` staying without any connected `Member`s.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub empty_timeout: Option<Duration>,
+
+    /// Maximum duration of the `Room` existence.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_duration: Option<Duration>,
+
+    /// URL to which `OnRoomClosed` Control API callback will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_close: Option<String>,
+
+    /// Maximum number of `Member`s in the `Room`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_members: Option<u32>,
+
+    /// Maximum number of publishing `Member`s in the `Room`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_publishers: Option<u32>,
+}
+
+impl Room {
+    /// Converts this [`Room`] into [`proto::Room`].
+    #[must_use]
+    pub fn into_proto(self) -> proto::Room {
+        proto::Room {
+            id: self.id,
+            pipeline: self
+                .spec
+                .pipeline
+                .into_iter()
+                .map(|(id, RoomElement::Member(member))| {
+                    let member = member.into_proto(id.clone());
+                    let el = proto::room::element::El::Member(member);
+                    (id, proto::room::Element { el: Some(el) })
+                })
+                .collect(),
+            empty_timeout: self.empty_timeout.map(Into::into),
+            max_duration: self.max_duration.map(Into::into),
+            on_close: self.on_close.unwrap_or_default(),
+            max_members: self.max_members.unwrap_or_default(),
+            max_publishers: self.max_publishers.unwrap_or_default(),
+            rejected_ice_candidates: HashMap::new(),
+            negotiation_timeouts: HashMap::new(),
+            recreated_peers: 0,
+        }
+    }
+}
+
+impl From<proto::Room> for Room {
+    fn from(proto: proto::Room) -> Self {
+        use proto::room::element::El;
+
+        let pipeline = proto
+            .pipeline
+            .into_iter()
+            .filter_map(|(id, el)| match el.el? {
+                El::Member(member) => {
+                    Some((id, RoomElement::Member(member.into())))
+                }
+                El::WebrtcPlay(_) | El::WebrtcPub(_) => None,
+            })
+            .collect();
+        Self {
+            id: proto.id,
+            spec: Pipeline { pipeline },
+            empty_timeout: proto.empty_timeout.and_then(into_duration),
+            max_duration: proto.max_duration.and_then(into_duration),
+            on_close: non_empty(proto.on_close),
+            max_members: Some(proto.max_members).filter(|max| *max > 0),
+            max_publishers: Some(proto.max_publishers).filter(|max| *max > 0),
+        }
+    }
+}
+
+/// Element of [`Room`]'s [`Pipeline`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum RoomElement {
+    Member(Member),
+}
+
+/// Spec of [Control API] `Member` element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Member {
+    /// ID of this `Member`.
+    ///
+    /// Required only when this `Member` is a root element of a spec file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Pipeline of this `Member`.
+    #[serde(default)]
+    pub spec: Pipeline<MemberElement>,
+
+    /// Credentials of this `Member`.
+    ///
+    /// If [`None`], then random credentials will be generated by a media
+    /// server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<Credentials>,
+
+    /// URL to which `OnJoin` Control API callback will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_join: Option<String>,
+
+    /// URL to which `OnLeave` Control API callback will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_leave: Option<String>,
+
+    /// URL to which `OnCallRecord`, `OnNetworkCongestion` and
+    /// `OnPeerMetricsEvent` Control API callbacks will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_metrics: Option<String>,
+
+    /// Timeout of receiving heartbeat messages from this `Member`.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub idle_timeout: Option<Duration>,
+
+    /// Timeout of this `Member` reconnecting.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reconnect_timeout: Option<Duration>,
+
+    /// Interval of sending pings from a media server to this `Member`.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ping_interval: Option<Duration>,
+
+    /// Indicator whether this `Member` should be removed once it leaves its
+    /// `Room`.
+    #[serde(default)]
+    pub remove_on_leave: bool,
+
+    /// Region of this `Member`, used to select STUN/TURN servers for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// Indicator whether this `Member` is external, so its private host ICE
+    /// candidates are not revealed to other `Member`s.
+    #[serde(default)]
+    pub external: bool,
+}
+
+impl Member {
+    /// Converts this [`Member`] into [`proto::Member`] with the provided ID.
+    #[must_use]
+    pub fn into_proto(self, id: String) -> proto::Member {
+        use proto::member::element::El;
+
+        let pipeline = self
+            .spec
+            .pipeline
+            .into_iter()
+            .map(|(id, element)| {
+                let el = match element {
+                    MemberElement::WebRtcPublishEndpoint { spec } => {
+                        El::WebrtcPub(spec.into_proto(id.clone()))
+                    }
+                    MemberElement::WebRtcPlayEndpoint { spec } => {
+                        El::WebrtcPlay(spec.into_proto(id.clone()))
+                    }
+                };
+                (id, proto::member::Element { el: Some(el) })
+            })
+            .collect();
+        proto::Member {
+            id,
+            pipeline,
+            credentials: self.credentials.map(Into::into),
+            on_join: self.on_join.unwrap_or_default(),
+            on_leave: self.on_leave.unwrap_or_default(),
+            on_metrics: self.on_metrics.unwrap_or_default(),
+            idle_timeout: self.idle_timeout.map(Into::into),
+            reconnect_timeout: self.reconnect_timeout.map(Into::into),
+            ping_interval: self.ping_interval.map(Into::into),
+            remove_on_leave: self.remove_on_leave,
+            connected: false,
+            region: self.region.unwrap_or_default(),
+            external: self.external,
+            relay_usage: None,
+            peers_relay_usage: HashMap::new(),
+            relay_fallback_peers: Vec::new(),
+            peers_stats: HashMap::new(),
+        }
+    }
+
+    /// Returns IDs of `Member`s which this [`Member`] plays media from.
+    pub fn play_src_members(&self) -> impl Iterator<Item = &str> {
+        self.spec
+            .pipeline
+            .values()
+            .filter_map(|element| match element {
+                MemberElement::WebRtcPlayEndpoint { spec } => {
+                    spec.src.split('/').nth(3)
+                }
+                MemberElement::WebRtcPublishEndpoint { .. } => None,
+            })
+    }
+}
+
+impl From<proto::Member> for Member {
+    fn from(proto: proto::Member) -> Self {
+        use proto::member::element::El;
+
+        let pipeline = proto
+            .pipeline
+            .into_iter()
+            .filter_map(|(id, el)| {
+                let element = match el.el? {
+                    El::WebrtcPub(publish) => {
+                        MemberElement::WebRtcPublishEndpoint {
+                            spec: publish.into(),
+                        }
+                    }
+                    El::WebrtcPlay(play) => {
+                        MemberElement::WebRtcPlayEndpoint { spec: play.into() }
+                    }
+                };
+                Some((id, element))
+            })
+            .collect();
+        Self {
+            id: None,
+            spec: Pipeline { pipeline },
+            credentials: proto.credentials.map(Into::into),
+            on_join: non_empty(proto.on_join),
+            on_leave: non_empty(proto.on_leave),
+            on_metrics: non_empty(proto.on_metrics),
+            idle_timeout: proto.idle_timeout.and_then(into_duration),
+            reconnect_timeout: proto.reconnect_timeout.and_then(into_duration),
+            ping_interval: proto.ping_interval.and_then(into_duration),
+            remove_on_leave: proto.remove_on_leave,
+            region: non_empty(proto.region),
+            external: proto.external,
+        }
+    }
+}
+
+/// Credentials of a [`Member`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Credentials {
+    /// [Argon2] hash of credentials.
+    ///
+    /// [Argon2]: https://en.wikipedia.org/wiki/Argon2
+    Hash(String),
+
+    /// Plain text credentials.
+    Plain(String),
+}
+
+impl From<proto::member::Credentials> for Credentials {
+    fn from(proto: proto::member::Credentials) -> Self {
+        use proto::member::Credentials as C;
+
+        match proto {
+            C::Hash(hash) => Self::Hash(hash),
+            C::Plain(plain) => Self::Plain(plain),
+        }
+    }
+}
+
+impl Into<proto::member::Credentials> for Credentials {
+    fn into(self) -> proto::member::Credentials {
+        use proto::member::Credentials as C;
+
+        match self {
+            Self::Hash(hash) => C::Hash(hash),
+            Self::Plain(plain) => C::Plain(plain),
+        }
+    }
+}
+
+/// Element of [`Member`]'s [`Pipeline`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum MemberElement {
+    WebRtcPublishEndpoint { spec: PublishSpec },
+    WebRtcPlayEndpoint { spec: PlaySpec },
+}
+
+/// [Control API] `WebRtcPublishEndpoint` element being a root of a spec file.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WebRtcPublishEndpoint {
+    /// ID of this `WebRtcPublishEndpoint`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Spec of this `WebRtcPublishEndpoint`.
+    pub spec: PublishSpec,
+}
+
+/// [Control API] `WebRtcPlayEndpoint` element being a root of a spec file.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WebRtcPlayEndpoint {
+    /// ID of this `WebRtcPlayEndpoint`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Spec of this `WebRtcPlayEndpoint`.
+    pub spec: PlaySpec,
+}
+
+/// P2P mode of a `WebRtcPublishEndpoint`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum P2pMode {
+    Always,
+    Never,
+    IfPossible,
+}
+
+impl From<P2pMode> for proto::web_rtc_publish_endpoint::P2p {
+    fn from(mode: P2pMode) -> Self {
+        match mode {
+            P2pMode::Always => Self::Always,
+            P2pMode::Never => Self::Never,
+            P2pMode::IfPossible => Self::IfPossible,
+        }
+    }
+}
+
+impl From<proto::web_rtc_publish_endpoint::P2p> for P2pMode {
+    fn from(proto: proto::web_rtc_publish_endpoint::P2p) -> Self {
+        use proto::web_rtc_publish_endpoint::P2p;
+
+        match proto {
+            P2p::Always => Self::Always,
+            P2p::Never => Self::Never,
+            P2p::IfPossible => Self::IfPossible,
+        }
+    }
+}
+
+/// Publishing policy of a media type in a `WebRtcPublishEndpoint`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PublishPolicy {
+    Optional,
+    Required,
+    Disabled,
+}
+
+impl Default for PublishPolicy {
+    fn default() -> Self {
+        Self::Optional
+    }
+}
+
+impl From<PublishPolicy> for proto::web_rtc_publish_endpoint::PublishPolicy {
+    fn from(policy: PublishPolicy) -> Self {
+        match policy {
+            PublishPolicy::Optional => Self::Optional,
+            PublishPolicy::Required => Self::Required,
+            PublishPolicy::Disabled => Self::Disabled,
+        }
+    }
+}
+
+impl From<i32> for PublishPolicy {
+    fn from(proto: i32) -> Self {
+        use proto::web_rtc_publish_endpoint::PublishPolicy as P;
+
+        match P::from_i32(proto).unwrap_or_default() {
+            P::Optional => Self::Optional,
+            P::Required => Self::Required,
+            P::Disabled => Self::Disabled,
+        }
+    }
+}
+
+/// Settings of a media type in a `WebRtcPublishEndpoint`.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
+pub struct MediaSettings {
+    /// Publishing policy of the media type.
+    #[serde(default)]
+    pub publish_policy: PublishPolicy,
+}
+
+/// Spec of [Control API] `WebRtcPublishEndpoint` element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PublishSpec {
+    /// P2P mode of this `WebRtcPublishEndpoint`.
+    pub p2p: P2pMode,
+
+    /// Option to relay all media through a TURN server forcibly.
+    #[serde(default)]
+    pub force_relay: bool,
+
+    /// Settings of the audio media type.
+    #[serde(default)]
+    pub audio_settings: MediaSettings,
+
+    /// Settings of the video media type.
+    #[serde(default)]
+    pub video_settings: MediaSettings,
+}
+
+impl PublishSpec {
+    /// Converts this [`PublishSpec`] into [`proto::WebRtcPublishEndpoint`]
+    /// with the provided ID.
+    #[must_use]
+    pub fn into_proto(self, id: String) -> proto::WebRtcPublishEndpoint {
+        use proto::web_rtc_publish_endpoint::{
+            AudioSettings, P2p, PublishPolicy as P, VideoSettings,
+        };
+
+        proto::WebRtcPublishEndpoint {
+            id,
+            p2p: P2p::from(self.p2p) as i32,
+            on_start: String::new(),
+            on_stop: String::new(),
+            force_relay: self.force_relay,
+            audio_settings: Some(AudioSettings {
+                publish_policy: P::from(self.audio_settings.publish_policy)
+                    as i32,
+            }),
+            video_settings: Some(VideoSettings {
+                publish_policy: P::from(self.video_settings.publish_policy)
+                    as i32,
+            }),
+        }
+    }
+}
+
+impl From<proto::WebRtcPublishEndpoint> for PublishSpec {
+    fn from(proto: proto::WebRtcPublishEndpoint) -> Self {
+        use proto::web_rtc_publish_endpoint::P2p;
+
+        Self {
+            p2p: P2p::from_i32(proto.p2p).unwrap_or_default().into(),
+            force_relay: proto.force_relay,
+            audio_settings: MediaSettings {
+                publish_policy: proto
+                    .audio_settings
+                    .map(|s| s.publish_policy.into())
+                    .unwrap_or_default(),
+            },
+            video_settings: MediaSettings {
+                publish_policy: proto
+                    .video_settings
+                    .map(|s| s.publish_policy.into())
+                    .unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// Spec of [Control API] `WebRtcPlayEndpoint` element.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PlaySpec {
+    /// Source URI in `local://{room_id}/{member_id}/{endpoint_id}` format.
+    pub src: String,
+
+    /// Option to relay all media through a TURN server forcibly.
+    #[serde(default)]
+    pub force_relay: bool,
+}
+
+impl PlaySpec {
+    /// Converts this [`PlaySpec`] into [`proto::WebRtcPlayEndpoint`] with the
+    /// provided ID.
+    #[must_use]
+    pub fn into_proto(self, id: String) -> proto::WebRtcPlayEndpoint {
+        proto::WebRtcPlayEndpoint {
+            id,
+            src: self.src,
+            on_start: String::new(),
+            on_stop: String::new(),
+            force_relay: self.force_relay,
+        }
+    }
+}
+
+impl From<proto::WebRtcPlayEndpoint> for PlaySpec {
+    fn from(proto: proto::WebRtcPlayEndpoint) -> Self {
+        Self {
+            src: proto.src,
+            force_relay: proto.force_relay,
+        }
+    }
+}
+
+/// Converts the provided [`prost_types::Duration`] into [`Duration`], if it's
+/// not negative.
+fn into_duration(dur: prost_types::Duration) -> Option<Duration> {
+    dur.try_into().ok()
+}
+
+/// Returns [`None`] if the provided [`String`] is empty.
+fn non_empty(s: String) -> Option<String> {
+    Some(s).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOM_YAML: &str = r#"
+        kind: Room
+        id: test-call
+        empty_timeout: 1m
+        spec:
+          pipeline:
+            caller:
+              kind: Member
+              credentials:
+                plain: test
+              on_join: "grpc://127.0.0.1:9099"
+              spec:
+                pipeline:
+                  publish:
+                    kind: WebRtcPublishEndpoint
+                    spec:
+                      p2p: Always
+            responder:
+              kind: Member
+              spec:
+                pipeline:
+                  play:
+                    kind: WebRtcPlayEndpoint
+                    spec:
+                      src: "local://test-call/caller/publish"
+    "#;
+
+    #[test]
+    fn parses_room_spec() {
+        let room = match Element::from_yaml(ROOM_YAML).unwrap() {
+            Element::Room(room) => room,
+            _ => unreachable!(),
+        };
+        assert_eq!(room.id, "test-call");
+        assert_eq!(room.empty_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(room.spec.pipeline.len(), 2);
+
+        let RoomElement::Member(responder) = &room.spec.pipeline["responder"];
+        assert_eq!(
+            responder.play_src_members().collect::<Vec<_>>(),
+            ["caller"]
+        );
+    }
+
+    #[test]
+    fn json_and_yaml_specs_are_equal() {
+        let yaml = Element::from_yaml(ROOM_YAML).unwrap();
+        let json = serde_json::to_string(&yaml).unwrap();
+
+        assert_eq!(Element::from_json(&json).unwrap(), yaml);
+    }
+
+    #[test]
+    fn room_survives_proto_round_trip() {
+        let room = match Element::from_yaml(ROOM_YAML).unwrap() {
+            Element::Room(room) => room,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(Room::from(room.clone().into_proto()), room);
+    }
+
+    #[test]
+    fn static_specs_survive_round_trip() {
+        let specs_dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/specs");
+        let toml_dir = tempfile::tempdir().unwrap();
+        for entry in fs::read_dir(specs_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let element = load_from_file(&path).unwrap();
+
+            let yaml: serde_yaml::Value =
+                serde_yaml::from_str(&fs::read_to_string(&path).unwrap())
+                    .unwrap();
+            let toml_path = toml_dir
+                .path()
+                .join(path.file_name().unwrap())
+                .with_extension("toml");
+            fs::write(&toml_path, toml::to_string(&yaml).unwrap()).unwrap();
+            assert_eq!(load_from_file(&toml_path).unwrap(), element);
+
+            let json = serde_json::to_string(&element).unwrap();
+            assert_eq!(Element::from_json(&json).unwrap(), element);
+
+            let room = match element {
+                Element::Room(room) => room,
+                _ => unreachable!(),
+            };
+            assert_eq!(Room::from(room.clone().into_proto()), room);
+        }
+    }
+
+    #[test]
+    fn requires_parent_for_non_room_elements() {
+        let member = Element::from_yaml(
+            r#"
+            kind: Member
+            id: caller
+            credentials:
+              plain: test
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            member.clone().into_create_request(None),
+            Err(SpecError::MissingParent("Member")),
+        ));
+        let req = member
+            .into_create_request(Some("test-call".to_owned()))
+            .unwrap();
+        assert_eq!(req.parent_fid, "test-call");
+    }
+
+    #[test]
+    fn requires_id_for_non_room_elements() {
+        let play = Element::from_yaml(
+            r#"
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://test-call/caller/publish"
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            play.into_create_request(Some("test-call/responder".to_owned())),
+            Err(SpecError::MissingId("WebRtcPlayEndpoint")),
+        ));
+    }
+}
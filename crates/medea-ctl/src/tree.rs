@@ -0,0 +1,194 @@
+//! Rendering of [Control API] elements as a tree.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::fmt::{self, Write as _};
+
+use medea_control_api_proto::grpc::api as proto;
+
+/// Node of a rendered tree.
+#[derive(Debug)]
+pub struct Node {
+    /// Label of this [`Node`].
+    label: String,
+
+    /// Child [`Node`]s sorted by their labels.
+    children: Vec<Node>,
+}
+
+impl Node {
+    /// Creates a new [`Node`] with the provided label and children.
+    fn new(label: String, mut children: Vec<Node>) -> Self {
+        children.sort_by(|a, b| a.label.cmp(&b.label));
+        Self { label, children }
+    }
+
+    /// Writes children of this [`Node`] with the provided `prefix`.
+    fn fmt_children(&self, out: &mut String, prefix: &str) -> fmt::Result {
+        let last = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            let (branch, indent) = if i == last {
+                ("\u{2514}\u{2500}\u{2500} ", "    ")
+            } else {
+                ("\u{251c}\u{2500}\u{2500} ", "\u{2502}   ")
+            };
+            writeln!(out, "{}{}{}", prefix, branch, child.label)?;
+            child.fmt_children(out, &format!("{}{}", prefix, indent))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        writeln!(out, "{}", self.label)?;
+        self.fmt_children(&mut out, "")?;
+        f.write_str(&out)
+    }
+}
+
+impl From<proto::Element> for Node {
+    fn from(element: proto::Element) -> Self {
+        use proto::element::El;
+
+        match element.el {
+            Some(El::Room(room)) => room_node(room),
+            Some(El::Member(member)) => member_node(member),
+            Some(El::WebrtcPub(publish)) => publish_node(&publish),
+            Some(El::WebrtcPlay(play)) => play_node(&play),
+            None => Self::new("<empty>".to_owned(), Vec::new()),
+        }
+    }
+}
+
+/// Builds [`Node`] of the provided [`proto::Room`].
+fn room_node(room: proto::Room) -> Node {
+    use proto::room::element::El;
+
+    let members = room
+        .pipeline
+        .into_iter()
+        .filter_map(|(_, el)| match el.el? {
+            El::Member(member) => Some(member_node(member)),
+            El::WebrtcPlay(play) => Some(play_node(&play)),
+            El::WebrtcPub(publish) => Some(publish_node(&publish)),
+        })
+        .collect();
+    Node::new(format!("{} (Room)", room.id), members)
+}
+
+/// Builds [`Node`] of the provided [`proto::Member`] including its connection
+/// status.
+fn member_node(member: proto::Member) -> Node {
+    use proto::member::element::El;
+
+    let label =
+        format!("{} (Member) [{}]", member.id, connection_status(&member));
+    let endpoints = member
+        .pipeline
+        .into_iter()
+        .filter_map(|(_, el)| match el.el? {
+            El::WebrtcPlay(play) => Some(play_node(&play)),
+            El::WebrtcPub(publish) => Some(publish_node(&publish)),
+        })
+        .collect();
+    Node::new(label, endpoints)
+}
+
+/// Builds [`Node`] of the provided [`proto::WebRtcPublishEndpoint`].
+fn publish_node(publish: &proto::WebRtcPublishEndpoint) -> Node {
+    use proto::web_rtc_publish_endpoint::P2p;
+
+    let p2p = match P2p::from_i32(publish.p2p).unwrap_or_default() {
+        P2p::Always => "Always",
+        P2p::Never => "Never",
+        P2p::IfPossible => "IfPossible",
+    };
+    let mut label =
+        format!("{} (WebRtcPublishEndpoint) p2p={}", publish.id, p2p);
+    if publish.force_relay {
+        label.push_str(" force_relay");
+    }
+    Node::new(label, Vec::new())
+}
+
+/// Builds [`Node`] of the provided [`proto::WebRtcPlayEndpoint`].
+fn play_node(play: &proto::WebRtcPlayEndpoint) -> Node {
+    let mut label =
+        format!("{} (WebRtcPlayEndpoint) src={}", play.id, play.src);
+    if play.force_relay {
+        label.push_str(" force_relay");
+    }
+    Node::new(label, Vec::new())
+}
+
+/// Returns human-readable connection status of the provided [`proto::Member`].
+#[must_use]
+pub fn connection_status(member: &proto::Member) -> &'static str {
+    if member.connected {
+        "connected"
+    } else {
+        "disconnected"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::{Element, Room};
+
+    use super::*;
+
+    #[test]
+    fn renders_room_tree() {
+        let room: Room = match Element::from_yaml(
+            r#"
+            kind: Room
+            id: test-call
+            spec:
+              pipeline:
+                caller:
+                  kind: Member
+                  spec:
+                    pipeline:
+                      publish:
+                        kind: WebRtcPublishEndpoint
+                        spec:
+                          p2p: Always
+                responder:
+                  kind: Member
+                  spec:
+                    pipeline:
+                      play:
+                        kind: WebRtcPlayEndpoint
+                        spec:
+                          src: "local://test-call/caller/publish"
+                          force_relay: true
+            "#,
+        )
+        .unwrap()
+        {
+            Element::Room(room) => room,
+            _ => unreachable!(),
+        };
+        let mut room = room.into_proto();
+        if let Some(proto::room::element::El::Member(caller)) = room
+            .pipeline
+            .get_mut("caller")
+            .and_then(|el| el.el.as_mut())
+        {
+            caller.connected = true;
+        }
+        let tree = Node::from(proto::Element {
+            el: Some(proto::element::El::Room(room)),
+        });
+
+        assert_eq!(
+            tree.to_string(),
+            "test-call (Room)\n├── caller (Member) [connected]\n│   └── \
+             publish (WebRtcPublishEndpoint) p2p=Always\n└── responder \
+             (Member) [disconnected]\n    └── play (WebRtcPlayEndpoint) \
+             src=local://test-call/caller/publish force_relay\n",
+        );
+    }
+}
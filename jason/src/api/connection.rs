@@ -6,12 +6,14 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use medea_client_api_proto::{ConnectionQualityScore, MemberId, PeerId};
+use medea_client_api_proto::{
+    self as proto, ConnectionQualityScore, MemberId, PeerId, TrackQualityScore,
+};
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    media::track::remote,
-    utils::{Callback0, Callback1, HandlerDetachedError},
+    media::{track::remote, MediaKind},
+    utils::{Callback0, Callback1, Callback2, HandlerDetachedError},
 };
 
 /// Service which manages [`Connection`]s with the remote `Member`s.
@@ -101,6 +103,14 @@ struct InnerConnection {
     /// Current [`ConnectionQualityScore`] of this [`Connection`].
     quality_score: Cell<Option<ConnectionQualityScore>>,
 
+    /// Current worst [`ConnectionQualityScore`] of the audio
+    /// [`remote::Track`]s received via this [`Connection`].
+    audio_quality_score: Cell<Option<ConnectionQualityScore>>,
+
+    /// Current worst [`ConnectionQualityScore`] of the video
+    /// [`remote::Track`]s received via this [`Connection`].
+    video_quality_score: Cell<Option<ConnectionQualityScore>>,
+
     /// JS callback, that will be invoked when [`remote::Track`] is
     /// received.
     on_remote_track_added: Callback1<remote::Track>,
@@ -109,6 +119,10 @@ struct InnerConnection {
     /// be updated.
     on_quality_score_update: Callback1<u8>,
 
+    /// JS callback, that will be invoked when [`ConnectionQualityScore`] of
+    /// the received [`remote::Track`]s of some [`MediaKind`] will be updated.
+    on_track_quality_score_update: Callback2<String, u8>,
+
     /// JS callback, that will be invoked when this connection is closed.
     on_close: Callback0,
 }
@@ -144,6 +158,19 @@ impl ConnectionHandle {
         upgrade_or_detached!(self.0)
             .map(|inner| inner.on_quality_score_update.set_func(f))
     }
+
+    /// Sets callback, which will be invoked when quality score of the
+    /// received tracks of some [`MediaKind`] will be updated by server.
+    ///
+    /// Callback receives [`MediaKind`] as string (`audio` or `video`) and the
+    /// worst quality score of the received tracks of this [`MediaKind`].
+    pub fn on_track_quality_score_update(
+        &self,
+        f: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        upgrade_or_detached!(self.0)
+            .map(|inner| inner.on_track_quality_score_update.set_func(f))
+    }
 }
 
 /// Connection with a specific remote `Member`, that is used on Rust side.
@@ -157,7 +184,10 @@ impl Connection {
         Self(Rc::new(InnerConnection {
             remote_id,
             quality_score: Cell::default(),
+            audio_quality_score: Cell::default(),
+            video_quality_score: Cell::default(),
             on_quality_score_update: Callback1::default(),
+            on_track_quality_score_update: Callback2::default(),
             on_close: Callback0::default(),
             on_remote_track_added: Callback1::default(),
         }))
@@ -181,4 +211,37 @@ impl Connection {
             self.0.on_quality_score_update.call(score as u8);
         }
     }
+
+    /// Updates [`ConnectionQualityScore`]s of the [`remote::Track`]s received
+    /// via this [`Connection`].
+    ///
+    /// Invokes `on_track_quality_score_update` JS callback with the worst
+    /// [`ConnectionQualityScore`] of each [`MediaKind`], if it has changed.
+    pub fn update_tracks_quality_scores(&self, scores: &[TrackQualityScore]) {
+        for (kind, current) in &[
+            (MediaKind::Audio, &self.0.audio_quality_score),
+            (MediaKind::Video, &self.0.video_quality_score),
+        ] {
+            let worst = scores
+                .iter()
+                .filter_map(|score| {
+                    let score_kind = match score.kind {
+                        proto::TrackMediaKind::Audio => MediaKind::Audio,
+                        proto::TrackMediaKind::Video => MediaKind::Video,
+                    };
+                    Some(score.quality_score).filter(|_| score_kind == *kind)
+                })
+                .min_by_key(|score| *score as u8);
+            if let Some(worst) = worst {
+                if current.replace(Some(worst)).map(|s| s as u8)
+                    != Some(worst as u8)
+                {
+                    self.0.on_track_quality_score_update.call(
+                        Some(kind.as_str().to_owned()),
+                        Some(worst as u8),
+                    );
+                }
+            }
+        }
+    }
 }
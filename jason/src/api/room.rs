@@ -15,10 +15,10 @@ use futures::{
 };
 use js_sys::Promise;
 use medea_client_api_proto::{
-    self as proto, Command, ConnectionQualityScore, Event as RpcEvent,
-    EventHandler, IceCandidate, IceConnectionState, IceServer, MediaSourceKind,
-    MemberId, NegotiationRole, PeerConnectionState, PeerId, PeerMetrics, Track,
-    TrackId, TrackUpdate,
+    self as proto, Command, CongestionDirection, CongestionSeverity,
+    ConnectionQualityScore, Event as RpcEvent, EventHandler, IceCandidate,
+    IceConnectionState, IceServer, MediaSourceKind, MemberId, NegotiationRole,
+    PeerConnectionState, PeerId, PeerMetrics, Track, TrackId, TrackUpdate,
 };
 use tracerr::Traced;
 use wasm_bindgen::{prelude::*, JsValue};
@@ -41,8 +41,8 @@ use crate::{
         ConnectionInfoParseError, ReconnectHandle, RpcSession, SessionError,
     },
     utils::{
-        AsProtoState, Callback1, HandlerDetachedError, JasonError, JsCaused,
-        JsError,
+        AsProtoState, Callback1, Callback2, HandlerDetachedError, JasonError,
+        JsCaused, JsError,
     },
     JsMediaSourceKind,
 };
@@ -353,6 +353,21 @@ impl RoomHandle {
             .map(|inner| inner.on_connection_loss.set_func(f))
     }
 
+    /// Sets `on_network_congestion` callback, which will be invoked when
+    /// media server detects sustained congestion of the sent or received
+    /// media, or its ending.
+    ///
+    /// Callback receives direction of the congested media as string (`uplink`
+    /// or `downlink`) and congestion severity as string (`clear`, `moderate`
+    /// or `severe`), so app may react, e.g. by disabling video.
+    pub fn on_network_congestion(
+        &self,
+        f: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        upgrade_or_detached!(self.0)
+            .map(|inner| inner.on_network_congestion.set_func(f))
+    }
+
     /// Connects media server and enters [`Room`] with provided authorization
     /// `token`.
     ///
@@ -764,6 +779,10 @@ struct InnerRoom {
     /// Callback to be invoked when [`RpcSession`] loses connection.
     on_connection_loss: Callback1<ReconnectHandle>,
 
+    /// Callback to be invoked when media server detects sustained network
+    /// congestion, or its ending.
+    on_network_congestion: Callback2<String, String>,
+
     /// JS callback which will be called when this [`Room`] will be closed.
     on_close: Rc<Callback1<RoomCloseReason>>,
 
@@ -980,6 +999,7 @@ impl InnerRoom {
             recv_constraints,
             connections,
             on_connection_loss: Callback1::default(),
+            on_network_congestion: Callback2::default(),
             on_failed_local_media: Rc::new(Callback1::default()),
             on_local_track: Callback1::default(),
             on_close: Rc::new(Callback1::default()),
@@ -1426,6 +1446,25 @@ impl EventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Updates [`IceServer`]s and relay mode of the specified
+    /// [`peer::State`].
+    async fn on_ice_servers_updated(
+        &self,
+        peer_id: PeerId,
+        ice_servers: Vec<IceServer>,
+        force_relay: bool,
+    ) -> Self::Output {
+        let peer_state = self
+            .peers
+            .state()
+            .get(peer_id)
+            .ok_or_else(|| tracerr::new!(RoomError::NoSuchPeer(peer_id)))?;
+        peer_state.set_force_relay(force_relay);
+        peer_state.set_ice_servers(ice_servers);
+
+        Ok(())
+    }
+
     /// Applies specified SDP Answer to a specified [`PeerConnection`].
     async fn on_sdp_answer_made(
         &self,
@@ -1526,22 +1565,48 @@ impl EventHandler for InnerRoom {
         Ok(())
     }
 
-    /// Updates [`Connection`]'s [`ConnectionQualityScore`] by calling
-    /// [`Connection::update_quality_score()`][1].
+    /// Updates [`Connection`]'s [`ConnectionQualityScore`]s by calling
+    /// [`Connection::update_quality_score()`][1] and
+    /// [`Connection::update_tracks_quality_scores()`][2].
     ///
     /// [`Connection`]: crate::api::Connection
     /// [1]: crate::api::Connection::update_quality_score
+    /// [2]: crate::api::Connection::update_tracks_quality_scores
     async fn on_connection_quality_updated(
         &self,
         partner_member_id: MemberId,
         quality_score: ConnectionQualityScore,
+        tracks_quality_scores: Vec<proto::TrackQualityScore>,
     ) -> Self::Output {
         if let Some(conn) = self.connections.get(&partner_member_id) {
             conn.update_quality_score(quality_score);
+            conn.update_tracks_quality_scores(&tracks_quality_scores);
         }
         Ok(())
     }
 
+    /// Invokes `on_network_congestion` callback of this [`Room`] with the
+    /// provided [`CongestionDirection`] and [`CongestionSeverity`].
+    async fn on_network_congestion(
+        &self,
+        _: PeerId,
+        direction: CongestionDirection,
+        severity: CongestionSeverity,
+    ) -> Self::Output {
+        let direction = match direction {
+            CongestionDirection::Uplink => "uplink",
+            CongestionDirection::Downlink => "downlink",
+        };
+        let severity = match severity {
+            CongestionSeverity::Clear => "clear",
+            CongestionSeverity::Moderate => "moderate",
+            CongestionSeverity::Severe => "severe",
+        };
+        self.on_network_congestion
+            .call(Some(direction.to_owned()), Some(severity.to_owned()));
+        Ok(())
+    }
+
     #[inline]
     async fn on_room_joined(&self, _: MemberId) -> Self::Output {
         unreachable!("Room can't receive Event::RoomJoined")
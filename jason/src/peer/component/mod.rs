@@ -104,10 +104,10 @@ pub struct State {
 
     /// Indicator whether this [`Component`] should relay all media through a
     /// TURN server forcibly.
-    force_relay: bool,
+    force_relay: ObservableCell<bool>,
 
     /// List of [`IceServer`]s which this [`Component`] should use.
-    ice_servers: Vec<IceServer>,
+    ice_servers: ObservableCell<Vec<IceServer>>,
 
     /// Current [`NegotiationRole`] of this [`Component`].
     negotiation_role: ObservableCell<Option<NegotiationRole>>,
@@ -149,8 +149,8 @@ impl State {
             id,
             senders: TracksRepository::new(),
             receivers: TracksRepository::new(),
-            ice_servers,
-            force_relay,
+            ice_servers: ObservableCell::new(ice_servers),
+            force_relay: ObservableCell::new(force_relay),
             remote_sdp: ProgressableCell::new(None),
             local_sdp: LocalSdp::new(),
             negotiation_role: ObservableCell::new(negotiation_role),
@@ -172,15 +172,28 @@ impl State {
     /// Returns all [`IceServer`]s of this [`State`].
     #[inline]
     #[must_use]
-    pub fn ice_servers(&self) -> &Vec<IceServer> {
-        &self.ice_servers
+    pub fn ice_servers(&self) -> Vec<IceServer> {
+        self.ice_servers.get()
+    }
+
+    /// Sets [`IceServer`]s which this [`State`] should use from now on.
+    #[inline]
+    pub fn set_ice_servers(&self, ice_servers: Vec<IceServer>) {
+        self.ice_servers.set(ice_servers);
     }
 
     /// Indicates whether [`PeerConnection`] should be relayed forcibly.
     #[inline]
     #[must_use]
     pub fn force_relay(&self) -> bool {
-        self.force_relay
+        self.force_relay.get()
+    }
+
+    /// Sets indicator whether [`PeerConnection`] should be relayed forcibly
+    /// from now on.
+    #[inline]
+    pub fn set_force_relay(&self, force_relay: bool) {
+        self.force_relay.set(force_relay);
     }
 
     /// Inserts a new [`sender::State`] into this [`State`].
@@ -426,8 +439,8 @@ impl AsProtoState for State {
             senders: self.senders.as_proto(),
             receivers: self.receivers.as_proto(),
             ice_candidates: self.ice_candidates.as_proto(),
-            force_relay: self.force_relay,
-            ice_servers: self.ice_servers.clone(),
+            force_relay: self.force_relay.get(),
+            ice_servers: self.ice_servers.get(),
             negotiation_role: self.negotiation_role.get(),
             local_sdp: self.local_sdp.current(),
             remote_sdp: self.remote_sdp.get(),
@@ -480,6 +493,8 @@ impl SynchronizableState for State {
             self.local_sdp.approved_set(sdp_offer);
         }
         self.remote_sdp.set(state.remote_sdp);
+        self.force_relay.set(state.force_relay);
+        self.ice_servers.set(state.ice_servers);
         self.ice_candidates.apply(state.ice_candidates, send_cons);
         self.senders.apply(state.senders, send_cons);
         self.receivers.apply(state.receivers, send_cons);
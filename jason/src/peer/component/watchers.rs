@@ -3,7 +3,9 @@
 use std::rc::Rc;
 
 use futures::{future, StreamExt as _};
-use medea_client_api_proto::{IceCandidate, NegotiationRole, TrackId};
+use medea_client_api_proto::{
+    IceCandidate, IceServer, NegotiationRole, TrackId,
+};
 use medea_macro::watchers;
 use medea_reactive::Guarded;
 use tracerr::Traced;
@@ -41,6 +43,36 @@ impl Component {
         .map_err(tracerr::map_from_and_wrap!())
     }
 
+    /// Watcher for the [`State::ice_servers`] update.
+    ///
+    /// Calls [`PeerConnection::set_ice_servers()`] with the new
+    /// [`IceServer`]s.
+    #[inline]
+    #[watch(self.ice_servers.subscribe().skip(1))]
+    async fn ice_servers_changed(
+        peer: Rc<PeerConnection>,
+        state: Rc<State>,
+        ice_servers: Vec<IceServer>,
+    ) -> Result<(), Traced<PeerError>> {
+        peer.set_ice_servers(ice_servers, state.force_relay())
+            .map_err(tracerr::wrap!())
+    }
+
+    /// Watcher for the [`State::force_relay`] update.
+    ///
+    /// Calls [`PeerConnection::set_ice_servers()`] with the current
+    /// [`IceServer`]s and the new relay mode.
+    #[inline]
+    #[watch(self.force_relay.subscribe().skip(1))]
+    async fn force_relay_changed(
+        peer: Rc<PeerConnection>,
+        state: Rc<State>,
+        force_relay: bool,
+    ) -> Result<(), Traced<PeerError>> {
+        peer.set_ice_servers(state.ice_servers(), force_relay)
+            .map_err(tracerr::wrap!())
+    }
+
     /// Watcher for the [`State::remote_sdp`] update.
     ///
     /// Calls [`PeerConnection::set_remote_answer()`] with a new value if the
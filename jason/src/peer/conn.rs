@@ -5,8 +5,10 @@ use std::{
 };
 
 use derive_more::{Display, From};
+use js_sys::Function;
 use medea_client_api_proto::{IceServer, PeerConnectionState};
 use tracerr::Traced;
+use wasm_bindgen::{prelude::*, JsCast as _};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
     Event, RtcBundlePolicy, RtcConfiguration, RtcIceCandidateInit,
@@ -134,6 +136,12 @@ pub enum RTCPeerConnectionError {
     #[display(fmt = "Failed to set remote SDP description: {}", _0)]
     #[from(ignore)]
     SetRemoteDescriptionFailed(JsError),
+
+    /// Occurs if the configuration of the [`RtcPeerConnection`] cannot be
+    /// changed.
+    #[display(fmt = "Failed to set PeerConnection configuration: {}", _0)]
+    #[from(ignore)]
+    SetConfigurationFailed(JsError),
 }
 
 type Result<T> = std::result::Result<T, Traced<RTCPeerConnectionError>>;
@@ -209,15 +217,7 @@ impl RtcPeerConnection {
     where
         I: IntoIterator<Item = IceServer>,
     {
-        let mut peer_conf = RtcConfiguration::new();
-        let policy = if is_force_relayed {
-            RtcIceTransportPolicy::Relay
-        } else {
-            RtcIceTransportPolicy::All
-        };
-        peer_conf.bundle_policy(RtcBundlePolicy::MaxBundle);
-        peer_conf.ice_transport_policy(policy);
-        peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
+        let peer_conf = Self::configuration(ice_servers, is_force_relayed);
         let peer = SysRtcPeerConnection::new_with_configuration(&peer_conf)
             .map_err(Into::into)
             .map_err(RTCPeerConnectionError::PeerCreationError)
@@ -233,6 +233,70 @@ impl RtcPeerConnection {
         })
     }
 
+    /// Builds [RTCConfiguration][1] with the provided [`IceServer`]s.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcconfiguration
+    fn configuration<I>(
+        ice_servers: I,
+        is_force_relayed: bool,
+    ) -> RtcConfiguration
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        let mut peer_conf = RtcConfiguration::new();
+        let policy = if is_force_relayed {
+            RtcIceTransportPolicy::Relay
+        } else {
+            RtcIceTransportPolicy::All
+        };
+        peer_conf.bundle_policy(RtcBundlePolicy::MaxBundle);
+        peer_conf.ice_transport_policy(policy);
+        peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
+        peer_conf
+    }
+
+    /// Updates [`IceServer`]s used by the underlying [RTCPeerConnection][1],
+    /// so they're used on the next ICE restart.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RTCPeerConnectionError::SetConfigurationFailed`] if
+    /// [RTCPeerConnection.setConfiguration()][2] fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection-setconfiguration
+    pub fn set_ice_servers<I>(
+        &self,
+        ice_servers: I,
+        is_force_relayed: bool,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        // `RTCPeerConnection.setConfiguration()` is missing in `web-sys`.
+        let set_configuration =
+            get_property_by_name(&*self.peer, "setConfiguration", |v| {
+                v.dyn_into::<Function>().ok()
+            })
+            .ok_or_else(|| {
+                JsValue::from_str(
+                    "RTCPeerConnection.setConfiguration() is missing",
+                )
+            })
+            .map_err(Into::into)
+            .map_err(RTCPeerConnectionError::SetConfigurationFailed)
+            .map_err(tracerr::wrap!())?;
+        set_configuration
+            .call1(
+                &*self.peer,
+                &Self::configuration(ice_servers, is_force_relayed),
+            )
+            .map(drop)
+            .map_err(Into::into)
+            .map_err(RTCPeerConnectionError::SetConfigurationFailed)
+            .map_err(tracerr::wrap!())
+    }
+
     /// Returns [`RtcStats`] of this [`PeerConnection`].
     ///
     /// # Errors
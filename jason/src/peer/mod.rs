@@ -23,8 +23,9 @@ use std::{
 use derive_more::{Display, From};
 use futures::{channel::mpsc, future, StreamExt as _};
 use medea_client_api_proto::{
-    stats::StatId, Command, IceConnectionState, MediaSourceKind, MemberId,
-    PeerConnectionState, PeerId as Id, PeerId, TrackId, TrackPatchCommand,
+    stats::StatId, Command, IceConnectionState, IceServer, MediaSourceKind,
+    MemberId, PeerConnectionState, PeerId as Id, PeerId, TrackId,
+    TrackPatchCommand,
 };
 use medea_macro::dispatchable;
 use tracerr::Traced;
@@ -316,11 +317,8 @@ impl PeerConnection {
         recv_constraints: Rc<RecvConstraints>,
     ) -> Result<Rc<Self>> {
         let peer = Rc::new(
-            RtcPeerConnection::new(
-                state.ice_servers().clone(),
-                state.force_relay(),
-            )
-            .map_err(tracerr::map_from_and_wrap!())?,
+            RtcPeerConnection::new(state.ice_servers(), state.force_relay())
+                .map_err(tracerr::map_from_and_wrap!())?,
         );
         let (track_events_sender, mut track_events_rx) = mpsc::unbounded();
         let media_connections = Rc::new(MediaConnections::new(
@@ -538,6 +536,24 @@ impl PeerConnection {
         self.id
     }
 
+    /// Updates [`IceServer`]s used by the underlying [`RtcPeerConnection`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`PeerError::RtcPeerConnection`] if
+    /// [RTCPeerConnection.setConfiguration()][1] fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection-setconfiguration
+    pub fn set_ice_servers(
+        &self,
+        ice_servers: Vec<IceServer>,
+        is_force_relayed: bool,
+    ) -> Result<()> {
+        self.peer
+            .set_ice_servers(ice_servers, is_force_relayed)
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
     /// Handle `icecandidate` event from underlying peer emitting
     /// [`PeerEvent::IceCandidateDiscovered`] event into this peers
     /// `peer_events_sender`.
@@ -34,6 +34,11 @@ pub struct Member {
     #[serde(skip_serializing_if = "Option::is_none")]
     on_leave: Option<String>,
 
+    /// URL to which `OnCallRecord`, `OnNetworkCongestion` and
+    /// `OnPeerMetricsEvent` Control API callbacks will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on_metrics: Option<String>,
+
     /// Timeout of receiving heartbeat messages from the `Member` via Client
     /// API. Once reached, the `Member` is considered being idle.
     #[serde(default, with = "humantime_serde")]
@@ -47,6 +52,20 @@ pub struct Member {
     /// Interval of sending pings from Medea to the `Member` via Client API.
     #[serde(default, with = "humantime_serde")]
     ping_interval: Option<Duration>,
+
+    /// Indicator whether the `Member` should be removed automatically once it
+    /// leaves its `Room`.
+    #[serde(default)]
+    remove_on_leave: bool,
+
+    /// Region of the `Member`, used to select STUN/TURN servers for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+
+    /// Indicator whether the `Member` is external, so its private host ICE
+    /// candidates are not revealed to other `Member`s.
+    #[serde(default)]
+    external: bool,
 }
 
 impl Member {
@@ -65,9 +84,18 @@ impl Member {
             credentials: self.credentials.map(Into::into),
             on_join: self.on_join.unwrap_or_default(),
             on_leave: self.on_leave.unwrap_or_default(),
+            on_metrics: self.on_metrics.unwrap_or_default(),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
             ping_interval: self.ping_interval.map(Into::into),
+            remove_on_leave: self.remove_on_leave,
+            connected: false,
+            region: self.region.unwrap_or_default(),
+            external: self.external,
+            relay_usage: None,
+            peers_relay_usage: HashMap::new(),
+            relay_fallback_peers: Vec::new(),
+            peers_stats: HashMap::new(),
         }
     }
 
@@ -94,6 +122,7 @@ impl From<proto::Member> for Member {
             credentials: proto.credentials.map(Into::into),
             on_join: Some(proto.on_join).filter(|s| !s.is_empty()),
             on_leave: Some(proto.on_leave).filter(|s| !s.is_empty()),
+            on_metrics: Some(proto.on_metrics).filter(|s| !s.is_empty()),
             idle_timeout: proto.idle_timeout.map(|dur| dur.try_into().unwrap()),
             reconnect_timeout: proto
                 .reconnect_timeout
@@ -101,6 +130,9 @@ impl From<proto::Member> for Member {
             ping_interval: proto
                 .ping_interval
                 .map(|dur| dur.try_into().unwrap()),
+            remove_on_leave: proto.remove_on_leave,
+            region: Some(proto.region).filter(|s| !s.is_empty()),
+            external: proto.external,
         }
     }
 }
@@ -1,6 +1,6 @@
 //! `Room` element related methods and entities.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryInto as _, time::Duration};
 
 use medea_control_api_proto::grpc::api as proto;
 use serde::{Deserialize, Serialize};
@@ -18,6 +18,28 @@ pub struct Room {
 
     /// Pipeline of `Room`.
     pipeline: HashMap<String, RoomElement>,
+
+    /// Timeout of the `Room` staying without any connected `Member`s.
+    /// Once reached, the `Room` is removed automatically.
+    #[serde(default, with = "humantime_serde")]
+    empty_timeout: Option<Duration>,
+
+    /// Maximum duration of the `Room` existence.
+    /// Once reached, the `Room` is removed automatically.
+    #[serde(default, with = "humantime_serde")]
+    max_duration: Option<Duration>,
+
+    /// URL to which `OnRoomClosed` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on_close: Option<String>,
+
+    /// Maximum number of `Member`s in `Room`.
+    #[serde(default)]
+    max_members: u32,
+
+    /// Maximum number of publishing `Member`s in `Room`.
+    #[serde(default)]
+    max_publishers: u32,
 }
 
 impl Room {
@@ -31,6 +53,14 @@ impl Room {
                 .into_iter()
                 .map(|(id, member)| (id.clone(), member.into_proto(id)))
                 .collect(),
+            empty_timeout: self.empty_timeout.map(Into::into),
+            max_duration: self.max_duration.map(Into::into),
+            on_close: self.on_close.unwrap_or_default(),
+            max_members: self.max_members,
+            max_publishers: self.max_publishers,
+            rejected_ice_candidates: HashMap::new(),
+            negotiation_timeouts: HashMap::new(),
+            recreated_peers: 0,
         }
     }
 }
@@ -74,6 +104,13 @@ impl From<proto::Room> for Room {
                 .into_iter()
                 .map(|(id, member)| (id, member.into()))
                 .collect(),
+            empty_timeout: proto
+                .empty_timeout
+                .map(|dur| dur.try_into().unwrap()),
+            max_duration: proto.max_duration.map(|dur| dur.try_into().unwrap()),
+            on_close: Some(proto.on_close).filter(|s| !s.is_empty()),
+            max_members: proto.max_members,
+            max_publishers: proto.max_publishers,
         }
     }
 }
@@ -11,6 +11,11 @@ use serde::Serialize;
 pub enum CallbackEvent {
     OnJoin(join::OnJoin),
     OnLeave(leave::OnLeave),
+    OnRoomClosed(room_closed::OnRoomClosed),
+    OnMemberRemoved(member_removed::OnMemberRemoved),
+    OnCallRecord(call_record::OnCallRecord),
+    OnPeerMetricsEvent(peer_metrics_event::OnPeerMetricsEvent),
+    OnNetworkCongestion(network_congestion::OnNetworkCongestion),
 }
 
 impl From<proto::request::Event> for CallbackEvent {
@@ -22,6 +27,21 @@ impl From<proto::request::Event> for CallbackEvent {
             proto::request::Event::OnJoin(on_join) => {
                 Self::OnJoin(on_join.into())
             }
+            proto::request::Event::OnRoomClosed(on_room_closed) => {
+                Self::OnRoomClosed(on_room_closed.into())
+            }
+            proto::request::Event::OnMemberRemoved(on_member_removed) => {
+                Self::OnMemberRemoved(on_member_removed.into())
+            }
+            proto::request::Event::OnCallRecord(on_call_record) => {
+                Self::OnCallRecord(on_call_record.into())
+            }
+            proto::request::Event::OnPeerMetricsEvent(event) => {
+                Self::OnPeerMetricsEvent(event.into())
+            }
+            proto::request::Event::OnNetworkCongestion(event) => {
+                Self::OnNetworkCongestion(event.into())
+            }
         }
     }
 }
@@ -116,3 +136,256 @@ mod leave {
         }
     }
 }
+
+/// `on_close` `Room` callback's related entities and implementations.
+mod room_closed {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::Serialize;
+
+    /// `OnRoomClosed` callback of Control API.
+    #[derive(Clone, Serialize)]
+    pub struct OnRoomClosed {
+        /// Reason of why `Room` was closed.
+        reason: OnRoomClosedReason,
+    }
+
+    impl From<proto::OnRoomClosed> for OnRoomClosed {
+        fn from(proto: proto::OnRoomClosed) -> Self {
+            Self {
+                reason: proto::on_room_closed::Reason::from_i32(proto.reason)
+                    .unwrap_or_default()
+                    .into(),
+            }
+        }
+    }
+
+    /// Reason of why `Room` was closed.
+    #[derive(Clone, Serialize)]
+    pub enum OnRoomClosedReason {
+        /// `Room` stayed without connected `Member`s longer than its empty
+        /// timeout.
+        Empty,
+
+        /// `Room` has reached its maximum duration.
+        MaxDuration,
+    }
+
+    impl From<proto::on_room_closed::Reason> for OnRoomClosedReason {
+        fn from(proto: proto::on_room_closed::Reason) -> Self {
+            use proto::on_room_closed::Reason as R;
+
+            match proto {
+                R::Empty => Self::Empty,
+                R::MaxDuration => Self::MaxDuration,
+            }
+        }
+    }
+}
+
+/// `on_member_removed` callback's related entities and implementations.
+mod member_removed {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::Serialize;
+
+    /// `OnMemberRemoved` callback of Control API.
+    #[derive(Clone, Serialize)]
+    pub struct OnMemberRemoved;
+
+    impl From<proto::OnMemberRemoved> for OnMemberRemoved {
+        fn from(_: proto::OnMemberRemoved) -> Self {
+            Self
+        }
+    }
+}
+
+/// `on_call_record` callback's related entities and implementations.
+mod call_record {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::Serialize;
+
+    /// `OnCallRecord` callback of Control API.
+    #[derive(Clone, Serialize)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    pub enum OnCallRecord {
+        /// Session of a `Member`, which has left its `Room`.
+        Member(MemberRecord),
+
+        /// All sessions of a closed `Room`.
+        Room(RoomRecord),
+    }
+
+    impl From<proto::OnCallRecord> for OnCallRecord {
+        fn from(proto: proto::OnCallRecord) -> Self {
+            use proto::on_call_record::Record as R;
+
+            match proto.record.unwrap() {
+                R::Member(member) => Self::Member(member.into()),
+                R::Room(room) => Self::Room(room.into()),
+            }
+        }
+    }
+
+    /// Call detail record of all sessions of a `Room`.
+    #[derive(Clone, Serialize)]
+    pub struct RoomRecord {
+        /// Time of the `Room` starting.
+        started_at: String,
+
+        /// Time of the `Room` closing.
+        closed_at: String,
+
+        /// Sessions of the `Room`'s `Member`s.
+        members: Vec<MemberRecord>,
+    }
+
+    impl From<proto::RoomRecord> for RoomRecord {
+        fn from(proto: proto::RoomRecord) -> Self {
+            Self {
+                started_at: proto.started_at,
+                closed_at: proto.closed_at,
+                members: proto.members.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    /// Call detail record of a single session of a `Member`.
+    #[derive(Clone, Serialize)]
+    pub struct MemberRecord {
+        /// ID of the `Member`.
+        member_id: String,
+
+        /// Time of the `Member` joining its `Room`.
+        joined_at: String,
+
+        /// Time of the `Member` leaving its `Room`.
+        left_at: String,
+
+        /// IDs of the `Member`'s `Peer`s during the session.
+        peers: Vec<u64>,
+    }
+
+    impl From<proto::MemberRecord> for MemberRecord {
+        fn from(proto: proto::MemberRecord) -> Self {
+            Self {
+                member_id: proto.member_id,
+                joined_at: proto.joined_at,
+                left_at: proto.left_at,
+                peers: proto.peers.into_iter().map(|p| p.peer_id).collect(),
+            }
+        }
+    }
+}
+
+/// `on_peer_metrics_event` callback's related entities and implementations.
+mod peer_metrics_event {
+    use std::collections::HashMap;
+
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::Serialize;
+
+    /// `OnPeerMetricsEvent` callback of Control API.
+    #[derive(Clone, Serialize)]
+    pub struct OnPeerMetricsEvent {
+        /// ID of the `Peer` the event is about.
+        peer_id: u64,
+
+        /// Name of the event.
+        name: String,
+
+        /// Arbitrary data of the event.
+        data: HashMap<String, String>,
+    }
+
+    impl From<proto::OnPeerMetricsEvent> for OnPeerMetricsEvent {
+        fn from(proto: proto::OnPeerMetricsEvent) -> Self {
+            Self {
+                peer_id: proto.peer_id,
+                name: proto.name,
+                data: proto.data,
+            }
+        }
+    }
+}
+
+/// `on_network_congestion` callback's related entities and implementations.
+mod network_congestion {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::Serialize;
+
+    /// `OnNetworkCongestion` callback of Control API.
+    #[derive(Clone, Serialize)]
+    pub struct OnNetworkCongestion {
+        /// ID of the congested `Peer`.
+        peer_id: u64,
+
+        /// Direction of the congested media traffic.
+        direction: Direction,
+
+        /// Severity of the congestion.
+        severity: Severity,
+    }
+
+    impl From<proto::OnNetworkCongestion> for OnNetworkCongestion {
+        fn from(proto: proto::OnNetworkCongestion) -> Self {
+            Self {
+                peer_id: proto.peer_id,
+                direction: proto::on_network_congestion::Direction::from_i32(
+                    proto.direction,
+                )
+                .unwrap_or_default()
+                .into(),
+                severity: proto::on_network_congestion::Severity::from_i32(
+                    proto.severity,
+                )
+                .unwrap_or_default()
+                .into(),
+            }
+        }
+    }
+
+    /// Direction of the congested media traffic.
+    #[derive(Clone, Serialize)]
+    pub enum Direction {
+        /// Media traffic sent by the `Peer`.
+        Uplink,
+
+        /// Media traffic received by the `Peer`.
+        Downlink,
+    }
+
+    impl From<proto::on_network_congestion::Direction> for Direction {
+        fn from(proto: proto::on_network_congestion::Direction) -> Self {
+            use proto::on_network_congestion::Direction as D;
+
+            match proto {
+                D::Uplink => Self::Uplink,
+                D::Downlink => Self::Downlink,
+            }
+        }
+    }
+
+    /// Severity of the congestion.
+    #[derive(Clone, Serialize)]
+    pub enum Severity {
+        /// Congestion has ended.
+        Clear,
+
+        /// Media quality is noticeably degraded.
+        Moderate,
+
+        /// Media is hardly usable.
+        Severe,
+    }
+
+    impl From<proto::on_network_congestion::Severity> for Severity {
+        fn from(proto: proto::on_network_congestion::Severity) -> Self {
+            use proto::on_network_congestion::Severity as S;
+
+            match proto {
+                S::Clear => Self::Clear,
+                S::Moderate => Self::Moderate,
+                S::Severe => Self::Severe,
+            }
+        }
+    }
+}
@@ -57,8 +57,7 @@ pub struct MemberId(pub String);
     feature = "medea",
     derive(Deserialize, Debug, Hash, Eq, Default, PartialEq)
 )]
-#[cfg_attr(feature = "jason", derive(Serialize))]
-#[derive(Clone, Copy, Display)]
+#[derive(Clone, Copy, Display, Serialize)]
 pub struct PeerId(pub u32);
 
 /// ID of `MediaTrack`.
@@ -305,9 +304,7 @@ pub enum IceConnectionState {
 }
 
 /// Peer Connection's connection state.
-#[cfg_attr(feature = "medea", derive(Deserialize))]
-#[cfg_attr(feature = "jason", derive(Serialize))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum PeerConnectionState {
     /// At least one of the connection's ICE transports are in the
     /// [`IceConnectionState::New`] state, and none of them are in one
@@ -387,6 +384,10 @@ pub enum CloseReason {
 
     /// Client was evicted on the server side.
     Evicted,
+
+    /// Establishing of connection with a server was rejected on server side
+    /// because `Room` has reached its capacity limits.
+    RoomFull,
 }
 
 /// Description which is sent in [Close] WebSocket frame from Media Server
@@ -461,6 +462,22 @@ pub enum Event {
         negotiation_role: Option<NegotiationRole>,
     },
 
+    /// Media Server notifies Web Client about fresh credentials issued for
+    /// the `Peer` before the current ones expire, or about the `Peer` being
+    /// switched to relayed media, which should be applied to its
+    /// RTCPeerConnection.
+    IceServersUpdated {
+        /// [`PeerId`] of the `Peer` whose [`IceServer`]s are updated.
+        peer_id: PeerId,
+
+        /// [`IceServer`]s which should be used by the `Peer` from now on.
+        ice_servers: Vec<IceServer>,
+
+        /// Indicator whether all media of the `Peer` should be relayed
+        /// through TURN server from now on.
+        force_relay: bool,
+    },
+
     /// Media Server notifies about connection quality score update.
     ConnectionQualityUpdated {
         /// Partner [`MemberId`] of the `Peer`.
@@ -468,6 +485,24 @@ pub enum Event {
 
         /// Estimated connection quality.
         quality_score: ConnectionQualityScore,
+
+        /// Estimated quality of each media [`Track`] received from the
+        /// partner `Member`.
+        #[serde(default)]
+        tracks_quality_scores: Vec<TrackQualityScore>,
+    },
+
+    /// Media Server notifies about sustained network congestion detected on
+    /// uplink or downlink of the `Peer`, or about its ending.
+    NetworkCongestion {
+        /// [`PeerId`] of the `Peer` which media traffic is congested.
+        peer_id: PeerId,
+
+        /// Direction of the congested media traffic.
+        direction: CongestionDirection,
+
+        /// Severity of the congestion.
+        severity: CongestionSeverity,
     },
 
     /// Media Server synchronizes Web Client about State synchronization.
@@ -627,8 +662,8 @@ impl TrackPatchEvent {
 ///
 /// [1]: https://developer.mozilla.org/en-US/docs/Web/API/RTCIceServer
 /// [2]: https://developer.mozilla.org/en-US/docs/Web/API/RTCConfiguration
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "medea", derive(Serialize))]
 #[cfg_attr(feature = "jason", derive(Deserialize))]
 pub struct IceServer {
     pub urls: Vec<String>,
@@ -655,9 +690,8 @@ pub enum Direction {
 }
 
 /// Type of [`Track`].
-#[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
-#[cfg_attr(feature = "jason", derive(Deserialize))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "medea", derive(Eq, PartialEq))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum MediaType {
     Audio(AudioSettings),
     Video(VideoSettings),
@@ -674,9 +708,8 @@ impl MediaType {
     }
 }
 
-#[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
-#[cfg_attr(feature = "jason", derive(Deserialize))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "medea", derive(Eq, PartialEq))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AudioSettings {
     /// Importance of the audio media type.
     ///
@@ -684,9 +717,8 @@ pub struct AudioSettings {
     pub required: bool,
 }
 
-#[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
-#[cfg_attr(feature = "jason", derive(Deserialize))]
-#[derive(Clone, Debug)]
+#[cfg_attr(feature = "medea", derive(Eq, PartialEq))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VideoSettings {
     /// Importance of the video media type.
     ///
@@ -698,9 +730,8 @@ pub struct VideoSettings {
 }
 
 /// Media source kind.
-#[cfg_attr(feature = "medea", derive(Debug, Eq, PartialEq, Serialize))]
-#[cfg_attr(feature = "jason", derive(Deserialize))]
-#[derive(Clone, Copy)]
+#[cfg_attr(feature = "medea", derive(Debug, Eq, PartialEq))]
+#[derive(Clone, Copy, Deserialize, Serialize)]
 pub enum MediaSourceKind {
     /// Media is sourced by some media device (webcam or microphone).
     Device,
@@ -709,6 +740,80 @@ pub enum MediaSourceKind {
     Display,
 }
 
+/// Kind of a [`Track`]'s media.
+#[cfg_attr(feature = "medea", derive(Debug, Eq, Hash, PartialEq, Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy)]
+pub enum TrackMediaKind {
+    /// Audio media.
+    Audio,
+
+    /// Video media.
+    Video,
+}
+
+impl From<&MediaType> for TrackMediaKind {
+    #[inline]
+    fn from(media_type: &MediaType) -> Self {
+        match media_type {
+            MediaType::Audio(_) => Self::Audio,
+            MediaType::Video(_) => Self::Video,
+        }
+    }
+}
+
+/// Estimated quality of a media [`Track`] received from a partner `Member`.
+#[cfg_attr(feature = "medea", derive(Clone, Debug, Eq, PartialEq, Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+pub struct TrackQualityScore {
+    /// ID of the received [`Track`].
+    pub track_id: TrackId,
+
+    /// Kind of the received [`Track`]'s media.
+    pub kind: TrackMediaKind,
+
+    /// Source kind of the received [`Track`]'s media.
+    pub source_kind: MediaSourceKind,
+
+    /// Estimated quality of the received [`Track`]'s media.
+    ///
+    /// Audio quality is estimated with E-model, taking concealed samples
+    /// into account, while video quality is estimated by its freezes,
+    /// dropped frames and framerate.
+    pub quality_score: ConnectionQualityScore,
+}
+
+/// Direction of the media traffic affected by a network congestion.
+#[cfg_attr(feature = "medea", derive(Debug, Eq, PartialEq, Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy)]
+pub enum CongestionDirection {
+    /// Media traffic sent by the `Peer` (publisher's uplink).
+    Uplink,
+
+    /// Media traffic received by the `Peer` (receiver's downlink).
+    Downlink,
+}
+
+/// Severity of a network congestion, estimated by packet loss and round trip
+/// time of the media traffic.
+#[cfg_attr(
+    feature = "medea",
+    derive(Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)
+)]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy)]
+pub enum CongestionSeverity {
+    /// Congestion has ended.
+    Clear = 0,
+
+    /// Media quality is noticeably degraded.
+    Moderate = 1,
+
+    /// Media is hardly usable.
+    Severe = 2,
+}
+
 /// Estimated connection quality.
 #[cfg_attr(
     feature = "medea",
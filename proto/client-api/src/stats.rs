@@ -818,6 +818,14 @@ pub enum ReceiverStatsKind {
 #[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RtcIceCandidatePairStats {
+    /// Unique ID of the [`RtcStatsType::LocalCandidate`] associated with this
+    /// candidate pair.
+    pub local_candidate_id: Option<StatId>,
+
+    /// Unique ID of the [`RtcStatsType::RemoteCandidate`] associated with this
+    /// candidate pair.
+    pub remote_candidate_id: Option<StatId>,
+
     /// State of the checklist for the local and remote candidates in a pair.
     pub state: IceCandidatePairState,
 
@@ -1095,6 +1103,21 @@ pub enum RtcInboundRtpStreamMediaType {
         ///
         /// This metric is incremented when the complete frame is received.
         frames_received: Option<u64>,
+
+        /// Total number of frames dropped prior to decode or dropped because
+        /// the frame missed its display deadline for this receiver's track.
+        frames_dropped: Option<u64>,
+
+        /// Total number of video freezes experienced by this receiver.
+        ///
+        /// It is a freeze if frame duration, which is time interval between
+        /// two consecutively rendered frames, is equal or exceeds
+        /// `Max(3 * avg_frame_duration_ms, avg_frame_duration_ms + 150)`.
+        freeze_count: Option<u64>,
+
+        /// Total duration of rendered frames which are considered as frozen,
+        /// in seconds.
+        total_freezes_duration: Option<Float>,
     },
 }
 
@@ -1115,6 +1138,12 @@ pub struct RtcInboundRtpStreamStats {
     /// ID of the stats object representing the receiving track.
     pub track_id: Option<String>,
 
+    /// [`mid`] of the [RTCRtpTransceiver] owning this stream.
+    ///
+    /// [RTCRtpTransceiver]: https://w3.org/TR/webrtc/#dom-rtcrtptransceiver
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    pub mid: Option<String>,
+
     /// Fields which should be in the [`RtcStat`] based on `mediaType`.
     #[serde(flatten)]
     pub media_specific_stats: RtcInboundRtpStreamMediaType,
@@ -8,7 +8,7 @@ pub struct Request {
     #[prost(string, tag="2")]
     pub at: std::string::String,
     /// Occurred callback event.
-    #[prost(oneof="request::Event", tags="3, 4")]
+    #[prost(oneof="request::Event", tags="3, 4, 5, 6, 7, 8, 9")]
     pub event: ::std::option::Option<request::Event>,
 }
 pub mod request {
@@ -19,6 +19,16 @@ pub mod request {
         OnJoin(super::OnJoin),
         #[prost(message, tag="4")]
         OnLeave(super::OnLeave),
+        #[prost(message, tag="5")]
+        OnRoomClosed(super::OnRoomClosed),
+        #[prost(message, tag="6")]
+        OnMemberRemoved(super::OnMemberRemoved),
+        #[prost(message, tag="7")]
+        OnCallRecord(super::OnCallRecord),
+        #[prost(message, tag="8")]
+        OnPeerMetricsEvent(super::OnPeerMetricsEvent),
+        #[prost(message, tag="9")]
+        OnNetworkCongestion(super::OnNetworkCongestion),
     }
 }
 /// Empty response of the Callback service.
@@ -38,6 +48,10 @@ pub struct OnLeave {
     /// Reason of why Member leaves the Room.
     #[prost(enumeration="on_leave::Reason", tag="1")]
     pub reason: i32,
+    /// Traffic relayed through TURN servers for the Member so far, if relay
+    /// usage accounting is enabled on a media server.
+    #[prost(message, optional, tag="2")]
+    pub relay_usage: ::std::option::Option<RelayUsage>,
 }
 pub mod on_leave {
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -53,4 +67,230 @@ pub mod on_leave {
         ServerShutdown = 3,
     }
 }
+/// Traffic relayed through TURN servers.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelayUsage {
+    /// Number of bytes sent by TURN servers to client.
+    #[prost(uint64, tag="1")]
+    pub sent_bytes: u64,
+    /// Number of bytes received by TURN servers from client.
+    #[prost(uint64, tag="2")]
+    pub received_bytes: u64,
+    /// Number of packets sent by TURN servers to client.
+    #[prost(uint64, tag="3")]
+    pub sent_packets: u64,
+    /// Number of packets received by TURN servers from client.
+    #[prost(uint64, tag="4")]
+    pub received_packets: u64,
+}
+/// Event that fires when Room is closed and removed automatically.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnRoomClosed {
+    /// Reason of why Room was closed.
+    #[prost(enumeration="on_room_closed::Reason", tag="1")]
+    pub reason: i32,
+}
+pub mod on_room_closed {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Reason {
+        /// Room stayed without connected Members longer than its empty timeout.
+        Empty = 0,
+        /// Room has reached its maximum duration.
+        MaxDuration = 1,
+    }
+}
+/// Event that fires when Member is removed automatically after leaving its
+/// Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnMemberRemoved {
+}
+/// Event that fires with a call detail record (CDR), when Member leaves its
+/// Room, or when Room is closed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnCallRecord {
+    /// Summarized sessions.
+    #[prost(oneof="on_call_record::Record", tags="1, 2")]
+    pub record: ::std::option::Option<on_call_record::Record>,
+}
+pub mod on_call_record {
+    /// Summarized sessions.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Record {
+        /// Session of the Member, which has left its Room.
+        #[prost(message, tag="1")]
+        Member(super::MemberRecord),
+        /// All sessions of the closed Room.
+        #[prost(message, tag="2")]
+        Room(super::RoomRecord),
+    }
+}
+/// Call detail record of all sessions of a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomRecord {
+    /// Time of the Room starting.
+    #[prost(string, tag="1")]
+    pub started_at: std::string::String,
+    /// Time of the Room closing.
+    #[prost(string, tag="2")]
+    pub closed_at: std::string::String,
+    /// Sessions of the Room's Members, in order of their joining.
+    #[prost(message, repeated, tag="3")]
+    pub members: ::std::vec::Vec<MemberRecord>,
+}
+/// Call detail record of a single session of a Member.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemberRecord {
+    /// ID of the Member.
+    #[prost(string, tag="1")]
+    pub member_id: std::string::String,
+    /// Time of the Member joining its Room.
+    #[prost(string, tag="2")]
+    pub joined_at: std::string::String,
+    /// Time of the Member leaving its Room.
+    #[prost(string, tag="3")]
+    pub left_at: std::string::String,
+    /// Reason of why the Member left its Room.
+    ///
+    /// Unset if the Member was still connected when its Room was closed.
+    #[prost(message, optional, tag="4")]
+    pub leave: ::std::option::Option<OnLeave>,
+    /// Traffic relayed through TURN servers for the Member so far, if relay usage
+    /// accounting is enabled on a media server.
+    #[prost(message, optional, tag="5")]
+    pub relay_usage: ::std::option::Option<RelayUsage>,
+    /// Peers of the Member during the session, in order of their starting.
+    #[prost(message, repeated, tag="6")]
+    pub peers: ::std::vec::Vec<PeerRecord>,
+}
+/// Call detail record of a Peer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeerRecord {
+    /// ID of the Peer.
+    #[prost(uint64, tag="1")]
+    pub peer_id: u64,
+    /// ID of the Member owning the partner Peer.
+    #[prost(string, tag="2")]
+    pub partner_member_id: std::string::String,
+    /// Time of the Peer starting.
+    #[prost(string, tag="3")]
+    pub started_at: std::string::String,
+    /// Duration of the Peer existence.
+    #[prost(message, optional, tag="4")]
+    pub duration: ::std::option::Option<::prost_types::Duration>,
+    /// Number of ICE restarts caused by failed connection of the Peer.
+    #[prost(uint32, tag="5")]
+    pub ice_restarts: u32,
+    /// Connection quality scores of the Peer, in order of their calculation.
+    #[prost(message, repeated, tag="6")]
+    pub quality_history: ::std::vec::Vec<peer_record::QualityScore>,
+    /// Intervals when the Peer's traffic stopped flowing.
+    #[prost(message, repeated, tag="7")]
+    pub traffic_stopped: ::std::vec::Vec<peer_record::TrafficStopped>,
+    /// Traffic relayed through TURN servers for the Peer, if relay usage
+    /// accounting is enabled on a media server.
+    #[prost(message, optional, tag="8")]
+    pub relay_usage: ::std::option::Option<RelayUsage>,
+}
+pub mod peer_record {
+    /// Connection quality score calculated at some time.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QualityScore {
+        /// Time of calculating this score.
+        #[prost(string, tag="1")]
+        pub at: std::string::String,
+        /// Estimated connection quality: 1 (poor), 2 (low), 3 (medium) or 4
+        /// (high).
+        #[prost(uint32, tag="2")]
+        pub score: u32,
+    }
+    /// Interval when traffic of some media type stopped flowing.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TrafficStopped {
+        /// Type of the stopped media.
+        #[prost(enumeration="MediaType", tag="1")]
+        pub media_type: i32,
+        /// Direction of the stopped media.
+        #[prost(enumeration="MediaDirection", tag="2")]
+        pub direction: i32,
+        /// Time of the traffic stopping.
+        #[prost(string, tag="3")]
+        pub from: std::string::String,
+        /// Time of the traffic restoring.
+        ///
+        /// Empty if the traffic was not restored until the Peer was removed.
+        #[prost(string, tag="4")]
+        pub to: std::string::String,
+    }
+    /// Type of media.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum MediaType {
+        /// Audio media.
+        Audio = 0,
+        /// Video media.
+        Video = 1,
+        /// Both audio and video media.
+        Both = 2,
+    }
+    /// Direction of media.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum MediaDirection {
+        /// Media published by the Member.
+        Publish = 0,
+        /// Media played by the Member.
+        Play = 1,
+    }
+}
+/// Event that fires when a custom analyzer of WebRTC stats, registered on
+/// Medea media server, detects something about Member's Peer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnPeerMetricsEvent {
+    /// ID of the Peer the event is about.
+    #[prost(uint64, tag="1")]
+    pub peer_id: u64,
+    /// Name of the event, defined by the analyzer.
+    #[prost(string, tag="2")]
+    pub name: std::string::String,
+    /// Arbitrary data of the event, defined by the analyzer.
+    #[prost(map="string, string", tag="3")]
+    pub data: ::std::collections::HashMap<std::string::String, std::string::String>,
+}
+/// Event that fires when sustained network congestion of Member's Peer media
+/// traffic is detected, or when it ends.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnNetworkCongestion {
+    /// ID of the Peer which media traffic is congested.
+    #[prost(uint64, tag="1")]
+    pub peer_id: u64,
+    /// Direction of the congested media traffic.
+    #[prost(enumeration="on_network_congestion::Direction", tag="2")]
+    pub direction: i32,
+    /// Severity of the congestion.
+    #[prost(enumeration="on_network_congestion::Severity", tag="3")]
+    pub severity: i32,
+}
+pub mod on_network_congestion {
+    /// Direction of media traffic.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Direction {
+        /// Media traffic sent by the Peer (publisher's uplink).
+        Uplink = 0,
+        /// Media traffic received by the Peer (receiver's downlink).
+        Downlink = 1,
+    }
+    /// Severity of network congestion.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Severity {
+        /// Congestion has ended.
+        Clear = 0,
+        /// Media quality is noticeably degraded.
+        Moderate = 1,
+        /// Media is hardly usable.
+        Severe = 2,
+    }
+}
 # [doc = r" Generated client implementations."] pub mod callback_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Service for receiving callbacks from Medea media server."] pub struct CallbackClient < T > { inner : tonic :: client :: Grpc < T > , } impl CallbackClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > CallbackClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = "/ Fires when a certain callback event happens on Medea media server."] pub async fn on_event (& mut self , request : impl tonic :: IntoRequest < super :: Request > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/callback.Callback/OnEvent") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for CallbackClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for CallbackClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "CallbackClient {{ ... }}") } } }# [doc = r" Generated server implementations."] pub mod callback_server { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = "Generated trait containing gRPC methods that should be implemented for use with CallbackServer."] # [async_trait] pub trait Callback : Send + Sync + 'static { # [doc = "/ Fires when a certain callback event happens on Medea media server."] async fn on_event (& self , request : tonic :: Request < super :: Request >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; } # [doc = " Service for receiving callbacks from Medea media server."] # [derive (Debug)] pub struct CallbackServer < T : Callback > { inner : _Inner < T > , } struct _Inner < T > (Arc < T > , Option < tonic :: Interceptor >) ; impl < T : Callback > CallbackServer < T > { pub fn new (inner : T) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , None) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , Some (interceptor . into ())) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for CallbackServer < T > where T : Callback , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready (& mut self , _cx : & mut Context < '_ >) -> Poll < Result < () , Self :: Error >> { Poll :: Ready (Ok (())) } fn call (& mut self , req : http :: Request < B >) -> Self :: Future { let inner = self . inner . clone () ; match req . uri () . path () { "/callback.Callback/OnEvent" => { # [allow (non_camel_case_types)] struct OnEventSvc < T : Callback > (pub Arc < T >) ; impl < T : Callback > tonic :: server :: UnaryService < super :: Request > for OnEventSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: Request >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . on_event (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = OnEventSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } _ => Box :: pin (async move { Ok (http :: Response :: builder () . status (200) . header ("grpc-status" , "12") . body (tonic :: body :: BoxBody :: empty ()) . unwrap ()) }) , } } } impl < T : Callback > Clone for CallbackServer < T > { fn clone (& self) -> Self { let inner = self . inner . clone () ; Self { inner } } } impl < T : Callback > Clone for _Inner < T > { fn clone (& self) -> Self { Self (self . 0 . clone () , self . 1 . clone ()) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "{:?}" , self . 0) } } impl < T : Callback > tonic :: transport :: NamedService for CallbackServer < T > { const NAME : & 'static str = "callback.Callback" ; } }
\ No newline at end of file
@@ -2,7 +2,7 @@
 //!
 //! [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
 
-use std::{fmt, time::Duration};
+use std::{fmt, net::IpAddr, time::Duration};
 
 use actix::Message;
 use futures::future::LocalBoxFuture;
@@ -98,6 +98,11 @@ pub struct RpcConnectionEstablished {
     /// [`Member`]: crate::signalling::elements::Member
     pub credentials: Credential,
 
+    /// IP address the [`Member`] is connected from, if known.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    pub client_ip: Option<IpAddr>,
+
     /// Established [`RpcConnection`].
     pub connection: Box<dyn RpcConnection>,
 }
@@ -1,6 +1,10 @@
 //! HTTP server for handling WebSocket connections of Client API.
 
-use std::io;
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use actix::{Actor, Addr, Handler, ResponseFuture};
 use actix_web::{
@@ -14,14 +18,37 @@ use futures::FutureExt as _;
 
 use crate::{
     api::client::session::WsSession,
-    conf::{Conf, Rpc},
+    conf::{turn::IpNetwork, Conf, Rpc},
     log::prelude::*,
     shutdown::ShutdownGracefully,
     signalling::room_repo::RoomRepository,
+    turn::TurnAuthService,
 };
 
 use super::MAX_WS_MSG_SIZE;
 
+/// Returns IP address of the client performing the provided `request`.
+///
+/// `Forwarded` and `X-Forwarded-For` headers are respected only in requests
+/// coming from the provided `trusted_proxies`, as they can be spoofed by
+/// clients otherwise.
+fn client_ip(
+    request: &HttpRequest,
+    trusted_proxies: &[IpNetwork],
+) -> Option<IpAddr> {
+    let peer = request.peer_addr()?.ip();
+    if !trusted_proxies.iter().any(|net| net.contains(peer)) {
+        return Some(peer);
+    }
+    let info = request.connection_info();
+    let addr = info.realip_remote_addr()?;
+    addr.parse::<SocketAddr>()
+        .map(|addr| addr.ip())
+        .or_else(|_| addr.trim_matches(&['[', ']'][..]).parse())
+        .ok()
+        .or(Some(peer))
+}
+
 /// Handles all HTTP requests, performs WebSocket handshake (upgrade) and starts
 /// new [`WsSession`] for WebSocket connection.
 async fn ws_index(
@@ -35,6 +62,7 @@ async fn ws_index(
                 Box::new(state.rooms.clone()),
                 state.config.idle_timeout,
                 state.config.ping_interval,
+                client_ip(&request, &state.trusted_proxies),
             ),
             payload,
             actix_http::ws::Codec::new().max_size(MAX_WS_MSG_SIZE),
@@ -42,6 +70,24 @@ async fn ws_index(
     )
 }
 
+/// Responds with `200 OK` if this media server is healthy, or with
+/// `503 Service Unavailable` if connections with [Coturn] server admin
+/// interface are unavailable.
+///
+/// Details are not exposed, as this endpoint is reachable by anyone, but are
+/// logged instead.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+async fn health_index(state: Data<Context>) -> HttpResponse {
+    match state.turn_service.cli_health() {
+        Some(cli) if !cli.is_healthy() => {
+            warn!("Coturn admin interface is unavailable: {:?}", cli);
+            HttpResponse::ServiceUnavailable().finish()
+        }
+        _ => HttpResponse::Ok().finish(),
+    }
+}
+
 /// Context for [`App`] which holds all the necessary dependencies.
 pub struct Context {
     /// Repository of all currently existing [`Room`]s in application.
@@ -49,6 +95,12 @@ pub struct Context {
     /// [`Room`]: crate::signalling::Room
     pub rooms: RoomRepository,
 
+    /// Service managing credentials on STUN/TURN servers.
+    pub turn_service: Arc<dyn TurnAuthService>,
+
+    /// IP networks of proxies trusted to report IP addresses of clients.
+    pub trusted_proxies: Vec<IpNetwork>,
+
     /// Settings of application.
     pub config: Rpc,
 }
@@ -62,12 +114,21 @@ impl Server {
     /// # Errors
     ///
     /// Errors if binding [`HttpServer`] to a listening address fails.
-    pub fn run(rooms: RoomRepository, config: Conf) -> io::Result<Addr<Self>> {
+    pub fn run(
+        rooms: RoomRepository,
+        turn_service: Arc<dyn TurnAuthService>,
+        config: Conf,
+    ) -> io::Result<Addr<Self>> {
         let server_addr = config.server.client.http.bind_addr();
 
         let server = HttpServer::new(move || {
             App::new()
-                .app_data(Self::app_data(rooms.clone(), config.rpc))
+                .app_data(Self::app_data(
+                    rooms.clone(),
+                    turn_service.clone(),
+                    config.server.client.http.trusted_proxies.clone(),
+                    config.rpc,
+                ))
                 .configure(Self::configure)
                 .wrap(middleware::Logger::default())
         })
@@ -81,14 +142,28 @@ impl Server {
     }
 
     /// Set application data.
-    fn app_data(rooms: RoomRepository, config: Rpc) -> Data<Context> {
-        Data::new(Context { rooms, config })
+    fn app_data(
+        rooms: RoomRepository,
+        turn_service: Arc<dyn TurnAuthService>,
+        trusted_proxies: Vec<IpNetwork>,
+        config: Rpc,
+    ) -> Data<Context> {
+        Data::new(Context {
+            rooms,
+            turn_service,
+            trusted_proxies,
+            config,
+        })
     }
 
     /// Run external configuration as part of the application building
     /// process
     fn configure(cfg: &mut ServiceConfig) {
-        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)));
+        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)))
+            .service(
+                resource("/health")
+                    .route(actix_web::web::get().to(health_index)),
+            );
     }
 }
 
@@ -108,3 +183,109 @@ impl Handler<ShutdownGracefully> for Server {
         self.0.stop(true).boxed_local()
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use actix_web::{http::StatusCode, test};
+    use async_trait::async_trait;
+    use medea_client_api_proto::{PeerId, RoomId};
+
+    use crate::turn::{
+        cli::CoturnCliHealth, new_turn_auth_service_mock, IceUser,
+        TurnServiceErr, UnreachablePolicy,
+    };
+
+    use super::*;
+
+    /// [`TurnAuthService`] with unavailable [Coturn] admin interface.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[derive(Debug)]
+    struct UnhealthyTurnService;
+
+    #[async_trait]
+    impl TurnAuthService for UnhealthyTurnService {
+        async fn create(
+            &self,
+            _: RoomId,
+            _: PeerId,
+            _: Option<String>,
+            _: UnreachablePolicy,
+        ) -> Result<IceUser, TurnServiceErr> {
+            Err(TurnServiceErr::TimedOut)
+        }
+
+        fn cli_health(&self) -> Option<CoturnCliHealth> {
+            Some(CoturnCliHealth {
+                max_size: 1,
+                size: 0,
+                available: 0,
+                waiting: 0,
+                failed_connects: 3,
+                failed_recycles: 0,
+                auth_failures: 0,
+                retry_in: Some(Duration::from_secs(1)),
+            })
+        }
+    }
+
+    /// Requests `/health` endpoint of Client API HTTP server using the
+    /// provided [`TurnAuthService`].
+    async fn health(turn_service: Arc<dyn TurnAuthService>) -> StatusCode {
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Server::app_data(
+                    RoomRepository::new(),
+                    turn_service,
+                    Vec::new(),
+                    Rpc::default(),
+                ))
+                .configure(Server::configure),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &mut app,
+            test::TestRequest::get().uri("/health").to_request(),
+        )
+        .await;
+        let status = resp.status();
+        assert!(test::read_body(resp).await.is_empty());
+        status
+    }
+
+    #[test]
+    fn trusts_forwarded_headers_of_trusted_proxies_only() {
+        let request = |peer: &str| {
+            test::TestRequest::default()
+                .peer_addr(peer.parse().unwrap())
+                .header("x-forwarded-for", "10.1.2.3")
+                .to_http_request()
+        };
+        let trusted = ["127.0.0.1".parse().unwrap()];
+
+        assert_eq!(
+            client_ip(&request("127.0.0.1:1234"), &trusted),
+            Some("10.1.2.3".parse().unwrap()),
+        );
+        assert_eq!(
+            client_ip(&request("127.0.0.2:1234"), &trusted),
+            Some("127.0.0.2".parse().unwrap()),
+        );
+        assert_eq!(
+            client_ip(&request("127.0.0.1:1234"), &[]),
+            Some("127.0.0.1".parse().unwrap()),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn responds_with_health_status_only() {
+        assert_eq!(health(new_turn_auth_service_mock()).await, StatusCode::OK);
+        assert_eq!(
+            health(Arc::new(UnhealthyTurnService)).await,
+            StatusCode::SERVICE_UNAVAILABLE,
+        );
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     collections::HashMap,
     convert::TryInto as _,
     fmt::{Debug, Display, Error, Formatter},
+    net::IpAddr,
     sync::atomic::{AtomicU64, Ordering},
     time::{Duration, Instant},
 };
@@ -100,6 +101,9 @@ pub struct WsSession {
     /// Interval to send [`ServerMsg::Ping`]s to a client with.
     ping_interval: Duration,
 
+    /// IP address of the client, if known.
+    client_ip: Option<IpAddr>,
+
     /// [`WsSession`] closed reason. Should be set by the moment
     /// `Actor::stopped()` for this [`WsSession`] is called.
     close_reason: Option<InnerCloseReason>,
@@ -119,6 +123,7 @@ impl WsSession {
         rooms: Box<dyn RpcServerRepository>,
         idle_timeout: Duration,
         ping_interval: Duration,
+        client_ip: Option<IpAddr>,
     ) -> Self {
         Self {
             id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
@@ -129,6 +134,7 @@ impl WsSession {
             fragmentation_buffer: BytesMut::new(),
             last_ping_num: 0,
             ping_interval,
+            client_ip,
             close_reason: None,
             auth_timeout_handle: None,
             heartbeat_handle: None,
@@ -244,6 +250,7 @@ impl WsSession {
             room.connection_established(
                 member_id.clone(),
                 credential,
+                self.client_ip,
                 Box::new(ctx.address()),
             )
             .into_actor(self)
@@ -267,6 +274,7 @@ impl WsSession {
                     );
                     let reason = match err {
                         RpcServerError::Authorization => CloseReason::Rejected,
+                        RpcServerError::RoomIsFull => CloseReason::RoomFull,
                         RpcServerError::RoomError(_)
                         | RpcServerError::RoomMailbox(_) => {
                             CloseReason::InternalError
@@ -777,10 +785,10 @@ mod test {
                 let expected_member_id = member_id.clone();
                 rpc_server
                     .expect_connection_established()
-                    .withf(move |member_id, _, _| {
+                    .withf(move |member_id, _, _, _| {
                         *member_id == expected_member_id
                     })
-                    .return_once(|_, _, _| {
+                    .return_once(|_, _, _, _| {
                         future::err(RpcServerError::Authorization).boxed_local()
                     });
                 rpc_server
@@ -794,6 +802,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         }
 
@@ -841,7 +850,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
@@ -860,6 +869,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_millis(50),
+                None,
             )
         });
 
@@ -913,7 +923,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
@@ -937,6 +947,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_millis(100),
                 Duration::from_secs(10),
+                None,
             )
         });
 
@@ -985,7 +996,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
@@ -1008,6 +1019,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         });
 
@@ -1097,7 +1109,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, connection| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1123,6 +1135,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         });
 
@@ -1189,7 +1202,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, connection| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1215,6 +1228,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         });
 
@@ -1270,7 +1284,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, connection| {
                         let _ =
                             CHAN.0.lock().unwrap().unbounded_send(connection);
                         future::ok(RpcConnectionSettings {
@@ -1291,6 +1305,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         });
 
@@ -1404,7 +1419,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().returning(
-                    |_, _, _| {
+                    |_, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
@@ -1423,6 +1438,7 @@ mod test {
                 Box::new(rpc_server_repo),
                 Duration::from_secs(5),
                 Duration::from_secs(5),
+                None,
             )
         });
 
@@ -9,10 +9,13 @@ use medea_control_api_proto::grpc::callback::{
 use async_trait::async_trait;
 use tonic::transport::Channel;
 
-use crate::api::control::callback::{
-    clients::{CallbackClient, CallbackClientError},
-    url::GrpcCallbackUrl,
-    CallbackRequest,
+use crate::{
+    api::control::callback::{
+        clients::{CallbackClient, CallbackClientError},
+        url::GrpcCallbackUrl,
+        CallbackRequest,
+    },
+    tracing::TRACEPARENT,
 };
 
 /// gRPC client for sending [`CallbackRequest`]s.
@@ -47,7 +50,14 @@ impl CallbackClient for GrpcCallbackClient {
         request: CallbackRequest,
     ) -> Result<(), CallbackClientError> {
         let mut client = self.client.clone();
-        client.on_event(tonic::Request::new(request.into())).await?;
+        let trace = request.trace();
+        let mut request = tonic::Request::new(request.into());
+        if let Some(trace) = trace {
+            if let Ok(value) = trace.to_traceparent().parse() {
+                request.metadata_mut().insert(TRACEPARENT, value);
+            }
+        }
+        client.on_event(request).await?;
 
         Ok(())
     }
@@ -70,6 +80,7 @@ pub mod test {
     use medea_control_api_proto::grpc::callback::{
         callback_server::{Callback, CallbackServer as TonicCallbackServer},
         on_leave::Reason,
+        on_room_closed::Reason as RoomClosedReason,
         request::Event,
         Request, Response,
     };
@@ -79,6 +90,15 @@ pub mod test {
     pub trait GrpcCallbackServer {
         fn on_join(&self, fid: &str) -> Result<(), ()>;
         fn on_leave(&self, fid: &str, event: Reason) -> Result<(), ()>;
+        fn on_room_closed(
+            &self,
+            fid: &str,
+            reason: RoomClosedReason,
+        ) -> Result<(), ()>;
+        fn on_member_removed(&self, fid: &str) -> Result<(), ()>;
+        fn on_call_record(&self, fid: &str) -> Result<(), ()>;
+        fn on_peer_metrics_event(&self, fid: &str) -> Result<(), ()>;
+        fn on_network_congestion(&self, fid: &str) -> Result<(), ()>;
     }
 
     #[async_trait::async_trait]
@@ -94,6 +114,20 @@ pub mod test {
                     &request.fid,
                     Reason::from_i32(on_leave.reason).unwrap(),
                 ),
+                Event::OnRoomClosed(on_room_closed) => self.on_room_closed(
+                    &request.fid,
+                    RoomClosedReason::from_i32(on_room_closed.reason).unwrap(),
+                ),
+                Event::OnMemberRemoved(_) => {
+                    self.on_member_removed(&request.fid)
+                }
+                Event::OnCallRecord(_) => self.on_call_record(&request.fid),
+                Event::OnPeerMetricsEvent(_) => {
+                    self.on_peer_metrics_event(&request.fid)
+                }
+                Event::OnNetworkCongestion(_) => {
+                    self.on_network_congestion(&request.fid)
+                }
             }
             .map(|_| tonic::Response::new(Response {}))
             .map_err(|_| Status::internal(""))
@@ -4,12 +4,19 @@ pub mod clients;
 pub mod service;
 pub mod url;
 
+use std::collections::HashMap;
+
 use actix::Message;
 use chrono::{DateTime, Utc};
 use derive_more::{Display, From};
+use medea_client_api_proto::{CongestionDirection, CongestionSeverity, PeerId};
 use medea_control_api_proto::grpc::callback as proto;
+use serde::Serialize;
 
-use crate::api::control::refs::StatefulFid;
+use crate::{
+    api::control::refs::StatefulFid, cdr::CallRecord, tracing::SpanContext,
+    turn::RelayUsage,
+};
 
 #[doc(inline)]
 pub use self::{
@@ -23,12 +30,18 @@ pub use self::{
 pub struct OnLeaveEvent {
     /// Reason of why `Member` was lost.
     reason: OnLeaveReason,
+
+    /// Traffic relayed through TURN servers for `Member` so far.
+    relay_usage: RelayUsage,
 }
 
 impl OnLeaveEvent {
     #[inline]
-    pub fn new(reason: OnLeaveReason) -> Self {
-        Self { reason }
+    pub fn new(reason: OnLeaveReason, relay_usage: RelayUsage) -> Self {
+        Self {
+            reason,
+            relay_usage,
+        }
     }
 }
 
@@ -37,12 +50,14 @@ impl Into<proto::OnLeave> for OnLeaveEvent {
         let on_leave: proto::on_leave::Reason = self.reason.into();
         proto::OnLeave {
             reason: on_leave as i32,
+            relay_usage: Some(self.relay_usage.into()),
         }
     }
 }
 
 /// Reason of why `Member` was lost.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OnLeaveReason {
     /// `Member` was normally disconnected.
     Disconnected,
@@ -78,11 +93,156 @@ impl Into<proto::OnJoin> for OnJoinEvent {
     }
 }
 
+/// Event for `on_close` `Room` callback.
+#[derive(Debug)]
+pub struct OnRoomClosedEvent {
+    /// Reason of why `Room` was closed.
+    reason: OnRoomClosedReason,
+}
+
+impl OnRoomClosedEvent {
+    #[inline]
+    pub fn new(reason: OnRoomClosedReason) -> Self {
+        Self { reason }
+    }
+}
+
+impl Into<proto::OnRoomClosed> for OnRoomClosedEvent {
+    fn into(self) -> proto::OnRoomClosed {
+        let reason: proto::on_room_closed::Reason = self.reason.into();
+        proto::OnRoomClosed {
+            reason: reason as i32,
+        }
+    }
+}
+
+/// Reason of why `Room` was closed automatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OnRoomClosedReason {
+    /// `Room` stayed without connected `Member`s longer than its
+    /// `empty_timeout`.
+    Empty,
+
+    /// `Room` has reached its `max_duration`.
+    MaxDuration,
+}
+
+impl Into<proto::on_room_closed::Reason> for OnRoomClosedReason {
+    fn into(self) -> proto::on_room_closed::Reason {
+        match self {
+            Self::Empty => proto::on_room_closed::Reason::Empty,
+            Self::MaxDuration => proto::on_room_closed::Reason::MaxDuration,
+        }
+    }
+}
+
+/// Event which is sent to `on_leave` URL of a `Member` when it's removed
+/// automatically after leaving its `Room`.
+///
+/// It's a separate event from [`OnLeaveEvent`], which is sent before it.
+#[derive(Debug)]
+pub struct OnMemberRemovedEvent;
+
+impl Into<proto::OnMemberRemoved> for OnMemberRemovedEvent {
+    fn into(self) -> proto::OnMemberRemoved {
+        proto::OnMemberRemoved {}
+    }
+}
+
+/// Event for `on_metrics` `Member` and `on_close` `Room` callbacks, carrying
+/// [`CallRecord`] of the left `Member` or the closed `Room`.
+#[derive(Debug)]
+pub struct OnCallRecordEvent(pub CallRecord);
+
+impl Into<proto::OnCallRecord> for OnCallRecordEvent {
+    fn into(self) -> proto::OnCallRecord {
+        use proto::on_call_record::Record;
+
+        proto::OnCallRecord {
+            record: Some(match self.0 {
+                CallRecord::Member(member) => Record::Member(member.into()),
+                CallRecord::Room(room) => Record::Room(room.into()),
+            }),
+        }
+    }
+}
+
+/// `on_metrics` `Member` callback which is sent when some custom
+/// [`RtcStatsHandler`] emits [`PeersMetricsEvent::Custom`] about `Member`'s
+/// `Peer`.
+///
+/// [`PeersMetricsEvent::Custom`]: crate::signalling::peers::PeersMetricsEvent::Custom
+/// [`RtcStatsHandler`]: crate::signalling::peers::RtcStatsHandler
+#[derive(Debug)]
+pub struct OnPeerMetricsEvent {
+    /// ID of the `Peer` this event is about.
+    pub peer_id: PeerId,
+
+    /// Name of this event.
+    pub name: String,
+
+    /// Arbitrary data of this event.
+    pub data: HashMap<String, String>,
+}
+
+impl Into<proto::OnPeerMetricsEvent> for OnPeerMetricsEvent {
+    fn into(self) -> proto::OnPeerMetricsEvent {
+        proto::OnPeerMetricsEvent {
+            peer_id: u64::from(self.peer_id.0),
+            name: self.name,
+            data: self.data,
+        }
+    }
+}
+
+/// `on_metrics` `Member` callback which is sent when sustained network
+/// congestion of `Member`'s `Peer` media traffic is detected, or when it ends.
+#[derive(Debug)]
+pub struct OnNetworkCongestionEvent {
+    /// ID of the congested `Peer`.
+    pub peer_id: PeerId,
+
+    /// Direction of the congested media traffic.
+    pub direction: CongestionDirection,
+
+    /// Severity of the congestion.
+    pub severity: CongestionSeverity,
+}
+
+impl Into<proto::OnNetworkCongestion> for OnNetworkCongestionEvent {
+    fn into(self) -> proto::OnNetworkCongestion {
+        use proto::on_network_congestion::{Direction, Severity};
+
+        let direction = match self.direction {
+            CongestionDirection::Uplink => Direction::Uplink,
+            CongestionDirection::Downlink => Direction::Downlink,
+        };
+        let severity = match self.severity {
+            CongestionSeverity::Clear => Severity::Clear,
+            CongestionSeverity::Moderate => Severity::Moderate,
+            CongestionSeverity::Severe => Severity::Severe,
+        };
+        proto::OnNetworkCongestion {
+            peer_id: u64::from(self.peer_id.0),
+            direction: direction as i32,
+            severity: severity as i32,
+        }
+    }
+}
+
 /// All callbacks which can happen.
+// Variants are named after the `On*` callbacks of the gRPC Callback API they
+// are sent as.
+#[allow(clippy::pub_enum_variant_names)]
 #[derive(Debug, From)]
 pub enum CallbackEvent {
     OnJoin(OnJoinEvent),
     OnLeave(OnLeaveEvent),
+    OnRoomClosed(OnRoomClosedEvent),
+    OnMemberRemoved(OnMemberRemovedEvent),
+    OnCallRecord(OnCallRecordEvent),
+    OnPeerMetricsEvent(OnPeerMetricsEvent),
+    OnNetworkCongestion(OnNetworkCongestionEvent),
 }
 
 impl Into<proto::request::Event> for CallbackEvent {
@@ -94,12 +254,32 @@ impl Into<proto::request::Event> for CallbackEvent {
             Self::OnLeave(on_leave) => {
                 proto::request::Event::OnLeave(on_leave.into())
             }
+            Self::OnRoomClosed(on_room_closed) => {
+                proto::request::Event::OnRoomClosed(on_room_closed.into())
+            }
+            Self::OnMemberRemoved(on_member_removed) => {
+                proto::request::Event::OnMemberRemoved(on_member_removed.into())
+            }
+            Self::OnCallRecord(on_call_record) => {
+                proto::request::Event::OnCallRecord(on_call_record.into())
+            }
+            Self::OnPeerMetricsEvent(on_peer_metrics_event) => {
+                proto::request::Event::OnPeerMetricsEvent(
+                    on_peer_metrics_event.into(),
+                )
+            }
+            Self::OnNetworkCongestion(on_network_congestion) => {
+                proto::request::Event::OnNetworkCongestion(
+                    on_network_congestion.into(),
+                )
+            }
         }
     }
 }
 
 /// Media type of the traffic which starts/stops flowing in some `Endpoint`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MediaType {
     /// Started/stopped audio traffic.
     Audio = 0b1,
@@ -152,7 +332,8 @@ impl From<&medea_client_api_proto::MediaType> for MediaType {
 
 /// Media direction of the `Endpoint` for which `on_start` or `on_stop` Control
 /// API callback was received.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MediaDirection {
     /// `Endpoint` is a publisher.
     Publish,
@@ -178,6 +359,10 @@ pub struct CallbackRequest {
 
     /// Time at which event occurred.
     at: DateTime<Utc>,
+
+    /// [`SpanContext`] of the traced operation sending this
+    /// [`CallbackRequest`], propagated to its receiver.
+    trace: Option<SpanContext>,
 }
 
 impl CallbackRequest {
@@ -188,8 +373,25 @@ impl CallbackRequest {
             fid: element,
             event,
             at: Utc::now(),
+            trace: None,
         }
     }
+
+    /// Sets [`SpanContext`] to be propagated with this [`CallbackRequest`].
+    #[inline]
+    #[must_use]
+    pub fn with_trace(mut self, trace: Option<SpanContext>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Returns [`SpanContext`] to be propagated with this
+    /// [`CallbackRequest`].
+    #[inline]
+    #[must_use]
+    pub fn trace(&self) -> Option<SpanContext> {
+        self.trace
+    }
 }
 
 impl Into<proto::Request> for CallbackRequest {
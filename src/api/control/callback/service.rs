@@ -1,7 +1,8 @@
 //! Service which stores and lazily creates [`CallbackRequest`] clients.
 
 use std::{
-    collections::hash_map::HashMap, fmt::Debug, marker::PhantomData, sync::Arc,
+    collections::hash_map::HashMap, fmt::Debug, future::Future,
+    marker::PhantomData, sync::Arc,
 };
 
 use actix::Arbiter;
@@ -19,6 +20,7 @@ use crate::{
         refs::StatefulFid,
     },
     log::prelude::*,
+    tracing::{Span, SpanKind},
 };
 
 /// Service which stores and lazily creates [`CallbackRequest`] clients.
@@ -93,8 +95,32 @@ impl<B: CallbackClientFactory + 'static> CallbackService<B> {
         fid: StatefulFid,
         event: T,
     ) -> Result<(), CallbackClientError> {
-        self.inner_send(CallbackRequest::new(fid, event.into()), callback_url)
-            .await
+        self.traced_send(callback_url, fid, event.into()).await
+    }
+
+    /// Starts client [`Span`] of sending [`CallbackEvent`] as a child of the
+    /// [`Span`] entered on the current thread, and returns [`Future`] sending
+    /// it with the [`Span`]'s context propagated to the receiver.
+    fn traced_send(
+        &self,
+        callback_url: CallbackUrl,
+        fid: StatefulFid,
+        event: CallbackEvent,
+    ) -> impl Future<Output = Result<(), CallbackClientError>> + 'static {
+        let mut span = Span::new("callback.send")
+            .kind(SpanKind::Client)
+            .attr("callback.url", &callback_url)
+            .attr("fid", &fid);
+        let request =
+            CallbackRequest::new(fid, event).with_trace(span.context());
+        let this = self.clone();
+        async move {
+            let res = this.inner_send(request, callback_url).await;
+            if let Err(e) = &res {
+                span.set_error(e);
+            }
+            res
+        }
     }
 
     /// Asynchronously sends [`CallbackEvent`] for provided [`StatefulFid`] to
@@ -107,9 +133,9 @@ impl<B: CallbackClientFactory + 'static> CallbackService<B> {
         fid: StatefulFid,
         event: T,
     ) {
-        let this = self.clone();
+        let send = self.traced_send(callback_url, fid, event.into());
         Arbiter::spawn(async move {
-            if let Err(e) = this.send(callback_url, fid, event).await {
+            if let Err(e) = send.await {
                 error!("Failed to send callback because {:?}.", e);
             }
         })
@@ -17,7 +17,7 @@ use crate::api::control::{refs::SrcUri, TryFromProtobufError};
 pub struct WebRtcPlayId(String);
 
 /// Media element which is able to play media data for client via WebRTC.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
 pub struct WebRtcPlayEndpoint {
     /// Source URI in format `local://{room_id}/{member_id}/{endpoint_id}`.
     pub src: SrcUri,
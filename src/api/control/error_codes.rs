@@ -284,6 +284,24 @@ pub enum ErrorCode {
     #[display(fmt = "Encountered negative duration")]
     NegativeDuration = 1023,
 
+    /// Media server has reached its limit of `Room`s.
+    ///
+    /// Code: __1024__.
+    #[display(fmt = "Media server has reached its limit of Rooms.")]
+    RoomsLimitReached = 1024,
+
+    /// `Room` has reached its limit of `Member`s.
+    ///
+    /// Code: __1025__.
+    #[display(fmt = "Room has reached its limit of Members.")]
+    MembersLimitReached = 1025,
+
+    /// `Room` has reached its limit of publishing `Member`s.
+    ///
+    /// Code: __1026__.
+    #[display(fmt = "Room has reached its limit of publishing Members.")]
+    PublishersLimitReached = 1026,
+
     /// Unexpected server error.
     ///
     /// Use this [`ErrorCode`] only with [`ErrorResponse::unexpected`]
@@ -423,6 +441,16 @@ impl From<RoomError> for ErrorResponse {
             E::EndpointAlreadyExists(id) => {
                 Self::new(ErrorCode::EndpointAlreadyExists, &id)
             }
+            E::MembersLimitReached(max) => Self::with_explanation(
+                ErrorCode::MembersLimitReached,
+                format!("Limit is {} Members.", max),
+                None,
+            ),
+            E::PublishersLimitReached(max) => Self::with_explanation(
+                ErrorCode::PublishersLimitReached,
+                format!("Limit is {} publishing Members.", max),
+                None,
+            ),
             E::WrongRoomId(_, _)
             | E::PeerNotFound(_)
             | E::CallbackClientError(_)
@@ -486,6 +514,11 @@ impl From<RoomServiceError> for ErrorResponse {
                 Self::new(ErrorCode::RoomAlreadyExists, &id)
             }
             E::RoomError(e) => e.into(),
+            E::RoomsLimitReached(max) => Self::with_explanation(
+                ErrorCode::RoomsLimitReached,
+                format!("Limit is {} Rooms.", max),
+                None,
+            ),
             E::EmptyUrisList => Self::without_id(ErrorCode::EmptyElementsList),
             E::NotSameRoomIds(id1, id2) => Self::with_explanation(
                 ErrorCode::ProvidedNotSameRoomIds,
@@ -0,0 +1,276 @@
+//! Audit log of gRPC [Control API] mutations.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{io, net::SocketAddr, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use medea_control_api_proto::grpc::api as proto;
+use serde::Serialize;
+
+use crate::{
+    conf::{self, audit::AuditSink},
+    log::prelude::*,
+    utils::JsonLinesSink,
+};
+
+/// Record of a single [Control API] mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditRecord {
+    /// Time of the mutation being processed.
+    pub at: DateTime<Utc>,
+
+    /// Address of the caller performed the mutation.
+    ///
+    /// [`None`] if the underlying transport doesn't provide it.
+    pub caller: Option<SocketAddr>,
+
+    /// [Control API] method performed the mutation.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub method: AuditMethod,
+
+    /// FIDs of the elements affected by the mutation.
+    pub fids: Vec<String>,
+
+    /// Human-readable summary of the request payload.
+    ///
+    /// [`None`] if the request carries nothing besides [`AuditRecord::fids`].
+    pub payload: Option<String>,
+
+    /// Result of the mutation.
+    pub result: AuditResult,
+}
+
+impl AuditRecord {
+    /// Creates new [`AuditRecord`] of the mutation being processed right now.
+    ///
+    /// Provided `error` is the one returned to the caller, if any.
+    #[must_use]
+    pub fn new(
+        caller: Option<SocketAddr>,
+        method: AuditMethod,
+        fids: Vec<String>,
+        payload: Option<String>,
+        error: Option<&proto::Error>,
+    ) -> Self {
+        Self {
+            at: Utc::now(),
+            caller,
+            method,
+            fids,
+            payload,
+            result: error.map_or(AuditResult::Ok, |e| AuditResult::Error {
+                code: e.code,
+                text: e.text.clone(),
+                element: Some(e.element.clone()).filter(|e| !e.is_empty()),
+            }),
+        }
+    }
+}
+
+/// [Control API] method performed a mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditMethod {
+    /// `Create` method.
+    Create,
+
+    /// `Delete` method.
+    Delete,
+}
+
+/// Result of a [Control API] mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditResult {
+    /// Mutation succeeded.
+    Ok,
+
+    /// Mutation failed.
+    Error {
+        /// Code of the error returned to the caller.
+        code: u32,
+
+        /// Text of the error returned to the caller.
+        text: String,
+
+        /// FID of the element the error is related to.
+        element: Option<String>,
+    },
+}
+
+/// Returns FIDs of the elements to be created by the provided
+/// [`proto::CreateRequest`] along with a summary of its payload.
+#[must_use]
+pub fn describe_create(req: &proto::CreateRequest) -> (Vec<String>, String) {
+    use proto::{create_request::El, web_rtc_publish_endpoint::P2p};
+
+    let (id, summary) = match &req.el {
+        Some(El::Room(room)) => (
+            &room.id,
+            format!("Room with {} Members", room.pipeline.len()),
+        ),
+        Some(El::Member(member)) => (
+            &member.id,
+            format!("Member with {} Endpoints", member.pipeline.len()),
+        ),
+        Some(El::WebrtcPlay(play)) => {
+            (&play.id, format!("WebRtcPlayEndpoint from {}", play.src))
+        }
+        Some(El::WebrtcPub(publish)) => (
+            &publish.id,
+            format!(
+                "WebRtcPublishEndpoint with {:?} P2P mode",
+                P2p::from_i32(publish.p2p).unwrap_or(P2p::Never),
+            ),
+        ),
+        None => return (vec![req.parent_fid.clone()], "no element".to_owned()),
+    };
+    let fid = if req.parent_fid.is_empty() {
+        id.clone()
+    } else {
+        format!("{}/{}", req.parent_fid, id)
+    };
+    (vec![fid], summary)
+}
+
+/// Sink writing [`AuditRecord`]s as JSON lines to the destination configured
+/// in [`conf::Audit`].
+///
+/// Does nothing if [`AuditSink::Off`] is configured.
+#[derive(Clone, Debug, Default)]
+pub struct AuditLog(JsonLinesSink);
+
+impl AuditLog {
+    /// Creates new [`AuditLog`] writing to the destination configured in the
+    /// provided [`conf::Audit`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the configured file cannot be opened or created.
+    pub fn new(cf: &conf::Audit) -> io::Result<Self> {
+        match cf.sink {
+            AuditSink::Off => Ok(JsonLinesSink::default()),
+            AuditSink::Stdout => Ok(JsonLinesSink::stdout()),
+            AuditSink::File => JsonLinesSink::rotating_file(
+                PathBuf::from(&cf.file),
+                cf.rotation_size,
+                cf.rotation_keep,
+            ),
+        }
+        .map(Self)
+    }
+
+    /// Writes the provided [`AuditRecord`] as a single JSON line.
+    ///
+    /// Failures are logged, since they should not affect the mutation
+    /// itself.
+    pub fn write(&self, record: &AuditRecord) {
+        if let Err(e) = self.0.write(record) {
+            error!("Failed to write AuditRecord: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{collections::HashMap, fs};
+
+    use super::*;
+
+    fn record(fid: &str) -> AuditRecord {
+        AuditRecord::new(
+            None,
+            AuditMethod::Delete,
+            vec![fid.to_owned()],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn describes_create_requests() {
+        let (fids, payload) = describe_create(&proto::CreateRequest {
+            parent_fid: String::new(),
+            el: Some(proto::create_request::El::Room(proto::Room {
+                id: "room".into(),
+                pipeline: HashMap::new(),
+                ..proto::Room::default()
+            })),
+        });
+        assert_eq!(fids, vec!["room".to_owned()]);
+        assert_eq!(payload, "Room with 0 Members");
+
+        let (fids, payload) = describe_create(&proto::CreateRequest {
+            parent_fid: "room/member".into(),
+            el: Some(proto::create_request::El::WebrtcPlay(
+                proto::WebRtcPlayEndpoint {
+                    id: "play".into(),
+                    src: "local://room/other/publish".into(),
+                    ..proto::WebRtcPlayEndpoint::default()
+                },
+            )),
+        });
+        assert_eq!(fids, vec!["room/member/play".to_owned()]);
+        assert_eq!(
+            payload,
+            "WebRtcPlayEndpoint from local://room/other/publish",
+        );
+    }
+
+    #[test]
+    fn serializes_error_result() {
+        let record = AuditRecord::new(
+            None,
+            AuditMethod::Create,
+            vec!["room".into()],
+            None,
+            Some(&proto::Error {
+                code: 1001,
+                text: "Room already exists.".into(),
+                doc: String::new(),
+                element: "room".into(),
+            }),
+        );
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["method"], "create");
+        assert_eq!(json["result"]["status"], "error");
+        assert_eq!(json["result"]["code"], 1001);
+        assert_eq!(json["result"]["element"], "room");
+    }
+
+    #[test]
+    fn rotates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = AuditLog::new(&conf::Audit {
+            sink: AuditSink::File,
+            file: path.to_str().unwrap().to_owned(),
+            rotation_size: 1,
+            rotation_keep: 2,
+        })
+        .unwrap();
+
+        for fid in &["first", "second", "third", "fourth"] {
+            log.write(&record(fid));
+        }
+
+        let read = |suffix: &str| {
+            let mut path = path.clone().into_os_string();
+            path.push(suffix);
+            fs::read_to_string(path).ok()
+        };
+        assert!(read("").unwrap().contains("fourth"));
+        assert!(read(".1").unwrap().contains("third"));
+        assert!(read(".2").unwrap().contains("second"));
+        assert_eq!(read(".3"), None);
+    }
+}
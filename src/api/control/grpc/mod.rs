@@ -2,4 +2,5 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+pub mod audit;
 pub mod server;
@@ -28,6 +28,7 @@ use crate::{
             ErrorCode::{ElementIdIsTooLong, ElementIdMismatch},
             ErrorResponse,
         },
+        grpc::audit::{self, AuditLog, AuditMethod, AuditRecord},
         refs::{fid::ParseFidError, Fid, StatefulFid, ToMember, ToRoom},
         EndpointId, EndpointSpec, MemberSpec, RoomSpec, TryFromProtobufError,
     },
@@ -35,8 +36,9 @@ use crate::{
     shutdown::ShutdownGracefully,
     signalling::room_service::{
         CreateEndpointInRoom, CreateMemberInRoom, CreateRoom, DeleteElements,
-        Get, RoomService, RoomServiceError, Sids,
+        Get, GetJournals, RoomService, RoomServiceError, Sids,
     },
+    tracing::{Span, SpanContext, SpanKind, TRACEPARENT},
     AppContext,
 };
 
@@ -64,7 +66,13 @@ pub enum GrpcControlApiError {
 }
 
 /// Service which provides gRPC [Control API] implementation.
-struct ControlApiService(Addr<RoomService>);
+struct ControlApiService {
+    /// [`Addr`] of the [`RoomService`] performing requested operations.
+    room_service: Addr<RoomService>,
+
+    /// [`AuditLog`] recording performed mutations.
+    audit: AuditLog,
+}
 
 impl ControlApiService {
     /// Implementation of `Create` method for [`Room`].
@@ -73,8 +81,9 @@ impl ControlApiService {
     async fn create_room(
         &self,
         spec: RoomSpec,
+        trace: Option<SpanContext>,
     ) -> Result<Sids, GrpcControlApiError> {
-        Ok(self.0.send(CreateRoom { spec }).await??)
+        Ok(self.room_service.send(CreateRoom { spec, trace }).await??)
     }
 
     /// Implementation of `Create` method for [`Member`] element.
@@ -85,13 +94,15 @@ impl ControlApiService {
         id: MemberId,
         parent_fid: Fid<ToRoom>,
         spec: MemberSpec,
+        trace: Option<SpanContext>,
     ) -> Result<Sids, GrpcControlApiError> {
         Ok(self
-            .0
+            .room_service
             .send(CreateMemberInRoom {
                 id,
                 parent_fid,
                 spec,
+                trace,
             })
             .await??)
     }
@@ -102,21 +113,27 @@ impl ControlApiService {
         id: EndpointId,
         parent_fid: Fid<ToMember>,
         spec: EndpointSpec,
+        trace: Option<SpanContext>,
     ) -> Result<Sids, GrpcControlApiError> {
         Ok(self
-            .0
+            .room_service
             .send(CreateEndpointInRoom {
                 id,
                 parent_fid,
                 spec,
+                trace,
             })
             .await??)
     }
 
     /// Creates element based on provided [`proto::CreateRequest`].
+    ///
+    /// Provided `trace` is propagated to the [`RoomService`] as a parent of
+    /// its [`Span`]s.
     async fn create_element(
         &self,
         req: proto::CreateRequest,
+        trace: Option<SpanContext>,
     ) -> Result<Sids, ErrorResponse> {
         let unparsed_parent_fid = req.parent_fid;
         let elem = if let Some(elem) = req.el {
@@ -129,7 +146,9 @@ impl ControlApiService {
         };
 
         if unparsed_parent_fid.is_empty() {
-            return Ok(self.create_room(RoomSpec::try_from(elem)?).await?);
+            return Ok(self
+                .create_room(RoomSpec::try_from(elem)?, trace)
+                .await?);
         }
 
         let parent_fid = StatefulFid::try_from(unparsed_parent_fid)?;
@@ -138,7 +157,9 @@ impl ControlApiService {
                 proto::create_request::El::Member(member) => {
                     let id: MemberId = member.id.clone().into();
                     let member_spec = MemberSpec::try_from(member)?;
-                    Ok(self.create_member(id, parent_fid, member_spec).await?)
+                    Ok(self
+                        .create_member(id, parent_fid, member_spec, trace)
+                        .await?)
                 }
                 _ => Err(ErrorResponse::new(ElementIdMismatch, &parent_fid)),
             },
@@ -164,7 +185,9 @@ impl ControlApiService {
                     }
                 };
 
-                Ok(self.create_endpoint(id, parent_fid, endpoint_spec).await?)
+                Ok(self
+                    .create_endpoint(id, parent_fid, endpoint_spec, trace)
+                    .await?)
             }
             StatefulFid::Endpoint(_) => {
                 Err(ErrorResponse::new(ElementIdIsTooLong, &parent_fid))
@@ -173,16 +196,23 @@ impl ControlApiService {
     }
 
     /// Deletes element by [`proto::IdRequest`].
+    ///
+    /// Provided `trace` is propagated to the [`RoomService`] as a parent of
+    /// its [`Span`]s.
     async fn delete_element(
         &self,
         req: proto::IdRequest,
+        trace: Option<SpanContext>,
     ) -> Result<(), GrpcControlApiError> {
         let mut delete_elements_msg = DeleteElements::new();
+        delete_elements_msg.set_trace(trace);
         for id in req.fid {
             let fid = StatefulFid::try_from(id)?;
             delete_elements_msg.add_fid(fid);
         }
-        self.0.send(delete_elements_msg.validate()?).await??;
+        self.room_service
+            .send(delete_elements_msg.validate()?)
+            .await??;
         Ok(())
     }
 
@@ -198,13 +228,35 @@ impl ControlApiService {
             fids.push(fid);
         }
 
-        let elements = self.0.send(Get(fids)).await??;
+        let elements = self.room_service.send(Get(fids)).await??;
 
         Ok(elements
             .into_iter()
             .map(|(id, value)| (id.to_string(), value))
             .collect())
     }
+
+    /// Returns journals of [`Room`]s requested by [`proto::IdRequest`],
+    /// serialized as JSON arrays of their entries.
+    ///
+    /// [`Room`]: crate::signalling::Room
+    async fn get_journals(
+        &self,
+        req: proto::IdRequest,
+    ) -> Result<HashMap<String, String>, GrpcControlApiError> {
+        let mut fids = Vec::new();
+        for id in req.fid {
+            let fid = StatefulFid::try_from(id)?;
+            fids.push(fid);
+        }
+
+        let journals = self.room_service.send(GetJournals(fids)).await??;
+
+        Ok(journals
+            .into_iter()
+            .map(|(id, journal)| (id.to_string(), journal))
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -214,14 +266,29 @@ impl ControlApi for ControlApiService {
         request: tonic::Request<proto::CreateRequest>,
     ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
         debug!("Create gRPC Request: [{:?}]", request);
+        let mut span = start_span("control_api.create", &request);
+        let caller = request.remote_addr();
+        let request = request.into_inner();
+        let (fids, payload) = audit::describe_create(&request);
+        span.set_attr("fids", fids.join(","));
         let create_response =
-            match self.create_element(request.into_inner()).await {
+            match self.create_element(request, span.context()).await {
                 Ok(sid) => proto::CreateResponse { sid, error: None },
                 Err(err) => proto::CreateResponse {
                     sid: HashMap::new(),
                     error: Some(err.into()),
                 },
             };
+        if let Some(e) = &create_response.error {
+            span.set_error(&e.text);
+        }
+        self.audit.write(&AuditRecord::new(
+            caller,
+            AuditMethod::Create,
+            fids,
+            Some(payload),
+            create_response.error.as_ref(),
+        ));
         Ok(tonic::Response::new(create_response))
     }
 
@@ -230,12 +297,28 @@ impl ControlApi for ControlApiService {
         request: tonic::Request<proto::IdRequest>,
     ) -> Result<tonic::Response<proto::Response>, Status> {
         debug!("Delete gRPC Request: [{:?}]", request);
-        let response = match self.delete_element(request.into_inner()).await {
+        let mut span = start_span("control_api.delete", &request);
+        let caller = request.remote_addr();
+        let request = request.into_inner();
+        let fids = request.fid.clone();
+        span.set_attr("fids", fids.join(","));
+        let response = match self.delete_element(request, span.context()).await
+        {
             Ok(_) => proto::Response { error: None },
             Err(e) => proto::Response {
                 error: Some(ErrorResponse::from(e).into()),
             },
         };
+        if let Some(e) = &response.error {
+            span.set_error(&e.text);
+        }
+        self.audit.write(&AuditRecord::new(
+            caller,
+            AuditMethod::Delete,
+            fids,
+            None,
+            response.error.as_ref(),
+        ));
         Ok(tonic::Response::new(response))
     }
 
@@ -244,7 +327,10 @@ impl ControlApi for ControlApiService {
         request: tonic::Request<proto::IdRequest>,
     ) -> Result<tonic::Response<proto::GetResponse>, Status> {
         debug!("Get gRPC Request: [{:?}]", request);
-        let response = match self.get_element(request.into_inner()).await {
+        let mut span = start_span("control_api.get", &request);
+        let request = request.into_inner();
+        span.set_attr("fids", request.fid.join(","));
+        let response = match self.get_element(request).await {
             Ok(elements) => proto::GetResponse {
                 elements,
                 error: None,
@@ -254,10 +340,52 @@ impl ControlApi for ControlApiService {
                 error: Some(ErrorResponse::from(e).into()),
             },
         };
+        if let Some(e) = &response.error {
+            span.set_error(&e.text);
+        }
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn get_journal(
+        &self,
+        request: tonic::Request<proto::IdRequest>,
+    ) -> Result<tonic::Response<proto::JournalResponse>, Status> {
+        debug!("GetJournal gRPC Request: [{:?}]", request);
+        let mut span = start_span("control_api.get_journal", &request);
+        let request = request.into_inner();
+        span.set_attr("fids", request.fid.join(","));
+        let response = match self.get_journals(request).await {
+            Ok(journals) => proto::JournalResponse {
+                journals,
+                error: None,
+            },
+            Err(e) => proto::JournalResponse {
+                journals: HashMap::new(),
+                error: Some(ErrorResponse::from(e).into()),
+            },
+        };
+        if let Some(e) = &response.error {
+            span.set_error(&e.text);
+        }
         Ok(tonic::Response::new(response))
     }
 }
 
+/// Starts server [`Span`] of the provided gRPC `request` as a child of the
+/// [`SpanContext`] propagated in its [`TRACEPARENT`] metadata, if any.
+fn start_span<T>(name: &'static str, request: &tonic::Request<T>) -> Span {
+    let parent = request
+        .metadata()
+        .get(TRACEPARENT)
+        .and_then(|v| v.to_str().ok())
+        .and_then(SpanContext::from_traceparent);
+    let mut span = Span::with_parent(name, parent).kind(SpanKind::Server);
+    if let Some(addr) = request.remote_addr() {
+        span.set_attr("net.peer.addr", addr);
+    }
+    span
+}
+
 /// Actor wrapper for [`tonic`] gRPC server which provides dynamic [Control
 /// API].
 ///
@@ -306,11 +434,13 @@ pub async fn run(
         futures::channel::oneshot::channel();
 
     let addr = format!("{}:{}", bind_ip, bind_port).parse().unwrap();
+    let service = ControlApiService {
+        room_service,
+        audit: app.audit.clone(),
+    };
     Arbiter::spawn(async move {
         Server::builder()
-            .add_service(TonicControlApiServer::new(ControlApiService(
-                room_service,
-            )))
+            .add_service(TonicControlApiServer::new(service))
             .serve_with_shutdown(addr, async move {
                 grpc_shutdown_rx.await.ok();
             })
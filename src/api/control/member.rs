@@ -2,11 +2,7 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
-use std::{
-    collections::HashMap,
-    convert::{TryFrom, TryInto},
-    time::Duration,
-};
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
 use medea_client_api_proto::{self as client_proto, MemberId as Id};
 use medea_control_api_proto::grpc::api as proto;
@@ -19,6 +15,7 @@ use crate::{
             webrtc_play_endpoint::WebRtcPlayEndpoint,
             webrtc_publish_endpoint::{WebRtcPublishEndpoint, WebRtcPublishId},
         },
+        parse_duration,
         pipeline::Pipeline,
         room::RoomElement,
         EndpointId, EndpointSpec, TryFromElementError, TryFromProtobufError,
@@ -91,7 +88,7 @@ impl From<Credential> for proto::member::Credentials {
 /// Element of [`Member`]'s [`Pipeline`].
 ///
 /// [`Member`]: crate::signalling::elements::Member
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
 #[serde(tag = "kind")]
 pub enum MemberElement {
     /// Represent [`WebRtcPublishEndpoint`].
@@ -122,6 +119,10 @@ pub struct MemberSpec {
     /// URL to which `OnLeave` Control API callback will be sent.
     on_leave: Option<CallbackUrl>,
 
+    /// URL to which `OnCallRecord`, `OnNetworkCongestion` and
+    /// `OnPeerMetricsEvent` Control API callbacks will be sent.
+    on_metrics: Option<CallbackUrl>,
+
     /// Timeout of receiving heartbeat messages from the `Member` via Client
     /// API.
     ///
@@ -135,6 +136,17 @@ pub struct MemberSpec {
 
     /// Interval of sending `Ping`s to the `Member` via Client API.
     ping_interval: Option<Duration>,
+
+    /// Indicator whether the `Member` should be removed automatically once it
+    /// leaves its `Room`.
+    remove_on_leave: bool,
+
+    /// Region of the `Member`, used to select STUN/TURN servers for it.
+    region: Option<String>,
+
+    /// Indicator whether the `Member` is external, so its private host ICE
+    /// candidates are not revealed to other `Member`s.
+    external: bool,
 }
 
 impl Into<RoomElement> for MemberSpec {
@@ -144,33 +156,46 @@ impl Into<RoomElement> for MemberSpec {
             credentials: self.credentials,
             on_join: self.on_join,
             on_leave: self.on_leave,
+            on_metrics: self.on_metrics,
             idle_timeout: self.idle_timeout,
             reconnect_timeout: self.reconnect_timeout,
             ping_interval: self.ping_interval,
+            remove_on_leave: self.remove_on_leave,
+            region: self.region,
+            external: self.external,
         }
     }
 }
 
 impl MemberSpec {
     /// Creates new [`MemberSpec`] with the given parameters.
+    #[allow(clippy::too_many_arguments)]
     #[inline]
     pub fn new(
         pipeline: Pipeline<EndpointId, MemberElement>,
         credentials: Credential,
         on_join: Option<CallbackUrl>,
         on_leave: Option<CallbackUrl>,
+        on_metrics: Option<CallbackUrl>,
         idle_timeout: Option<Duration>,
         reconnect_timeout: Option<Duration>,
         ping_interval: Option<Duration>,
+        remove_on_leave: bool,
+        region: Option<String>,
+        external: bool,
     ) -> Self {
         Self {
             pipeline,
             credentials,
             on_join,
             on_leave,
+            on_metrics,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            remove_on_leave,
+            region,
+            external,
         }
     }
 
@@ -226,6 +251,11 @@ impl MemberSpec {
         &self.on_leave
     }
 
+    /// Returns reference to `on_metrics` [`CallbackUrl`].
+    pub fn on_metrics(&self) -> &Option<CallbackUrl> {
+        &self.on_metrics
+    }
+
     /// Returns timeout of receiving heartbeat messages from the `Member` via
     /// Client API.
     ///
@@ -245,23 +275,30 @@ impl MemberSpec {
     pub fn ping_interval(&self) -> Option<Duration> {
         self.ping_interval
     }
+
+    /// Indicates whether the `Member` should be removed automatically once it
+    /// leaves its `Room`.
+    pub fn remove_on_leave(&self) -> bool {
+        self.remove_on_leave
+    }
+
+    /// Returns region of the `Member`, used to select STUN/TURN servers for
+    /// it.
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Indicates whether the `Member` is external, so its private host ICE
+    /// candidates are not revealed to other `Member`s.
+    pub fn is_external(&self) -> bool {
+        self.external
+    }
 }
 
 impl TryFrom<proto::Member> for MemberSpec {
     type Error = TryFromProtobufError;
 
     fn try_from(member: proto::Member) -> Result<Self, Self::Error> {
-        fn parse_duration<T: TryInto<Duration>>(
-            duration: Option<T>,
-            member_id: &str,
-            field: &'static str,
-        ) -> Result<Option<Duration>, TryFromProtobufError> {
-            #[allow(clippy::map_err_ignore)]
-            duration.map(TryInto::try_into).transpose().map_err(|_| {
-                TryFromProtobufError::NegativeDuration(member_id.into(), field)
-            })
-        }
-
         let mut pipeline = HashMap::new();
         for (id, member_element) in member.pipeline {
             if let Some(elem) = member_element.el {
@@ -293,6 +330,14 @@ impl TryFrom<proto::Member> for MemberSpec {
                 Some(CallbackUrl::try_from(on_join)?)
             }
         };
+        let on_metrics = {
+            let on_metrics = member.on_metrics;
+            if on_metrics.is_empty() {
+                None
+            } else {
+                Some(CallbackUrl::try_from(on_metrics)?)
+            }
+        };
 
         let idle_timeout =
             parse_duration(member.idle_timeout, &member.id, "idle_timeout")?;
@@ -309,9 +354,13 @@ impl TryFrom<proto::Member> for MemberSpec {
             credentials,
             on_join,
             on_leave,
+            on_metrics,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            remove_on_leave: member.remove_on_leave,
+            region: Some(member.region).filter(|r| !r.is_empty()),
+            external: member.external,
         })
     }
 }
@@ -352,17 +401,25 @@ impl TryFrom<&RoomElement> for MemberSpec {
                 credentials,
                 on_leave,
                 on_join,
+                on_metrics,
                 idle_timeout,
                 reconnect_timeout,
                 ping_interval,
+                remove_on_leave,
+                region,
+                external,
             } => Ok(Self {
                 pipeline: spec.clone(),
                 credentials: credentials.clone(),
                 on_leave: on_leave.clone(),
                 on_join: on_join.clone(),
+                on_metrics: on_metrics.clone(),
                 idle_timeout: *idle_timeout,
                 reconnect_timeout: *reconnect_timeout,
                 ping_interval: *ping_interval,
+                remove_on_leave: *remove_on_leave,
+                region: region.clone(),
+                external: *external,
             }),
             _ => Err(TryFromElementError::NotMember),
         }
@@ -11,7 +11,14 @@ pub mod pipeline;
 pub mod refs;
 pub mod room;
 
-use std::{convert::TryFrom as _, fs::File, io::Read as _, path::Path};
+use std::{
+    collections::HashMap,
+    convert::{TryFrom as _, TryInto},
+    fs::{self, File},
+    io::Read as _,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use actix::Addr;
 use derive_more::Display;
@@ -20,10 +27,12 @@ use medea_client_api_proto::{MemberId, RoomId};
 use serde::Deserialize;
 
 use crate::{
-    api::control::callback::url::CallbackUrlParseError,
+    api::control::callback::url::{CallbackUrl, CallbackUrlParseError},
+    conf,
     log::prelude::*,
-    signalling::room_service::{
-        RoomService, RoomServiceError, StartStaticRooms,
+    signalling::{
+        elements::{parse_members, MembersLoadError},
+        room_service::{RoomService, RoomServiceError, StartStaticRooms},
     },
 };
 
@@ -103,6 +112,24 @@ impl From<CallbackUrlParseError> for TryFromProtobufError {
     }
 }
 
+/// Parses optional protobuf [`Duration`] field of the element with the
+/// provided ID.
+///
+/// # Errors
+///
+/// Errors with [`TryFromProtobufError::NegativeDuration`] if the provided
+/// duration is negative.
+pub(crate) fn parse_duration<T: TryInto<Duration>>(
+    duration: Option<T>,
+    element_id: &str,
+    field: &'static str,
+) -> Result<Option<Duration>, TryFromProtobufError> {
+    #[allow(clippy::map_err_ignore)]
+    duration.map(TryInto::try_into).transpose().map_err(|_| {
+        TryFromProtobufError::NegativeDuration(element_id.into(), field)
+    })
+}
+
 /// Root elements of [Control API] spec.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
@@ -114,6 +141,15 @@ pub enum RootElement {
     Room {
         id: RoomId,
         spec: Pipeline<MemberId, RoomElement>,
+        #[serde(default, with = "humantime_serde")]
+        empty_timeout: Option<Duration>,
+        #[serde(default, with = "humantime_serde")]
+        max_duration: Option<Duration>,
+        on_close: Option<CallbackUrl>,
+        #[serde(default)]
+        max_members: Option<usize>,
+        #[serde(default)]
+        max_publishers: Option<usize>,
     },
 }
 
@@ -170,6 +206,24 @@ pub enum LoadStaticControlSpecsError {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     #[display(fmt = "Error while deserialization static spec. {:?}", _0)]
     YamlDeserializationError(serde_yaml::Error),
+
+    /// Error while deserialization static [Control API] specs from JSON file.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Error while deserialization static spec. {:?}", _0)]
+    JsonDeserializationError(serde_json::Error),
+
+    /// Error while deserialization static [Control API] specs from TOML file.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Error while deserialization static spec. {:?}", _0)]
+    TomlDeserializationError(toml::de::Error),
+
+    /// Static [Control API] spec file has unsupported extension.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Unsupported static spec file format: {:?}", _0)]
+    UnsupportedFormat(PathBuf),
 }
 
 impl From<std::io::Error> for LoadStaticControlSpecsError {
@@ -190,47 +244,186 @@ impl From<serde_yaml::Error> for LoadStaticControlSpecsError {
     }
 }
 
-/// Loads [`RoomSpec`] from file with YAML format.
+impl From<serde_json::Error> for LoadStaticControlSpecsError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::JsonDeserializationError(err)
+    }
+}
+
+impl From<toml::de::Error> for LoadStaticControlSpecsError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::TomlDeserializationError(err)
+    }
+}
+
+/// Format of a static [Control API] spec file.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpecFormat {
+    /// YAML format (`.yml` or `.yaml` files).
+    Yaml,
+
+    /// JSON format (`.json` files).
+    Json,
+
+    /// TOML format (`.toml` files).
+    Toml,
+}
+
+impl SpecFormat {
+    /// Detects [`SpecFormat`] of the file by the provided [`Path`] extension.
+    ///
+    /// Returns [`None`] if the file extension is not supported.
+    #[must_use]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Parses [`RoomSpec`] from the provided string of this [`SpecFormat`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`LoadStaticControlSpecsError`] if deserialization or
+    /// [`RoomSpec`] conversation fails.
+    pub fn parse(
+        self,
+        spec: &str,
+    ) -> Result<RoomSpec, LoadStaticControlSpecsError> {
+        let parsed: RootElement = match self {
+            Self::Yaml => serde_yaml::from_str(spec)?,
+            Self::Json => serde_json::from_str(spec)?,
+            Self::Toml => toml::from_str(spec)?,
+        };
+        Ok(RoomSpec::try_from(&parsed)?)
+    }
+}
+
+/// Loads [`RoomSpec`] from file with YAML, JSON or TOML format.
+///
+/// Format is detected by file extension (see [`SpecFormat::from_path`]).
 ///
 /// # Errors
 ///
+/// Errors with [`LoadStaticControlSpecsError::UnsupportedFormat`] if file
+/// extension is not supported.
+///
 /// Errors with [`LoadStaticControlSpecsError::IoError`] if reading of provided
 /// [`Path`] to file fails.
 ///
-/// Errors with [`LoadStaticControlSpecsError::YamlDeserializationError`] if
-/// YAML deserialization fails.
+/// Errors with [`LoadStaticControlSpecsError::YamlDeserializationError`],
+/// [`LoadStaticControlSpecsError::JsonDeserializationError`] or
+/// [`LoadStaticControlSpecsError::TomlDeserializationError`] if
+/// deserialization fails.
 ///
 /// Errors with [`LoadStaticControlSpecsError::TryFromElementError`] if
 /// [`RoomSpec`] conversation fails.
-pub fn load_from_yaml_file<P: AsRef<Path>>(
+pub fn load_from_file<P: AsRef<Path>>(
     path: P,
 ) -> Result<RoomSpec, LoadStaticControlSpecsError> {
+    let format = SpecFormat::from_path(&path).ok_or_else(|| {
+        LoadStaticControlSpecsError::UnsupportedFormat(
+            path.as_ref().to_path_buf(),
+        )
+    })?;
     let mut file = File::open(path)?;
     let mut buf = String::new();
     file.read_to_string(&mut buf)?;
-    let parsed: RootElement = serde_yaml::from_str(&buf)?;
-    let room = RoomSpec::try_from(&parsed)?;
-    Ok(room)
+    format.parse(&buf)
 }
 
-/// Loads all [`RoomSpec`] from YAML files from provided path.
+/// Lists all static [Control API] spec files of supported formats (see
+/// [`SpecFormat`]) in the provided directory along with their last
+/// modification time.
 ///
 /// # Errors
 ///
 /// Errors with [`LoadStaticControlSpecsError::SpecDirReadError`] if reading
 /// provided [`Path`] fails.
-pub fn load_static_specs_from_dir<P: AsRef<Path>>(
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub fn list_static_spec_files<P: AsRef<Path>>(
     path: P,
-) -> Result<Vec<RoomSpec>, LoadStaticControlSpecsError> {
-    let mut specs = Vec::new();
-    for entry in std::fs::read_dir(path)
+) -> Result<HashMap<PathBuf, SystemTime>, LoadStaticControlSpecsError> {
+    let mut files = HashMap::new();
+    for entry in fs::read_dir(path)
         .map_err(LoadStaticControlSpecsError::SpecDirReadError)?
     {
-        let entry = entry?;
-        let spec = load_from_yaml_file(entry.path())?;
-        specs.push(spec)
+        let path = entry?.path();
+        if SpecFormat::from_path(&path).is_none() {
+            continue;
+        }
+        let metadata = fs::metadata(&path)?;
+        if metadata.is_file() {
+            files.insert(path, metadata.modified()?);
+        }
     }
-    Ok(specs)
+    Ok(files)
+}
+
+/// Errors which can happen while validating static [Control API] spec.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Display, Fail)]
+pub enum SpecValidationError {
+    /// Spec cannot be loaded.
+    #[display(fmt = "{}", _0)]
+    Load(LoadStaticControlSpecsError),
+
+    /// `Member`s of the spec cannot be loaded, which is the case of dangling
+    /// source URI references.
+    #[display(fmt = "{}", _0)]
+    Members(MembersLoadError),
+
+    /// `WebRtcPlayEndpoint` references `Room` other than its own.
+    #[display(
+        fmt = "Endpoint [id = {}/{}] references source in the other Room [id \
+               = {}]",
+        _0,
+        _1,
+        _2
+    )]
+    ForeignSrcUri(MemberId, EndpointId, RoomId),
+}
+
+/// Validates static [Control API] spec file in the same way as it's done on
+/// [`Room`] start, along with checking that all source URIs reference
+/// existing `Endpoint`s of the same [`Room`].
+///
+/// Returns the validated [`RoomSpec`].
+///
+/// # Errors
+///
+/// Errors with [`SpecValidationError`] if validation fails.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+/// [`Room`]: crate::signalling::room::Room
+pub fn validate_spec_file<P: AsRef<Path>>(
+    path: P,
+    rpc_conf: conf::Rpc,
+) -> Result<RoomSpec, SpecValidationError> {
+    let spec = load_from_file(path).map_err(SpecValidationError::Load)?;
+    let members = spec
+        .members()
+        .map_err(|e| SpecValidationError::Load(e.into()))?;
+    for (member_id, member) in &members {
+        for (endpoint_id, play) in member.play_endpoints() {
+            if play.src.room_id != spec.id {
+                return Err(SpecValidationError::ForeignSrcUri(
+                    member_id.clone(),
+                    String::from(endpoint_id).into(),
+                    play.src.room_id.clone(),
+                ));
+            }
+        }
+    }
+    parse_members(&spec, rpc_conf).map_err(SpecValidationError::Members)?;
+    Ok(spec)
 }
 
 /// Starts all [`Room`]s from static [Control API] specs.
@@ -252,8 +445,64 @@ pub async fn start_static_rooms(
              specs not loaded. {}",
             e,
         ),
-        Err(e) => panic!("{}", e),
+        Err(e) => error!("Failed to start static Control API specs: {}", e),
         Ok(_) => {}
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    /// Writes the provided spec into a temporary YAML file.
+    fn spec_file(spec: &str) -> tempfile::NamedTempFile {
+        let mut file =
+            tempfile::Builder::new().suffix(".yml").tempfile().unwrap();
+        file.write_all(spec.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn validates_correct_spec() {
+        let file = spec_file(include_str!(
+            "../../../tests/specs/pub-sub-video-call.yml"
+        ));
+
+        let spec =
+            validate_spec_file(file.path(), conf::Rpc::default()).unwrap();
+
+        assert_eq!(spec.id, "pub-sub-video-call".into());
+    }
+
+    #[test]
+    fn detects_dangling_src_uri() {
+        let spec = include_str!("../../../tests/specs/pub-sub-video-call.yml");
+        let file = spec_file(&spec.replace("caller/publish", "caller/foo"));
+
+        let res = validate_spec_file(file.path(), conf::Rpc::default());
+
+        assert!(matches!(
+            res,
+            Err(SpecValidationError::Members(
+                MembersLoadError::EndpointNotFound(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn detects_foreign_src_uri() {
+        let spec = include_str!("../../../tests/specs/pub-sub-video-call.yml");
+        let file =
+            spec_file(&spec.replace(
+                "local://pub-sub-video-call/",
+                "local://another-room/",
+            ));
+
+        let res = validate_spec_file(file.path(), conf::Rpc::default());
+
+        assert!(matches!(res, Err(SpecValidationError::ForeignSrcUri(..))));
+    }
+}
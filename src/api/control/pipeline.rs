@@ -11,7 +11,7 @@ use std::{
 use serde::Deserialize;
 
 /// Entity that represents some pipeline of spec.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
 pub struct Pipeline<K: Hash + Eq, V> {
     pipeline: HashMap<K, V>,
 }
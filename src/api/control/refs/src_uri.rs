@@ -53,7 +53,7 @@ pub enum SrcParseError {
 /// crate::api::control::endpoints::WebRtcPublishEndpoint
 /// [Control API]: https://tinyurl.com/yxsqplq7
 /// [`EndpointId`]: crate::api::control::EndpointId
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SrcUri {
     /// ID of [`Room`].
     ///
@@ -2,14 +2,18 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
-use std::{collections::HashMap, convert::TryFrom, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    time::Duration,
+};
 
 use medea_client_api_proto::{MemberId, RoomId as Id};
 use medea_control_api_proto::grpc::api as proto;
 use serde::Deserialize;
 
 use crate::api::control::{
-    callback::url::CallbackUrl, member::Credential, EndpointId,
+    callback::url::CallbackUrl, member::Credential, parse_duration, EndpointId,
     TryFromProtobufError,
 };
 
@@ -22,7 +26,7 @@ use super::{
 /// Element of [`Room`]'s [`Pipeline`].
 ///
 /// [`Room`]: crate::signalling::room::Room
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
 #[serde(tag = "kind")]
 pub enum RoomElement {
     /// Represent [`MemberSpec`].
@@ -32,24 +36,68 @@ pub enum RoomElement {
         credentials: Credential,
         on_leave: Option<CallbackUrl>,
         on_join: Option<CallbackUrl>,
+        #[serde(default)]
+        on_metrics: Option<CallbackUrl>,
         #[serde(default, with = "humantime_serde")]
         idle_timeout: Option<Duration>,
         #[serde(default, with = "humantime_serde")]
         reconnect_timeout: Option<Duration>,
         #[serde(default, with = "humantime_serde")]
         ping_interval: Option<Duration>,
+        #[serde(default)]
+        remove_on_leave: bool,
+        #[serde(default)]
+        region: Option<String>,
+        #[serde(default)]
+        external: bool,
     },
 }
 
+impl RoomElement {
+    /// Returns IDs of `Member`s which this [`RoomElement`] plays media from.
+    fn play_src_members(&self) -> impl Iterator<Item = &MemberId> {
+        let Self::Member { spec, .. } = self;
+        spec.iter().filter_map(|(_, element)| match element {
+            MemberElement::WebRtcPlayEndpoint { spec } => {
+                Some(&spec.src.member_id)
+            }
+            MemberElement::WebRtcPublishEndpoint { .. } => None,
+        })
+    }
+}
+
 /// [Control API]'s `Room` element specification.
 ///
 /// Newtype for [`RootElement::Room`].
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RoomSpec {
     pub id: Id,
     pub pipeline: Pipeline<MemberId, RoomElement>,
+
+    /// Timeout of the `Room` staying without any connected `Member`s.
+    ///
+    /// Once reached, the `Room` is closed and removed automatically.
+    pub empty_timeout: Option<Duration>,
+
+    /// Maximum duration of the `Room` existence.
+    ///
+    /// Once reached, the `Room` is closed and removed automatically.
+    pub max_duration: Option<Duration>,
+
+    /// URL to which `OnRoomClosed` Control API callback will be sent.
+    pub on_close: Option<CallbackUrl>,
+
+    /// Maximum number of `Member`s in the `Room`.
+    ///
+    /// If [`None`], then the default limit of a media server is used.
+    pub max_members: Option<usize>,
+
+    /// Maximum number of publishing `Member`s in the `Room`.
+    ///
+    /// If [`None`], then the default limit of a media server is used.
+    pub max_publishers: Option<usize>,
 }
 
 impl TryFrom<proto::create_request::El> for RoomSpec {
@@ -71,10 +119,33 @@ impl TryFrom<proto::create_request::El> for RoomSpec {
                     }
                 }
 
+                let empty_timeout = parse_duration(
+                    room.empty_timeout,
+                    &room.id,
+                    "empty_timeout",
+                )?;
+                let max_duration = parse_duration(
+                    room.max_duration,
+                    &room.id,
+                    "max_duration",
+                )?;
+                let on_close = if room.on_close.is_empty() {
+                    None
+                } else {
+                    Some(CallbackUrl::try_from(room.on_close)?)
+                };
+
                 let pipeline = Pipeline::new(pipeline);
                 return Ok(Self {
                     id: room.id.into(),
                     pipeline,
+                    empty_timeout,
+                    max_duration,
+                    on_close,
+                    max_members: Some(room.max_members as usize)
+                        .filter(|max| *max > 0),
+                    max_publishers: Some(room.max_publishers as usize)
+                        .filter(|max| *max > 0),
                 });
             }
             El::Member(member) => member.id,
@@ -113,6 +184,53 @@ impl RoomSpec {
     pub fn id(&self) -> &Id {
         &self.id
     }
+
+    /// Calculates which `Member`s should be deleted from this [`RoomSpec`]
+    /// and which `Member`s of the `new` [`RoomSpec`] should be created to
+    /// turn this [`RoomSpec`]'s pipeline into the `new` one.
+    ///
+    /// Changed `Member`s and `Member`s playing media from the changed or
+    /// deleted ones are recreated.
+    ///
+    /// Returns IDs of the `Member`s to be deleted and IDs of the `Member`s to
+    /// be created.
+    #[must_use]
+    pub fn members_diff(
+        &self,
+        new: &Self,
+    ) -> (HashSet<MemberId>, HashSet<MemberId>) {
+        let mut stale: HashSet<MemberId> = self
+            .pipeline
+            .iter()
+            .filter(|(id, element)| new.pipeline.get(id) != Some(element))
+            .map(|(id, _)| id.clone())
+            .collect();
+        loop {
+            let dependent: Vec<_> = self
+                .pipeline
+                .iter()
+                .filter(|(id, element)| {
+                    !stale.contains(id)
+                        && element.play_src_members().any(|m| stale.contains(m))
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            if dependent.is_empty() {
+                break;
+            }
+            stale.extend(dependent);
+        }
+
+        let created = new
+            .pipeline
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| stale.contains(id) || self.pipeline.get(id).is_none())
+            .cloned()
+            .collect();
+
+        (stale, created)
+    }
 }
 
 impl TryFrom<&RootElement> for RoomSpec {
@@ -122,11 +240,111 @@ impl TryFrom<&RootElement> for RoomSpec {
     #[allow(unreachable_patterns)]
     fn try_from(from: &RootElement) -> Result<Self, Self::Error> {
         match from {
-            RootElement::Room { id, spec } => Ok(Self {
+            RootElement::Room {
+                id,
+                spec,
+                empty_timeout,
+                max_duration,
+                on_close,
+                max_members,
+                max_publishers,
+            } => Ok(Self {
                 id: id.clone(),
                 pipeline: spec.clone(),
+                empty_timeout: *empty_timeout,
+                max_duration: *max_duration,
+                on_close: on_close.clone(),
+                max_members: *max_members,
+                max_publishers: *max_publishers,
             }),
             _ => Err(TryFromElementError::NotRoom),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns [`RoomSpec`] parsed from the provided YAML.
+    fn spec(yaml: &str) -> RoomSpec {
+        let parsed: RootElement = serde_yaml::from_str(yaml).unwrap();
+        RoomSpec::try_from(&parsed).unwrap()
+    }
+
+    /// Returns sorted IDs of the provided `Member`s.
+    fn ids(members: HashSet<MemberId>) -> Vec<String> {
+        let mut ids: Vec<_> = members.into_iter().map(|id| id.0).collect();
+        ids.sort();
+        ids
+    }
+
+    const PUB_SUB: &str = r#"
+        kind: Room
+        id: test-room
+        spec:
+          pipeline:
+            caller:
+              kind: Member
+              credentials:
+                plain: test
+              spec:
+                pipeline:
+                  publish:
+                    kind: WebRtcPublishEndpoint
+                    spec:
+                      p2p: Always
+            responder:
+              kind: Member
+              credentials:
+                plain: test
+              spec:
+                pipeline:
+                  play:
+                    kind: WebRtcPlayEndpoint
+                    spec:
+                      src: "local://test-room/caller/publish"
+    "#;
+
+    #[test]
+    fn no_diff_for_same_spec() {
+        let (deleted, created) = spec(PUB_SUB).members_diff(&spec(PUB_SUB));
+
+        assert!(deleted.is_empty());
+        assert!(created.is_empty());
+    }
+
+    #[test]
+    fn recreates_dependent_members() {
+        let changed = PUB_SUB.replace("p2p: Always", "p2p: Never");
+
+        let (deleted, created) = spec(PUB_SUB).members_diff(&spec(&changed));
+
+        assert_eq!(ids(deleted), ["caller", "responder"]);
+        assert_eq!(ids(created), ["caller", "responder"]);
+    }
+
+    #[test]
+    fn recreates_only_changed_member() {
+        let changed = PUB_SUB.replace(
+            "src: \"local://test-room/caller/publish\"",
+            "src: \"local://test-room/caller/publish\"\n                      \
+             force_relay: true",
+        );
+
+        let (deleted, created) = spec(PUB_SUB).members_diff(&spec(&changed));
+
+        assert_eq!(ids(deleted), ["responder"]);
+        assert_eq!(ids(created), ["responder"]);
+    }
+
+    #[test]
+    fn deletes_removed_members() {
+        let removed = PUB_SUB.split("            responder:").next().unwrap();
+
+        let (deleted, created) = spec(PUB_SUB).members_diff(&spec(removed));
+
+        assert_eq!(ids(deleted), ["responder"]);
+        assert!(created.is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 pub mod client;
 pub mod control;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, net::IpAddr};
 
 use actix::MailboxError;
 use futures::future::LocalBoxFuture;
@@ -31,12 +31,19 @@ pub enum RpcServerError {
     ///
     /// [`Room`]: crate::signalling::room::Room
     RoomMailbox(MailboxError),
+
+    /// [`Room`] has reached its capacity limits.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    RoomIsFull,
 }
 
 impl From<RoomError> for RpcServerError {
     fn from(err: RoomError) -> Self {
         match &err {
             RoomError::AuthorizationError => Self::Authorization,
+            RoomError::MembersLimitReached(_)
+            | RoomError::PublishersLimitReached(_) => Self::RoomIsFull,
             _ => Self::RoomError(err),
         }
     }
@@ -49,11 +56,15 @@ pub trait RpcServer: Debug + Send {
     /// [`Member`]. Transport should consider dropping connection if message
     /// result is err.
     ///
+    /// `client_ip` is the IP address the [`Member`] is connected from, if
+    /// known.
+    ///
     /// [`Member`]: crate::signalling::elements::Member
     fn connection_established(
         &self,
         member_id: MemberId,
         credential: Credential,
+        client_ip: Option<IpAddr>,
         connection: Box<dyn RpcConnection>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>;
 
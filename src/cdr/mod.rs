@@ -0,0 +1,220 @@
+//! Call detail records (CDR) summarizing sessions of `Member`s and `Room`s.
+
+mod recorder;
+mod sink;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use medea_client_api_proto::{
+    ConnectionQualityScore, MemberId, PeerId, RoomId,
+};
+use medea_control_api_proto::grpc::callback as proto;
+use serde::Serialize;
+
+use crate::{
+    api::control::callback::{MediaDirection, MediaType, OnLeaveReason},
+    turn::RelayUsage,
+};
+
+#[doc(inline)]
+pub use self::{recorder::CallRecorder, sink::CdrSink};
+
+/// Call detail record of a `Member` or a `Room`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CallRecord {
+    /// Session of a `Member`, which has left its `Room`.
+    Member(MemberRecord),
+
+    /// All sessions of a closed `Room`.
+    Room(RoomRecord),
+}
+
+/// Call detail record of all sessions of a `Room`.
+#[derive(Clone, Debug, Serialize)]
+pub struct RoomRecord {
+    /// ID of the `Room`.
+    pub room_id: RoomId,
+
+    /// Time of the `Room` starting.
+    pub started_at: DateTime<Utc>,
+
+    /// Time of the `Room` closing.
+    pub closed_at: DateTime<Utc>,
+
+    /// Sessions of the `Room`'s `Member`s, in order of their joining.
+    pub members: Vec<MemberRecord>,
+}
+
+impl Into<proto::RoomRecord> for RoomRecord {
+    fn into(self) -> proto::RoomRecord {
+        proto::RoomRecord {
+            started_at: self.started_at.to_rfc3339(),
+            closed_at: self.closed_at.to_rfc3339(),
+            members: self.members.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Call detail record of a single session of a `Member`.
+#[derive(Clone, Debug, Serialize)]
+pub struct MemberRecord {
+    /// ID of the `Room` the `Member` belongs to.
+    pub room_id: RoomId,
+
+    /// ID of the `Member`.
+    pub member_id: MemberId,
+
+    /// Time of the `Member` joining its `Room`.
+    pub joined_at: DateTime<Utc>,
+
+    /// Time of the `Member` leaving its `Room`.
+    pub left_at: DateTime<Utc>,
+
+    /// Reason of why the `Member` left its `Room`.
+    ///
+    /// [`None`] if the `Member` was still connected when its `Room` was
+    /// closed.
+    pub leave_reason: Option<OnLeaveReason>,
+
+    /// Traffic relayed through TURN servers for the `Member` so far.
+    pub relay_usage: RelayUsage,
+
+    /// `Peer`s of the `Member` during the session, in order of their starting.
+    pub peers: Vec<PeerRecord>,
+}
+
+impl Into<proto::MemberRecord> for MemberRecord {
+    fn into(self) -> proto::MemberRecord {
+        let relay_usage = self.relay_usage;
+        proto::MemberRecord {
+            member_id: self.member_id.to_string(),
+            joined_at: self.joined_at.to_rfc3339(),
+            left_at: self.left_at.to_rfc3339(),
+            leave: self.leave_reason.map(|reason| {
+                let reason: proto::on_leave::Reason = reason.into();
+                proto::OnLeave {
+                    reason: reason as i32,
+                    relay_usage: Some(relay_usage.into()),
+                }
+            }),
+            relay_usage: Some(relay_usage.into()),
+            peers: self.peers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Call detail record of a `Peer`.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerRecord {
+    /// ID of the `Peer`.
+    pub peer_id: PeerId,
+
+    /// ID of the `Member` owning the partner `Peer`.
+    pub partner_member_id: MemberId,
+
+    /// Time of the `Peer` starting.
+    pub started_at: DateTime<Utc>,
+
+    /// Duration of the `Peer` existence.
+    #[serde(with = "humantime_serde")]
+    pub duration: Duration,
+
+    /// Number of ICE restarts caused by failed connection of the `Peer`.
+    pub ice_restarts: u32,
+
+    /// [`ConnectionQualityScore`]s of the `Peer`, in order of their
+    /// calculation.
+    pub quality_history: Vec<QualityScoreRecord>,
+
+    /// Intervals when traffic of the `Peer` stopped flowing.
+    pub traffic_stopped: Vec<TrafficStoppedRecord>,
+
+    /// Traffic relayed through TURN servers for the `Peer`.
+    pub relay_usage: RelayUsage,
+}
+
+impl Into<proto::PeerRecord> for PeerRecord {
+    fn into(self) -> proto::PeerRecord {
+        proto::PeerRecord {
+            peer_id: u64::from(self.peer_id.0),
+            partner_member_id: self.partner_member_id.to_string(),
+            started_at: self.started_at.to_rfc3339(),
+            duration: Some(self.duration.into()),
+            ice_restarts: self.ice_restarts,
+            quality_history: self
+                .quality_history
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            traffic_stopped: self
+                .traffic_stopped
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            relay_usage: Some(self.relay_usage.into()),
+        }
+    }
+}
+
+/// [`ConnectionQualityScore`] calculated at some time.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct QualityScoreRecord {
+    /// Time of calculating the [`ConnectionQualityScore`].
+    pub at: DateTime<Utc>,
+
+    /// Calculated [`ConnectionQualityScore`].
+    pub score: ConnectionQualityScore,
+}
+
+impl Into<proto::peer_record::QualityScore> for QualityScoreRecord {
+    fn into(self) -> proto::peer_record::QualityScore {
+        proto::peer_record::QualityScore {
+            at: self.at.to_rfc3339(),
+            score: self.score as u32,
+        }
+    }
+}
+
+/// Interval when traffic of some [`MediaType`] stopped flowing.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct TrafficStoppedRecord {
+    /// [`MediaType`] of the stopped traffic.
+    pub media_type: MediaType,
+
+    /// [`MediaDirection`] of the stopped traffic.
+    pub direction: MediaDirection,
+
+    /// Time of the traffic stopping.
+    pub from: DateTime<Utc>,
+
+    /// Time of the traffic restoring.
+    ///
+    /// [`None`] if the traffic was not restored until the `Peer` was removed.
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl Into<proto::peer_record::TrafficStopped> for TrafficStoppedRecord {
+    fn into(self) -> proto::peer_record::TrafficStopped {
+        use proto::peer_record::{
+            MediaDirection as Direction, MediaType as Type,
+        };
+
+        let media_type = match self.media_type {
+            MediaType::Audio => Type::Audio,
+            MediaType::Video => Type::Video,
+            MediaType::Both => Type::Both,
+        };
+        let direction = match self.direction {
+            MediaDirection::Publish => Direction::Publish,
+            MediaDirection::Play => Direction::Play,
+        };
+        proto::peer_record::TrafficStopped {
+            media_type: media_type as i32,
+            direction: direction as i32,
+            from: self.from.to_rfc3339(),
+            to: self.to.map(|to| to.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! Recorder of [`CallRecord`]s of a single `Room`.
+//!
+//! [`CallRecord`]: crate::cdr::CallRecord
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use medea_client_api_proto::{
+    ConnectionQualityScore, MemberId, PeerId, RoomId,
+};
+
+use crate::{
+    api::control::callback::{MediaDirection, MediaType, OnLeaveReason},
+    cdr::{
+        MemberRecord, PeerRecord, QualityScoreRecord, RoomRecord,
+        TrafficStoppedRecord,
+    },
+    turn::RelayUsage,
+};
+
+/// `Peer` being recorded at the moment.
+#[derive(Debug)]
+struct ActivePeer {
+    /// ID of the `Member` owning this `Peer`.
+    member_id: MemberId,
+
+    /// ID of the `Member` owning the partner `Peer`.
+    partner_member_id: MemberId,
+
+    /// Time of this `Peer` starting.
+    started_at: DateTime<Utc>,
+
+    /// Number of ICE restarts caused by failed connection of this `Peer`.
+    ice_restarts: u32,
+
+    /// [`ConnectionQualityScore`]s of this `Peer` calculated so far.
+    quality_history: Vec<QualityScoreRecord>,
+
+    /// Intervals when traffic of this `Peer` stopped flowing so far.
+    traffic_stopped: Vec<TrafficStoppedRecord>,
+}
+
+impl ActivePeer {
+    /// Finishes recording of this [`ActivePeer`] with the provided [`PeerId`].
+    fn finish(self, peer_id: PeerId) -> PeerRecord {
+        PeerRecord {
+            peer_id,
+            partner_member_id: self.partner_member_id,
+            started_at: self.started_at,
+            duration: (Utc::now() - self.started_at)
+                .to_std()
+                .unwrap_or_default(),
+            ice_restarts: self.ice_restarts,
+            quality_history: self.quality_history,
+            traffic_stopped: self.traffic_stopped,
+            relay_usage: RelayUsage::default(),
+        }
+    }
+}
+
+/// Session of a `Member` being recorded at the moment.
+#[derive(Debug)]
+struct ActiveSession {
+    /// Time of the `Member` joining its `Room`.
+    joined_at: DateTime<Utc>,
+
+    /// Records of the already removed `Peer`s of the `Member`.
+    peers: Vec<PeerRecord>,
+}
+
+/// Recorder of [`CallRecord`]s of a single `Room`.
+///
+/// Tracks sessions of `Member`s and their `Peer`s, producing
+/// [`MemberRecord`]s when `Member`s leave and [`RoomRecord`] when the `Room`
+/// is closed.
+///
+/// [`CallRecord`]: crate::cdr::CallRecord
+#[derive(Debug)]
+pub struct CallRecorder {
+    /// ID of the recorded `Room`.
+    room_id: RoomId,
+
+    /// Time of the recorded `Room` starting.
+    started_at: DateTime<Utc>,
+
+    /// Sessions of the currently joined `Member`s.
+    sessions: HashMap<MemberId, ActiveSession>,
+
+    /// `Peer`s existing at the moment.
+    peers: HashMap<PeerId, ActivePeer>,
+
+    /// Records of the finished sessions.
+    finished: Vec<MemberRecord>,
+}
+
+impl CallRecorder {
+    /// Starts recording of the `Room` with the provided [`RoomId`].
+    #[must_use]
+    pub fn new(room_id: RoomId) -> Self {
+        Self {
+            room_id,
+            started_at: Utc::now(),
+            sessions: HashMap::new(),
+            peers: HashMap::new(),
+            finished: Vec::new(),
+        }
+    }
+
+    /// Starts recording of a new session of the `Member` with the provided
+    /// [`MemberId`].
+    ///
+    /// Does nothing if the session is recorded already.
+    pub fn member_joined(&mut self, member_id: MemberId) {
+        self.sessions
+            .entry(member_id)
+            .or_insert_with(|| ActiveSession {
+                joined_at: Utc::now(),
+                peers: Vec::new(),
+            });
+    }
+
+    /// Returns [`MemberId`]s of the `Member`s, whose sessions are recorded at
+    /// the moment.
+    #[must_use]
+    pub fn joined_members(&self) -> Vec<MemberId> {
+        self.sessions.keys().cloned().collect()
+    }
+
+    /// Starts recording of the `Peer` with the provided [`PeerId`].
+    ///
+    /// Does nothing if the `Peer` is recorded already.
+    pub fn peer_started(
+        &mut self,
+        peer_id: PeerId,
+        member_id: MemberId,
+        partner_member_id: MemberId,
+    ) {
+        self.peers.entry(peer_id).or_insert_with(|| ActivePeer {
+            member_id,
+            partner_member_id,
+            started_at: Utc::now(),
+            ice_restarts: 0,
+            quality_history: Vec::new(),
+            traffic_stopped: Vec::new(),
+        });
+    }
+
+    /// Records the provided [`ConnectionQualityScore`] of the `Peer` with the
+    /// provided [`PeerId`].
+    pub fn quality_score_updated(
+        &mut self,
+        peer_id: PeerId,
+        score: ConnectionQualityScore,
+    ) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.quality_history.push(QualityScoreRecord {
+                at: Utc::now(),
+                score,
+            });
+        }
+    }
+
+    /// Records ICE restart of the `Peer` with the provided [`PeerId`].
+    pub fn ice_restarted(&mut self, peer_id: PeerId) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.ice_restarts += 1;
+        }
+    }
+
+    /// Records stopping of the `Peer`'s traffic with the provided
+    /// [`MediaType`] and [`MediaDirection`].
+    pub fn traffic_stopped(
+        &mut self,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
+        at: DateTime<Utc>,
+    ) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.traffic_stopped.push(TrafficStoppedRecord {
+                media_type,
+                direction,
+                from: at,
+                to: None,
+            });
+        }
+    }
+
+    /// Records restoring of the `Peer`'s traffic with the provided
+    /// [`MediaType`] and [`MediaDirection`].
+    pub fn traffic_flows(
+        &mut self,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
+    ) {
+        let stopped = self.peers.get_mut(&peer_id).and_then(|peer| {
+            peer.traffic_stopped.iter_mut().rev().find(|s| {
+                s.to.is_none()
+                    && s.media_type == media_type
+                    && s.direction == direction
+            })
+        });
+        if let Some(stopped) = stopped {
+            stopped.to = Some(Utc::now());
+        }
+    }
+
+    /// Finishes recording of the `Peer`s with the provided [`PeerId`]s,
+    /// attaching their records to the sessions of their `Member`s.
+    pub fn peers_removed(&mut self, peers_ids: &[PeerId]) {
+        for peer_id in peers_ids {
+            if let Some(peer) = self.peers.remove(peer_id) {
+                if let Some(session) = self.sessions.get_mut(&peer.member_id) {
+                    session.peers.push(peer.finish(*peer_id));
+                }
+            }
+        }
+    }
+
+    /// Finishes recording of the session of the `Member` with the provided
+    /// [`MemberId`], along with its remaining `Peer`s.
+    ///
+    /// Returns [`None`] if no session of this `Member` is recorded.
+    pub fn member_left(
+        &mut self,
+        member_id: &MemberId,
+        leave_reason: Option<OnLeaveReason>,
+        relay_usage: RelayUsage,
+        peers_relay_usage: &HashMap<PeerId, RelayUsage>,
+    ) -> Option<MemberRecord> {
+        let remaining: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| &peer.member_id == member_id)
+            .map(|(id, _)| *id)
+            .collect();
+        self.peers_removed(&remaining);
+
+        let session = self.sessions.remove(member_id)?;
+        let mut peers = session.peers;
+        peers.sort_by_key(|peer| peer.started_at);
+        for peer in &mut peers {
+            if let Some(usage) = peers_relay_usage.get(&peer.peer_id) {
+                peer.relay_usage = *usage;
+            }
+        }
+
+        let record = MemberRecord {
+            room_id: self.room_id.clone(),
+            member_id: member_id.clone(),
+            joined_at: session.joined_at,
+            left_at: Utc::now(),
+            leave_reason,
+            relay_usage,
+            peers,
+        };
+        self.finished.push(record.clone());
+        Some(record)
+    }
+
+    /// Finishes recording of the `Room`.
+    ///
+    /// Sessions of the still joined `Member`s must be finished with
+    /// [`CallRecorder::member_left`] beforehand to be included.
+    #[must_use]
+    pub fn room_closed(&mut self) -> RoomRecord {
+        let mut members: Vec<_> = self.finished.drain(..).collect();
+        members.sort_by_key(|member| member.joined_at);
+        RoomRecord {
+            room_id: self.room_id.clone(),
+            started_at: self.started_at,
+            closed_at: Utc::now(),
+            members,
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    fn usage(sent_bytes: u64) -> RelayUsage {
+        RelayUsage {
+            sent_bytes,
+            ..RelayUsage::default()
+        }
+    }
+
+    #[test]
+    fn records_member_session() {
+        let alice = MemberId::from("alice");
+        let bob = MemberId::from("bob");
+        let mut recorder = CallRecorder::new(RoomId::from("room"));
+
+        recorder.member_joined(alice.clone());
+        recorder.member_joined(bob.clone());
+        recorder.peer_started(PeerId(0), alice.clone(), bob.clone());
+        recorder.peer_started(PeerId(1), bob.clone(), alice.clone());
+        recorder.quality_score_updated(PeerId(0), ConnectionQualityScore::Low);
+        recorder.quality_score_updated(PeerId(0), ConnectionQualityScore::High);
+        recorder.ice_restarted(PeerId(0));
+        recorder.traffic_stopped(
+            PeerId(0),
+            MediaType::Video,
+            MediaDirection::Publish,
+            Utc::now(),
+        );
+        recorder.traffic_flows(
+            PeerId(0),
+            MediaType::Video,
+            MediaDirection::Publish,
+        );
+        recorder.traffic_stopped(
+            PeerId(0),
+            MediaType::Audio,
+            MediaDirection::Play,
+            Utc::now(),
+        );
+
+        let mut peers_usage = HashMap::new();
+        peers_usage.insert(PeerId(0), usage(10));
+        let record = recorder
+            .member_left(
+                &alice,
+                Some(OnLeaveReason::Disconnected),
+                usage(10),
+                &peers_usage,
+            )
+            .unwrap();
+
+        assert_eq!(record.member_id, alice);
+        assert_eq!(record.leave_reason, Some(OnLeaveReason::Disconnected));
+        assert_eq!(record.relay_usage, usage(10));
+        assert_eq!(record.peers.len(), 1);
+        let peer = &record.peers[0];
+        assert_eq!(peer.peer_id.0, 0);
+        assert_eq!(peer.partner_member_id, bob);
+        assert_eq!(peer.ice_restarts, 1);
+        assert_eq!(peer.relay_usage, usage(10));
+        assert_eq!(
+            peer.quality_history
+                .iter()
+                .map(|q| q.score)
+                .collect::<Vec<_>>(),
+            vec![ConnectionQualityScore::Low, ConnectionQualityScore::High],
+        );
+        assert_eq!(peer.traffic_stopped.len(), 2);
+        assert!(peer.traffic_stopped[0].to.is_some());
+        assert!(peer.traffic_stopped[1].to.is_none());
+
+        assert!(recorder
+            .member_left(&alice, None, usage(0), &HashMap::new())
+            .is_none());
+    }
+
+    #[test]
+    fn records_all_sessions_of_room() {
+        let alice = MemberId::from("alice");
+        let bob = MemberId::from("bob");
+        let mut recorder = CallRecorder::new(RoomId::from("room"));
+
+        recorder.member_joined(alice.clone());
+        recorder.member_joined(bob.clone());
+        recorder.peer_started(PeerId(0), alice.clone(), bob.clone());
+        recorder.peer_started(PeerId(1), bob.clone(), alice.clone());
+
+        recorder.peers_removed(&[PeerId(0), PeerId(1)]);
+        recorder
+            .member_left(
+                &alice,
+                Some(OnLeaveReason::LostConnection),
+                usage(0),
+                &HashMap::new(),
+            )
+            .unwrap();
+        recorder.member_joined(alice.clone());
+
+        let mut joined = recorder.joined_members();
+        joined.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(joined, vec![alice.clone(), bob.clone()]);
+        for member_id in joined {
+            recorder.member_left(&member_id, None, usage(0), &HashMap::new());
+        }
+
+        let room = recorder.room_closed();
+        assert_eq!(room.members.len(), 3);
+        assert_eq!(room.members[0].member_id, alice);
+        assert_eq!(room.members[0].peers.len(), 1);
+        let bob_record =
+            room.members.iter().find(|m| m.member_id == bob).unwrap();
+        assert_eq!(bob_record.leave_reason, None);
+        assert_eq!(bob_record.peers.len(), 1);
+        assert!(recorder.room_closed().members.is_empty());
+    }
+}
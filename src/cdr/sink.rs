@@ -0,0 +1,39 @@
+//! File sink of [`CallRecord`]s.
+
+use std::io;
+
+use crate::{cdr::CallRecord, conf, log::prelude::*, utils::JsonLinesSink};
+
+/// Sink appending [`CallRecord`]s to a file as JSON lines.
+///
+/// Does nothing if no file is configured.
+#[derive(Clone, Debug, Default)]
+pub struct CdrSink(JsonLinesSink);
+
+impl CdrSink {
+    /// Opens the file configured in the provided [`conf::Cdr`] for appending.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the configured file cannot be opened or created.
+    pub fn new(cf: &conf::Cdr) -> io::Result<Self> {
+        JsonLinesSink::file(cf.file()).map(Self)
+    }
+
+    /// Indicates whether this [`CdrSink`] writes [`CallRecord`]s anywhere.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_enabled()
+    }
+
+    /// Appends the provided [`CallRecord`] as a single JSON line.
+    ///
+    /// Failures are logged, since losing a single [`CallRecord`] should not
+    /// affect the `Room`.
+    pub fn write(&self, record: &CallRecord) {
+        if let Err(e) = self.0.write(record) {
+            error!("Failed to write CallRecord: {}", e);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Audit log of [Control API] mutations settings.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Audit log of [Control API] mutations settings.
+///
+/// Every `Create` and `Delete` call of gRPC [Control API] is recorded along
+/// with its caller, affected elements and result.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Audit {
+    /// Sink which audit records are written to.
+    ///
+    /// Defaults to [`AuditSink::Off`].
+    pub sink: AuditSink,
+
+    /// Path to the file which audit records are appended to as JSON lines
+    /// in [`AuditSink::File`] mode.
+    ///
+    /// Defaults to `audit.jsonl`.
+    #[default = "audit.jsonl"]
+    pub file: String,
+
+    /// Size of the audit log file (in bytes), exceeding which the file is
+    /// rotated.
+    ///
+    /// `0` disables rotation.
+    ///
+    /// Defaults to `100MiB`.
+    #[default(100 * 1024 * 1024)]
+    pub rotation_size: u64,
+
+    /// Number of rotated audit log files to keep.
+    ///
+    /// Defaults to `5`.
+    #[default = 5]
+    pub rotation_keep: usize,
+}
+
+/// Sink of audit records of [Control API] mutations.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditSink {
+    /// Audit records are not written anywhere.
+    #[default]
+    Off,
+
+    /// Audit records are written to the stdout as JSON lines.
+    Stdout,
+
+    /// Audit records are appended to the [`Audit::file`] as JSON lines,
+    /// rotating it once it exceeds [`Audit::rotation_size`].
+    File,
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    use super::AuditSink;
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_AUDIT__SINK" => "file",
+            "MEDEA_AUDIT__FILE" => "/var/log/medea/audit.jsonl",
+            "MEDEA_AUDIT__ROTATION_SIZE" => "1024",
+            "MEDEA_AUDIT__ROTATION_KEEP" => "2",
+        );
+
+        assert_eq!(default_conf.audit.sink, AuditSink::Off);
+        assert_eq!(env_conf.audit.sink, AuditSink::File);
+
+        assert_ne!(default_conf.audit.file, env_conf.audit.file);
+        assert_eq!(env_conf.audit.file, "/var/log/medea/audit.jsonl");
+
+        assert_ne!(
+            default_conf.audit.rotation_size,
+            env_conf.audit.rotation_size,
+        );
+        assert_eq!(env_conf.audit.rotation_size, 1024);
+
+        assert_ne!(
+            default_conf.audit.rotation_keep,
+            env_conf.audit.rotation_keep,
+        );
+        assert_eq!(env_conf.audit.rotation_keep, 2);
+    }
+}
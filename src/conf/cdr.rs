@@ -0,0 +1,55 @@
+//! Call detail records settings.
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Call detail records (CDR) settings.
+///
+/// CDR summarizes a session of a `Member` when it leaves its `Room`, and all
+/// the sessions of a `Room` when it's closed.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Cdr {
+    /// Path to the file which CDRs are appended to as JSON lines.
+    ///
+    /// Empty path disables writing CDRs to file.
+    #[default(String::new())]
+    pub file: String,
+
+    /// Indicator whether CDRs should be sent as `OnCallRecord` Control API
+    /// callbacks to `on_metrics` URL of `Member` and `on_close` URL of `Room`.
+    #[default(false)]
+    pub callbacks: bool,
+}
+
+impl Cdr {
+    /// Returns [`Cdr::file`] if writing CDRs to file is enabled.
+    #[inline]
+    #[must_use]
+    pub fn file(&self) -> Option<&str> {
+        Some(self.file.as_str()).filter(|f| !f.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_CDR__FILE" => "/var/log/medea/cdr.jsonl",
+            "MEDEA_CDR__CALLBACKS" => "true",
+        );
+
+        assert_eq!(default_conf.cdr.file(), None);
+        assert_eq!(env_conf.cdr.file(), Some("/var/log/medea/cdr.jsonl"));
+
+        assert_ne!(default_conf.cdr.callbacks, env_conf.cdr.callbacks);
+        assert!(env_conf.cdr.callbacks);
+    }
+}
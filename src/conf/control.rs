@@ -2,6 +2,8 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
@@ -16,10 +18,23 @@ pub struct ControlApi {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     #[default = "specs/"]
     pub static_specs_dir: String,
+
+    /// Interval of polling static [Control API] specs directory for changes.
+    ///
+    /// Spec files are detected as changed by their modification time. Added,
+    /// changed and removed spec files are applied to the running `Room`s on
+    /// the fly. Zero disables watching.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[default(Duration::from_secs(0))]
+    #[serde(with = "humantime_serde")]
+    pub static_specs_watch_interval: Duration,
 }
 
 #[cfg(test)]
 mod spec {
+    use std::time::Duration;
+
     use serial_test::serial;
 
     use crate::{conf::Conf, overrided_by_env_conf};
@@ -30,6 +45,7 @@ mod spec {
         let default_conf = Conf::default();
         let env_conf = overrided_by_env_conf!(
             "MEDEA_CONTROL__STATIC_SPECS_DIR" => "test/",
+            "MEDEA_CONTROL__STATIC_SPECS_WATCH_INTERVAL" => "10s",
         );
 
         assert_ne!(
@@ -38,5 +54,14 @@ mod spec {
         );
 
         assert_eq!(env_conf.control.static_specs_dir, "test/");
+
+        assert_ne!(
+            default_conf.control.static_specs_watch_interval,
+            env_conf.control.static_specs_watch_interval
+        );
+        assert_eq!(
+            env_conf.control.static_specs_watch_interval,
+            Duration::from_secs(10),
+        );
     }
 }
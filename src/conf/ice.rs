@@ -0,0 +1,100 @@
+//! ICE settings of `Peer`s.
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// ICE settings of `Peer`s.
+///
+/// ICE candidates violating the policies of these settings are not forwarded
+/// to the partner `Peer`.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Ice {
+    /// Indicator whether host candidates with private IP addresses should be
+    /// dropped for `Member`s flagged as external.
+    #[default(true)]
+    pub drop_private_host_of_external: bool,
+
+    /// Indicator whether host candidates with [mDNS] `.local` hostnames
+    /// should be dropped.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/draft-ietf-mmusic-mdns-ice-candidates
+    #[default(false)]
+    pub drop_mdns: bool,
+
+    /// Indicator whether only relay candidates should be allowed for `Peer`s
+    /// with forcibly relayed media.
+    #[default(true)]
+    pub relay_only_if_forced: bool,
+
+    /// Maximum number of candidates forwarded from a single `Peer` per ICE
+    /// generation (the counter is reset on ICE restart).
+    ///
+    /// `0` means that the number of candidates is unlimited.
+    #[default(0)]
+    pub max_candidates_per_peer: usize,
+
+    /// Number of failed ICE restarts between two `Peer`s, after which they
+    /// are switched to forcibly relayed media.
+    ///
+    /// `0` means that `Peer`s are never switched to relayed media.
+    #[default(2)]
+    pub relay_fallback_after: u32,
+}
+
+impl Ice {
+    /// Returns [`Ice::max_candidates_per_peer`] if it's limited.
+    #[inline]
+    #[must_use]
+    pub fn max_candidates_per_peer(&self) -> Option<usize> {
+        Some(self.max_candidates_per_peer).filter(|max| *max > 0)
+    }
+
+    /// Returns [`Ice::relay_fallback_after`] if relay fallback is enabled.
+    #[inline]
+    #[must_use]
+    pub fn relay_fallback_after(&self) -> Option<u32> {
+        Some(self.relay_fallback_after).filter(|n| *n > 0)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_ICE__DROP_PRIVATE_HOST_OF_EXTERNAL" => "false",
+            "MEDEA_ICE__DROP_MDNS" => "true",
+            "MEDEA_ICE__RELAY_ONLY_IF_FORCED" => "false",
+            "MEDEA_ICE__MAX_CANDIDATES_PER_PEER" => "20",
+            "MEDEA_ICE__RELAY_FALLBACK_AFTER" => "0",
+        );
+
+        assert_ne!(
+            default_conf.ice.drop_private_host_of_external,
+            env_conf.ice.drop_private_host_of_external,
+        );
+        assert!(!env_conf.ice.drop_private_host_of_external);
+
+        assert_ne!(default_conf.ice.drop_mdns, env_conf.ice.drop_mdns);
+        assert!(env_conf.ice.drop_mdns);
+
+        assert_ne!(
+            default_conf.ice.relay_only_if_forced,
+            env_conf.ice.relay_only_if_forced,
+        );
+        assert!(!env_conf.ice.relay_only_if_forced);
+
+        assert_eq!(default_conf.ice.max_candidates_per_peer(), None);
+        assert_eq!(env_conf.ice.max_candidates_per_peer(), Some(20));
+
+        assert_eq!(default_conf.ice.relay_fallback_after(), Some(2));
+        assert_eq!(env_conf.ice.relay_fallback_after(), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! Journal of `Room`s activity settings.
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Journal of `Room`s activity settings.
+///
+/// Each `Room` keeps a bounded journal of received commands, emitted events,
+/// `Peer`s state transitions, sent callbacks and errors, which can be dumped
+/// via [Control API] and is flushed to [`Journal::dir`] when the `Room` is
+/// closed after errors.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Journal {
+    /// Maximum number of entries kept in the journal of a single `Room`.
+    /// Oldest entries are dropped once it's reached.
+    ///
+    /// `0` disables journaling.
+    ///
+    /// Defaults to `0`.
+    #[default = 0]
+    pub capacity: usize,
+
+    /// Path to the directory which journals of `Room`s closed after errors
+    /// are written to.
+    ///
+    /// Defaults to `journals`.
+    #[default = "journals"]
+    pub dir: String,
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_JOURNAL__CAPACITY" => "1000",
+            "MEDEA_JOURNAL__DIR" => "/var/log/medea/journals",
+        );
+
+        assert_ne!(default_conf.journal.capacity, env_conf.journal.capacity);
+        assert_eq!(env_conf.journal.capacity, 1000);
+
+        assert_ne!(default_conf.journal.dir, env_conf.journal.dir);
+        assert_eq!(env_conf.journal.dir, "/var/log/medea/journals");
+    }
+}
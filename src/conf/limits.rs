@@ -0,0 +1,95 @@
+//! Capacity limits of a media server.
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Capacity limits of a media server.
+///
+/// `0` means that the corresponding capacity is unlimited.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Limits {
+    /// Maximum number of `Room`s served by this media server.
+    #[default(0)]
+    pub max_rooms: usize,
+
+    /// Default maximum number of `Member`s in a single `Room`.
+    ///
+    /// Can be overridden for a concrete `Room` via its spec. Static specs
+    /// exceeding it are not rejected.
+    #[default(0)]
+    pub max_members_per_room: usize,
+
+    /// Default maximum number of publishing `Member`s (ones having at least
+    /// one `WebRtcPublishEndpoint`) in a single `Room`.
+    ///
+    /// Can be overridden for a concrete `Room` via its spec. Static specs
+    /// exceeding it are not rejected.
+    #[default(0)]
+    pub max_publishers_per_room: usize,
+}
+
+impl Limits {
+    /// Returns [`Limits::max_rooms`] if it's limited.
+    #[inline]
+    #[must_use]
+    pub fn max_rooms(&self) -> Option<usize> {
+        Some(self.max_rooms).filter(|max| *max > 0)
+    }
+
+    /// Returns [`Limits::max_members_per_room`] if it's limited.
+    #[inline]
+    #[must_use]
+    pub fn max_members_per_room(&self) -> Option<usize> {
+        Some(self.max_members_per_room).filter(|max| *max > 0)
+    }
+
+    /// Returns [`Limits::max_publishers_per_room`] if it's limited.
+    #[inline]
+    #[must_use]
+    pub fn max_publishers_per_room(&self) -> Option<usize> {
+        Some(self.max_publishers_per_room).filter(|max| *max > 0)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_LIMITS__MAX_ROOMS" => "10",
+            "MEDEA_LIMITS__MAX_MEMBERS_PER_ROOM" => "11",
+            "MEDEA_LIMITS__MAX_PUBLISHERS_PER_ROOM" => "12",
+        );
+
+        assert_ne!(default_conf.limits.max_rooms, env_conf.limits.max_rooms);
+        assert_eq!(env_conf.limits.max_rooms(), Some(10));
+
+        assert_ne!(
+            default_conf.limits.max_members_per_room,
+            env_conf.limits.max_members_per_room,
+        );
+        assert_eq!(env_conf.limits.max_members_per_room(), Some(11));
+
+        assert_ne!(
+            default_conf.limits.max_publishers_per_room,
+            env_conf.limits.max_publishers_per_room,
+        );
+        assert_eq!(env_conf.limits.max_publishers_per_room(), Some(12));
+    }
+
+    #[test]
+    fn zero_is_unlimited() {
+        let conf = Conf::default();
+
+        assert_eq!(conf.limits.max_rooms(), None);
+        assert_eq!(conf.limits.max_members_per_room(), None);
+        assert_eq!(conf.limits.max_publishers_per_room(), None);
+    }
+}
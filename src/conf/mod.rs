@@ -1,28 +1,37 @@
 //! Provides application configuration options.
 
+pub mod audit;
+pub mod cdr;
 pub mod control;
+pub mod ice;
+pub mod journal;
+pub mod limits;
 pub mod log;
 pub mod media;
+pub mod negotiation;
 pub mod rpc;
 pub mod server;
 pub mod shutdown;
+pub mod stats_recording;
+pub mod tracing;
 pub mod turn;
 
-use std::env;
+use std::{env, time::Duration};
 
 use config::{Config, Environment, File};
-use failure::Error;
+use derive_more::Display;
+use failure::{Error, Fail};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 #[doc(inline)]
 pub use self::{
-    control::ControlApi, log::Log, media::Media, rpc::Rpc, server::Server,
-    shutdown::Shutdown, turn::Turn,
+    audit::Audit, cdr::Cdr, control::ControlApi, ice::Ice, journal::Journal,
+    limits::Limits, log::Log, media::Media, negotiation::Negotiation, rpc::Rpc,
+    server::Server, shutdown::Shutdown, stats_recording::StatsRecording,
+    tracing::Tracing, turn::Turn,
 };
 
-/// CLI argument that is responsible for holding application configuration
-/// file path.
-static APP_CONF_PATH_CMD_ARG_NAME: &str = "--conf";
 /// Environment variable that is responsible for holding application
 /// configuration file path.
 static APP_CONF_PATH_ENV_VAR_NAME: &str = "MEDEA_CONF";
@@ -55,23 +64,53 @@ pub struct Conf {
     ///
     /// [`Peer`]: crate::media::peer::Peer
     pub media: Media,
+
+    /// Capacity limits of this media server.
+    pub limits: Limits,
+
+    /// ICE settings of [`Peer`]s.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    pub ice: Ice,
+
+    /// SDP negotiation deadlines settings of [`Peer`]s.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    pub negotiation: Negotiation,
+
+    /// Call detail records settings.
+    pub cdr: Cdr,
+
+    /// Recording of WebRTC stats settings.
+    pub stats_recording: StatsRecording,
+
+    /// Audit log of [Control API] mutations settings.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub audit: Audit,
+
+    /// Distributed tracing settings.
+    pub tracing: Tracing,
+
+    /// Journal of `Room`s activity settings.
+    pub journal: Journal,
 }
 
 impl Conf {
     /// Creates new [`Conf`] and applies values from the following sources
     /// (in the following order):
     /// - default values;
-    /// - configuration file, the name of which is given as a command line
-    ///   parameter or environment variable;
+    /// - configuration file, the name of which is given as the provided
+    ///   `conf_path` command line parameter or environment variable;
     /// - environment variables.
     ///
     /// # Errors
     ///
     /// Errors if parsing fails.
-    pub fn parse() -> Result<Self, Error> {
+    pub fn parse(conf_path: Option<&str>) -> Result<Self, Error> {
         let mut cfg = Config::new();
 
-        if let Some(path) = get_conf_file_name(env::args()) {
+        if let Some(path) = get_conf_file_name(conf_path) {
             cfg.merge(File::with_name(&path))?;
         }
 
@@ -79,20 +118,120 @@ impl Conf {
 
         Ok(cfg.try_into()?)
     }
+
+    /// Checks this [`Conf`] for semantic errors, which cannot be detected
+    /// while parsing.
+    ///
+    /// # Errors
+    ///
+    /// Errors with all the found [`ConfError`]s.
+    pub fn validate(&self) -> Result<(), Vec<ConfError>> {
+        let mut errors = Vec::new();
+
+        let non_zero = [
+            ("rpc.idle_timeout", self.rpc.idle_timeout),
+            ("rpc.reconnect_timeout", self.rpc.reconnect_timeout),
+            ("rpc.ping_interval", self.rpc.ping_interval),
+            ("media.max_lag", self.media.max_lag),
+            ("media.init_timeout", self.media.init_timeout),
+        ];
+        for (option, value) in &non_zero {
+            if *value == Duration::from_secs(0) {
+                errors.push(ConfError::ZeroDuration(option));
+            }
+        }
+
+        if self.rpc.ping_interval >= self.rpc.idle_timeout {
+            errors.push(ConfError::PingIntervalExceedsIdleTimeout);
+        }
+
+        let public_url = &self.server.client.http.public_url;
+        match Url::parse(public_url) {
+            Ok(url) if url.scheme() == "ws" || url.scheme() == "wss" => {}
+            _ => errors.push(ConfError::InvalidPublicUrl(public_url.clone())),
+        }
+
+        if let (Some(max_members), Some(max_publishers)) = (
+            self.limits.max_members_per_room(),
+            self.limits.max_publishers_per_room(),
+        ) {
+            if max_publishers > max_members {
+                errors.push(ConfError::PublishersExceedMembers);
+            }
+        }
+
+        if self.turn.auth == turn::AuthMode::Secret {
+            if self.turn.secret.key.is_empty() {
+                errors.push(ConfError::EmptyTurnSecret);
+            }
+            if self.turn.secret.ttl == Duration::from_secs(0) {
+                errors.push(ConfError::ZeroDuration("turn.secret.ttl"));
+            }
+        }
+
+        let (ttl_option, ttl) = if self.turn.auth == turn::AuthMode::Secret {
+            ("turn.secret.ttl", self.turn.secret.ttl)
+        } else {
+            ("turn.ttl", self.turn.ttl)
+        };
+        if ttl != Duration::from_secs(0) && self.turn.rotate_before >= ttl {
+            errors.push(ConfError::RotationExceedsTtl(ttl_option));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
-/// Returns the path to the configuration file, if it's set via CLI `args`
-/// or environment variables.
-fn get_conf_file_name<T>(args: T) -> Option<String>
-where
-    T: IntoIterator<Item = String>,
-{
-    // First, check CLI arguments as they have the highest priority.
-    let mut args = args
-        .into_iter()
-        .skip_while(|x| x != APP_CONF_PATH_CMD_ARG_NAME);
-    if args.next().is_some() {
-        return args.next().filter(|v| !v.is_empty());
+/// Semantic error of [`Conf`].
+#[derive(Debug, Display, Fail)]
+pub enum ConfError {
+    /// Duration option is zero, while it should not.
+    #[display(fmt = "`{}` should be greater than zero", _0)]
+    ZeroDuration(&'static str),
+
+    /// `rpc.ping_interval` is not less than `rpc.idle_timeout`, so clients
+    /// will be considered idle before receiving `Ping`s.
+    #[display(
+        fmt = "`rpc.ping_interval` should be less than `rpc.idle_timeout`"
+    )]
+    PingIntervalExceedsIdleTimeout,
+
+    /// `server.client.http.public_url` is not a valid WebSocket URL.
+    #[display(
+        fmt = "`server.client.http.public_url` should be a valid `ws://` or \
+               `wss://` URL, but is `{}`",
+        _0
+    )]
+    InvalidPublicUrl(String),
+
+    /// `limits.max_publishers_per_room` is greater than
+    /// `limits.max_members_per_room`.
+    #[display(fmt = "`limits.max_publishers_per_room` should not be greater \
+                     than `limits.max_members_per_room`")]
+    PublishersExceedMembers,
+
+    /// `turn.secret.key` is empty, while `turn.auth` is `secret`.
+    #[display(
+        fmt = "`turn.secret.key` should be set when `turn.auth` is `secret`"
+    )]
+    EmptyTurnSecret,
+
+    /// `turn.rotate_before` is not less than TTL of TURN credentials, so they
+    /// will be rotated continuously.
+    #[display(fmt = "`turn.rotate_before` should be less than `{}`", _0)]
+    RotationExceedsTtl(&'static str),
+}
+
+/// Returns the path to the configuration file, if it's set via the provided
+/// CLI argument or environment variables.
+fn get_conf_file_name(arg: Option<&str>) -> Option<String> {
+    // First, check CLI argument as it has the highest priority.
+    if let Some(path) = arg {
+        return Some(path.to_owned()).filter(|v| !v.is_empty());
     }
 
     // Then check env var.
@@ -131,7 +270,7 @@ pub(crate) mod spec {
     macro_rules! overrided_by_env_conf {
         ($($env:expr => $value:expr),+ $(,)?) => {{
             $(::std::env::set_var($env, $value);)+
-            let conf = crate::conf::Conf::parse().unwrap();
+            let conf = crate::conf::Conf::parse(None).unwrap();
             $(::std::env::remove_var($env);)+
             conf
         }};
@@ -141,20 +280,14 @@ pub(crate) mod spec {
     #[serial]
     fn get_conf_file_name_spec_none_if_nothing_is_set() {
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
-        assert_eq!(get_conf_file_name(Vec::new()), None);
+        assert_eq!(get_conf_file_name(None), None);
     }
 
     #[test]
     #[serial]
     fn get_conf_file_name_spec_none_if_empty() {
         env::set_var(APP_CONF_PATH_ENV_VAR_NAME, "env_path");
-        assert_eq!(
-            get_conf_file_name(vec![
-                APP_CONF_PATH_CMD_ARG_NAME.to_owned(),
-                "".to_owned(),
-            ]),
-            None,
-        );
+        assert_eq!(get_conf_file_name(Some("")), None);
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
     }
 
@@ -162,7 +295,7 @@ pub(crate) mod spec {
     #[serial]
     fn get_conf_file_name_spec_env_if_set() {
         env::set_var(APP_CONF_PATH_ENV_VAR_NAME, "env_path");
-        assert_eq!(get_conf_file_name(Vec::new()), Some("env_path".to_owned()));
+        assert_eq!(get_conf_file_name(None), Some("env_path".to_owned()));
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
     }
 
@@ -171,10 +304,7 @@ pub(crate) mod spec {
     fn get_conf_file_name_spec_arg_if_set() {
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
         assert_eq!(
-            get_conf_file_name(vec![
-                APP_CONF_PATH_CMD_ARG_NAME.to_owned(),
-                "arg_path".to_owned(),
-            ]),
+            get_conf_file_name(Some("arg_path")),
             Some("arg_path".to_owned()),
         );
     }
@@ -184,12 +314,84 @@ pub(crate) mod spec {
     fn get_conf_file_name_spec_arg_is_prioritized() {
         env::set_var(APP_CONF_PATH_ENV_VAR_NAME, "env_path");
         assert_eq!(
-            get_conf_file_name(vec![
-                APP_CONF_PATH_CMD_ARG_NAME.to_owned(),
-                "arg_path".to_owned(),
-            ]),
+            get_conf_file_name(Some("arg_path")),
             Some("arg_path".to_owned()),
         );
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
     }
+
+    #[test]
+    fn default_conf_is_valid() {
+        assert!(Conf::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_reports_all_errors() {
+        let mut conf = Conf::default();
+        conf.rpc.idle_timeout = Duration::from_secs(0);
+        conf.server.client.http.public_url = "http://127.0.0.1".to_owned();
+        conf.limits.max_members_per_room = 2;
+        conf.limits.max_publishers_per_room = 3;
+
+        let errors = conf.validate().unwrap_err();
+
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(
+            errors[0],
+            ConfError::ZeroDuration("rpc.idle_timeout")
+        ));
+        assert!(matches!(
+            errors[1],
+            ConfError::PingIntervalExceedsIdleTimeout
+        ));
+        assert!(matches!(errors[2], ConfError::InvalidPublicUrl(_)));
+        assert!(matches!(errors[3], ConfError::PublishersExceedMembers));
+    }
+
+    #[test]
+    fn validate_requires_turn_secret() {
+        let mut conf = Conf::default();
+        conf.turn.auth = turn::AuthMode::Secret;
+
+        let errors = conf.validate().unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ConfError::EmptyTurnSecret));
+
+        conf.turn.secret.key = "secret".into();
+
+        assert!(conf.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_requires_rotation_before_expiry() {
+        let mut conf = Conf::default();
+        conf.turn.ttl = Duration::from_secs(60);
+
+        let errors = conf.validate().unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            ConfError::RotationExceedsTtl("turn.ttl")
+        ));
+
+        conf.turn.ttl = Duration::from_secs(3600);
+
+        assert!(conf.validate().is_ok());
+    }
+
+    #[test]
+    fn default_conf_serializes_to_toml() {
+        let conf = Conf::default();
+
+        let serialized = toml::to_string_pretty(&conf).unwrap();
+        let parsed: Conf = toml::from_str(&serialized).unwrap();
+
+        assert_eq!(parsed.rpc.idle_timeout, conf.rpc.idle_timeout);
+        assert_eq!(
+            parsed.server.client.http.public_url,
+            conf.server.client.http.public_url,
+        );
+    }
 }
@@ -0,0 +1,105 @@
+//! SDP negotiation deadlines settings.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// SDP negotiation deadlines settings of [`Peer`]s.
+///
+/// [`Peer`]s not making their SDP offer or answer in time are rolled back to
+/// the `Stable` state, and the negotiation is restarted with swapped roles.
+///
+/// [`Peer`]: crate::media::peer::Peer
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Negotiation {
+    /// Duration given to the offerer `Peer` to make its SDP offer.
+    ///
+    /// `0` means that the SDP offer is awaited forever.
+    ///
+    /// Defaults to `15s`.
+    #[default(Duration::from_secs(15))]
+    #[serde(with = "humantime_serde")]
+    pub offer_timeout: Duration,
+
+    /// Duration given to the answerer `Peer` to make its SDP answer.
+    ///
+    /// `0` means that the SDP answer is awaited forever.
+    ///
+    /// Defaults to `15s`.
+    #[default(Duration::from_secs(15))]
+    #[serde(with = "humantime_serde")]
+    pub answer_timeout: Duration,
+
+    /// Number of timed out negotiations in a row between two `Peer`s, after
+    /// which they are removed and recreated.
+    ///
+    /// `0` means that `Peer`s are never recreated.
+    ///
+    /// Defaults to `3`.
+    #[default(3)]
+    pub recreate_after: u32,
+}
+
+impl Negotiation {
+    /// Returns [`Negotiation::offer_timeout`] if it's enabled.
+    #[inline]
+    #[must_use]
+    pub fn offer_timeout(&self) -> Option<Duration> {
+        Some(self.offer_timeout).filter(|t| *t > Duration::default())
+    }
+
+    /// Returns [`Negotiation::answer_timeout`] if it's enabled.
+    #[inline]
+    #[must_use]
+    pub fn answer_timeout(&self) -> Option<Duration> {
+        Some(self.answer_timeout).filter(|t| *t > Duration::default())
+    }
+
+    /// Returns [`Negotiation::recreate_after`] if recreation of `Peer`s is
+    /// enabled.
+    #[inline]
+    #[must_use]
+    pub fn recreate_after(&self) -> Option<u32> {
+        Some(self.recreate_after).filter(|n| *n > 0)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_NEGOTIATION__OFFER_TIMEOUT" => "0s",
+            "MEDEA_NEGOTIATION__ANSWER_TIMEOUT" => "5s",
+            "MEDEA_NEGOTIATION__RECREATE_AFTER" => "0",
+        );
+
+        assert_eq!(
+            default_conf.negotiation.offer_timeout(),
+            Some(Duration::from_secs(15)),
+        );
+        assert_eq!(env_conf.negotiation.offer_timeout(), None);
+
+        assert_ne!(
+            default_conf.negotiation.answer_timeout,
+            env_conf.negotiation.answer_timeout,
+        );
+        assert_eq!(
+            env_conf.negotiation.answer_timeout(),
+            Some(Duration::from_secs(5)),
+        );
+
+        assert_eq!(default_conf.negotiation.recreate_after(), Some(3));
+        assert_eq!(env_conf.negotiation.recreate_after(), None);
+    }
+}
@@ -94,6 +94,22 @@ mod spec {
         );
     }
 
+    #[test]
+    #[serial]
+    fn conf_parse_spec_arg_file_overrides_defaults() {
+        // Don't delete me! Otherwise temporary dir will be deleted.
+        let dir = tempfile::tempdir().unwrap();
+        let conf_path =
+            dir.path().join("test_config.toml").display().to_string();
+
+        let data = "[rpc]\nidle_timeout = \"45s\"".to_owned();
+        fs::write(&conf_path, data).unwrap();
+
+        let new_config = Conf::parse(Some(&conf_path)).unwrap();
+
+        assert_eq!(new_config.rpc.idle_timeout, Duration::from_secs(45));
+    }
+
     #[test]
     #[serial]
     fn conf_parse_spec_env_overrides_file() {
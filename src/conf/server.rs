@@ -5,6 +5,8 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs as _};
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
+use super::turn::IpNetwork;
+
 /// [Client API] servers settings.
 ///
 /// [Client API]: https://tinyurl.com/yx9thsnr
@@ -48,6 +50,15 @@ pub struct ClientApiHttpServer {
     /// Defaults to `8080`.
     #[default = 8080]
     pub bind_port: u16,
+
+    /// IP networks of proxies trusted to report IP addresses of clients via
+    /// `Forwarded` and `X-Forwarded-For` headers.
+    ///
+    /// These headers are ignored in requests from other addresses, as they
+    /// can be spoofed by clients.
+    ///
+    /// Defaults to empty list.
+    pub trusted_proxies: Vec<IpNetwork>,
 }
 
 impl ClientApiHttpServer {
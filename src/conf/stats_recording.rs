@@ -0,0 +1,50 @@
+//! Recording of WebRTC stats settings.
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Recording of WebRTC stats settings.
+///
+/// Recorded stats may be replayed later to reproduce decisions made by the
+/// stats analysis of a media server.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct StatsRecording {
+    /// Path to the gzip-compressed file which WebRTC stats received from
+    /// clients are appended to as JSON lines.
+    ///
+    /// Empty path disables recording.
+    #[default(String::new())]
+    pub file: String,
+}
+
+impl StatsRecording {
+    /// Returns [`StatsRecording::file`] if recording is enabled.
+    #[inline]
+    #[must_use]
+    pub fn file(&self) -> Option<&str> {
+        Some(self.file.as_str()).filter(|f| !f.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_STATS_RECORDING__FILE" => "/var/log/medea/stats.jsonl.gz",
+        );
+
+        assert_eq!(default_conf.stats_recording.file(), None);
+        assert_eq!(
+            env_conf.stats_recording.file(),
+            Some("/var/log/medea/stats.jsonl.gz"),
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Distributed tracing settings.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+/// Distributed tracing settings.
+///
+/// Spans of [Control API] requests, `Room`s operations, SDP negotiations and
+/// callbacks are exported to [OpenTelemetry] collector via [OTLP].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+/// [OpenTelemetry]: https://opentelemetry.io
+/// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Tracing {
+    /// URL of [OTLP]/HTTP traces endpoint of collector which spans are
+    /// exported to in JSON encoding.
+    ///
+    /// Empty URL disables tracing.
+    ///
+    /// Defaults to empty URL.
+    ///
+    /// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+    #[default(String::new())]
+    pub endpoint: String,
+
+    /// Name of this service reported in exported spans.
+    ///
+    /// Defaults to `medea`.
+    #[default = "medea"]
+    pub service_name: String,
+
+    /// Interval of exporting collected spans.
+    ///
+    /// Defaults to `5s`.
+    #[default(Duration::from_secs(5))]
+    #[serde(with = "humantime_serde")]
+    pub export_interval: Duration,
+
+    /// Maximum number of spans exported at once. Reaching it exports spans
+    /// without waiting for [`Tracing::export_interval`].
+    ///
+    /// Defaults to `512`.
+    #[default = 512]
+    pub max_batch_size: usize,
+
+    /// Timeout of a single export request to collector.
+    ///
+    /// Defaults to `10s`.
+    #[default(Duration::from_secs(10))]
+    #[serde(with = "humantime_serde")]
+    pub export_timeout: Duration,
+}
+
+impl Tracing {
+    /// Returns [`Tracing::endpoint`] if tracing is enabled.
+    #[inline]
+    #[must_use]
+    pub fn endpoint(&self) -> Option<&str> {
+        Some(self.endpoint.as_str()).filter(|e| !e.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TRACING__ENDPOINT" => "http://127.0.0.1:4318/v1/traces",
+            "MEDEA_TRACING__SERVICE_NAME" => "medea-1",
+            "MEDEA_TRACING__EXPORT_INTERVAL" => "1s",
+            "MEDEA_TRACING__MAX_BATCH_SIZE" => "10",
+            "MEDEA_TRACING__EXPORT_TIMEOUT" => "2s",
+        );
+
+        assert_eq!(default_conf.tracing.endpoint(), None);
+        assert_eq!(
+            env_conf.tracing.endpoint(),
+            Some("http://127.0.0.1:4318/v1/traces"),
+        );
+
+        assert_ne!(
+            default_conf.tracing.service_name,
+            env_conf.tracing.service_name,
+        );
+        assert_eq!(env_conf.tracing.service_name, "medea-1");
+
+        assert_ne!(
+            default_conf.tracing.export_interval,
+            env_conf.tracing.export_interval,
+        );
+        assert_eq!(env_conf.tracing.export_interval, Duration::from_secs(1));
+
+        assert_ne!(
+            default_conf.tracing.max_batch_size,
+            env_conf.tracing.max_batch_size,
+        );
+        assert_eq!(env_conf.tracing.max_batch_size, 10);
+
+        assert_ne!(
+            default_conf.tracing.export_timeout,
+            env_conf.tracing.export_timeout,
+        );
+        assert_eq!(env_conf.tracing.export_timeout, Duration::from_secs(2));
+    }
+}
@@ -1,9 +1,13 @@
 //! STUN/TURN server settings.
 
-use std::{borrow::Cow, time::Duration};
+use std::{
+    borrow::Cow, net::IpAddr, num::ParseIntError, path::PathBuf, str::FromStr,
+    time::Duration,
+};
 
-use redis::ConnectionInfo;
-use serde::{Deserialize, Serialize};
+use derive_more::Display;
+use redis::{ConnectionAddr, ConnectionInfo};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use smart_default::SmartDefault;
 
 /// STUN/TURN server settings.
@@ -34,6 +38,65 @@ pub struct Turn {
     #[default = "PASS"]
     pub pass: Cow<'static, str>,
 
+    /// Mode of authenticating users on TURN server.
+    ///
+    /// Defaults to [`AuthMode::Db`].
+    pub auth: AuthMode,
+
+    /// Strategy of selecting [`Server`]s provided to a `Peer`.
+    ///
+    /// Defaults to [`Selection::All`].
+    pub selection: Selection,
+
+    /// Indicator whether traffic relayed through TURN servers is accounted
+    /// for `Peer`s and `Member`s, based on allocation events published by
+    /// [Coturn] to its [Redis] database (`redis-statsdb`).
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [Redis]: https://redis.io
+    pub relay_usage: bool,
+
+    /// Lifetime of credentials generated for `Peer`s in [`AuthMode::Db`].
+    /// [`AuthMode::Secret`] uses [`Secret::ttl`] instead.
+    ///
+    /// Zero means that credentials never expire.
+    ///
+    /// Defaults to `0s`.
+    #[default(Duration::from_secs(0))]
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+
+    /// Time before credentials expiry when fresh ones are issued and pushed
+    /// to the client, and ICE restart is performed.
+    ///
+    /// Defaults to `1m`.
+    #[default(Duration::from_secs(60))]
+    #[serde(with = "humantime_serde")]
+    pub rotate_before: Duration,
+
+    /// List of STUN/TURN servers provided to clients.
+    ///
+    /// If empty, then a single server at `host` and `port` is used.
+    ///
+    /// Defaults to empty list.
+    pub servers: Vec<Server>,
+
+    /// List of regions resolved from IP addresses of clients, used in
+    /// [`Selection::Region`] strategy for `Member`s without their own
+    /// `region`.
+    ///
+    /// Defaults to empty list.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub client_regions: Vec<ClientRegion>,
+
+    /// Shared secret settings, used in [`AuthMode::Secret`].
+    pub secret: Secret,
+
+    /// Health checking settings of [`Server`]s.
+    pub health_check: HealthCheck,
+
     /// Database settings
     pub db: Db,
 
@@ -47,17 +110,279 @@ impl Turn {
     pub fn addr(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    /// Returns configured [`Server`]s, falling back to a single [`Server`] at
+    /// `host` and `port` if none is configured.
+    #[must_use]
+    pub fn servers(&self) -> Vec<Server> {
+        if self.servers.is_empty() {
+            vec![Server {
+                host: self.host.clone(),
+                port: self.port,
+                ..Server::default()
+            }]
+        } else {
+            self.servers.clone()
+        }
+    }
+}
+
+/// STUN/TURN server provided to clients.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Server {
+    /// Host of this [`Server`].
+    ///
+    /// Defaults to `localhost`.
+    #[default = "localhost"]
+    pub host: Cow<'static, str>,
+
+    /// Port of this [`Server`] for UDP and TCP connections.
+    ///
+    /// Defaults to `3478`.
+    #[default = 3478]
+    pub port: u16,
+
+    /// Port of this [`Server`] for TLS connections, provided as `turns:` URL.
+    ///
+    /// `0` means that TLS is not supported by this [`Server`].
+    ///
+    /// Defaults to `0`.
+    #[default = 0]
+    pub tls_port: u16,
+
+    /// Indicator whether this [`Server`] is provided as STUN server.
+    ///
+    /// Defaults to `true`.
+    #[default = true]
+    pub stun: bool,
+
+    /// Indicator whether this [`Server`] is provided as TURN server.
+    ///
+    /// Defaults to `true`.
+    #[default = true]
+    pub turn: bool,
+
+    /// Region tag of this [`Server`], matched against `region` of `Member`s
+    /// in [`Selection::Region`] strategy.
+    ///
+    /// Defaults to [`None`].
+    pub region: Option<String>,
+}
+
+/// Region of clients connecting from the specified IP networks.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ClientRegion {
+    /// Name of this [`ClientRegion`], matched against `region` of
+    /// [`Server`]s.
+    pub name: String,
+
+    /// IP networks of clients in this [`ClientRegion`].
+    pub networks: Vec<IpNetwork>,
+}
+
+impl ClientRegion {
+    /// Indicates whether the provided client `ip` belongs to this
+    /// [`ClientRegion`].
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|net| net.contains(ip))
+    }
+}
+
+/// IP network in CIDR notation, e.g. `10.0.0.0/8` or `2001:db8::/32`.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[display(fmt = "{}/{}", addr, prefix)]
+pub struct IpNetwork {
+    /// Address of this [`IpNetwork`].
+    addr: IpAddr,
+
+    /// Length of this [`IpNetwork`] prefix in bits.
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Indicates whether the provided `ip` belongs to this [`IpNetwork`].
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix));
+                let mask = mask.unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix));
+                let mask = mask.unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr: IpAddr = parts
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|e| format!("invalid IP network `{}`: {}", s, e))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match parts.next() {
+            Some(prefix) => {
+                prefix.parse().ok().filter(|p| *p <= max).ok_or_else(|| {
+                    format!("invalid IP network prefix `{}`", s)
+                })?
+            }
+            None => max,
+        };
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl<'de> Deserialize<'de> for IpNetwork {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        String::deserialize(d)?.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for IpNetwork {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+/// Strategy of selecting [`Server`]s provided to a `Peer`.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Selection {
+    /// All alive [`Server`]s are provided.
+    #[default]
+    All,
+
+    /// A single alive [`Server`] is provided, rotating them between `Peer`s.
+    RoundRobin,
+
+    /// Alive [`Server`]s of `Member`'s region are provided, falling back to
+    /// [`Server`]s without region, and then to all alive [`Server`]s.
+    ///
+    /// `Member`s without their own region are matched against
+    /// [`ClientRegion`]s by the IP address they're connected from.
+    Region,
+}
+
+/// Health checking settings of [`Server`]s.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct HealthCheck {
+    /// Interval between checks of [`Server`]s availability.
+    ///
+    /// `0` disables health checking, so all [`Server`]s are considered alive.
+    ///
+    /// Defaults to `10s`.
+    #[default(Duration::from_secs(10))]
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+
+    /// Timeout of awaiting a response to a STUN Binding request sent to a
+    /// [`Server`] via UDP, after which it's considered dead.
+    ///
+    /// Defaults to `2s`.
+    #[default(Duration::from_secs(2))]
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+/// Mode of authenticating users on TURN server.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    /// Every user is registered in [Coturn]'s database and its sessions are
+    /// removed via [Coturn]'s admin interface once it's not used anymore.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[default]
+    Db,
+
+    /// Time-limited credentials are generated with a secret shared with TURN
+    /// server, as described in [TURN REST API] (`use-auth-secret` mode of
+    /// [Coturn]).
+    ///
+    /// Neither database nor admin interface of TURN server are used.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [TURN REST API]: https://tools.ietf.org/html/draft-uberti-behave-turn-rest-00
+    Secret,
+}
+
+/// Settings of credentials generation with a secret shared with TURN server.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct Secret {
+    /// Secret shared with TURN server (`static-auth-secret` of [Coturn]).
+    ///
+    /// Defaults to empty value.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[default = ""]
+    pub key: Cow<'static, str>,
+
+    /// Lifetime of the generated credentials.
+    ///
+    /// Defaults to `1day`.
+    #[default(Duration::from_secs(24 * 60 * 60))]
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
 #[serde(default)]
 pub struct Db {
+    /// Kind of database storing TURN credentials.
+    ///
+    /// Defaults to [`DbKind::Redis`].
+    pub kind: DbKind,
+
     /// [Redis] database settings.
     ///
     /// [Redis]: https://redis.io
     pub redis: Redis,
 }
 
+/// Kind of database storing TURN credentials in [`AuthMode::Db`].
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DbKind {
+    /// Credentials are stored in [Redis] database backing [Coturn] storage,
+    /// and sessions are removed via [Coturn]'s admin interface.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [Redis]: https://redis.io
+    #[default]
+    Redis,
+
+    /// Credentials are generated without being stored anywhere, so neither
+    /// [Redis] nor [Coturn] are required.
+    ///
+    /// Intended for single-node development setups only, as TURN server is
+    /// unable to verify such credentials, so it should either not
+    /// authenticate users at all, or only STUN servers should be used.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [Redis]: https://redis.io
+    Memory,
+}
+
 /// Setting of [Redis] database server which backs [Coturn] storage.
 ///
 /// [Coturn]: https://github.com/coturn/coturn
@@ -77,6 +402,13 @@ pub struct Redis {
     #[default = 6379]
     pub port: u16,
 
+    /// Path to Unix socket of Redis database server to connect via, instead
+    /// of [`Redis::host`] and [`Redis::port`].
+    ///
+    /// Defaults to empty value, meaning TCP connection is used.
+    #[default = ""]
+    pub socket: Cow<'static, str>,
+
     /// User to authenticate on Redis database server as.
     ///
     /// Defaults to empty value.
@@ -95,27 +427,91 @@ pub struct Redis {
     #[default = 0]
     pub db_number: i64,
 
-    // TODO: replace with PoolConfig
-    /// Timeout for establishing connection with Redis database server.
-    #[default(Duration::from_secs(5))]
-    #[serde(with = "humantime_serde")]
-    pub connect_timeout: Duration,
+    /// Settings for pool of connections with Redis database server.
+    pub pool: PoolConfig,
+
+    /// [Redis Sentinel] settings to discover Redis database server with.
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    pub sentinel: RedisSentinel,
 }
 
 impl From<&Redis> for ConnectionInfo {
     fn from(cf: &Redis) -> Self {
+        let addr = if cf.socket.is_empty() {
+            ConnectionAddr::Tcp(cf.host.to_string(), cf.port)
+        } else {
+            ConnectionAddr::Unix(PathBuf::from(cf.socket.as_ref()))
+        };
         Self {
             username: Some(cf.user.to_string()).filter(|u| !u.is_empty()),
-            addr: Box::new(redis::ConnectionAddr::Tcp(
-                cf.host.to_string(),
-                cf.port,
-            )),
+            addr: Box::new(addr),
             db: cf.db_number,
             passwd: Some(cf.pass.to_string()).filter(|p| !p.is_empty()),
         }
     }
 }
 
+/// Settings of [Redis Sentinel] which discovers the current master of Redis
+/// database server, so failovers are followed.
+///
+/// [Redis Sentinel]: https://redis.io/topics/sentinel
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct RedisSentinel {
+    /// Name of the master monitored by Sentinels.
+    ///
+    /// Defaults to empty value, meaning Sentinel is not used.
+    #[default = ""]
+    pub master_name: Cow<'static, str>,
+
+    /// Comma-separated list of Sentinels in `host:port` format. Port defaults
+    /// to `26379` if omitted.
+    ///
+    /// Defaults to empty value.
+    #[default = ""]
+    pub nodes: Cow<'static, str>,
+
+    /// Password to authenticate on Sentinels with.
+    ///
+    /// Defaults to empty value.
+    #[default = ""]
+    pub pass: Cow<'static, str>,
+}
+
+impl RedisSentinel {
+    /// Default port of [Redis Sentinel].
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    pub const DEFAULT_PORT: u16 = 26379;
+
+    /// Indicates whether [Redis Sentinel] is used.
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        !self.master_name.is_empty()
+    }
+
+    /// Parses [`RedisSentinel::nodes`] into `(host, port)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// If some node has invalid port.
+    pub fn nodes(&self) -> Result<Vec<(String, u16)>, ParseIntError> {
+        self.nodes
+            .split(',')
+            .map(str::trim)
+            .filter(|node| !node.is_empty())
+            .map(|node| match node.rfind(':') {
+                Some(i) => Ok((node[..i].to_owned(), node[i + 1..].parse()?)),
+                None => Ok((node.to_owned(), Self::DEFAULT_PORT)),
+            })
+            .collect()
+    }
+}
+
 /// Settings of [Coturn]'s admin interface.
 ///
 /// [Coturn]: https://github.com/coturn/coturn
@@ -144,6 +540,53 @@ pub struct CoturnCli {
 
     /// Settings for pool of connections with admin interface.
     pub pool: PoolConfig,
+
+    /// Settings of reconnection backoff for admin interface.
+    pub backoff: CoturnCliBackoff,
+}
+
+/// Settings of reconnection backoff for [Coturn]'s admin interface.
+///
+/// After [`CoturnCliBackoff::failure_threshold`] consecutive connection
+/// failures, requests to admin interface fail fast until the next reconnection
+/// attempt.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct CoturnCliBackoff {
+    /// Number of consecutive connection failures after which requests fail
+    /// fast.
+    ///
+    /// Defaults to `3`.
+    #[default = 3]
+    pub failure_threshold: u32,
+
+    /// Delay before the first reconnection attempt, doubled on each next
+    /// failed attempt.
+    ///
+    /// Defaults to `500ms`.
+    #[default(Duration::from_millis(500))]
+    #[serde(with = "humantime_serde")]
+    pub min_delay: Duration,
+
+    /// Maximum delay between reconnection attempts.
+    ///
+    /// Defaults to `30s`.
+    #[default(Duration::from_secs(30))]
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+}
+
+impl From<CoturnCliBackoff> for medea_coturn_telnet_client::pool::Backoff {
+    #[inline]
+    fn from(cf: CoturnCliBackoff) -> Self {
+        Self {
+            failure_threshold: cf.failure_threshold,
+            min_delay: cf.min_delay,
+            max_delay: cf.max_delay,
+        }
+    }
 }
 
 /// Settings for pool of connections with [Coturn]'s admin interface.
@@ -222,7 +665,7 @@ mod spec {
             "MEDEA_TURN__DB__REDIS__PORT" => "1234",
             "MEDEA_TURN__DB__REDIS__PASS" => "hellofellow",
             "MEDEA_TURN__DB__REDIS__DB_NUMBER" => "10",
-            "MEDEA_TURN__DB__REDIS__CONNECT_TIMEOUT" => "10s",
+            "MEDEA_TURN__DB__REDIS__POOL__CONNECT_TIMEOUT" => "10s",
         );
 
         assert_ne!(
@@ -242,18 +685,54 @@ mod spec {
             env_conf.turn.db.redis.db_number,
         );
         assert_ne!(
-            default_conf.turn.db.redis.connect_timeout,
-            env_conf.turn.db.redis.connect_timeout,
+            default_conf.turn.db.redis.pool.connect_timeout,
+            env_conf.turn.db.redis.pool.connect_timeout,
         );
 
         assert_eq!(env_conf.turn.db.redis.host, "5.5.5.5");
         assert_eq!(env_conf.turn.db.redis.port, 1234);
         assert_eq!(
-            env_conf.turn.db.redis.connect_timeout,
+            env_conf.turn.db.redis.pool.connect_timeout,
             Duration::from_secs(10),
         );
     }
 
+    #[test]
+    #[serial]
+    fn redis_sentinel_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__DB__REDIS__SENTINEL__MASTER_NAME" => "turn",
+            "MEDEA_TURN__DB__REDIS__SENTINEL__NODES" => "s1:1234, s2",
+        );
+
+        assert!(!default_conf.turn.db.redis.sentinel.is_enabled());
+        assert!(env_conf.turn.db.redis.sentinel.is_enabled());
+        assert_eq!(
+            env_conf.turn.db.redis.sentinel.nodes().unwrap(),
+            [
+                ("s1".to_owned(), 1234),
+                ("s2".to_owned(), RedisSentinel::DEFAULT_PORT),
+            ],
+        );
+    }
+
+    #[test]
+    fn builds_redis_connection_addr() {
+        let mut cf = Redis::default();
+
+        assert_eq!(
+            ConnectionInfo::from(&cf).addr.to_string(),
+            "127.0.0.1:6379",
+        );
+
+        cf.socket = "/var/run/redis.sock".into();
+        assert!(matches!(
+            *ConnectionInfo::from(&cf).addr,
+            ConnectionAddr::Unix(_),
+        ));
+    }
+
     #[test]
     #[serial]
     fn overrides_defaults() {
@@ -275,6 +754,181 @@ mod spec {
         assert_eq!(env_conf.turn.addr(), "example.com:1234");
     }
 
+    #[test]
+    #[serial]
+    fn relay_usage_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__RELAY_USAGE" => "true",
+        );
+
+        assert!(!default_conf.turn.relay_usage);
+        assert!(env_conf.turn.relay_usage);
+    }
+
+    #[test]
+    #[serial]
+    fn credentials_ttl_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__TTL" => "1h",
+            "MEDEA_TURN__ROTATE_BEFORE" => "5m",
+        );
+
+        assert_ne!(default_conf.turn.ttl, env_conf.turn.ttl);
+        assert_ne!(
+            default_conf.turn.rotate_before,
+            env_conf.turn.rotate_before
+        );
+
+        assert_eq!(env_conf.turn.ttl, Duration::from_secs(3600));
+        assert_eq!(env_conf.turn.rotate_before, Duration::from_secs(300));
+    }
+
+    #[test]
+    #[serial]
+    fn db_kind_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__DB__KIND" => "memory",
+        );
+
+        assert_ne!(default_conf.turn.db.kind, env_conf.turn.db.kind);
+        assert_eq!(env_conf.turn.db.kind, DbKind::Memory);
+    }
+
+    #[test]
+    #[serial]
+    fn secret_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__AUTH" => "secret",
+            "MEDEA_TURN__SECRET__KEY" => "qwerty",
+            "MEDEA_TURN__SECRET__TTL" => "1h",
+        );
+
+        assert_ne!(default_conf.turn.auth, env_conf.turn.auth);
+        assert_ne!(default_conf.turn.secret.key, env_conf.turn.secret.key);
+        assert_ne!(default_conf.turn.secret.ttl, env_conf.turn.secret.ttl);
+
+        assert_eq!(env_conf.turn.auth, AuthMode::Secret);
+        assert_eq!(env_conf.turn.secret.key, "qwerty");
+        assert_eq!(env_conf.turn.secret.ttl, Duration::from_secs(3600));
+    }
+
+    #[test]
+    #[serial]
+    fn servers_selection_overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__SELECTION" => "round-robin",
+            "MEDEA_TURN__HEALTH_CHECK__INTERVAL" => "1m",
+            "MEDEA_TURN__HEALTH_CHECK__TIMEOUT" => "5s",
+        );
+
+        assert_ne!(default_conf.turn.selection, env_conf.turn.selection);
+        assert_ne!(
+            default_conf.turn.health_check.interval,
+            env_conf.turn.health_check.interval,
+        );
+        assert_ne!(
+            default_conf.turn.health_check.timeout,
+            env_conf.turn.health_check.timeout,
+        );
+
+        assert_eq!(env_conf.turn.selection, Selection::RoundRobin);
+        assert_eq!(
+            env_conf.turn.health_check.interval,
+            Duration::from_secs(60),
+        );
+        assert_eq!(env_conf.turn.health_check.timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn falls_back_to_single_server() {
+        let mut turn = Turn::default();
+        turn.host = "example.com".into();
+        turn.port = 1234;
+
+        assert_eq!(
+            turn.servers(),
+            vec![Server {
+                host: "example.com".into(),
+                port: 1234,
+                ..Server::default()
+            }]
+        );
+
+        turn.servers = vec![Server::default(), Server::default()];
+
+        assert_eq!(turn.servers().len(), 2);
+    }
+
+    #[test]
+    fn parses_servers_list() {
+        let conf: Conf = toml::from_str(
+            r#"
+            [turn]
+            selection = "region"
+
+            [[turn.servers]]
+            host = "eu.example.com"
+            tls_port = 5349
+            region = "eu"
+
+            [[turn.servers]]
+            host = "stun.example.com"
+            port = 19302
+            turn = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(conf.turn.selection, Selection::Region);
+        assert_eq!(conf.turn.servers.len(), 2);
+        assert_eq!(conf.turn.servers[0].port, 3478);
+        assert_eq!(conf.turn.servers[0].tls_port, 5349);
+        assert_eq!(conf.turn.servers[0].region.as_deref(), Some("eu"));
+        assert!(!conf.turn.servers[1].turn);
+        assert!(conf.turn.servers[1].stun);
+
+        let printed: Conf =
+            toml::from_str(&toml::to_string_pretty(&conf).unwrap()).unwrap();
+
+        assert_eq!(printed.turn.servers.len(), 2);
+    }
+
+    #[test]
+    fn parses_client_regions() {
+        let conf: Conf = toml::from_str(
+            r#"
+            [[turn.client_regions]]
+            name = "eu"
+            networks = ["10.0.0.0/8", "2001:db8::/32", "192.168.1.1"]
+            "#,
+        )
+        .unwrap();
+        let region = &conf.turn.client_regions[0];
+
+        assert_eq!(region.name, "eu");
+        assert!(region.contains("10.1.2.3".parse().unwrap()));
+        assert!(region.contains("2001:db8::1".parse().unwrap()));
+        assert!(region.contains("192.168.1.1".parse().unwrap()));
+        assert!(!region.contains("192.168.1.2".parse().unwrap()));
+        assert!(!region.contains("11.0.0.1".parse().unwrap()));
+        assert!(!region.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!("0.0.0.0/0"
+            .parse::<IpNetwork>()
+            .unwrap()
+            .contains("8.8.8.8".parse().unwrap()));
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+
+        let printed: Conf =
+            toml::from_str(&toml::to_string_pretty(&conf).unwrap()).unwrap();
+
+        assert_eq!(printed.turn.client_regions, conf.turn.client_regions);
+    }
+
     #[test]
     #[serial]
     fn coturn_cli() {
@@ -334,6 +988,37 @@ mod spec {
         );
     }
 
+    #[test]
+    #[serial]
+    fn coturn_cli_backoff() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_TURN__CLI__BACKOFF__FAILURE_THRESHOLD" => "5",
+            "MEDEA_TURN__CLI__BACKOFF__MIN_DELAY" => "1s",
+            "MEDEA_TURN__CLI__BACKOFF__MAX_DELAY" => "1m",
+        );
+
+        assert_ne!(
+            default_conf.turn.cli.backoff.failure_threshold,
+            env_conf.turn.cli.backoff.failure_threshold,
+        );
+        assert_ne!(
+            default_conf.turn.cli.backoff.min_delay,
+            env_conf.turn.cli.backoff.min_delay,
+        );
+        assert_ne!(
+            default_conf.turn.cli.backoff.max_delay,
+            env_conf.turn.cli.backoff.max_delay,
+        );
+
+        assert_eq!(env_conf.turn.cli.backoff.failure_threshold, 5);
+        assert_eq!(env_conf.turn.cli.backoff.min_delay, Duration::from_secs(1));
+        assert_eq!(
+            env_conf.turn.cli.backoff.max_delay,
+            Duration::from_secs(60),
+        );
+    }
+
     #[test]
     fn into_deadpool_pool_config() {
         let pool_cfg = PoolConfig {
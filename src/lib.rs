@@ -8,21 +8,28 @@
 #[macro_use]
 pub mod utils;
 pub mod api;
+pub mod cdr;
 pub mod conf;
 pub mod log;
 pub mod media;
 pub mod shutdown;
 pub mod signalling;
+pub mod tracing;
 pub mod turn;
 
 use std::sync::Arc;
 
 use crate::{
-    api::control::callback::{
-        clients::CallbackClientFactoryImpl, service::CallbackService,
+    api::control::{
+        callback::{
+            clients::CallbackClientFactoryImpl, service::CallbackService,
+        },
+        grpc::audit::AuditLog,
     },
+    cdr::CdrSink,
     conf::Conf,
-    turn::TurnAuthService,
+    signalling::peers::RtcStatsHandlersRegistry,
+    turn::{RelayUsageRepository, TurnAuthService},
 };
 
 /// Global application context.
@@ -38,6 +45,23 @@ pub struct AppContext {
 
     /// Service for sending Control API Callbacks.
     pub callbacks: CallbackService<CallbackClientFactoryImpl>,
+
+    /// Traffic relayed through TURN servers for `Peer`s and `Member`s.
+    pub relay_usage: RelayUsageRepository,
+
+    /// Sink of call detail records.
+    pub cdr: CdrSink,
+
+    /// Custom [`RtcStatsHandler`]s used by each `Room` along with the
+    /// built-in ones.
+    ///
+    /// [`RtcStatsHandler`]: crate::signalling::peers::RtcStatsHandler
+    pub stats_handlers: RtcStatsHandlersRegistry,
+
+    /// Audit log of [Control API] mutations.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub audit: AuditLog,
 }
 
 impl AppContext {
@@ -48,6 +72,10 @@ impl AppContext {
             config: Arc::new(config),
             turn_service: turn,
             callbacks: CallbackService::default(),
+            relay_usage: RelayUsageRepository::default(),
+            cdr: CdrSink::default(),
+            stats_handlers: RtcStatsHandlersRegistry::default(),
+            audit: AuditLog::default(),
         }
     }
 }
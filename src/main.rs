@@ -1,22 +1,157 @@
 //! Medea media server application.
 
+use std::{fs::File, process};
+
 use actix::{Actor, Arbiter, System};
+use clap::{
+    app_from_crate, crate_authors, crate_description, crate_name,
+    crate_version, App, AppSettings, Arg, ArgMatches, SubCommand,
+};
 use failure::Error;
 use futures::FutureExt as _;
 use medea::{
-    api::{client::server::Server, control::grpc},
+    api::{
+        client::server::Server,
+        control::{
+            grpc::{self, audit::AuditLog},
+            validate_spec_file,
+        },
+    },
+    cdr::CdrSink,
     conf::Conf,
     log::{self, prelude::*},
     shutdown::{self, GracefulShutdown},
-    signalling::{RoomRepository, RoomService},
+    signalling::{
+        peers::{
+            build_peers_traffic_watcher, read_records, StatsRecordsSink,
+            StatsReplayer,
+        },
+        RoomRepository, RoomService,
+    },
+    tracing,
     turn::new_turn_auth_service,
     AppContext,
 };
 
 fn main() -> Result<(), Error> {
     dotenv::dotenv().ok();
-    let config = Conf::parse()?;
+    let opts = cli().get_matches();
+    let config = Conf::parse(opts.value_of("conf"))?;
+
+    match opts.subcommand() {
+        ("check-config", _) => check_config(&config),
+        ("validate-spec", Some(args)) => validate_specs(&config, args),
+        ("replay-stats", Some(args)) => replay_stats(&config, args),
+        ("print-config", _) => {
+            print!("{}", toml::to_string_pretty(&config)?);
+            Ok(())
+        }
+        _ => run(config),
+    }
+}
+
+/// Describes CLI of the application.
+fn cli() -> App<'static, 'static> {
+    app_from_crate!()
+        .setting(AppSettings::VersionlessSubcommands)
+        .arg(
+            Arg::with_name("conf")
+                .help("Path to the configuration file.")
+                .long("conf")
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(
+            SubCommand::with_name("check-config")
+                .about("Parses configuration and reports semantic errors."),
+        )
+        .subcommand(
+            SubCommand::with_name("validate-spec")
+                .about("Validates static Control API spec files.")
+                .arg(
+                    Arg::with_name("file")
+                        .help("Spec file in YAML, JSON or TOML format.")
+                        .required(true)
+                        .multiple(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("print-config")
+                .about("Prints effective configuration in TOML format."),
+        )
+        .subcommand(
+            SubCommand::with_name("replay-stats")
+                .about(
+                    "Replays recorded WebRTC stats and prints the results of \
+                     their analysis.",
+                )
+                .arg(
+                    Arg::with_name("file")
+                        .help("File of recorded WebRTC stats.")
+                        .required(true),
+                ),
+        )
+}
+
+/// Reports semantic errors of the provided [`Conf`] to stderr and exits with
+/// non-zero code if there are any.
+fn check_config(config: &Conf) -> Result<(), Error> {
+    if let Err(errors) = config.validate() {
+        for e in errors {
+            eprintln!("error: {}", e);
+        }
+        process::exit(1);
+    }
+    println!("Configuration is valid");
+    Ok(())
+}
+
+/// Validates static Control API spec files provided in `args`, reports
+/// errors to stderr and exits with non-zero code if there are any.
+fn validate_specs(config: &Conf, args: &ArgMatches<'_>) -> Result<(), Error> {
+    let mut is_valid = true;
+    for file in args.values_of("file").into_iter().flatten() {
+        match validate_spec_file(file, config.rpc) {
+            Ok(spec) => println!("{}: Room [id = {}] is valid", file, spec.id),
+            Err(e) => {
+                is_valid = false;
+                eprintln!("{}: error: {}", file, e);
+            }
+        }
+    }
+    if !is_valid {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Feeds WebRTC stats recorded in the file provided in `args` to their analysis
+/// and prints all the resulting events to stdout.
+fn replay_stats(config: &Conf, args: &ArgMatches<'_>) -> Result<(), Error> {
+    let file = File::open(args.value_of("file").unwrap())?;
+    let mut replayer = StatsReplayer::new(
+        build_peers_traffic_watcher(&config.media),
+        config.media.clone(),
+    );
+    for record in read_records(file) {
+        let record = record?;
+        let (at, room_id, peer_id) =
+            (record.at, record.room_id.clone(), record.peer_id);
+        for event in replayer.replay(record) {
+            println!(
+                "{} Room [id = {}] Peer [id = {}]: {:?}",
+                at.to_rfc3339(),
+                room_id,
+                peer_id,
+                event
+            );
+        }
+    }
+    Ok(())
+}
 
+/// Runs media server with the provided [`Conf`].
+fn run(config: Conf) -> Result<(), Error> {
     if let Some(lvl) = config.log.level() {
         std::env::set_var("RUST_LOG", lvl.as_str());
     }
@@ -30,10 +165,15 @@ fn main() -> Result<(), Error> {
     let sys = System::new("medea");
     Arbiter::spawn(
         async move {
+            tracing::init(&config.tracing);
             let turn_service = new_turn_auth_service(&config.turn)?;
             let graceful_shutdown =
                 GracefulShutdown::new(config.shutdown.timeout).start();
-            let app_context = AppContext::new(config.clone(), turn_service);
+            let mut app_context = AppContext::new(config.clone(), turn_service);
+            app_context.cdr = CdrSink::new(&config.cdr)?;
+            app_context.audit = AuditLog::new(&config.audit)?;
+            StatsRecordsSink::new(&config.stats_recording)?
+                .register_in(&mut app_context.stats_handlers);
 
             let room_repo = RoomRepository::new();
             let room_service = RoomService::new(
@@ -47,7 +187,11 @@ fn main() -> Result<(), Error> {
 
             let grpc_server =
                 grpc::server::run(room_service, &app_context).await;
-            let server = Server::run(room_repo, config)?;
+            let server = Server::run(
+                room_repo,
+                app_context.turn_service.clone(),
+                config,
+            )?;
 
             shutdown::subscribe(
                 &graceful_shutdown,
@@ -0,0 +1,342 @@
+//! Inspection of [ICE candidates][1] and enforcement of their policies.
+//!
+//! [1]: https://tools.ietf.org/html/rfc8445#section-5.1.1
+
+use std::net::IpAddr;
+
+use derive_more::Display;
+
+use crate::conf;
+
+/// Type of [ICE candidate][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc8445#section-5.1.1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CandidateType {
+    /// Candidate obtained from a local interface.
+    Host,
+
+    /// Candidate obtained from a STUN server.
+    ServerReflexive,
+
+    /// Candidate learned from connectivity checks.
+    PeerReflexive,
+
+    /// Candidate obtained from a TURN server.
+    Relay,
+}
+
+/// Connection address of [ICE candidate][1].
+///
+/// [1]: https://tools.ietf.org/html/rfc8445#section-5.1.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CandidateAddress {
+    /// IP address.
+    Ip(IpAddr),
+
+    /// [mDNS] hostname hiding the real IP address.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/draft-ietf-mmusic-mdns-ice-candidates
+    Mdns(String),
+
+    /// Any other hostname.
+    Hostname(String),
+}
+
+impl CandidateAddress {
+    /// Indicates whether this [`CandidateAddress`] is a private, loopback or
+    /// link-local IP address, which reveals the local network of a client.
+    #[must_use]
+    pub fn is_private_ip(&self) -> bool {
+        match self {
+            Self::Ip(IpAddr::V4(ip)) => {
+                ip.is_private() || ip.is_loopback() || ip.is_link_local()
+            }
+            Self::Ip(IpAddr::V6(ip)) => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    // Unique local addresses: fc00::/7
+                    || (first & 0xfe00) == 0xfc00
+                    // Link-local unicast addresses: fe80::/10
+                    || (first & 0xffc0) == 0xfe80
+            }
+            Self::Mdns(_) | Self::Hostname(_) => false,
+        }
+    }
+}
+
+/// Parsed `candidate-attribute` of [ICE candidate][1] SDP.
+///
+/// [1]: https://tools.ietf.org/html/rfc8839#section-5.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    /// Type of this [`Candidate`].
+    pub kind: CandidateType,
+
+    /// Connection address of this [`Candidate`].
+    pub address: CandidateAddress,
+}
+
+impl Candidate {
+    /// Parses the provided `candidate-attribute` SDP line, returning [`None`]
+    /// if it's malformed.
+    #[must_use]
+    pub fn parse(candidate: &str) -> Option<Self> {
+        let candidate = candidate.trim();
+        let candidate = candidate.strip_prefix("a=").unwrap_or(candidate);
+        let mut parts =
+            candidate.strip_prefix("candidate:")?.split_whitespace();
+
+        // Skip `foundation`, `component-id`, `transport` and `priority`.
+        let address = parts.nth(4)?;
+        let _port = parts.next()?;
+        if parts.next()? != "typ" {
+            return None;
+        }
+        let kind = match parts.next()? {
+            "host" => CandidateType::Host,
+            "srflx" => CandidateType::ServerReflexive,
+            "prflx" => CandidateType::PeerReflexive,
+            "relay" => CandidateType::Relay,
+            _ => return None,
+        };
+
+        let address = if let Ok(ip) = address.parse() {
+            CandidateAddress::Ip(ip)
+        } else if address.ends_with(".local") {
+            CandidateAddress::Mdns(address.to_owned())
+        } else {
+            CandidateAddress::Hostname(address.to_owned())
+        };
+
+        Some(Self { kind, address })
+    }
+}
+
+/// Reason of rejecting [ICE candidate][1] by [`IceCandidatePolicy`].
+///
+/// [1]: https://tools.ietf.org/html/rfc8445#section-5.1.1
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum PolicyViolation {
+    /// Candidate cannot be parsed to be inspected.
+    #[display(fmt = "malformed candidate")]
+    Malformed,
+
+    /// Host candidate with a private IP address of an external `Member`.
+    #[display(fmt = "private host candidate of external Member")]
+    PrivateHost,
+
+    /// Host candidate with an [mDNS] hostname.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/draft-ietf-mmusic-mdns-ice-candidates
+    #[display(fmt = "mDNS candidate")]
+    Mdns,
+
+    /// Non-relay candidate of a `Peer` with forcibly relayed media.
+    #[display(fmt = "non-relay candidate of force-relayed Peer")]
+    NotRelay,
+
+    /// `Peer` has reached its limit of candidates.
+    #[display(fmt = "candidates limit reached")]
+    LimitReached,
+}
+
+impl PolicyViolation {
+    /// Returns `snake_case` name of this [`PolicyViolation`].
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Malformed => "malformed",
+            Self::PrivateHost => "private_host",
+            Self::Mdns => "mdns",
+            Self::NotRelay => "not_relay",
+            Self::LimitReached => "limit_reached",
+        }
+    }
+}
+
+/// Policies of [ICE candidates][1] forwarded between `Peer`s.
+///
+/// [1]: https://tools.ietf.org/html/rfc8445#section-5.1.1
+#[derive(Clone, Debug, Default)]
+pub struct IceCandidatePolicy(conf::Ice);
+
+impl IceCandidatePolicy {
+    /// Creates new [`IceCandidatePolicy`] with the provided settings.
+    #[inline]
+    #[must_use]
+    pub fn new(cf: conf::Ice) -> Self {
+        Self(cf)
+    }
+
+    /// Checks the provided `candidate` against this [`IceCandidatePolicy`].
+    ///
+    /// `is_external` indicates whether the candidate belongs to an external
+    /// `Member`, `is_force_relayed` indicates whether its `Peer` media is
+    /// forcibly relayed, and `candidates_count` is the number of candidates
+    /// already forwarded from its `Peer` in the current ICE generation.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`PolicyViolation`] if the `candidate` should not be
+    /// forwarded.
+    pub fn check(
+        &self,
+        candidate: &str,
+        is_external: bool,
+        is_force_relayed: bool,
+        candidates_count: usize,
+    ) -> Result<(), PolicyViolation> {
+        if let Some(max) = self.0.max_candidates_per_peer() {
+            if candidates_count >= max {
+                return Err(PolicyViolation::LimitReached);
+            }
+        }
+
+        let check_private = self.0.drop_private_host_of_external && is_external;
+        let check_relay = self.0.relay_only_if_forced && is_force_relayed;
+        if !check_private && !check_relay && !self.0.drop_mdns {
+            return Ok(());
+        }
+
+        let candidate =
+            Candidate::parse(candidate).ok_or(PolicyViolation::Malformed)?;
+        if check_relay && candidate.kind != CandidateType::Relay {
+            return Err(PolicyViolation::NotRelay);
+        }
+        if candidate.kind == CandidateType::Host {
+            if self.0.drop_mdns {
+                if let CandidateAddress::Mdns(_) = candidate.address {
+                    return Err(PolicyViolation::Mdns);
+                }
+            }
+            if check_private && candidate.address.is_private_ip() {
+                return Err(PolicyViolation::PrivateHost);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    const HOST_PRIVATE: &str = "candidate:842163049 1 udp 1677729535 \
+                                192.168.1.5 46154 typ host generation 0";
+    const HOST_V6_LOCAL: &str =
+        "candidate:1 1 udp 2122262783 fe80::1 50000 typ host";
+    const HOST_PUBLIC: &str =
+        "candidate:2 1 udp 2122260223 8.8.8.8 50001 typ host";
+    const HOST_MDNS: &str = "candidate:3 1 udp 2122260223 \
+                             8c5b0e4a-3a1f-4c63-9e7d-0f2a1b2c3d4e.local 50002 \
+                             typ host generation 0";
+    const SRFLX: &str = "candidate:4 1 udp 1686052607 1.2.3.4 50003 typ srflx \
+                         raddr 192.168.1.5 rport 50000";
+    const RELAY: &str = "a=candidate:5 1 udp 41885439 5.6.7.8 50004 typ relay \
+                         raddr 1.2.3.4 rport 50003";
+
+    #[test]
+    fn parses_candidates() {
+        assert_eq!(
+            Candidate::parse(HOST_PRIVATE),
+            Some(Candidate {
+                kind: CandidateType::Host,
+                address: CandidateAddress::Ip("192.168.1.5".parse().unwrap()),
+            }),
+        );
+        assert!(matches!(
+            Candidate::parse(HOST_MDNS).unwrap().address,
+            CandidateAddress::Mdns(_),
+        ));
+        assert_eq!(
+            Candidate::parse(SRFLX).unwrap().kind,
+            CandidateType::ServerReflexive,
+        );
+        assert_eq!(Candidate::parse(RELAY).unwrap().kind, CandidateType::Relay);
+
+        assert_eq!(Candidate::parse("candidate:1 1 udp"), None);
+        assert_eq!(Candidate::parse("1 1 udp 1 1.2.3.4 1 typ host"), None);
+    }
+
+    #[test]
+    fn detects_private_ips() {
+        for c in &[HOST_PRIVATE, HOST_V6_LOCAL] {
+            assert!(Candidate::parse(c).unwrap().address.is_private_ip());
+        }
+        for c in &[HOST_PUBLIC, HOST_MDNS] {
+            assert!(!Candidate::parse(c).unwrap().address.is_private_ip());
+        }
+    }
+
+    #[test]
+    fn drops_private_host_of_external_only() {
+        let policy = IceCandidatePolicy::default();
+
+        assert_eq!(
+            policy.check(HOST_PRIVATE, true, false, 0),
+            Err(PolicyViolation::PrivateHost),
+        );
+        assert_eq!(
+            policy.check(HOST_V6_LOCAL, true, false, 0),
+            Err(PolicyViolation::PrivateHost),
+        );
+        assert_eq!(policy.check(HOST_PUBLIC, true, false, 0), Ok(()));
+        assert_eq!(policy.check(SRFLX, true, false, 0), Ok(()));
+        assert_eq!(policy.check(HOST_PRIVATE, false, false, 0), Ok(()));
+    }
+
+    #[test]
+    fn drops_mdns_if_configured() {
+        assert_eq!(
+            IceCandidatePolicy::default().check(HOST_MDNS, false, false, 0),
+            Ok(()),
+        );
+
+        let policy = IceCandidatePolicy::new(conf::Ice {
+            drop_mdns: true,
+            ..conf::Ice::default()
+        });
+        assert_eq!(
+            policy.check(HOST_MDNS, false, false, 0),
+            Err(PolicyViolation::Mdns),
+        );
+        assert_eq!(policy.check(HOST_PRIVATE, false, false, 0), Ok(()));
+        assert_eq!(
+            policy.check("candidate:garbage", false, false, 0),
+            Err(PolicyViolation::Malformed),
+        );
+    }
+
+    #[test]
+    fn allows_only_relay_if_forced() {
+        let policy = IceCandidatePolicy::default();
+
+        for c in &[HOST_PUBLIC, HOST_MDNS, SRFLX] {
+            assert_eq!(
+                policy.check(c, false, true, 0),
+                Err(PolicyViolation::NotRelay),
+            );
+            assert_eq!(policy.check(c, false, false, 0), Ok(()));
+        }
+        assert_eq!(policy.check(RELAY, true, true, 0), Ok(()));
+    }
+
+    #[test]
+    fn enforces_candidates_limit() {
+        let policy = IceCandidatePolicy::new(conf::Ice {
+            max_candidates_per_peer: 2,
+            ..conf::Ice::default()
+        });
+
+        assert_eq!(policy.check(RELAY, false, false, 1), Ok(()));
+        assert_eq!(
+            policy.check(RELAY, false, false, 2),
+            Err(PolicyViolation::LimitReached),
+        );
+        assert_eq!(
+            IceCandidatePolicy::default().check(RELAY, false, false, 100),
+            Ok(()),
+        );
+    }
+}
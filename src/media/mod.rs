@@ -1,10 +1,12 @@
 //! Representations of media and media connection establishment objects.
 
+pub mod ice_candidate;
 pub mod peer;
 pub mod track;
 
 #[doc(inline)]
 pub use self::{
+    ice_candidate::IceCandidatePolicy,
     peer::{
         Peer, PeerError, PeerStateMachine, Stable, WaitLocalSdp, WaitRemoteSdp,
     },
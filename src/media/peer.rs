@@ -72,7 +72,7 @@ use crate::{
         },
         peers::Counter,
     },
-    turn::IceUser,
+    turn::{ice_user::Expiry, IceUser},
 };
 
 /// Subscriber to the events indicating that [`Peer`] was updated.
@@ -176,8 +176,13 @@ impl PeerError {
 #[enum_delegate(pub fn local_sdp(&self) -> Option<&str>)]
 #[enum_delegate(pub fn remote_sdp(&self) -> Option<&str>)]
 #[enum_delegate(pub fn is_force_relayed(&self) -> bool)]
+#[enum_delegate(pub fn is_relay_fallback(&self) -> bool)]
+#[enum_delegate(pub fn fall_back_to_relay(&mut self))]
 #[enum_delegate(pub fn ice_servers_list(&self) -> Option<Vec<IceServer>>)]
-#[enum_delegate(pub fn set_ice_user(&mut self, ice_user: IceUser))]
+#[enum_delegate(
+    pub fn set_ice_user(&mut self, ice_user: IceUser) -> Option<IceUser>
+)]
+#[enum_delegate(pub fn ice_user_expiry(&self) -> Option<Expiry>)]
 #[enum_delegate(pub fn endpoints(&self) -> Vec<WeakEndpoint>)]
 #[enum_delegate(pub fn add_endpoint(&mut self, endpoint: &Endpoint))]
 #[enum_delegate(
@@ -292,6 +297,17 @@ impl PeerStateMachine {
         }
     }
 
+    /// Rolls back SDP negotiation of this [`PeerStateMachine`], if any, and
+    /// returns it in [`Stable`] state.
+    #[must_use]
+    pub fn rollback_negotiation(self) -> Peer<Stable> {
+        match self {
+            PeerStateMachine::WaitLocalSdp(peer) => peer.rollback(),
+            PeerStateMachine::WaitRemoteSdp(peer) => peer.rollback(),
+            PeerStateMachine::Stable(peer) => peer,
+        }
+    }
+
     /// Indicates whether this [`PeerStateMachine`] is currently in a [`Stable`]
     /// state.
     #[inline]
@@ -377,6 +393,20 @@ impl_peer_converts!(WaitLocalSdp);
 impl_peer_converts!(WaitRemoteSdp);
 impl_peer_converts!(Stable);
 
+/// Mode of relaying media of a [`Peer`] through a TURN server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RelayMode {
+    /// Media may be sent directly, if possible.
+    Direct,
+
+    /// Media must be forcibly relayed through a TURN server.
+    Forced,
+
+    /// Media is forcibly relayed through a TURN server, because the direct
+    /// connection kept failing.
+    Fallback,
+}
+
 /// Action which can be done on a negotiation process finish.
 #[derive(Clone, Copy, Debug)]
 enum OnNegotiationFinish {
@@ -422,9 +452,8 @@ pub struct Context {
     /// All [`MediaTrack`]s with a `Send` direction.
     senders: HashMap<TrackId, Rc<MediaTrack>>,
 
-    /// Indicator whether this [`Peer`] must be forcibly connected through
-    /// TURN.
-    is_force_relayed: bool,
+    /// [`RelayMode`] of this [`Peer`] media.
+    relay_mode: RelayMode,
 
     /// Weak references to the [`Endpoint`]s related to this [`Peer`].
     endpoints: Vec<WeakEndpoint>,
@@ -619,9 +648,13 @@ impl<T> TrackChangeHandler for Peer<T> {
     }
 
     /// Sets [`Context::ice_restart`] flag to `true`.
+    ///
+    /// Clears [`Context::ice_candidates`], as they belong to the previous ICE
+    /// generation.
     #[inline]
     fn on_ice_restart(&mut self) -> Self::Output {
         self.context.ice_restart = true;
+        self.context.ice_candidates.clear();
         TrackChange::IceRestart
     }
 }
@@ -776,7 +809,21 @@ impl<T> Peer<T> {
     /// Indicates whether all media is forcibly relayed through a TURN server.
     #[inline]
     pub fn is_force_relayed(&self) -> bool {
-        self.context.is_force_relayed
+        self.context.relay_mode != RelayMode::Direct
+    }
+
+    /// Indicates whether this [`Peer`] was switched to forcibly relayed media
+    /// after its direct connection kept failing.
+    #[inline]
+    pub fn is_relay_fallback(&self) -> bool {
+        self.context.relay_mode == RelayMode::Fallback
+    }
+
+    /// Switches this [`Peer`] to forcibly relayed media, because its direct
+    /// connection keeps failing.
+    #[inline]
+    pub fn fall_back_to_relay(&mut self) {
+        self.context.relay_mode = RelayMode::Fallback;
     }
 
     /// Returns vector of [`IceServer`]s built from this [`Peer`]s [`IceUser`].
@@ -785,10 +832,18 @@ impl<T> Peer<T> {
         self.context.ice_user.as_ref().map(IceUser::servers_list)
     }
 
-    /// Sets [`IceUser`], which is used to generate [`IceServer`]s
+    /// Sets [`IceUser`], which is used to generate [`IceServer`]s, returning
+    /// the previous one.
     #[inline]
-    pub fn set_ice_user(&mut self, ice_user: IceUser) {
-        self.context.ice_user.replace(ice_user);
+    pub fn set_ice_user(&mut self, ice_user: IceUser) -> Option<IceUser> {
+        self.context.ice_user.replace(ice_user)
+    }
+
+    /// Returns [`Expiry`] of this [`Peer`]'s [`IceUser`], if its credentials
+    /// are time-limited.
+    #[inline]
+    pub fn ice_user_expiry(&self) -> Option<Expiry> {
+        self.context.ice_user.as_ref().and_then(IceUser::expiry)
     }
 
     /// Returns [`WeakEndpoint`]s for which this [`Peer`] was created.
@@ -974,6 +1029,15 @@ impl<T> Peer<T> {
         self.context.ice_candidates.insert(ice_candidate);
     }
 
+    /// Removes all [`IceCandidate`]s received for this [`Peer`].
+    ///
+    /// Should be called once its partner [`Peer`] restarts ICE, so the
+    /// received [`IceCandidate`]s belong to the previous ICE generation.
+    #[inline]
+    pub fn clear_ice_candidates(&mut self) {
+        self.context.ice_candidates.clear();
+    }
+
     /// Returns all [`IceCandidate`]s received for this [`Peer`].
     #[inline]
     #[must_use]
@@ -994,6 +1058,25 @@ impl<T> Peer<T> {
     pub fn negotiation_role(&self) -> Option<NegotiationRole> {
         self.context.negotiation_role.clone()
     }
+
+    /// Rolls back SDP negotiation of this [`Peer`] and transitions it to
+    /// [`Stable`] state.
+    ///
+    /// Discards [`Context::local_sdp`] and [`Context::remote_sdp`] of the
+    /// unfinished negotiation. [`Context::pending_track_updates`] are kept, so
+    /// the remote side will be notified about them on the next negotiation.
+    #[must_use]
+    pub fn rollback(self) -> Peer<Stable> {
+        let mut context = self.context;
+        context.local_sdp = None;
+        context.remote_sdp = None;
+        context.negotiation_role = None;
+
+        Peer {
+            context,
+            state: Stable {},
+        }
+    }
 }
 
 impl Peer<WaitLocalSdp> {
@@ -1118,7 +1201,11 @@ impl Peer<Stable> {
             remote_sdp: None,
             receivers: HashMap::new(),
             senders: HashMap::new(),
-            is_force_relayed,
+            relay_mode: if is_force_relayed {
+                RelayMode::Forced
+            } else {
+                RelayMode::Direct
+            },
             endpoints: Vec::new(),
             is_known_to_remote: false,
             pending_track_updates: Vec::new(),
@@ -1136,6 +1223,25 @@ impl Peer<Stable> {
         }
     }
 
+    /// Adds the provided send and receive [`MediaTrack`]s to this [`Peer`]
+    /// right away, without any negotiation.
+    ///
+    /// Used to restore [`Peer`]s of the recorded WebRTC stats.
+    #[must_use]
+    pub fn with_tracks(
+        mut self,
+        senders: Vec<Rc<MediaTrack>>,
+        receivers: Vec<Rc<MediaTrack>>,
+    ) -> Self {
+        for track in senders {
+            self.context.senders.insert(track.id(), track);
+        }
+        for track in receivers {
+            self.context.receivers.insert(track.id(), track);
+        }
+        self
+    }
+
     /// Changes [`Peer`] state to [`WaitLocalSdp`] and discards previously saved
     /// [SDP] Offer and Answer.
     ///
@@ -1529,6 +1635,33 @@ pub mod tests {
         assert_eq!(rx.recv().unwrap(), PeerId(0));
     }
 
+    #[test]
+    fn rollback_keeps_pending_updates() {
+        let peer = Peer::new(
+            PeerId(0),
+            MemberId::from("member-1"),
+            PeerId(1),
+            MemberId::from("member-2"),
+            false,
+            dummy_negotiation_sub_mock(),
+        );
+        let mut peer = peer.start_as_offerer();
+        peer.context
+            .pending_track_updates
+            .push(TrackChange::AddSendTrack(media_track(0)));
+
+        let peer = PeerStateMachine::from(peer.set_local_offer("offer".into()))
+            .rollback_negotiation();
+
+        assert_eq!(peer.local_sdp(), None);
+        assert_eq!(peer.negotiation_role(), None);
+        assert!(!peer.is_known_to_remote());
+        assert_eq!(peer.context.pending_track_updates.len(), 1);
+
+        let peer = peer.start_as_offerer();
+        assert_eq!(peer.get_updates().len(), 1);
+    }
+
     #[test]
     fn force_updates_works() {
         let (force_update_tx, force_update_rx) = std::sync::mpsc::channel();
@@ -2056,7 +2189,7 @@ pub mod tests {
                 Rc::new(negotiation_sub),
             );
             peer.set_ice_user(IceUser::new_static(
-                String::new(),
+                Vec::new(),
                 String::new(),
                 String::new(),
             ));
@@ -2124,6 +2257,22 @@ pub mod tests {
             assert!(state.restart_ice);
         }
 
+        #[test]
+        fn ice_restart_clears_ice_candidates() {
+            let mut peer = peer();
+            peer.add_ice_candidate(IceCandidate {
+                candidate: "candidate:1 1 UDP 1 10.0.0.1 9 typ host".into(),
+                sdp_m_line_index: Some(0),
+                sdp_mid: None,
+            });
+
+            peer.as_changes_scheduler().restart_ice();
+            assert_eq!(peer.ice_candidates().len(), 1);
+            peer.commit_scheduled_changes();
+
+            assert!(peer.ice_candidates().is_empty());
+        }
+
         #[test]
         fn sender_patch() {
             let mut peer = peer();
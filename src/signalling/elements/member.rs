@@ -92,6 +92,9 @@ struct MemberInner {
     /// URL to which `on_leave` Control API callback will be sent.
     on_leave: Option<CallbackUrl>,
 
+    /// URL to which `on_metrics` Control API callbacks will be sent.
+    on_metrics: Option<CallbackUrl>,
+
     /// Timeout of receiving heartbeat messages from the [`Member`] via Client
     /// API.
     ///
@@ -105,6 +108,23 @@ struct MemberInner {
 
     /// Interval of sending heartbeat `Ping`s to the [`Member`] via Client API.
     ping_interval: Duration,
+
+    /// Indicator whether this [`Member`] should be removed from its [`Room`]
+    /// automatically once it leaves.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    remove_on_leave: bool,
+
+    /// Region of this [`Member`], used to select STUN/TURN servers for it.
+    region: Option<String>,
+
+    /// Region of this [`Member`] resolved from the IP address it's connected
+    /// from.
+    client_region: Option<String>,
+
+    /// Indicator whether this [`Member`] is external, so its private host ICE
+    /// candidates are not revealed to other [`Member`]s.
+    external: bool,
 }
 
 impl Member {
@@ -128,9 +148,14 @@ impl Member {
             room_id,
             on_leave: None,
             on_join: None,
+            on_metrics: None,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            remove_on_leave: false,
+            region: None,
+            client_region: None,
+            external: false,
         })))
     }
 
@@ -176,6 +201,10 @@ impl Member {
             .ok_or_else(|| MembersLoadError::MemberNotFound(self.get_fid()))?;
 
         this_member.set_callback_urls(&this_member_spec);
+        this_member.set_remove_on_leave(this_member_spec.remove_on_leave());
+        this_member
+            .set_region(this_member_spec.region().map(ToOwned::to_owned));
+        this_member.set_external(this_member_spec.is_external());
 
         for (spec_play_name, spec_play_endpoint) in
             this_member_spec.play_endpoints()
@@ -468,6 +497,12 @@ impl Member {
         self.0.borrow().on_leave.clone()
     }
 
+    /// Returns [`CallbackUrl`] to which Medea should send `OnCallRecord`,
+    /// `OnNetworkCongestion` and `OnPeerMetricsEvent` callbacks.
+    pub fn get_on_metrics(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_metrics.clone()
+    }
+
     /// Returns timeout of receiving heartbeat messages from the [`Member`] via
     /// Client API.
     ///
@@ -493,6 +528,60 @@ impl Member {
     pub fn set_callback_urls(&self, spec: &MemberSpec) {
         self.0.borrow_mut().on_leave = spec.on_leave().clone();
         self.0.borrow_mut().on_join = spec.on_join().clone();
+        self.0.borrow_mut().on_metrics = spec.on_metrics().clone();
+    }
+
+    /// Indicates whether this [`Member`] should be removed from its [`Room`]
+    /// automatically once it leaves.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    pub fn is_remove_on_leave(&self) -> bool {
+        self.0.borrow().remove_on_leave
+    }
+
+    /// Sets whether this [`Member`] should be removed from its [`Room`]
+    /// automatically once it leaves.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    pub fn set_remove_on_leave(&self, remove_on_leave: bool) {
+        self.0.borrow_mut().remove_on_leave = remove_on_leave;
+    }
+
+    /// Returns region of this [`Member`], used to select STUN/TURN servers for
+    /// it.
+    pub fn region(&self) -> Option<String> {
+        self.0.borrow().region.clone()
+    }
+
+    /// Sets region of this [`Member`], used to select STUN/TURN servers for
+    /// it.
+    pub fn set_region(&self, region: Option<String>) {
+        self.0.borrow_mut().region = region;
+    }
+
+    /// Sets region of this [`Member`] resolved from the IP address it's
+    /// connected from.
+    pub fn set_client_region(&self, region: Option<String>) {
+        self.0.borrow_mut().client_region = region;
+    }
+
+    /// Returns region to select STUN/TURN servers for this [`Member`] by: its
+    /// own region, if any, or the one resolved from the IP address it's
+    /// connected from.
+    pub fn ice_region(&self) -> Option<String> {
+        let inner = self.0.borrow();
+        inner.region.clone().or_else(|| inner.client_region.clone())
+    }
+
+    /// Indicates whether this [`Member`] is external, so its private host ICE
+    /// candidates are not revealed to other [`Member`]s.
+    pub fn is_external(&self) -> bool {
+        self.0.borrow().external
+    }
+
+    /// Sets indicator whether this [`Member`] is external.
+    pub fn set_external(&self, external: bool) {
+        self.0.borrow_mut().external = external;
     }
 }
 
@@ -605,9 +694,21 @@ impl Into<proto::Member> for Member {
                 .get_on_join()
                 .map(|c| c.to_string())
                 .unwrap_or_default(),
+            on_metrics: self
+                .get_on_metrics()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
             reconnect_timeout: Some(self.get_reconnect_timeout().into()),
             idle_timeout: Some(self.get_idle_timeout().into()),
             ping_interval: Some(self.get_ping_interval().into()),
+            remove_on_leave: self.is_remove_on_leave(),
+            connected: false,
+            region: self.region().unwrap_or_default(),
+            external: self.is_external(),
+            relay_usage: None,
+            peers_relay_usage: HashMap::new(),
+            relay_fallback_peers: Vec::new(),
+            peers_stats: HashMap::new(),
             pipeline: member_pipeline,
         }
     }
@@ -767,4 +868,36 @@ mod tests {
         assert_eq!(caller_publisher.sinks().len(), 0);
         assert_eq!(some_member_publisher.sinks().len(), 0);
     }
+
+    #[test]
+    fn loads_on_metrics_callback_url() {
+        let room_element: RootElement = serde_yaml::from_str(
+            r#"
+            kind: Room
+            id: test-call
+            spec:
+              pipeline:
+                caller:
+                  kind: Member
+                  credentials:
+                    plain: test
+                  on_leave: "grpc://127.0.0.1:9091"
+                  on_metrics: "grpc://127.0.0.1:9092"
+                  spec:
+                    pipeline: {}
+        "#,
+        )
+        .unwrap();
+        let room_spec = RoomSpec::try_from(&room_element).unwrap();
+        let store = parse_members(&room_spec, conf::Rpc::default()).unwrap();
+        let caller = store.get(&id("caller")).unwrap();
+
+        assert_eq!(
+            caller.get_on_metrics().map(|url| url.to_string()),
+            Some(String::from("grpc://127.0.0.1:9092")),
+        );
+        let proto: proto::Member = caller.clone().into();
+        assert_eq!(proto.on_leave, "grpc://127.0.0.1:9091");
+        assert_eq!(proto.on_metrics, "grpc://127.0.0.1:9092");
+    }
 }
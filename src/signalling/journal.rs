@@ -0,0 +1,444 @@
+//! Bounded journal of a [`Room`] activity for post-mortem debugging.
+//!
+//! [`Room`]: crate::signalling::Room
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use chrono::{DateTime, Utc};
+use medea_client_api_proto::{Command, Event, MemberId, PeerId, RoomId};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    api::control::{callback::CallbackEvent, refs::StatefulFid},
+    media::PeerStateMachine,
+};
+
+/// Placeholder of secrets redacted from [`JournalRecord`]s.
+const REDACTED: &str = "<redacted>";
+
+/// Keys of serialized [`Event`]s fields carrying TURN credentials or SDP,
+/// which are redacted from [`JournalRecord::Event`]s.
+///
+/// `Answerer` is the key of [`NegotiationRole::Answerer`] carrying SDP offer.
+///
+/// [`NegotiationRole::Answerer`]: medea_client_api_proto::NegotiationRole
+const REDACTED_KEYS: &[&str] = &[
+    "username",
+    "credential",
+    "sdp_offer",
+    "sdp_answer",
+    "Answerer",
+];
+
+/// Single timestamped entry of a [`RoomJournal`].
+#[derive(Clone, Debug, Serialize)]
+pub struct JournalEntry {
+    /// Time of the [`JournalRecord`] being recorded.
+    pub at: DateTime<Utc>,
+
+    /// What has happened.
+    #[serde(flatten)]
+    pub record: JournalRecord,
+}
+
+/// Activity of a [`Room`] recorded in its [`RoomJournal`].
+///
+/// [`Room`]: crate::signalling::Room
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalRecord {
+    /// [`Command`] received from a `Member`.
+    Command {
+        /// ID of the `Member` sent the [`Command`].
+        member_id: MemberId,
+
+        /// [`Command`] in its [`Debug`] representation, with SDP redacted.
+        command: String,
+    },
+
+    /// [`Event`] sent to a `Member`.
+    Event {
+        /// ID of the `Member` the [`Event`] is sent to.
+        member_id: MemberId,
+
+        /// [`Event`] as it's sent to the `Member`, with TURN credentials and
+        /// SDP redacted.
+        event: Value,
+    },
+
+    /// Transition of a `Peer` to another state.
+    PeerState {
+        /// ID of the `Peer`.
+        peer_id: PeerId,
+
+        /// Previous state of the `Peer`.
+        ///
+        /// [`None`] if the `Peer` has been just created.
+        from: Option<String>,
+
+        /// New state of the `Peer`.
+        to: String,
+    },
+
+    /// Removal of a `Peer`.
+    PeerRemoved {
+        /// ID of the removed `Peer`.
+        peer_id: PeerId,
+    },
+
+    /// [Control API] callback sent.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    Callback {
+        /// FID of the element the callback is related to.
+        fid: String,
+
+        /// [`CallbackEvent`] in its [`Debug`] representation.
+        event: String,
+    },
+
+    /// Error happened.
+    Error {
+        /// Description of the error.
+        message: String,
+    },
+}
+
+/// Bounded journal of a [`Room`] activity.
+///
+/// Does nothing if created with zero capacity. Cloned [`RoomJournal`]s write
+/// to the same journal.
+///
+/// [`Room`]: crate::signalling::Room
+#[derive(Clone, Debug, Default)]
+pub struct RoomJournal(Option<Rc<RefCell<Inner>>>);
+
+/// Inner state of a [`RoomJournal`].
+#[derive(Debug)]
+struct Inner {
+    /// Maximum number of kept [`JournalEntry`]s.
+    capacity: usize,
+
+    /// Kept [`JournalEntry`]s, from the oldest to the newest one.
+    entries: VecDeque<JournalEntry>,
+
+    /// Last recorded states of the existing `Peer`s.
+    peer_states: HashMap<PeerId, String>,
+
+    /// Number of [`JournalRecord::Error`]s recorded, including the dropped
+    /// ones.
+    errors: usize,
+}
+
+impl RoomJournal {
+    /// Creates new [`RoomJournal`] keeping up to the provided `capacity` of
+    /// the latest [`JournalEntry`]s.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            return Self::default();
+        }
+        Self(Some(Rc::new(RefCell::new(Inner {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            peer_states: HashMap::new(),
+            errors: 0,
+        }))))
+    }
+
+    /// Records the provided [`Command`] received from the `Member` with the
+    /// provided [`MemberId`].
+    pub fn command(&self, member_id: &MemberId, command: &Command) {
+        self.record(|| {
+            let mut command = command.clone();
+            match &mut command {
+                Command::MakeSdpOffer { sdp_offer: sdp, .. }
+                | Command::MakeSdpAnswer {
+                    sdp_answer: sdp, ..
+                } => *sdp = REDACTED.to_owned(),
+                _ => (),
+            }
+            JournalRecord::Command {
+                member_id: member_id.clone(),
+                command: format!("{:?}", command),
+            }
+        });
+    }
+
+    /// Records the provided [`Event`] sent to the `Member` with the provided
+    /// [`MemberId`].
+    pub fn event(&self, member_id: &MemberId, event: &Event) {
+        self.record(|| {
+            let mut event = serde_json::to_value(event).unwrap_or_default();
+            redact(&mut event);
+            JournalRecord::Event {
+                member_id: member_id.clone(),
+                event,
+            }
+        });
+    }
+
+    /// Records the current state of the provided [`PeerStateMachine`], if it
+    /// differs from the previously recorded one.
+    pub fn peer_state(&self, peer: &PeerStateMachine) {
+        let inner = if let Some(inner) = &self.0 {
+            inner
+        } else {
+            return;
+        };
+        let to = peer.to_string();
+        let from = inner.borrow_mut().peer_states.insert(peer.id(), to.clone());
+        if from.as_ref() == Some(&to) {
+            return;
+        }
+        self.record(|| JournalRecord::PeerState {
+            peer_id: peer.id(),
+            from,
+            to,
+        });
+    }
+
+    /// Records removal of the `Peer`s with the provided [`PeerId`]s.
+    pub fn peers_removed(&self, peer_ids: &[PeerId]) {
+        for peer_id in peer_ids {
+            if let Some(inner) = &self.0 {
+                inner.borrow_mut().peer_states.remove(peer_id);
+            }
+            self.record(|| JournalRecord::PeerRemoved { peer_id: *peer_id });
+        }
+    }
+
+    /// Records the provided [`CallbackEvent`] sent for the element with the
+    /// provided [`StatefulFid`].
+    pub fn callback(&self, fid: &StatefulFid, event: &CallbackEvent) {
+        self.record(|| JournalRecord::Callback {
+            fid: fid.to_string(),
+            event: format!("{:?}", event),
+        });
+    }
+
+    /// Records error with the provided `message`.
+    pub fn error<M: fmt::Display>(&self, message: M) {
+        if let Some(inner) = &self.0 {
+            inner.borrow_mut().errors += 1;
+        }
+        self.record(|| JournalRecord::Error {
+            message: message.to_string(),
+        });
+    }
+
+    /// Indicates whether any [`JournalRecord::Error`] has been recorded into
+    /// this [`RoomJournal`].
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.0
+            .as_ref()
+            .map_or(false, |inner| inner.borrow().errors > 0)
+    }
+
+    /// Returns all kept [`JournalEntry`]s, from the oldest to the newest one.
+    #[must_use]
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.0.as_ref().map_or_else(Vec::new, |inner| {
+            inner.borrow().entries.iter().cloned().collect()
+        })
+    }
+
+    /// Returns all kept [`JournalEntry`]s serialized as JSON array.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.entries())
+            .expect("'JournalEntry' should always serialize to JSON")
+    }
+
+    /// Writes all kept [`JournalEntry`]s of the `Room` with the provided
+    /// [`RoomId`] as JSON to a new file in the provided `dir`.
+    ///
+    /// Returns path of the written file.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the `dir` cannot be created or the file cannot be written.
+    pub fn flush(&self, dir: &Path, room_id: &RoomId) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "{}-{}.json",
+            room_id,
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+        ));
+        let json = serde_json::to_vec_pretty(&self.entries())?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// Appends [`JournalRecord`] built by the provided function, dropping the
+    /// oldest [`JournalEntry`] if the capacity is reached.
+    ///
+    /// The function is not called if this [`RoomJournal`] is disabled.
+    fn record<F: FnOnce() -> JournalRecord>(&self, record: F) {
+        if let Some(inner) = &self.0 {
+            let mut inner = inner.borrow_mut();
+            if inner.entries.len() >= inner.capacity {
+                inner.entries.pop_front();
+            }
+            inner.entries.push_back(JournalEntry {
+                at: Utc::now(),
+                record: record(),
+            });
+        }
+    }
+}
+
+/// Replaces non-null values of [`REDACTED_KEYS`] in the provided `value`
+/// with [`REDACTED`] placeholder, recursively.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) && !value.is_null() {
+                    *value = Value::from(REDACTED);
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use medea_client_api_proto::{IceServer, NegotiationRole};
+
+    use crate::media::{peer::tests::dummy_negotiation_sub_mock, Peer, Stable};
+
+    use super::*;
+
+    #[test]
+    fn drops_oldest_entries() {
+        let journal = RoomJournal::new(2);
+        for i in 0..3 {
+            journal.error(i);
+        }
+
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].record,
+            JournalRecord::Error {
+                message: "1".into(),
+            },
+        );
+        assert!(journal.has_errors());
+    }
+
+    #[test]
+    fn records_only_peer_state_changes() {
+        let journal = RoomJournal::new(10);
+        let peer = PeerStateMachine::from(Peer::new(
+            PeerId(1),
+            MemberId::from("member-1"),
+            PeerId(2),
+            MemberId::from("member-2"),
+            false,
+            dummy_negotiation_sub_mock(),
+        ));
+        journal.peer_state(&peer);
+        journal.peer_state(&peer);
+        let peer = Peer::<Stable>::try_from(peer).unwrap();
+        let peer = PeerStateMachine::from(peer.start_as_offerer());
+        journal.peer_state(&peer);
+        journal.peers_removed(&[peer.id()]);
+
+        let records: Vec<_> =
+            journal.entries().into_iter().map(|e| e.record).collect();
+        assert_eq!(
+            records,
+            vec![
+                JournalRecord::PeerState {
+                    peer_id: PeerId(1),
+                    from: None,
+                    to: "Stable".into(),
+                },
+                JournalRecord::PeerState {
+                    peer_id: PeerId(1),
+                    from: Some("Stable".into()),
+                    to: "WaitLocalSdp".into(),
+                },
+                JournalRecord::PeerRemoved { peer_id: PeerId(1) },
+            ],
+        );
+        assert!(!journal.has_errors());
+    }
+
+    #[test]
+    fn redacts_credentials_and_sdp() {
+        let journal = RoomJournal::new(10);
+        let member_id = MemberId::from("member");
+        journal.event(
+            &member_id,
+            &Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Answerer("v=0".into()),
+                tracks: Vec::new(),
+                ice_servers: vec![IceServer {
+                    urls: vec!["turn:example.com".into()],
+                    username: Some("user".into()),
+                    credential: Some("pass".into()),
+                }],
+                force_relay: false,
+            },
+        );
+        journal.command(
+            &member_id,
+            &Command::MakeSdpAnswer {
+                peer_id: PeerId(1),
+                sdp_answer: "v=0".into(),
+                transceivers_statuses: HashMap::new(),
+            },
+        );
+
+        let json = journal.to_json();
+        assert!(!json.contains("pass"), "{}", json);
+        assert!(!json.contains("\"user\""), "{}", json);
+        assert!(!json.contains("v=0"), "{}", json);
+        assert!(json.contains("turn:example.com"), "{}", json);
+        assert!(json.contains(REDACTED), "{}", json);
+    }
+
+    #[test]
+    fn disabled_journal_records_nothing() {
+        let journal = RoomJournal::new(0);
+        journal.error("failed");
+
+        assert!(journal.entries().is_empty());
+        assert!(!journal.has_errors());
+        assert_eq!(journal.to_json(), "[]");
+    }
+
+    #[test]
+    fn flushes_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = RoomJournal::new(10);
+        journal.error("negotiation timed out");
+
+        let path = journal
+            .flush(&dir.path().join("journals"), &RoomId::from("room"))
+            .unwrap();
+
+        let json: Value =
+            serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+        assert_eq!(json[0]["kind"], "error");
+        assert_eq!(json[0]["message"], "negotiation timed out");
+    }
+}
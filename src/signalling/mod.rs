@@ -4,6 +4,7 @@
 //! [signalling]: https://webrtcglossary.com/signaling/
 
 pub mod elements;
+pub mod journal;
 pub mod participants;
 pub mod peers;
 pub mod room;
@@ -38,9 +38,11 @@ use crate::{
             member::MemberError,
             parse_members, Member, MembersLoadError,
         },
+        journal::RoomJournal,
         room::RoomError,
         Room,
     },
+    tracing::Span,
     AppContext,
 };
 
@@ -94,6 +96,10 @@ pub struct ParticipantService {
     /// If nothing provided into [`Member`] element spec then this values will
     /// be used.
     rpc_conf: RpcConf,
+
+    /// Journal of the [`Room`] activity, recording [`Event`]s sent to
+    /// [`Member`]s.
+    journal: RoomJournal,
 }
 
 impl ParticipantService {
@@ -105,6 +111,7 @@ impl ParticipantService {
     pub fn new(
         room_spec: &RoomSpec,
         context: &AppContext,
+        journal: RoomJournal,
     ) -> Result<Self, MembersLoadError> {
         Ok(Self {
             room_id: room_spec.id().clone(),
@@ -112,6 +119,7 @@ impl ParticipantService {
             connections: HashMap::new(),
             drop_connection_tasks: HashMap::new(),
             rpc_conf: context.config.rpc,
+            journal,
         })
     }
 
@@ -192,6 +200,12 @@ impl ParticipantService {
             && !self.drop_connection_tasks.contains_key(member_id)
     }
 
+    /// Checks if any [`Member`] has [`RpcConnection`], including the ones
+    /// waiting for reconnection.
+    pub fn has_connections(&self) -> bool {
+        !self.connections.is_empty()
+    }
+
     /// Sends [`Event`] to specified remote [`Member`].
     ///
     /// # Errors
@@ -203,13 +217,16 @@ impl ParticipantService {
         member_id: MemberId,
         event: Event,
     ) -> Result<(), RoomError> {
-        self.connections.get(&member_id).map_or(
-            Err(RoomError::ConnectionNotExists(member_id)),
-            |conn| {
-                conn.send_event(self.room_id.clone(), event);
-                Ok(())
-            },
-        )
+        let _span = Span::child("rpc.send_event")
+            .attr("room.id", &self.room_id)
+            .attr("member.id", &member_id);
+        let conn = self
+            .connections
+            .get(&member_id)
+            .ok_or_else(|| RoomError::ConnectionNotExists(member_id.clone()))?;
+        self.journal.event(&member_id, &event);
+        conn.send_event(self.room_id.clone(), event);
+        Ok(())
     }
 
     /// Saves provided [`RpcConnection`].
@@ -382,6 +399,9 @@ impl ParticipantService {
         );
 
         signalling_member.set_callback_urls(spec);
+        signalling_member.set_remove_on_leave(spec.remove_on_leave());
+        signalling_member.set_region(spec.region().map(ToOwned::to_owned));
+        signalling_member.set_external(spec.is_external());
 
         for (id, publish) in spec.publish_endpoints() {
             let signalling_publish = WebRtcPublishEndpoint::new(
@@ -445,13 +465,19 @@ mod test {
         let room_spec = RoomSpec {
             id: RoomId::from("test"),
             pipeline: Pipeline::new(HashMap::new()),
+            empty_timeout: None,
+            max_duration: None,
+            on_close: None,
+            max_members: None,
+            max_publishers: None,
         };
         let ctx = AppContext::new(
             Conf::default(),
             crate::turn::new_turn_auth_service_mock(),
         );
 
-        ParticipantService::new(&room_spec, &ctx).unwrap()
+        ParticipantService::new(&room_spec, &ctx, RoomJournal::default())
+            .unwrap()
     }
 
     /// Tests that when no RPC settings is provided in the `Member` element
@@ -468,6 +494,10 @@ mod test {
             None,
             None,
             None,
+            None,
+            false,
+            None,
+            false,
         );
 
         let test_member_id = MemberId::from("test-member");
@@ -507,9 +537,13 @@ mod test {
             Credential::Plain("w/e".into()),
             None,
             None,
+            None,
             Some(idle_timeout),
             Some(reconnect_timeout),
             Some(ping_interval),
+            false,
+            None,
+            false,
         );
 
         let test_member_id = MemberId::from("test-member");
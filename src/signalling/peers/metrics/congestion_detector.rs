@@ -0,0 +1,395 @@
+//! Implementation of the network congestion detector.
+
+use std::collections::HashMap;
+
+use futures::stream::LocalBoxStream;
+use medea_client_api_proto::{
+    stats::{RtcStat, RtcStatsType, StatId},
+    CongestionDirection, CongestionSeverity, PeerConnectionState, PeerId,
+};
+
+use crate::{media::PeerStateMachine, signalling::peers::metrics::EventSender};
+
+use super::{PeersMetricsEvent, RtcStatsHandler};
+
+/// Number of consecutive stats reports with the same [`CongestionSeverity`]
+/// required to consider it sustained.
+const SUSTAINED_REPORTS: u32 = 3;
+
+/// Packet loss fraction, starting from which congestion is
+/// [`CongestionSeverity::Moderate`].
+const MODERATE_LOSS: f64 = 0.05;
+
+/// Packet loss fraction, starting from which congestion is
+/// [`CongestionSeverity::Severe`].
+const SEVERE_LOSS: f64 = 0.15;
+
+/// Round trip time in seconds, starting from which congestion is
+/// [`CongestionSeverity::Moderate`].
+const MODERATE_RTT: f64 = 0.3;
+
+/// Round trip time in seconds, starting from which congestion is
+/// [`CongestionSeverity::Severe`].
+const SEVERE_RTT: f64 = 0.6;
+
+/// Estimates [`CongestionSeverity`] by the provided packet loss fraction and
+/// round trip time in seconds.
+fn estimate_severity(loss: f64, rtt: Option<f64>) -> CongestionSeverity {
+    let rtt = rtt.unwrap_or_default();
+    if loss >= SEVERE_LOSS || rtt >= SEVERE_RTT {
+        CongestionSeverity::Severe
+    } else if loss >= MODERATE_LOSS || rtt >= MODERATE_RTT {
+        CongestionSeverity::Moderate
+    } else {
+        CongestionSeverity::Clear
+    }
+}
+
+/// Congestion state of a single direction of `Peer`'s media traffic.
+#[derive(Debug)]
+struct LinkState {
+    /// Last sustained [`CongestionSeverity`], which was reported.
+    reported: CongestionSeverity,
+
+    /// [`CongestionSeverity`] of the latest stats reports.
+    candidate: CongestionSeverity,
+
+    /// Number of consecutive stats reports with the
+    /// [`LinkState::candidate`] severity.
+    reports: u32,
+}
+
+impl LinkState {
+    /// Returns new [`LinkState`] of a not congested traffic.
+    fn new() -> Self {
+        Self {
+            reported: CongestionSeverity::Clear,
+            candidate: CongestionSeverity::Clear,
+            reports: 0,
+        }
+    }
+
+    /// Accounts [`CongestionSeverity`] estimated by a new stats report.
+    ///
+    /// Returns new sustained [`CongestionSeverity`] if it differs from the
+    /// previously reported one.
+    fn update(
+        &mut self,
+        severity: CongestionSeverity,
+    ) -> Option<CongestionSeverity> {
+        if severity == self.candidate {
+            self.reports = self.reports.saturating_add(1);
+        } else {
+            self.candidate = severity;
+            self.reports = 1;
+        }
+        if self.reports >= SUSTAINED_REPORTS && self.candidate != self.reported
+        {
+            self.reported = self.candidate;
+            Some(self.reported)
+        } else {
+            None
+        }
+    }
+}
+
+/// Congestion state of a `Peer`.
+#[derive(Debug)]
+struct PeerCongestion {
+    /// Congestion state of the media traffic sent by the `Peer`.
+    uplink: LinkState,
+
+    /// Congestion state of the media traffic received by the `Peer`.
+    downlink: LinkState,
+
+    /// Latest numbers of received and lost packets of each inbound RTP
+    /// stream of the `Peer`.
+    inbound: HashMap<StatId, (u64, i64)>,
+}
+
+impl PeerCongestion {
+    /// Returns new [`PeerCongestion`] of a not congested `Peer`.
+    fn new() -> Self {
+        Self {
+            uplink: LinkState::new(),
+            downlink: LinkState::new(),
+            inbound: HashMap::new(),
+        }
+    }
+
+    /// Estimates uplink [`CongestionSeverity`] by the worst remote inbound RTP
+    /// stream in the provided [`RtcStat`]s.
+    ///
+    /// Returns [`None`] if there are no remote inbound RTP streams.
+    fn uplink_severity(stats: &[RtcStat]) -> Option<CongestionSeverity> {
+        stats
+            .iter()
+            .filter_map(|stat| match &stat.stats {
+                RtcStatsType::RemoteInboundRtp(remote) => {
+                    Some(estimate_severity(
+                        remote.fraction_lost.map_or(0.0, |f| f.0),
+                        remote.round_trip_time.map(|rtt| rtt.0),
+                    ))
+                }
+                _ => None,
+            })
+            .max()
+    }
+
+    /// Estimates downlink [`CongestionSeverity`] by the packets received and
+    /// lost by all inbound RTP streams since the previous stats report.
+    ///
+    /// Returns [`None`] if no packets were received or lost since then.
+    fn downlink_severity(
+        &mut self,
+        stats: &[RtcStat],
+    ) -> Option<CongestionSeverity> {
+        let mut received_packets = 0;
+        let mut lost_packets = 0;
+        for stat in stats {
+            if let RtcStatsType::InboundRtp(inbound) = &stat.stats {
+                let current = (
+                    inbound.packets_received,
+                    inbound.packets_lost.unwrap_or_default(),
+                );
+                if let Some(prev) =
+                    self.inbound.insert(stat.id.clone(), current)
+                {
+                    received_packets += current.0.saturating_sub(prev.0);
+                    lost_packets += (current.1 - prev.1).max(0) as u64;
+                }
+            }
+        }
+
+        let total_packets = received_packets + lost_packets;
+        if total_packets == 0 {
+            None
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let loss_ratio = lost_packets as f64 / total_packets as f64;
+            Some(estimate_severity(loss_ratio, None))
+        }
+    }
+}
+
+/// [`RtcStatsHandler`] responsible for detecting sustained congestion of
+/// `Peer`s media traffic and sending [`PeersMetricsEvent::NetworkCongestion`].
+///
+/// Uplink congestion is estimated by packet loss and round trip time reported
+/// by the remote side of the `Peer`'s outbound RTP streams, while downlink
+/// congestion is estimated by packet loss of the `Peer`'s inbound RTP streams.
+#[derive(Debug)]
+pub struct CongestionDetector {
+    /// Congestion states of all the registered `Peer`s.
+    peers: HashMap<PeerId, PeerCongestion>,
+
+    /// [`PeersMetricsEvent`]s sender.
+    event_tx: EventSender,
+}
+
+impl CongestionDetector {
+    /// Returns new [`CongestionDetector`].
+    pub(super) fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+            event_tx: EventSender::new(),
+        }
+    }
+
+    /// Sends [`PeersMetricsEvent::NetworkCongestion`] if the provided
+    /// [`CongestionSeverity`] is [`Some`].
+    fn notify(
+        &self,
+        peer_id: PeerId,
+        direction: CongestionDirection,
+        severity: Option<CongestionSeverity>,
+    ) {
+        if let Some(severity) = severity {
+            self.event_tx
+                .send_event(PeersMetricsEvent::NetworkCongestion {
+                    peer_id,
+                    direction,
+                    severity,
+                });
+        }
+    }
+}
+
+impl RtcStatsHandler for CongestionDetector {
+    fn register_peer(&mut self, peer: &PeerStateMachine) {
+        self.peers
+            .entry(peer.id())
+            .or_insert_with(PeerCongestion::new);
+    }
+
+    fn unregister_peers(&mut self, peers_ids: &[PeerId]) {
+        for peer_id in peers_ids {
+            self.peers.remove(peer_id);
+        }
+    }
+
+    fn update_peer(&mut self, _: &PeerStateMachine) {}
+
+    fn check(&mut self) {}
+
+    /// Estimates [`CongestionSeverity`] of the `Peer`'s uplink and downlink by
+    /// the provided [`RtcStat`]s, and sends
+    /// [`PeersMetricsEvent::NetworkCongestion`] if any of them changed and
+    /// stays the same for [`SUSTAINED_REPORTS`] reports.
+    fn add_stats(&mut self, peer_id: PeerId, stats: &[RtcStat]) {
+        let peer = if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer
+        } else {
+            return;
+        };
+
+        let uplink = PeerCongestion::uplink_severity(stats)
+            .and_then(|severity| peer.uplink.update(severity));
+        let downlink = peer
+            .downlink_severity(stats)
+            .and_then(|severity| peer.downlink.update(severity));
+
+        self.notify(peer_id, CongestionDirection::Uplink, uplink);
+        self.notify(peer_id, CongestionDirection::Downlink, downlink);
+    }
+
+    fn update_peer_connection_state(
+        &mut self,
+        _: PeerId,
+        _: PeerConnectionState,
+    ) {
+    }
+
+    fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+        self.event_tx.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use futures::{FutureExt as _, StreamExt as _};
+    use medea_client_api_proto::{
+        stats::{
+            Float, HighResTimeStamp, RtcInboundRtpStreamMediaType,
+            RtcInboundRtpStreamStats, RtcRemoteInboundRtpStreamStats, RtcStat,
+            RtcStatsType, StatId,
+        },
+        CongestionDirection, CongestionSeverity, PeerId,
+    };
+
+    use crate::signalling::peers::metrics::{
+        PeersMetricsEvent, RtcStatsHandler as _,
+    };
+
+    use super::{CongestionDetector, PeerCongestion};
+
+    /// Returns [`RtcStat`] of a remote inbound RTP stream with the provided
+    /// packet loss fraction and round trip time.
+    fn remote_inbound(fraction_lost: f64, rtt: f64) -> RtcStat {
+        RtcStat {
+            id: StatId("remote-inbound".into()),
+            timestamp: HighResTimeStamp(0.0),
+            stats: RtcStatsType::RemoteInboundRtp(Box::new(
+                RtcRemoteInboundRtpStreamStats {
+                    local_id: None,
+                    jitter: None,
+                    round_trip_time: Some(Float(rtt)),
+                    fraction_lost: Some(Float(fraction_lost)),
+                    reports_received: None,
+                    round_trip_time_measurements: None,
+                },
+            )),
+        }
+    }
+
+    /// Returns [`RtcStat`] of an inbound RTP stream with the provided packets
+    /// numbers.
+    fn inbound(packets_received: u64, packets_lost: i64) -> RtcStat {
+        RtcStat {
+            id: StatId("inbound".into()),
+            timestamp: HighResTimeStamp(0.0),
+            stats: RtcStatsType::InboundRtp(Box::new(
+                RtcInboundRtpStreamStats {
+                    track_id: None,
+                    mid: None,
+                    media_specific_stats: RtcInboundRtpStreamMediaType::Audio {
+                        voice_activity_flag: None,
+                        total_samples_received: None,
+                        concealed_samples: None,
+                        silent_concealed_samples: None,
+                        audio_level: None,
+                        total_audio_energy: None,
+                        total_samples_duration: None,
+                    },
+                    bytes_received: 0,
+                    packets_received,
+                    packets_lost: Some(packets_lost),
+                    jitter: None,
+                    total_decode_time: None,
+                    jitter_buffer_emitted_count: None,
+                },
+            )),
+        }
+    }
+
+    /// Returns [`CongestionDetector`] with a registered `Peer` with
+    /// `PeerId(1)`.
+    fn detector() -> CongestionDetector {
+        let mut detector = CongestionDetector::new();
+        detector.peers.insert(PeerId(1), PeerCongestion::new());
+        detector
+    }
+
+    #[test]
+    fn reports_sustained_uplink_congestion() {
+        let mut detector = detector();
+        let mut events = detector.subscribe();
+
+        for _ in 0..2 {
+            detector.add_stats(PeerId(1), &[remote_inbound(0.2, 0.1)]);
+        }
+        detector.add_stats(PeerId(1), &[remote_inbound(0.0, 0.1)]);
+        assert!(events.next().now_or_never().is_none());
+
+        for _ in 0..3 {
+            detector.add_stats(PeerId(1), &[remote_inbound(0.0, 0.4)]);
+        }
+        for _ in 0..3 {
+            detector.add_stats(PeerId(1), &[remote_inbound(0.0, 0.1)]);
+        }
+
+        for severity in
+            &[CongestionSeverity::Moderate, CongestionSeverity::Clear]
+        {
+            assert_eq!(
+                events.next().now_or_never().unwrap().unwrap(),
+                PeersMetricsEvent::NetworkCongestion {
+                    peer_id: PeerId(1),
+                    direction: CongestionDirection::Uplink,
+                    severity: *severity,
+                },
+            );
+        }
+        assert!(events.next().now_or_never().is_none());
+    }
+
+    #[test]
+    fn reports_sustained_downlink_congestion() {
+        let mut detector = detector();
+        let mut events = detector.subscribe();
+
+        for (received, lost) in &[(100, 0), (180, 20), (260, 40), (340, 60)] {
+            detector.add_stats(PeerId(1), &[inbound(*received, *lost)]);
+        }
+
+        assert_eq!(
+            events.next().now_or_never().unwrap().unwrap(),
+            PeersMetricsEvent::NetworkCongestion {
+                peer_id: PeerId(1),
+                direction: CongestionDirection::Downlink,
+                severity: CongestionSeverity::Severe,
+            },
+        );
+        assert!(events.next().now_or_never().is_none());
+    }
+}
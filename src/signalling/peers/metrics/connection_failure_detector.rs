@@ -35,6 +35,10 @@ mod peer_state {
 
         /// Current [`PeerConnectionState`] of this `PeerConnection`.
         connection_state: PeerConnectionState,
+
+        /// Number of consecutive failures of this `PeerConnection` and its
+        /// partner since they were connected last time.
+        failures: u32,
     }
 
     /// ICE connection state of `PeerConnection`.
@@ -51,11 +55,13 @@ mod peer_state {
                 id: first_peer_id,
                 partner_peer: Weak::default(),
                 connection_state: PeerConnectionState::New,
+                failures: 0,
             }));
             let second_peer = Rc::new(RefCell::new(Inner {
                 id: second_peer_id,
                 partner_peer: Rc::downgrade(&first_peer),
                 connection_state: PeerConnectionState::New,
+                failures: 0,
             }));
             first_peer.borrow_mut().partner_peer = Rc::downgrade(&second_peer);
 
@@ -85,6 +91,26 @@ mod peer_state {
         pub(super) fn set_state(&self, new_state: PeerConnectionState) {
             self.0.borrow_mut().connection_state = new_state;
         }
+
+        /// Records one more failure of this [`PeerState`] pair, returning the
+        /// number of consecutive failures.
+        pub(super) fn record_failure(&self) -> u32 {
+            let failures = self.0.borrow().failures + 1;
+            self.set_failures(failures);
+            failures
+        }
+
+        /// Resets consecutive failures of this [`PeerState`] pair.
+        #[inline]
+        pub(super) fn reset_failures(&self) {
+            self.set_failures(0);
+        }
+
+        /// Sets consecutive failures of this [`PeerState`] pair.
+        fn set_failures(&self, failures: u32) {
+            self.0.borrow_mut().failures = failures;
+            self.partner_peer().0.borrow_mut().failures = failures;
+        }
     }
 }
 
@@ -156,6 +182,9 @@ impl RtcStatsHandler for ConnectionFailureDetector {
     /// [`PeerConnectionState`] goes to [`PeerConnectionState::Failed`] from
     /// [`PeerConnectionState::Connected`] or
     /// [`PeerConnectionState::Disconnected`].
+    ///
+    /// Counts consecutive failures until [`PeerConnectionState::Connected`] is
+    /// reached.
     fn update_peer_connection_state(
         &mut self,
         peer_id: PeerId,
@@ -164,19 +193,22 @@ impl RtcStatsHandler for ConnectionFailureDetector {
         use PeerConnectionState as S;
 
         if let Some(peer) = self.peers.get(&peer_id) {
-            if let S::Failed = new_state {
-                match peer.state() {
+            match new_state {
+                S::Failed => match peer.state() {
                     S::Connecting | S::Connected | S::Disconnected => {
                         if let S::Failed = peer.partner_peer().state() {
                             self.event_tx.send_event(
                                 PeersMetricsEvent::PeerConnectionFailed {
                                     peer_id,
+                                    failures: peer.record_failure(),
                                 },
                             );
                         }
                     }
                     _ => (),
-                }
+                },
+                S::Connected => peer.reset_failures(),
+                _ => (),
             }
             peer.set_state(new_state);
         } else {
@@ -189,3 +221,52 @@ impl RtcStatsHandler for ConnectionFailureDetector {
         self.event_tx.subscribe()
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use futures::{FutureExt as _, StreamExt as _};
+    use medea_client_api_proto::{PeerConnectionState as S, PeerId};
+
+    use crate::signalling::peers::metrics::{
+        PeersMetricsEvent, RtcStatsHandler as _,
+    };
+
+    use super::{ConnectionFailureDetector, PeerState};
+
+    /// Fails both `PeerConnection`s of the registered pair.
+    fn fail_pair(detector: &mut ConnectionFailureDetector) {
+        for id in &[PeerId(1), PeerId(2)] {
+            detector.update_peer_connection_state(*id, S::Connecting);
+        }
+        for id in &[PeerId(1), PeerId(2)] {
+            detector.update_peer_connection_state(*id, S::Failed);
+        }
+    }
+
+    #[test]
+    fn counts_consecutive_failures() {
+        let mut detector = ConnectionFailureDetector::new();
+        let (first, second) = PeerState::new_pair(PeerId(1), PeerId(2));
+        detector.peers.insert(PeerId(1), first);
+        detector.peers.insert(PeerId(2), second);
+        let mut events = detector.subscribe();
+
+        fail_pair(&mut detector);
+        fail_pair(&mut detector);
+        for id in &[PeerId(1), PeerId(2)] {
+            detector.update_peer_connection_state(*id, S::Connected);
+        }
+        fail_pair(&mut detector);
+
+        for expected in &[1, 2, 1] {
+            assert_eq!(
+                events.next().now_or_never().unwrap().unwrap(),
+                PeersMetricsEvent::PeerConnectionFailed {
+                    peer_id: PeerId(2),
+                    failures: *expected,
+                },
+            );
+        }
+        assert!(events.next().now_or_never().is_none());
+    }
+}
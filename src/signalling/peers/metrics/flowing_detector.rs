@@ -31,11 +31,10 @@ use crate::{
         media_traffic_state::{
             get_diff_added, get_diff_removed, MediaTrafficState,
         },
-        metrics::{EventSender, RtcStatsHandler},
+        metrics::{Clock, EventSender, RtcStatsHandler},
         traffic_watcher::PeerTrafficWatcher,
         FlowMetricSource,
     },
-    utils::instant_into_utc,
 };
 
 use super::PeersMetricsEvent;
@@ -65,6 +64,9 @@ pub struct TrafficFlowDetector {
     ///
     /// [`Room`]: crate::signalling::room::Room
     event_tx: EventSender,
+
+    /// [`Clock`] providing the current time.
+    clock: Clock,
 }
 
 impl TrafficFlowDetector {
@@ -72,6 +74,7 @@ impl TrafficFlowDetector {
         room_id: RoomId,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
         stats_ttl: Duration,
+        clock: Clock,
     ) -> Self {
         Self {
             room_id,
@@ -79,6 +82,7 @@ impl TrafficFlowDetector {
             peers: HashMap::new(),
             stats_ttl,
             event_tx: EventSender::new(),
+            clock,
         }
     }
 
@@ -92,7 +96,7 @@ impl TrafficFlowDetector {
         let was_flowing_at = peer.get_tracks_last_update(direction, media_type);
         self.event_tx.send_event(PeersMetricsEvent::NoTrafficFlow {
             peer_id: peer.peer_id,
-            was_flowing_at: instant_into_utc(was_flowing_at),
+            was_flowing_at: self.clock.instant_into_utc(was_flowing_at),
             media_type,
             direction,
         });
@@ -130,7 +134,7 @@ impl RtcStatsHandler for TrafficFlowDetector {
             peer_id: peer.id(),
             member_id: peer.member_id(),
             partner_peer: Weak::new(),
-            last_update: Utc::now(),
+            last_update: self.clock.system_now().into(),
             senders: HashMap::new(),
             receivers: HashMap::new(),
             send_traffic_state: MediaTrafficState::new(),
@@ -138,6 +142,7 @@ impl RtcStatsHandler for TrafficFlowDetector {
             state: PeerStatState::Connecting,
             tracks_spec: PeerTracks::from(peer),
             stats_ttl: self.stats_ttl,
+            clock: self.clock.clone(),
         }));
         if let Some(partner_peer_stat) = self.peers.get(&peer.partner_peer_id())
         {
@@ -518,9 +523,9 @@ impl<T> TrackStat<T> {
 impl TrackStat<Send> {
     /// Updates this [`TrackStat`] with provided [`RtcOutboundRtpStreamStats`].
     ///
-    /// [`TrackStat::updated_at`] time will be updated.
-    fn update(&mut self, upd: &RtcOutboundRtpStreamStats) {
-        self.updated_at = Instant::now();
+    /// [`TrackStat::updated_at`] time will be updated to the provided `now`.
+    fn update(&mut self, upd: &RtcOutboundRtpStreamStats, now: Instant) {
+        self.updated_at = now;
         self.direction.packets_sent = upd.packets_sent;
     }
 }
@@ -528,23 +533,24 @@ impl TrackStat<Send> {
 impl TrackStat<Recv> {
     /// Updates this [`TrackStat`] with provided [`RtcInboundRtpStreamStats`].
     ///
-    /// [`TrackStat::updated_at`] time will be updated.
-    fn update(&mut self, upd: &RtcInboundRtpStreamStats) {
-        self.updated_at = Instant::now();
+    /// [`TrackStat::updated_at`] time will be updated to the provided `now`.
+    fn update(&mut self, upd: &RtcInboundRtpStreamStats, now: Instant) {
+        self.updated_at = now;
         self.direction.packets_received = upd.packets_received;
     }
 }
 
 impl<T> TrackStat<T> {
-    /// Checks that media traffic flows through provided [`TrackStat`].
+    /// Checks that media traffic flows through provided [`TrackStat`] at the
+    /// provided `now` time.
     ///
     /// [`TrackStat`] should be updated within
     /// [`PeerStat::stats_ttl`] or this [`TrackStat`] will be
     /// considered as stopped.
     // TODO: more asserts will be added when all browsers will adopt new stats
     //       spec https://www.w3.org/TR/webrtc-stats/
-    fn is_flowing(&self) -> bool {
-        self.updated_at().elapsed() < self.ttl
+    fn is_flowing(&self, now: Instant) -> bool {
+        now.saturating_duration_since(*self.updated_at()) < self.ttl
     }
 }
 
@@ -600,6 +606,9 @@ struct PeerStat {
 
     /// Duration, after which [`Peer`]s stats will be considered as stale.
     stats_ttl: Duration,
+
+    /// [`Clock`] providing the current time.
+    clock: Clock,
 }
 
 impl PeerStat {
@@ -612,15 +621,16 @@ impl PeerStat {
         stat_id: StatId,
         upd: &RtcOutboundRtpStreamStats,
     ) {
-        self.last_update = Utc::now();
+        self.last_update = self.clock.system_now().into();
+        let now = self.clock.now();
         let ttl = self.stats_ttl;
         let sender = self.senders.entry(stat_id).or_insert_with(|| TrackStat {
-            updated_at: Instant::now(),
+            updated_at: now,
             ttl,
             direction: Send { packets_sent: 0 },
             media_type: TrackMediaType::from(&upd.media_type),
         });
-        sender.update(upd);
+        sender.update(upd, now);
     }
 
     /// Updates [`TrackStat`] with provided [`StatId`] by
@@ -632,18 +642,19 @@ impl PeerStat {
         stat_id: StatId,
         upd: &RtcInboundRtpStreamStats,
     ) {
-        self.last_update = Utc::now();
+        self.last_update = self.clock.system_now().into();
+        let now = self.clock.now();
         let ttl = self.stats_ttl;
         let receiver =
             self.receivers.entry(stat_id).or_insert_with(|| TrackStat {
-                updated_at: Instant::now(),
+                updated_at: now,
                 ttl,
                 direction: Recv {
                     packets_received: 0,
                 },
                 media_type: TrackMediaType::from(&upd.media_specific_stats),
             });
-        receiver.update(upd);
+        receiver.update(upd, now);
     }
 
     /// Returns partner [`PeerStat`].
@@ -656,6 +667,7 @@ impl PeerStat {
     /// Updates `recv_traffic_state` based on current `receivers` state.
     /// Supposed to be called after you finished updating `receivers`.
     fn update_recv_traffic_state(&mut self) {
+        let now = self.clock.now();
         for track_media_type in &[TrackMediaType::Video, TrackMediaType::Audio]
         {
             let media_type = (*track_media_type).into();
@@ -663,7 +675,7 @@ impl PeerStat {
                 .receivers
                 .values()
                 .filter(|rx| rx.media_type == *track_media_type)
-                .filter(|rx| rx.is_flowing())
+                .filter(|rx| rx.is_flowing(now))
                 .count();
             if cnt_flowing != 0
                 && cnt_flowing
@@ -681,6 +693,7 @@ impl PeerStat {
     /// Updates `send_traffic_state` based on current `senders` state. Supposed
     /// to be called after you finished updating `senders`.
     fn update_send_traffic_state(&mut self) {
+        let now = self.clock.now();
         for track_media_type in &[TrackMediaType::Video, TrackMediaType::Audio]
         {
             let media_type = (*track_media_type).into();
@@ -688,7 +701,7 @@ impl PeerStat {
                 .senders
                 .values()
                 .filter(|rx| rx.media_type == *track_media_type)
-                .filter(|rx| rx.is_flowing())
+                .filter(|rx| rx.is_flowing(now))
                 .count();
             if cnt_flowing != 0
                 && cnt_flowing
@@ -718,14 +731,14 @@ impl PeerStat {
                 .filter(|recv| recv.media_type == media_type)
                 .map(|recv| recv.updated_at)
                 .max()
-                .unwrap_or_else(Instant::now),
+                .unwrap_or_else(|| self.clock.now()),
             MediaDirection::Publish => self
                 .senders
                 .values()
                 .filter(|send| send.media_type == media_type)
                 .map(|send| send.updated_at)
                 .max()
-                .unwrap_or_else(Instant::now),
+                .unwrap_or_else(|| self.clock.now()),
         }
     }
 
@@ -743,16 +756,17 @@ impl PeerStat {
         let mut audio_recv = 0;
         let mut video_recv = 0;
 
+        let now = self.clock.now();
         self.senders
             .values()
-            .filter(|sender| sender.is_flowing())
+            .filter(|sender| sender.is_flowing(now))
             .for_each(|sender| match sender.media_type {
                 TrackMediaType::Audio => audio_send += 1,
                 TrackMediaType::Video => video_send += 1,
             });
         self.receivers
             .values()
-            .filter(|receiver| receiver.is_flowing())
+            .filter(|receiver| receiver.is_flowing(now))
             .for_each(|receiver| match receiver.media_type {
                 TrackMediaType::Audio => audio_recv += 1,
                 TrackMediaType::Video => video_recv += 1,
@@ -794,7 +808,7 @@ impl PeerStat {
             .map(|send| send.updated_at)
             .chain(self.receivers.values().map(|recv| recv.updated_at))
             .min()
-            .unwrap_or_else(Instant::now)
+            .unwrap_or_else(|| self.clock.now())
     }
 
     /// Returns `Some` [`PeerId`] of a partner [`Peer`] if partner
@@ -879,7 +893,7 @@ mod tests {
         api::control::callback::{MediaDirection, MediaType},
         media::peer::tests::test_peer_from_peer_tracks,
         signalling::peers::{
-            metrics::{flowing_detector::PeerTracks, RtcStatsHandler},
+            metrics::{flowing_detector::PeerTracks, Clock, RtcStatsHandler},
             traffic_watcher::MockPeerTrafficWatcher,
             PeersMetricsEvent,
         },
@@ -947,12 +961,16 @@ mod tests {
                 sli_count: None,
                 concealment_events: None,
                 frames_received: None,
+                frames_dropped: None,
+                freeze_count: None,
+                total_freezes_duration: None,
             }
         };
 
         RtcInboundRtpStreamStats {
             packets_received,
             track_id: None,
+            mid: None,
             media_specific_stats: media_type,
             bytes_received: 0,
             packets_lost: None,
@@ -1006,6 +1024,7 @@ mod tests {
                 "test".to_string().into(),
                 Arc::new(watcher),
                 stats_ttl,
+                Clock::default(),
             );
 
             Self {
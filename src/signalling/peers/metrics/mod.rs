@@ -4,18 +4,34 @@
 //! 2. Use [`RtcStatsHandler::subscribe`] to subscribe to stats processing
 //!    results.
 //! 3. Provide [`Peer`]'s metrics to [`RtcStatsHandler::add_stats`].
-//! 4. Call [`RtcStatsHandler::check`] with reasonable interval
-//!    (~1-2 sec), to check for stale metrics.
+//! 4. Call [`RtcStatsHandler::check`] with reasonable interval (~1-2 sec), to
+//!    check for stale metrics.
 //!
 //! Stores [`RtcStatsHandler`]s implementors.
 //!
+//! Custom [`RtcStatsHandler`]s may be added to the built-in ones via
+//! [`RtcStatsHandlersRegistry`].
+//!
+//! All the inputs of [`RtcStatsHandler`]s may be recorded with
+//! [`StatsRecorder`], and replayed later with [`StatsReplayer`].
+//!
 //! [`Peer`]: crate::media::peer::Peer
 
+mod congestion_detector;
 mod connection_failure_detector;
 mod flowing_detector;
+mod peer_stats;
 mod quality_meter;
-
-use std::{cell::RefCell, fmt::Debug, rc::Rc, sync::Arc, time::Duration};
+mod recording;
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::{self, Debug},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
 use chrono::{DateTime, Utc};
 use futures::{
@@ -23,8 +39,9 @@ use futures::{
     stream::{self, LocalBoxStream, StreamExt as _},
 };
 use medea_client_api_proto::{
-    stats::RtcStat, ConnectionQualityScore, MemberId, PeerConnectionState,
-    PeerId, RoomId,
+    stats::RtcStat, CongestionDirection, CongestionSeverity,
+    ConnectionQualityScore, MemberId, PeerConnectionState, PeerId, RoomId,
+    TrackQualityScore,
 };
 use medea_macro::dispatchable;
 
@@ -33,6 +50,7 @@ use crate::{
     media::PeerStateMachine,
     signalling::peers::{
         metrics::{
+            congestion_detector::CongestionDetector,
             connection_failure_detector::ConnectionFailureDetector,
             flowing_detector::TrafficFlowDetector,
             quality_meter::QualityMeterStatsHandler,
@@ -41,6 +59,14 @@ use crate::{
     },
 };
 
+pub use self::{
+    peer_stats::{PeerStats, PeersStats},
+    recording::{
+        read_records, RecordedTrack, StatsRecord, StatsRecordEvent,
+        StatsRecorder, StatsRecordsSink, StatsReplayer,
+    },
+};
+
 /// WebRTC statistics analysis results.
 #[dispatchable]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -63,6 +89,12 @@ pub enum PeersMetricsEvent {
 
     /// [`ConnectionQualityScore`] updated.
     QualityMeterUpdate {
+        /// [`PeerId`] of the [`Peer`] which [`ConnectionQualityScore`] was
+        /// updated.
+        ///
+        /// [`Peer`]: crate::media::peer::Peer
+        peer_id: PeerId,
+
         /// [`MemberId`] of the [`Peer`] which [`ConnectionQualityScore`]
         /// was updated.
         ///
@@ -76,6 +108,12 @@ pub enum PeersMetricsEvent {
 
         /// Actual [`ConnectionQualityScore`].
         quality_score: ConnectionQualityScore,
+
+        /// Actual [`TrackQualityScore`]s of the media tracks received by the
+        /// [`Peer`].
+        ///
+        /// [`Peer`]: crate::media::peer::Peer
+        tracks_quality_scores: Vec<TrackQualityScore>,
     },
 
     /// One or more of the ICE transports on the connection is in the `failed`
@@ -83,6 +121,36 @@ pub enum PeersMetricsEvent {
     PeerConnectionFailed {
         /// [`PeerId`] of `PeerConnection`.
         peer_id: PeerId,
+
+        /// Number of consecutive failures of this `PeerConnection` and its
+        /// partner, including this one, since they were connected last time.
+        failures: u32,
+    },
+
+    /// Congestion of `Peer`'s media traffic in some direction has been
+    /// sustained with a new [`CongestionSeverity`].
+    NetworkCongestion {
+        /// [`PeerId`] of the `Peer` which media traffic is congested.
+        peer_id: PeerId,
+
+        /// Direction of the congested media traffic.
+        direction: CongestionDirection,
+
+        /// Severity of the congestion.
+        severity: CongestionSeverity,
+    },
+
+    /// Event emitted by some custom [`RtcStatsHandler`] registered in
+    /// [`RtcStatsHandlersRegistry`].
+    Custom {
+        /// [`PeerId`] of the `Peer` this event is about.
+        peer_id: PeerId,
+
+        /// Name of this event, defined by its [`RtcStatsHandler`].
+        name: String,
+
+        /// Arbitrary data of this event, defined by its [`RtcStatsHandler`].
+        data: HashMap<String, String>,
     },
 }
 
@@ -136,6 +204,41 @@ pub trait RtcStatsHandler: Debug {
 #[cfg(test)]
 impl_debug_by_struct_name!(MockRtcStatsHandler);
 
+/// Factory of a custom [`RtcStatsHandler`], creating it for the `Room` with
+/// the provided [`RoomId`].
+pub type RtcStatsHandlerFactory =
+    dyn Fn(&RoomId) -> Box<dyn RtcStatsHandler> + Send + Sync;
+
+/// Registry of custom [`RtcStatsHandler`]s, which are used by
+/// [`PeerMetricsService`] of each `Room` along with the built-in ones.
+#[derive(Clone, Default)]
+pub struct RtcStatsHandlersRegistry(Vec<Arc<RtcStatsHandlerFactory>>);
+
+impl RtcStatsHandlersRegistry {
+    /// Registers the provided [`RtcStatsHandlerFactory`], so each `Room`
+    /// created afterwards gets its own [`RtcStatsHandler`] created by it.
+    pub fn register<F>(&mut self, factory: F)
+    where
+        F: Fn(&RoomId) -> Box<dyn RtcStatsHandler> + Send + Sync + 'static,
+    {
+        self.0.push(Arc::new(factory));
+    }
+
+    /// Creates all registered [`RtcStatsHandler`]s for the `Room` with the
+    /// provided [`RoomId`].
+    fn build(&self, room_id: &RoomId) -> Vec<Box<dyn RtcStatsHandler>> {
+        self.0.iter().map(|factory| factory(room_id)).collect()
+    }
+}
+
+impl Debug for RtcStatsHandlersRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RtcStatsHandlersRegistry")
+            .field(&self.0.len())
+            .finish()
+    }
+}
+
 /// Service which is responsible for processing [`Peer`]s [`RtcStat`] metrics.
 ///
 /// [`Peer`]: crate::media::peer::Peer
@@ -150,22 +253,35 @@ pub struct PeerMetricsService {
 
 impl PeerMetricsService {
     /// Creates new [`PeerMetricsService`], registers all needed
-    /// [`RtcStatsHandler`]s.
+    /// [`RtcStatsHandler`]s, including the custom ones from the provided
+    /// [`RtcStatsHandlersRegistry`].
+    ///
+    /// Latest [`PeerStats`] are collected into the provided [`PeersStats`].
+    ///
+    /// Built-in [`RtcStatsHandler`]s take the current time from the provided
+    /// [`Clock`].
     pub fn new(
         room_id: RoomId,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
         stats_ttl: Duration,
+        peers_stats: PeersStats,
+        custom_handlers: &RtcStatsHandlersRegistry,
+        clock: Clock,
     ) -> Self {
         let event_tx = EventSender::new();
-        let handlers: Vec<Box<dyn RtcStatsHandler>> = vec![
+        let mut handlers: Vec<Box<dyn RtcStatsHandler>> = vec![
             Box::new(TrafficFlowDetector::new(
-                room_id,
+                room_id.clone(),
                 peers_traffic_watcher,
                 stats_ttl,
+                clock.clone(),
             )),
-            Box::new(QualityMeterStatsHandler::new()),
+            Box::new(QualityMeterStatsHandler::new(clock)),
             Box::new(ConnectionFailureDetector::new()),
+            Box::new(CongestionDetector::new()),
+            Box::new(peers_stats),
         ];
+        handlers.extend(custom_handlers.build(&room_id));
 
         Self { event_tx, handlers }
     }
@@ -239,21 +355,25 @@ impl RtcStatsHandler for PeerMetricsService {
 }
 
 /// [`PeersMetricsEvent`]s sender.
-#[derive(Debug, Clone)]
-struct EventSender(
+///
+/// May be used by [`RtcStatsHandler`]s to implement
+/// [`RtcStatsHandler::subscribe`].
+#[derive(Debug, Clone, Default)]
+pub struct EventSender(
     Rc<RefCell<Option<mpsc::UnboundedSender<PeersMetricsEvent>>>>,
 );
 
 impl EventSender {
     /// Returns new [`EventSender`].
-    fn new() -> Self {
+    #[must_use]
+    pub fn new() -> Self {
         Self(Rc::default())
     }
 
     /// Tries to send provided [`PeersMetricsEvent`] to the subscriber.
     ///
     /// If no one subscribed, then no-op.
-    fn send_event(&self, event: PeersMetricsEvent) {
+    pub fn send_event(&self, event: PeersMetricsEvent) {
         if let Some(tx) = self.0.borrow().as_ref() {
             let _ = tx.unbounded_send(event);
         }
@@ -266,9 +386,178 @@ impl EventSender {
     /// you won't receive any events happened before subscription was made.
     ///
     /// [`Stream`]: futures::stream::Stream
-    fn subscribe(&self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+    pub fn subscribe(&self) -> LocalBoxStream<'static, PeersMetricsEvent> {
         let (tx, rx) = mpsc::unbounded();
         self.0.borrow_mut().replace(tx);
         Box::pin(rx)
     }
 }
+
+/// Source of the current time for [`RtcStatsHandler`]s.
+///
+/// Follows the system time by default, while a manual [`Clock`] stands still
+/// until it's advanced via [`Clock::advance_to`], so [`StatsReplayer`] may
+/// drive it by the recorded time.
+#[derive(Clone, Debug, Default)]
+pub struct Clock(Option<Rc<ManualTime>>);
+
+/// Time of a manual [`Clock`].
+#[derive(Debug)]
+struct ManualTime {
+    /// [`Instant`] corresponding to the [`ManualTime::origin`].
+    base: Instant,
+
+    /// [`SystemTime`] which the manual [`Clock`] was started at.
+    origin: SystemTime,
+
+    /// Current [`SystemTime`] of the manual [`Clock`].
+    now: Cell<SystemTime>,
+}
+
+impl Clock {
+    /// Creates new manual [`Clock`] standing still at the provided time.
+    #[must_use]
+    pub fn manual(at: SystemTime) -> Self {
+        Self(Some(Rc::new(ManualTime {
+            base: Instant::now(),
+            origin: at,
+            now: Cell::new(at),
+        })))
+    }
+
+    /// Advances this [`Clock`] to the provided time, if it's manual.
+    ///
+    /// Manual [`Clock`] never goes backwards, so earlier times are ignored.
+    pub fn advance_to(&self, at: SystemTime) {
+        if let Some(time) = &self.0 {
+            if at > time.now.get() {
+                time.now.set(at);
+            }
+        }
+    }
+
+    /// Returns the current [`Instant`] of this [`Clock`].
+    #[must_use]
+    pub fn now(&self) -> Instant {
+        self.0.as_ref().map_or_else(Instant::now, |time| {
+            time.base
+                + time
+                    .now
+                    .get()
+                    .duration_since(time.origin)
+                    .unwrap_or_default()
+        })
+    }
+
+    /// Returns the current [`SystemTime`] of this [`Clock`].
+    #[must_use]
+    pub fn system_now(&self) -> SystemTime {
+        self.0
+            .as_ref()
+            .map_or_else(SystemTime::now, |time| time.now.get())
+    }
+
+    /// Converts the provided [`Instant`] of this [`Clock`] into
+    /// [`DateTime`].
+    #[must_use]
+    pub fn instant_into_utc(&self, instant: Instant) -> DateTime<Utc> {
+        let elapsed = self.now().saturating_duration_since(instant);
+        DateTime::from(self.system_now() - elapsed)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    use futures::{stream::LocalBoxStream, StreamExt as _};
+    use medea_client_api_proto::{
+        stats::RtcStat, PeerConnectionState, PeerId, RoomId,
+    };
+
+    use crate::{
+        media::PeerStateMachine,
+        signalling::peers::traffic_watcher::MockPeerTrafficWatcher,
+    };
+
+    use super::{
+        Clock, EventSender, PeerMetricsService, PeersMetricsEvent, PeersStats,
+        RtcStatsHandler, RtcStatsHandlersRegistry,
+    };
+
+    /// [`RtcStatsHandler`] sending [`PeersMetricsEvent::Custom`] on each
+    /// [`RtcStatsHandler::add_stats`] call.
+    #[derive(Debug)]
+    struct StatsCounter {
+        room_id: RoomId,
+        event_tx: EventSender,
+    }
+
+    impl RtcStatsHandler for StatsCounter {
+        fn register_peer(&mut self, _: &PeerStateMachine) {}
+
+        fn unregister_peers(&mut self, _: &[PeerId]) {}
+
+        fn update_peer(&mut self, _: &PeerStateMachine) {}
+
+        fn check(&mut self) {}
+
+        fn add_stats(&mut self, peer_id: PeerId, stats: &[RtcStat]) {
+            let mut data = HashMap::new();
+            data.insert("room_id".to_owned(), self.room_id.to_string());
+            data.insert("count".to_owned(), stats.len().to_string());
+            self.event_tx.send_event(PeersMetricsEvent::Custom {
+                peer_id,
+                name: "stats_received".to_owned(),
+                data,
+            });
+        }
+
+        fn update_peer_connection_state(
+            &mut self,
+            _: PeerId,
+            _: PeerConnectionState,
+        ) {
+        }
+
+        fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+            self.event_tx.subscribe()
+        }
+    }
+
+    /// Checks that [`PeerMetricsService`] uses [`RtcStatsHandler`]s from the
+    /// provided [`RtcStatsHandlersRegistry`] and forwards their events.
+    #[actix_rt::test]
+    async fn custom_handlers_events_are_forwarded() {
+        let mut registry = RtcStatsHandlersRegistry::default();
+        registry.register(|room_id| {
+            Box::new(StatsCounter {
+                room_id: room_id.clone(),
+                event_tx: EventSender::new(),
+            })
+        });
+
+        let mut metrics = PeerMetricsService::new(
+            RoomId::from("test"),
+            Arc::new(MockPeerTrafficWatcher::new()),
+            Duration::from_secs(10),
+            PeersStats::default(),
+            &registry,
+            Clock::default(),
+        );
+        let mut events = metrics.subscribe();
+        metrics.add_stats(PeerId(1), &[]);
+
+        let mut data = HashMap::new();
+        data.insert("room_id".to_owned(), "test".to_owned());
+        data.insert("count".to_owned(), "0".to_owned());
+        assert_eq!(
+            events.next().await.unwrap(),
+            PeersMetricsEvent::Custom {
+                peer_id: PeerId(1),
+                name: "stats_received".to_owned(),
+                data,
+            },
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! Collector of the latest [`RtcStat`]s and [`ConnectionQualityScore`]s
+//! history of `Peer`s.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, LocalBoxStream, StreamExt as _};
+use medea_client_api_proto::{
+    stats::{
+        CandidateType, KnownCandidateType, KnownIceCandidatePairState,
+        NonExhaustive, RtcIceCandidatePairStats, RtcStat, RtcStatsType, StatId,
+    },
+    ConnectionQualityScore, MemberId, PeerConnectionState, PeerId,
+};
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::{
+    media::PeerStateMachine,
+    signalling::peers::metrics::{PeersMetricsEvent, RtcStatsHandler},
+};
+
+/// Maximum number of [`ConnectionQualityScore`]s kept in the history of a
+/// single `Peer`.
+const QUALITY_HISTORY_LEN: usize = 50;
+
+/// Latest WebRTC stats of a `Peer` reported by its client.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeerStats {
+    /// Latest round trip time.
+    pub rtt: Option<Duration>,
+
+    /// Latest maximum jitter of all the inbound RTP streams.
+    pub jitter: Option<Duration>,
+
+    /// Percentage of packets lost in all the inbound RTP streams.
+    pub packet_loss: Option<f64>,
+
+    /// Total bitrate of all the outbound RTP streams, in bits per second.
+    pub outbound_bitrate: Option<u64>,
+
+    /// Total bitrate of all the inbound RTP streams, in bits per second.
+    pub inbound_bitrate: Option<u64>,
+
+    /// Type of the nominated ICE candidate pair.
+    ///
+    /// The least direct type of its local and remote candidates is taken.
+    pub candidate_type: Option<KnownCandidateType>,
+
+    /// Latest [`ConnectionQualityScore`]s along with the time they were
+    /// calculated at, from the oldest to the newest.
+    pub quality_history: VecDeque<(DateTime<Utc>, ConnectionQualityScore)>,
+}
+
+impl Into<proto::PeerStats> for PeerStats {
+    fn into(self) -> proto::PeerStats {
+        use proto::peer_stats::{CandidateType as Type, QualityScore};
+
+        let candidate_type = match self.candidate_type {
+            None => Type::Unknown,
+            Some(KnownCandidateType::Host) => Type::Host,
+            Some(KnownCandidateType::Srlfx) => Type::Srflx,
+            Some(KnownCandidateType::Prflx) => Type::Prflx,
+            Some(KnownCandidateType::Relay) => Type::Relay,
+        };
+        proto::PeerStats {
+            rtt: self.rtt.map(Into::into),
+            jitter: self.jitter.map(Into::into),
+            packet_loss: self.packet_loss.unwrap_or_default(),
+            outbound_bitrate: self.outbound_bitrate.unwrap_or_default(),
+            inbound_bitrate: self.inbound_bitrate.unwrap_or_default(),
+            candidate_type: candidate_type as i32,
+            quality_history: self
+                .quality_history
+                .into_iter()
+                .map(|(at, score)| QualityScore {
+                    at: at.to_rfc3339(),
+                    score: score as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Bitrate estimation of a single RTP stream.
+#[derive(Debug)]
+struct Bitrate {
+    /// Total number of bytes transferred by the stream.
+    bytes: u64,
+
+    /// Timestamp of the [`Bitrate::bytes`] measurement, in milliseconds.
+    at: f64,
+
+    /// Estimated bitrate, in bits per second.
+    bitrate: Option<u64>,
+}
+
+impl Bitrate {
+    /// Returns new [`Bitrate`] without estimation yet.
+    fn new(bytes: u64, at: f64) -> Self {
+        Self {
+            bytes,
+            at,
+            bitrate: None,
+        }
+    }
+
+    /// Estimates bitrate between the previous and the provided measurements.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn update(&mut self, bytes: u64, at: f64) {
+        let elapsed = (at - self.at) / 1000.;
+        if elapsed <= 0. {
+            return;
+        }
+        let bits = bytes.saturating_sub(self.bytes) * 8;
+        self.bitrate = Some((bits as f64 / elapsed) as u64);
+        self.bytes = bytes;
+        self.at = at;
+    }
+}
+
+/// Packets counters of an inbound RTP stream.
+#[derive(Debug, Default)]
+struct InboundPackets {
+    /// Total number of packets received.
+    received: u64,
+
+    /// Total number of packets lost.
+    lost: u64,
+}
+
+/// [`PeerStats`] accumulated from the [`RtcStat`]s of a single `Peer`.
+#[derive(Debug)]
+struct PeerStatsAccumulator {
+    /// [`MemberId`] of the `Member` owning the `Peer`.
+    member_id: MemberId,
+
+    /// [`PeerStats`] calculated from the latest [`RtcStat`]s.
+    stats: PeerStats,
+
+    /// [`Bitrate`]s of the outbound RTP streams.
+    outbound: HashMap<StatId, Bitrate>,
+
+    /// [`Bitrate`]s of the inbound RTP streams.
+    inbound: HashMap<StatId, Bitrate>,
+
+    /// [`InboundPackets`] of the inbound RTP streams.
+    packets: HashMap<StatId, InboundPackets>,
+
+    /// Latest jitters of the inbound RTP streams.
+    jitters: HashMap<StatId, Duration>,
+
+    /// Types of the local and remote ICE candidates.
+    candidates: HashMap<StatId, CandidateType>,
+}
+
+impl PeerStatsAccumulator {
+    /// Returns new empty [`PeerStatsAccumulator`] of a `Peer` owned by the
+    /// provided [`MemberId`].
+    fn new(member_id: MemberId) -> Self {
+        Self {
+            member_id,
+            stats: PeerStats::default(),
+            outbound: HashMap::new(),
+            inbound: HashMap::new(),
+            packets: HashMap::new(),
+            jitters: HashMap::new(),
+            candidates: HashMap::new(),
+        }
+    }
+
+    /// Accumulates the provided [`RtcStat`]s and recalculates [`PeerStats`].
+    fn add_stats(&mut self, stats: &[RtcStat]) {
+        let mut pairs = Vec::new();
+        for stat in stats {
+            let at = stat.timestamp.0;
+            match &stat.stats {
+                RtcStatsType::OutboundRtp(outbound) => {
+                    update_bitrate(
+                        &mut self.outbound,
+                        &stat.id,
+                        outbound.bytes_sent,
+                        at,
+                    );
+                }
+                RtcStatsType::InboundRtp(inbound) => {
+                    update_bitrate(
+                        &mut self.inbound,
+                        &stat.id,
+                        inbound.bytes_received,
+                        at,
+                    );
+                    #[allow(clippy::cast_sign_loss)]
+                    let lost = inbound.packets_lost.map_or(0, |l| l.max(0));
+                    self.packets.insert(
+                        stat.id.clone(),
+                        InboundPackets {
+                            received: inbound.packets_received,
+                            lost: lost as u64,
+                        },
+                    );
+                    if let Some(jitter) =
+                        inbound.jitter.map(|j| j.0).filter(|j| *j >= 0.)
+                    {
+                        self.jitters.insert(
+                            stat.id.clone(),
+                            Duration::from_secs_f64(jitter),
+                        );
+                    }
+                }
+                RtcStatsType::RemoteInboundRtp(remote_inbound) => {
+                    if let Some(rtt) = remote_inbound
+                        .round_trip_time
+                        .map(|t| t.0)
+                        .filter(|t| *t > 0.)
+                    {
+                        self.stats.rtt = Some(Duration::from_secs_f64(rtt));
+                    }
+                }
+                RtcStatsType::LocalCandidate(candidate)
+                | RtcStatsType::RemoteCandidate(candidate) => {
+                    self.candidates.insert(
+                        stat.id.clone(),
+                        candidate.candidate_type.clone(),
+                    );
+                }
+                RtcStatsType::CandidatePair(pair) => {
+                    if is_selected(pair) {
+                        pairs.push(pair);
+                    }
+                }
+                _ => (),
+            }
+        }
+        // Candidate pairs are processed after all the candidates are known.
+        for pair in pairs {
+            if let Some(rtt) = pair
+                .current_round_trip_time
+                .map(|t| t.0)
+                .filter(|t| *t > 0.)
+            {
+                self.stats.rtt = Some(Duration::from_secs_f64(rtt));
+            }
+            let local = self.candidate_type(pair.local_candidate_id.as_ref());
+            let remote = self.candidate_type(pair.remote_candidate_id.as_ref());
+            self.stats.candidate_type = match (local, remote) {
+                (Some(local), Some(remote)) => {
+                    Some(least_direct(local, remote))
+                }
+                (kind, None) | (None, kind) => kind,
+            };
+        }
+
+        self.stats.outbound_bitrate = total_bitrate(&self.outbound);
+        self.stats.inbound_bitrate = total_bitrate(&self.inbound);
+        self.stats.jitter = self.jitters.values().max().copied();
+        self.stats.packet_loss = if self.packets.is_empty() {
+            None
+        } else {
+            let (lost, total) =
+                self.packets.values().fold((0, 0), |(lost, total), p| {
+                    (lost + p.lost, total + p.lost + p.received)
+                });
+            #[allow(clippy::cast_precision_loss)]
+            Some(if total == 0 {
+                0.
+            } else {
+                (lost as f64 * 100.) / total as f64
+            })
+        };
+    }
+
+    /// Returns [`KnownCandidateType`] of the ICE candidate with the provided
+    /// [`StatId`], if it's known.
+    fn candidate_type(
+        &self,
+        id: Option<&StatId>,
+    ) -> Option<KnownCandidateType> {
+        match self.candidates.get(id?)? {
+            NonExhaustive::Known(kind) => Some(*kind),
+            NonExhaustive::Unknown(_) => None,
+        }
+    }
+
+    /// Pushes the provided [`ConnectionQualityScore`] to the history, removing
+    /// the oldest one if the history is full.
+    fn add_quality_score(&mut self, score: ConnectionQualityScore) {
+        let history = &mut self.stats.quality_history;
+        if history.len() == QUALITY_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back((Utc::now(), score));
+    }
+}
+
+/// Updates [`Bitrate`] of the RTP stream with the provided [`StatId`].
+fn update_bitrate(
+    bitrates: &mut HashMap<StatId, Bitrate>,
+    id: &StatId,
+    bytes: u64,
+    at: f64,
+) {
+    if let Some(bitrate) = bitrates.get_mut(id) {
+        bitrate.update(bytes, at);
+    } else {
+        bitrates.insert(id.clone(), Bitrate::new(bytes, at));
+    }
+}
+
+/// Sums all the estimated [`Bitrate`]s, returning [`None`] if there are no
+/// estimations yet.
+fn total_bitrate(bitrates: &HashMap<StatId, Bitrate>) -> Option<u64> {
+    bitrates
+        .values()
+        .filter_map(|b| b.bitrate)
+        .fold(None, |total, b| Some(total.unwrap_or(0) + b))
+}
+
+/// Indicates whether the provided [`RtcIceCandidatePairStats`] is the one
+/// actually used for media transfer.
+fn is_selected(pair: &RtcIceCandidatePairStats) -> bool {
+    pair.nominated
+        && pair.state
+            == NonExhaustive::Known(KnownIceCandidatePairState::Succeeded)
+}
+
+/// Returns the least direct of the provided [`KnownCandidateType`]s, so the
+/// relayed pair is reported as relayed regardless of its side.
+fn least_direct(
+    a: KnownCandidateType,
+    b: KnownCandidateType,
+) -> KnownCandidateType {
+    /// Returns indirection rank of the provided [`KnownCandidateType`].
+    fn rank(kind: KnownCandidateType) -> u8 {
+        match kind {
+            KnownCandidateType::Host => 0,
+            KnownCandidateType::Prflx => 1,
+            KnownCandidateType::Srlfx => 2,
+            KnownCandidateType::Relay => 3,
+        }
+    }
+
+    if rank(a) >= rank(b) {
+        a
+    } else {
+        b
+    }
+}
+
+/// Repository of [`PeerStats`] of all the `Peer`s in a `Room`.
+///
+/// Collects [`PeerStats`] as [`RtcStatsHandler`], while its clones may be used
+/// to read them.
+#[derive(Clone, Debug, Default)]
+pub struct PeersStats(Rc<RefCell<HashMap<PeerId, PeerStatsAccumulator>>>);
+
+impl PeersStats {
+    /// Returns [`PeerStats`] of the `Peer` with the provided [`PeerId`].
+    #[must_use]
+    pub fn get(&self, peer_id: PeerId) -> Option<PeerStats> {
+        self.0.borrow().get(&peer_id).map(|peer| peer.stats.clone())
+    }
+
+    /// Returns [`PeerStats`] of all the `Peer`s owned by the provided
+    /// [`MemberId`].
+    #[must_use]
+    pub fn member_peers_stats(
+        &self,
+        member_id: &MemberId,
+    ) -> HashMap<PeerId, PeerStats> {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|(_, peer)| &peer.member_id == member_id)
+            .map(|(id, peer)| (*id, peer.stats.clone()))
+            .collect()
+    }
+
+    /// Records the provided [`ConnectionQualityScore`] in the history of the
+    /// `Peer` with the provided [`PeerId`].
+    ///
+    /// Does nothing if this `Peer` is not registered.
+    pub fn record_quality_score(
+        &self,
+        peer_id: PeerId,
+        score: ConnectionQualityScore,
+    ) {
+        if let Some(peer) = self.0.borrow_mut().get_mut(&peer_id) {
+            peer.add_quality_score(score);
+        }
+    }
+}
+
+impl RtcStatsHandler for PeersStats {
+    /// Starts collecting [`PeerStats`] of the provided [`PeerStateMachine`].
+    fn register_peer(&mut self, peer: &PeerStateMachine) {
+        self.0
+            .borrow_mut()
+            .entry(peer.id())
+            .or_insert_with(|| PeerStatsAccumulator::new(peer.member_id()));
+    }
+
+    /// Removes [`PeerStats`] of the `Peer`s with the provided [`PeerId`]s.
+    fn unregister_peers(&mut self, peers_ids: &[PeerId]) {
+        let mut peers = self.0.borrow_mut();
+        for peer_id in peers_ids {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Does nothing.
+    fn update_peer(&mut self, _: &PeerStateMachine) {}
+
+    /// Does nothing.
+    fn check(&mut self) {}
+
+    /// Recalculates [`PeerStats`] of the `Peer` with the provided [`PeerId`].
+    ///
+    /// Does nothing if this `Peer` is not registered.
+    fn add_stats(&mut self, peer_id: PeerId, stats: &[RtcStat]) {
+        if let Some(peer) = self.0.borrow_mut().get_mut(&peer_id) {
+            peer.add_stats(stats);
+        }
+    }
+
+    /// Does nothing.
+    fn update_peer_connection_state(
+        &mut self,
+        _: PeerId,
+        _: PeerConnectionState,
+    ) {
+    }
+
+    /// Returns empty [`Stream`], since [`PeersStats`] doesn't produce any
+    /// [`PeersMetricsEvent`]s.
+    ///
+    /// [`Stream`]: futures::stream::Stream
+    fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+        stream::empty().boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::stats::{
+        Float, HighResTimeStamp, IceCandidatePairState, KnownProtocol,
+        RtcIceCandidateStats, RtcInboundRtpStreamMediaType,
+        RtcInboundRtpStreamStats, RtcOutboundRtpStreamMediaType,
+        RtcOutboundRtpStreamStats,
+    };
+
+    use super::*;
+
+    fn stat(id: &str, at: f64, stats: RtcStatsType) -> RtcStat {
+        RtcStat {
+            id: StatId(id.to_owned()),
+            timestamp: HighResTimeStamp(at),
+            stats,
+        }
+    }
+
+    fn outbound(bytes_sent: u64) -> RtcStatsType {
+        RtcStatsType::OutboundRtp(Box::new(RtcOutboundRtpStreamStats {
+            track_id: None,
+            media_type: RtcOutboundRtpStreamMediaType::Audio {
+                total_samples_sent: None,
+                voice_activity_flag: None,
+            },
+            bytes_sent,
+            packets_sent: 0,
+            media_source_id: None,
+        }))
+    }
+
+    fn inbound(received: u64, lost: i64, jitter: f64) -> RtcStatsType {
+        RtcStatsType::InboundRtp(Box::new(RtcInboundRtpStreamStats {
+            track_id: None,
+            mid: None,
+            media_specific_stats: RtcInboundRtpStreamMediaType::Audio {
+                voice_activity_flag: None,
+                total_samples_received: None,
+                concealed_samples: None,
+                silent_concealed_samples: None,
+                audio_level: None,
+                total_audio_energy: None,
+                total_samples_duration: None,
+            },
+            bytes_received: received * 100,
+            packets_received: received,
+            packets_lost: Some(lost),
+            jitter: Some(Float(jitter)),
+            total_decode_time: None,
+            jitter_buffer_emitted_count: None,
+        }))
+    }
+
+    fn candidate(kind: KnownCandidateType) -> Box<RtcIceCandidateStats> {
+        Box::new(RtcIceCandidateStats {
+            transport_id: None,
+            address: None,
+            port: 0,
+            protocol: NonExhaustive::Known(KnownProtocol::Udp),
+            candidate_type: NonExhaustive::Known(kind),
+            priority: 0,
+            url: None,
+            relay_protocol: None,
+        })
+    }
+
+    fn pair(nominated: bool, state: IceCandidatePairState) -> RtcStatsType {
+        RtcStatsType::CandidatePair(Box::new(RtcIceCandidatePairStats {
+            local_candidate_id: Some(StatId("local".to_owned())),
+            remote_candidate_id: Some(StatId("remote".to_owned())),
+            state,
+            nominated,
+            bytes_sent: 0,
+            bytes_received: 0,
+            total_round_trip_time: None,
+            current_round_trip_time: Some(HighResTimeStamp(0.12)),
+            available_outgoing_bitrate: None,
+        }))
+    }
+
+    fn accumulator() -> PeerStatsAccumulator {
+        PeerStatsAccumulator::new(MemberId::from("member"))
+    }
+
+    #[test]
+    fn estimates_bitrates_and_packet_loss() {
+        let mut peer = accumulator();
+
+        peer.add_stats(&[
+            stat("out", 1000., outbound(1000)),
+            stat("in", 1000., inbound(90, 10, 0.01)),
+        ]);
+        assert_eq!(peer.stats.outbound_bitrate, None);
+        assert_eq!(peer.stats.inbound_bitrate, None);
+        assert_eq!(peer.stats.packet_loss, Some(10.));
+        assert_eq!(peer.stats.jitter, Some(Duration::from_millis(10)));
+
+        peer.add_stats(&[
+            stat("out", 3000., outbound(11000)),
+            stat("in", 2000., inbound(190, 10, 0.02)),
+        ]);
+        assert_eq!(peer.stats.outbound_bitrate, Some(40_000));
+        assert_eq!(peer.stats.inbound_bitrate, Some(80_000));
+        assert_eq!(peer.stats.packet_loss, Some(5.));
+        assert_eq!(peer.stats.jitter, Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn detects_selected_candidate_pair_type() {
+        let succeeded =
+            NonExhaustive::Known(KnownIceCandidatePairState::Succeeded);
+        let mut peer = accumulator();
+
+        peer.add_stats(&[
+            stat(
+                "local",
+                1.,
+                RtcStatsType::LocalCandidate(candidate(
+                    KnownCandidateType::Host,
+                )),
+            ),
+            stat(
+                "remote",
+                1.,
+                RtcStatsType::RemoteCandidate(candidate(
+                    KnownCandidateType::Relay,
+                )),
+            ),
+            stat("pair", 1., pair(false, succeeded.clone())),
+        ]);
+        assert_eq!(peer.stats.candidate_type, None);
+        assert_eq!(peer.stats.rtt, None);
+
+        peer.add_stats(&[stat("pair", 2., pair(true, succeeded))]);
+        assert_eq!(peer.stats.candidate_type, Some(KnownCandidateType::Relay));
+        assert_eq!(peer.stats.rtt, Some(Duration::from_millis(120)));
+    }
+
+    #[test]
+    fn keeps_limited_quality_history() {
+        let mut peer = accumulator();
+
+        peer.add_quality_score(ConnectionQualityScore::Poor);
+        for _ in 0..QUALITY_HISTORY_LEN {
+            peer.add_quality_score(ConnectionQualityScore::High);
+        }
+
+        let history = &peer.stats.quality_history;
+        assert_eq!(history.len(), QUALITY_HISTORY_LEN);
+        assert!(history
+            .iter()
+            .all(|(_, score)| *score == ConnectionQualityScore::High));
+    }
+}
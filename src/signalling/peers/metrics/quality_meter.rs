@@ -10,16 +10,17 @@ use std::{
 use futures::stream::LocalBoxStream;
 use medea_client_api_proto::{
     stats::{
-        RtcInboundRtpStreamStats, RtcRemoteInboundRtpStreamStats, RtcStat,
-        RtcStatsType, StatId,
+        RtcInboundRtpStreamMediaType, RtcInboundRtpStreamStats,
+        RtcRemoteInboundRtpStreamStats, RtcStat, RtcStatsType, StatId,
     },
-    ConnectionQualityScore, MemberId, PeerConnectionState, PeerId,
+    ConnectionQualityScore, MediaSourceKind, MediaType, MemberId,
+    PeerConnectionState, PeerId, TrackId, TrackMediaKind, TrackQualityScore,
 };
 
 use crate::{
-    media::PeerStateMachine,
+    media::{MediaTrack, PeerStateMachine},
     signalling::peers::metrics::{
-        EventSender, PeersMetricsEvent, RtcStatsHandler,
+        Clock, EventSender, PeersMetricsEvent, RtcStatsHandler,
     },
 };
 
@@ -31,20 +32,26 @@ pub(super) struct QualityMeterStatsHandler {
 
     /// [`PeersMetricsEvent`]s sender.
     event_tx: EventSender,
+
+    /// [`Clock`] providing the current time.
+    clock: Clock,
 }
 
 impl QualityMeterStatsHandler {
-    /// Returns new empty [`QualityMeterStatsHandler`].
-    pub(super) fn new() -> Self {
+    /// Returns new empty [`QualityMeterStatsHandler`] taking the current time
+    /// from the provided [`Clock`].
+    pub(super) fn new(clock: Clock) -> Self {
         Self {
             peers: HashMap::new(),
             event_tx: EventSender::new(),
+            clock,
         }
     }
 
-    /// Recalculates [`ConnectionQualityScore`] for the provided
-    /// [`PeerMetric`], sends [`PeersMetricsEvent::QualityMeterUpdate`] if
-    /// new score is not equal to the previously calculated score.
+    /// Recalculates [`ConnectionQualityScore`] and [`TrackQualityScore`]s for
+    /// the provided [`PeerMetric`], sends
+    /// [`PeersMetricsEvent::QualityMeterUpdate`] if any of new scores is not
+    /// equal to the previously calculated one.
     fn update_quality_score(&self, peer: &mut PeerMetric) {
         let partner_score = peer
             .partner_peer
@@ -60,17 +67,23 @@ impl QualityMeterStatsHandler {
             .or(partner_score);
 
         if let Some(quality_score) = score {
-            if quality_score == peer.last_quality_score {
+            let tracks_quality_scores = peer.calculate_tracks();
+            if quality_score == peer.last_quality_score
+                && tracks_quality_scores == peer.last_tracks_quality_scores
+            {
                 return;
             }
 
             peer.last_quality_score = quality_score;
+            peer.last_tracks_quality_scores = tracks_quality_scores.clone();
             if let Some(partner_member_id) = peer.get_partner_member_id() {
                 self.event_tx.send_event(
                     PeersMetricsEvent::QualityMeterUpdate {
+                        peer_id: peer.id,
                         member_id: peer.member_id.clone(),
                         partner_member_id,
                         quality_score,
+                        tracks_quality_scores,
                     },
                 );
             }
@@ -95,9 +108,15 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
             id,
             member_id: peer.member_id(),
             partner_peer,
-            quality_meter: QualityMeter::new(Duration::from_secs(5)),
+            quality_meter: QualityMeter::new(
+                Duration::from_secs(5),
+                self.clock.clone(),
+            ),
+            receivers: peer.receivers().clone(),
+            tracks_meters: HashMap::new(),
             connection_state: PeerConnectionState::New,
             last_quality_score: ConnectionQualityScore::Poor,
+            last_tracks_quality_scores: Vec::new(),
         }));
         self.peers.insert(peer.id(), peer_metric.clone());
 
@@ -114,8 +133,19 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
         }
     }
 
-    /// Does nothing.
-    fn update_peer(&mut self, _: &PeerStateMachine) {}
+    /// Updates received media tracks of the [`PeerMetric`] with the provided
+    /// [`PeerStateMachine`]'s ID, dropping [`TrackQualityMeter`]s of the
+    /// removed ones.
+    fn update_peer(&mut self, peer: &PeerStateMachine) {
+        if let Some(peer_metric) = self.peers.get(&peer.id()) {
+            let mut peer_metric = peer_metric.borrow_mut();
+            let receivers = peer.receivers().clone();
+            peer_metric
+                .tracks_meters
+                .retain(|id, _| receivers.contains_key(id));
+            peer_metric.receivers = receivers;
+        }
+    }
 
     /// Calculates new score for every registered `Peer`, sends
     /// [`PeersMetricsEvent::QualityMeterUpdate`] if new score is not equal
@@ -136,6 +166,7 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
             for stat in stats {
                 match &stat.stats {
                     RtcStatsType::InboundRtp(inbound) => {
+                        peer_ref.add_inbound_rtp(inbound);
                         if let Some(partner_peer) =
                             peer_ref.partner_peer.upgrade()
                         {
@@ -196,9 +227,19 @@ struct PeerMetric {
     /// [`ConnectionQualityScore`] score calculator for this [`PeerMetric`].
     quality_meter: QualityMeter,
 
+    /// Media tracks received by the [`PeerStateMachine`].
+    receivers: HashMap<TrackId, Rc<MediaTrack>>,
+
+    /// [`ConnectionQualityScore`] score calculators for the media tracks
+    /// received by the [`PeerStateMachine`].
+    tracks_meters: HashMap<TrackId, TrackQualityMeter>,
+
     /// Last calculated [`ConnectionQualityScore`].
     last_quality_score: ConnectionQualityScore,
 
+    /// Last calculated [`TrackQualityScore`]s.
+    last_tracks_quality_scores: Vec<TrackQualityScore>,
+
     /// Current [`PeerConnectionState`].
     connection_state: PeerConnectionState,
 }
@@ -233,6 +274,65 @@ impl PeerMetric {
             .add_packets_sent(stat_id, upd.packets_received + packets_lost);
     }
 
+    /// Adds stats of the received media track from the provided
+    /// [`RtcInboundRtpStreamStats`] to its [`TrackQualityMeter`].
+    ///
+    /// Media track is looked up by `mid` of the [`RtcInboundRtpStreamStats`],
+    /// or by its media kind if there is only one media track of this kind.
+    fn add_inbound_rtp(&mut self, upd: &RtcInboundRtpStreamStats) {
+        let kind = match upd.media_specific_stats {
+            RtcInboundRtpStreamMediaType::Audio { .. } => TrackMediaKind::Audio,
+            RtcInboundRtpStreamMediaType::Video { .. } => TrackMediaKind::Video,
+        };
+        let track = if let Some(mid) = &upd.mid {
+            self.receivers
+                .values()
+                .find(|track| track.mid().as_ref() == Some(mid))
+        } else {
+            let mut of_kind = self.receivers.values().filter(|track| {
+                TrackMediaKind::from(track.media_type()) == kind
+            });
+            of_kind.next().filter(|_| of_kind.next().is_none())
+        };
+        if let Some(track) = track {
+            let meter = &self.quality_meter;
+            self.tracks_meters
+                .entry(track.id())
+                .or_insert_with(|| {
+                    TrackQualityMeter::new(
+                        track.media_type(),
+                        meter.stats_ttl,
+                        meter.clock.clone(),
+                    )
+                })
+                .add_inbound_rtp(upd);
+        }
+    }
+
+    /// Calculates current [`TrackQualityScore`]s of the received media tracks
+    /// based on the current connection state and [`TrackQualityMeter`]s
+    /// estimation.
+    fn calculate_tracks(&mut self) -> Vec<TrackQualityScore> {
+        let from_connection_state = self.calculate_from_connection_state();
+        let rtt = self.quality_meter.mean_rtt();
+        let mut scores: Vec<_> = self
+            .tracks_meters
+            .iter_mut()
+            .filter_map(|(track_id, meter)| {
+                let quality_score =
+                    from_connection_state.or_else(|| meter.calculate(rtt))?;
+                Some(TrackQualityScore {
+                    track_id: *track_id,
+                    kind: meter.kind,
+                    source_kind: meter.source_kind,
+                    quality_score,
+                })
+            })
+            .collect();
+        scores.sort_by_key(|score| score.track_id.0);
+        scores
+    }
+
     /// Returns [`MemberId`] of the partner [`Member`].
     ///
     /// [`Member`]: crate::signalling::elements::Member
@@ -286,6 +386,9 @@ struct QualityMeter {
     ///
     /// Expired stats will be automatically removed.
     packets_sent: HashMap<StatId, Vec<ExpiringStat<PacketsSent>>>,
+
+    /// [`Clock`] providing the current time.
+    clock: Clock,
 }
 
 impl QualityMeter {
@@ -312,42 +415,45 @@ impl QualityMeter {
     /// Returns new empty [`QualityMeter`].
     ///
     /// Provided stats TTL will be used to decide when [`ExpiringStat`] should
-    /// expire.
-    fn new(stats_ttl: Duration) -> Self {
+    /// expire according to the provided [`Clock`].
+    fn new(stats_ttl: Duration, clock: Clock) -> Self {
         Self {
             stats_ttl,
             rtt: Vec::new(),
             jitter: Vec::new(),
             packets_lost: HashMap::new(),
             packets_sent: HashMap::new(),
+            clock,
         }
     }
 
+    /// Wraps the provided `stat` into [`ExpiringStat`] measured now.
+    fn expiring<T>(&self, stat: T) -> ExpiringStat<T> {
+        ExpiringStat::new(stat, self.stats_ttl, self.clock.system_now())
+    }
+
     /// Adds new round trip time measurement.
     fn add_rtt(&mut self, rtt: Duration) {
-        self.rtt.push(ExpiringStat::new(Rtt(rtt), self.stats_ttl));
+        let stat = self.expiring(Rtt(rtt));
+        self.rtt.push(stat);
     }
 
     /// Adds new jitter measurement.
     fn add_jitter(&mut self, jitter: Duration) {
-        self.jitter
-            .push(ExpiringStat::new(Jitter(jitter), self.stats_ttl));
+        let stat = self.expiring(Jitter(jitter));
+        self.jitter.push(stat);
     }
 
     /// Adds new packets sent measurement.
     fn add_packets_sent(&mut self, stat_id: StatId, packets_sent: u64) {
-        self.packets_sent
-            .entry(stat_id)
-            .or_default()
-            .push(ExpiringStat::new(PacketsSent(packets_sent), self.stats_ttl));
+        let stat = self.expiring(PacketsSent(packets_sent));
+        self.packets_sent.entry(stat_id).or_default().push(stat);
     }
 
     /// Adds new packets lost measurement.
     fn add_packets_lost(&mut self, stat_id: StatId, packets_lost: u64) {
-        self.packets_lost
-            .entry(stat_id)
-            .or_default()
-            .push(ExpiringStat::new(PacketLost(packets_lost), self.stats_ttl));
+        let stat = self.expiring(PacketLost(packets_lost));
+        self.packets_lost.entry(stat_id).or_default().push(stat);
     }
 
     /// Returns [`ConnectionQualityScore`] based on accumulated stats.
@@ -365,6 +471,19 @@ impl QualityMeter {
         let jitter = self.mean_jitter()?.as_millis() as f64;
         let packet_loss = self.mean_packet_loss()?;
 
+        Some(Self::e_model_score(latency, jitter, packet_loss))
+    }
+
+    /// Returns [`ConnectionQualityScore`] for the provided latency and jitter
+    /// in milliseconds, and packet loss in percents, calculated with
+    /// [Algorithm-MOS].
+    ///
+    /// [Algorithm-MOS]: https://tinyurl.com/y3nojmot
+    fn e_model_score(
+        latency: f64,
+        jitter: f64,
+        packet_loss: f64,
+    ) -> ConnectionQualityScore {
         let effective_latency =
             jitter * Self::JITTER_FACTOR + latency * Self::LATENCY_FACTOR;
 
@@ -381,7 +500,7 @@ impl QualityMeter {
         {
             use ConnectionQualityScore::{High, Low, Medium, Poor};
 
-            Some(if r < Self::R_LOWER_LIMIT_LOW {
+            if r < Self::R_LOWER_LIMIT_LOW {
                 Poor
             } else if r < Self::R_LOWER_LIMIT_MEDIUM {
                 Low
@@ -389,7 +508,7 @@ impl QualityMeter {
                 Medium
             } else {
                 High
-            })
+            }
         }
     }
 
@@ -398,7 +517,7 @@ impl QualityMeter {
     ///
     /// Returns `None` if there are not enough data to make calculations.
     fn mean_rtt(&mut self) -> Option<Duration> {
-        remove_expired_stats(&mut self.rtt);
+        remove_expired_stats(&mut self.rtt, self.clock.system_now());
         if self.rtt.is_empty() {
             None
         } else {
@@ -415,7 +534,7 @@ impl QualityMeter {
     ///
     /// Returns `None` if there are not enough data to make calculations.
     fn mean_jitter(&mut self) -> Option<Duration> {
-        remove_expired_stats(&mut self.jitter);
+        remove_expired_stats(&mut self.jitter, self.clock.system_now());
         if self.jitter.is_empty() {
             None
         } else {
@@ -432,12 +551,13 @@ impl QualityMeter {
     ///
     /// Returns `None` if there are not enough data to make calculations.
     fn mean_packet_loss(&mut self) -> Option<f64> {
+        let now = self.clock.system_now();
         self.packets_lost.retain(|_, row| {
-            remove_expired_stats(row);
+            remove_expired_stats(row, now);
             !row.is_empty()
         });
         self.packets_sent.retain(|_, row| {
-            remove_expired_stats(row);
+            remove_expired_stats(row, now);
             !row.is_empty()
         });
 
@@ -466,11 +586,275 @@ impl QualityMeter {
     }
 }
 
+/// Calculator of the [`ConnectionQualityScore`] score of a single received
+/// media track based on its [`RtcInboundRtpStreamStats`].
+#[derive(Debug)]
+struct TrackQualityMeter {
+    /// Kind of the media track.
+    kind: TrackMediaKind,
+
+    /// Source kind of the media track.
+    source_kind: MediaSourceKind,
+
+    /// TTL of the all [`ExpiringStat`]s from this [`TrackQualityMeter`].
+    stats_ttl: Duration,
+
+    /// Snapshots of the media track's [`RtcInboundRtpStreamStats`].
+    ///
+    /// Expired snapshots will be automatically removed.
+    snapshots: Vec<ExpiringStat<InboundRtpSnapshot>>,
+
+    /// [`Clock`] providing the current time.
+    clock: Clock,
+}
+
+impl TrackQualityMeter {
+    /// Framerate of a camera video track, below which it's considered as
+    /// [`ConnectionQualityScore::Low`] at most.
+    ///
+    /// Not applied to screen-sharing video tracks, which framerate is
+    /// naturally low.
+    const VIDEO_FPS_LOW: u64 = 10;
+    /// Framerate of a camera video track, below which it's considered as
+    /// [`ConnectionQualityScore::Medium`] at most.
+    ///
+    /// Not applied to screen-sharing video tracks, which framerate is
+    /// naturally low.
+    const VIDEO_FPS_MEDIUM: u64 = 20;
+    /// Number of video freezes, starting from which a video track is
+    /// considered as [`ConnectionQualityScore::Poor`].
+    const VIDEO_FREEZES_POOR: u64 = 2;
+    /// Percents of dropped frames and lost packets of a video track, starting
+    /// from which it's considered as [`ConnectionQualityScore::Low`].
+    const VIDEO_IMPAIRMENT_LOW: f64 = 5.;
+    /// Percents of dropped frames and lost packets of a video track, starting
+    /// from which it's considered as [`ConnectionQualityScore::Medium`].
+    const VIDEO_IMPAIRMENT_MEDIUM: f64 = 2.;
+    /// Percents of dropped frames and lost packets of a video track, starting
+    /// from which it's considered as [`ConnectionQualityScore::Poor`].
+    const VIDEO_IMPAIRMENT_POOR: f64 = 10.;
+
+    /// Returns new empty [`TrackQualityMeter`] for the media track of the
+    /// provided [`MediaType`], expiring its snapshots according to the
+    /// provided [`Clock`].
+    fn new(media_type: &MediaType, stats_ttl: Duration, clock: Clock) -> Self {
+        let source_kind = match media_type {
+            MediaType::Audio(_) => MediaSourceKind::Device,
+            MediaType::Video(video) => video.source_kind,
+        };
+        Self {
+            kind: media_type.into(),
+            source_kind,
+            stats_ttl,
+            snapshots: Vec::new(),
+            clock,
+        }
+    }
+
+    /// Adds new snapshot of the media track's [`RtcInboundRtpStreamStats`].
+    fn add_inbound_rtp(&mut self, upd: &RtcInboundRtpStreamStats) {
+        self.snapshots.push(ExpiringStat::new(
+            upd.into(),
+            self.stats_ttl,
+            self.clock.system_now(),
+        ));
+    }
+
+    /// Returns [`ConnectionQualityScore`] of the media track based on the
+    /// accumulated snapshots and the provided round trip time of the
+    /// connection.
+    ///
+    /// Returns `None` if there are not enough data to make calculations.
+    fn calculate(
+        &mut self,
+        rtt: Option<Duration>,
+    ) -> Option<ConnectionQualityScore> {
+        remove_expired_stats(&mut self.snapshots, self.clock.system_now());
+        let first = &self.snapshots.first()?.stat;
+        let last = &self.snapshots.last()?.stat;
+
+        let packets_lost = last.packets_lost.saturating_sub(first.packets_lost);
+        let packets_received =
+            last.packets_received.saturating_sub(first.packets_received);
+        let packet_loss =
+            percents(packets_lost, packets_lost + packets_received);
+
+        Some(match self.kind {
+            TrackMediaKind::Audio => {
+                #[allow(clippy::cast_precision_loss)]
+                let latency = rtt.unwrap_or_default().as_millis() as f64;
+                #[allow(clippy::cast_precision_loss)]
+                let jitter = self.mean_jitter().as_millis() as f64;
+                let concealment = percents(
+                    delta(first.concealed_samples, last.concealed_samples),
+                    delta(
+                        first.total_samples_received,
+                        last.total_samples_received,
+                    ),
+                );
+                QualityMeter::e_model_score(
+                    latency,
+                    jitter,
+                    packet_loss.max(concealment),
+                )
+            }
+            TrackMediaKind::Video => {
+                use ConnectionQualityScore::{High, Low, Medium, Poor};
+
+                let impairment = packet_loss
+                    + percents(
+                        delta(first.frames_dropped, last.frames_dropped),
+                        delta(first.frames_received, last.frames_received),
+                    );
+                let mut score = if impairment >= Self::VIDEO_IMPAIRMENT_POOR {
+                    Poor
+                } else if impairment >= Self::VIDEO_IMPAIRMENT_LOW {
+                    Low
+                } else if impairment >= Self::VIDEO_IMPAIRMENT_MEDIUM {
+                    Medium
+                } else {
+                    High
+                };
+
+                let freezes = delta(first.freeze_count, last.freeze_count);
+                if freezes >= Self::VIDEO_FREEZES_POOR {
+                    score = Poor;
+                } else if freezes > 0 {
+                    score = score.min(Low);
+                }
+
+                if let (MediaSourceKind::Device, Some(fps)) =
+                    (self.source_kind, last.frames_per_second)
+                {
+                    if fps < Self::VIDEO_FPS_LOW {
+                        score = score.min(Low);
+                    } else if fps < Self::VIDEO_FPS_MEDIUM {
+                        score = score.min(Medium);
+                    }
+                }
+                score
+            }
+        })
+    }
+
+    /// Returns average jitter of the accumulated snapshots.
+    fn mean_jitter(&self) -> Duration {
+        let jitters: Vec<_> = self
+            .snapshots
+            .iter()
+            .filter_map(|s| s.stat.jitter)
+            .collect();
+        if jitters.is_empty() {
+            Duration::default()
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let len = jitters.len() as u32;
+            jitters.into_iter().sum::<Duration>() / len
+        }
+    }
+}
+
+/// Returns difference between the provided accumulated counters, or zero if
+/// any of them is unknown.
+fn delta(first: Option<u64>, last: Option<u64>) -> u64 {
+    first
+        .and_then(|first| last.map(|last| last.saturating_sub(first)))
+        .unwrap_or_default()
+}
+
+/// Returns `part` of the `total` in percents, or zero if `total` is zero.
+#[allow(clippy::cast_precision_loss)]
+fn percents(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.
+    } else {
+        (part.min(total) as f64 * 100.) / total as f64
+    }
+}
+
+/// Snapshot of accumulated [`RtcInboundRtpStreamStats`] counters of a
+/// received media track.
+#[derive(Debug)]
+struct InboundRtpSnapshot {
+    /// Total number of RTP packets received.
+    packets_received: u64,
+
+    /// Total number of RTP packets lost.
+    packets_lost: u64,
+
+    /// Packet jitter.
+    jitter: Option<Duration>,
+
+    /// Total number of audio samples received.
+    total_samples_received: Option<u64>,
+
+    /// Total number of audio samples concealed.
+    concealed_samples: Option<u64>,
+
+    /// Total number of video frames received.
+    frames_received: Option<u64>,
+
+    /// Total number of video frames dropped.
+    frames_dropped: Option<u64>,
+
+    /// Total number of video freezes.
+    freeze_count: Option<u64>,
+
+    /// Number of video frames decoded in the last second.
+    frames_per_second: Option<u64>,
+}
+
+impl From<&RtcInboundRtpStreamStats> for InboundRtpSnapshot {
+    fn from(stats: &RtcInboundRtpStreamStats) -> Self {
+        #[allow(clippy::cast_sign_loss)]
+        let mut snapshot = Self {
+            packets_received: stats.packets_received,
+            packets_lost: stats.packets_lost.map_or(0, |lost| lost.max(0))
+                as u64,
+            jitter: stats
+                .jitter
+                .map(|j| j.0)
+                .filter(|j| *j > 0.)
+                .map(Duration::from_secs_f64),
+            total_samples_received: None,
+            concealed_samples: None,
+            frames_received: None,
+            frames_dropped: None,
+            freeze_count: None,
+            frames_per_second: None,
+        };
+        match &stats.media_specific_stats {
+            RtcInboundRtpStreamMediaType::Audio {
+                total_samples_received,
+                concealed_samples,
+                ..
+            } => {
+                snapshot.total_samples_received = *total_samples_received;
+                snapshot.concealed_samples = *concealed_samples;
+            }
+            RtcInboundRtpStreamMediaType::Video {
+                frames_received,
+                frames_dropped,
+                freeze_count,
+                frames_per_second,
+                ..
+            } => {
+                snapshot.frames_received = *frames_received;
+                snapshot.frames_dropped = *frames_dropped;
+                snapshot.freeze_count = *freeze_count;
+                snapshot.frames_per_second = *frames_per_second;
+            }
+        }
+        snapshot
+    }
+}
+
 /// Retains expired [`ExpiringStat`]s from the `Vec<ExpiringStat<T>` storage.
 ///
-/// Expiration will be considered by calling [`ExpiringStat::is_expired`].
-fn remove_expired_stats<T>(stats: &mut Vec<ExpiringStat<T>>) {
-    stats.retain(|s| !s.is_expired());
+/// Expiration will be considered by calling [`ExpiringStat::is_expired`] with
+/// the provided `now` time.
+fn remove_expired_stats<T>(stats: &mut Vec<ExpiringStat<T>>, now: SystemTime) {
+    stats.retain(|s| !s.is_expired(now));
 }
 
 /// Wrapper around stat which implements expiration logic.
@@ -491,22 +875,23 @@ struct ExpiringStat<T> {
 }
 
 impl<T> ExpiringStat<T> {
-    /// Creates new [`ExpiringStat`] with a provided TTL.
+    /// Creates new [`ExpiringStat`] measured at the provided time with a
+    /// provided TTL.
     ///
     /// This [`ExpiringStat`] will be considered as expired after provided
     /// [`Duration`].
-    fn new(stat: T, ttl: Duration) -> Self {
+    fn new(stat: T, ttl: Duration, measured_at: SystemTime) -> Self {
         Self {
-            measured_at: SystemTime::now(),
+            measured_at,
             ttl,
             stat,
         }
     }
 
-    /// Indicates whether this [`ExpiringStat`] was considered as expired and
-    /// should be removed from the storage.
-    fn is_expired(&self) -> bool {
-        self.measured_at.elapsed().unwrap() > self.ttl
+    /// Indicates whether this [`ExpiringStat`] was considered as expired at
+    /// the provided `now` time and should be removed from the storage.
+    fn is_expired(&self, now: SystemTime) -> bool {
+        now.duration_since(self.measured_at).unwrap_or_default() > self.ttl
     }
 }
 
@@ -531,8 +916,9 @@ struct PacketsSent(u64);
 #[cfg(test)]
 mod tests {
     use futures::StreamExt as _;
-    use medea_client_api_proto::stats::{
-        Float, HighResTimeStamp, RtcInboundRtpStreamMediaType,
+    use medea_client_api_proto::{
+        stats::{Float, HighResTimeStamp, RtcInboundRtpStreamMediaType},
+        AudioSettings, VideoSettings,
     };
 
     use crate::media::{peer::MockPeerUpdatesSubscriber, Peer};
@@ -543,7 +929,7 @@ mod tests {
 
     #[test]
     fn packets_lost() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
         meter.add_packets_sent(StatId::from("audio"), 100);
         assert_eq!(meter.mean_packet_loss(), Some(0.));
 
@@ -576,7 +962,7 @@ mod tests {
 
     #[test]
     fn very_good_call_quality() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
         meter.add_packets_lost(StatId::from("111"), 0);
         meter.add_packets_sent(StatId::from("111"), 1000);
         meter.add_rtt(Duration::from_millis(0));
@@ -589,7 +975,7 @@ mod tests {
 
     #[test]
     fn regular_normal_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
 
         for jitter in &[0, 10, 12, 10] {
             meter.add_jitter(Duration::from_millis(*jitter));
@@ -615,7 +1001,7 @@ mod tests {
 
     #[test]
     fn bad_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
 
         for jitter in &[10, 20, 15, 16, 11] {
             meter.add_jitter(Duration::from_millis(*jitter));
@@ -642,7 +1028,7 @@ mod tests {
 
     #[test]
     fn extremely_bad_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
         meter.add_packets_lost(StatId::from("a"), 100);
         meter.add_packets_sent(StatId::from("a"), 100);
         meter.add_rtt(Duration::from_millis(1000));
@@ -657,7 +1043,7 @@ mod tests {
     fn rtt_and_jitter_stats_expire() {
         let expired = SystemTime::now() - Duration::from_secs(6);
 
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
 
         meter.add_rtt(Duration::from_millis(0));
         meter.add_jitter(Duration::from_millis(0));
@@ -681,7 +1067,7 @@ mod tests {
     fn psent_and_plost_stats_expire() {
         let expired = SystemTime::now() - Duration::from_secs(6);
 
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter = QualityMeter::new(STATS_TTL, Clock::default());
 
         meter.add_rtt(Duration::from_millis(0));
         meter.add_jitter(Duration::from_millis(0));
@@ -746,7 +1132,7 @@ mod tests {
 
     #[tokio::test]
     async fn connection_state() {
-        let mut stats_handler = QualityMeterStatsHandler::new();
+        let mut stats_handler = QualityMeterStatsHandler::new(Clock::default());
         let metrics_events = stats_handler.subscribe();
         let member_id = MemberId::from("member-1");
         let partner_member_id = MemberId::from("member-1");
@@ -780,6 +1166,7 @@ mod tests {
                 stats: RtcStatsType::InboundRtp(Box::new(
                     RtcInboundRtpStreamStats {
                         track_id: None,
+                        mid: None,
                         media_specific_stats:
                             RtcInboundRtpStreamMediaType::Audio {
                                 voice_activity_flag: None,
@@ -857,16 +1244,197 @@ mod tests {
         drop(stats_handler);
 
         let high = PeersMetricsEvent::QualityMeterUpdate {
+            peer_id: PeerId(1),
             member_id: partner_member_id.clone(),
             partner_member_id: member_id.clone(),
             quality_score: ConnectionQualityScore::High,
+            tracks_quality_scores: Vec::new(),
         };
         let poor = PeersMetricsEvent::QualityMeterUpdate {
+            peer_id: PeerId(1),
             member_id: partner_member_id,
             partner_member_id: member_id,
             quality_score: ConnectionQualityScore::Poor,
+            tracks_quality_scores: Vec::new(),
         };
         let events: Vec<_> = metrics_events.collect().await;
         assert_eq!(events, &[high.clone(), poor.clone(), high, poor]);
     }
+
+    fn audio_inbound(
+        mid: Option<&str>,
+        packets_received: u64,
+        total_samples_received: u64,
+        concealed_samples: u64,
+    ) -> RtcInboundRtpStreamStats {
+        RtcInboundRtpStreamStats {
+            track_id: None,
+            mid: mid.map(String::from),
+            media_specific_stats: RtcInboundRtpStreamMediaType::Audio {
+                voice_activity_flag: None,
+                total_samples_received: Some(total_samples_received),
+                concealed_samples: Some(concealed_samples),
+                silent_concealed_samples: None,
+                audio_level: None,
+                total_audio_energy: None,
+                total_samples_duration: None,
+            },
+            bytes_received: 0,
+            packets_received,
+            packets_lost: Some(0),
+            jitter: Some(Float(0.01)),
+            total_decode_time: None,
+            jitter_buffer_emitted_count: None,
+        }
+    }
+
+    fn video_inbound(
+        frames_received: u64,
+        frames_dropped: u64,
+        freeze_count: u64,
+        frames_per_second: u64,
+    ) -> RtcInboundRtpStreamStats {
+        RtcInboundRtpStreamStats {
+            track_id: None,
+            mid: None,
+            media_specific_stats: RtcInboundRtpStreamMediaType::Video {
+                frames_decoded: None,
+                key_frames_decoded: None,
+                frame_width: None,
+                frame_height: None,
+                total_inter_frame_delay: None,
+                frames_per_second: Some(frames_per_second),
+                frame_bit_depth: None,
+                fir_count: None,
+                pli_count: None,
+                sli_count: None,
+                concealment_events: None,
+                frames_received: Some(frames_received),
+                frames_dropped: Some(frames_dropped),
+                freeze_count: Some(freeze_count),
+                total_freezes_duration: None,
+            },
+            bytes_received: 0,
+            packets_received: frames_received * 10,
+            packets_lost: Some(0),
+            jitter: None,
+            total_decode_time: None,
+            jitter_buffer_emitted_count: None,
+        }
+    }
+
+    fn video_type(source_kind: MediaSourceKind) -> MediaType {
+        MediaType::Video(VideoSettings {
+            required: true,
+            source_kind,
+        })
+    }
+
+    #[test]
+    fn audio_track_concealment() {
+        let audio = MediaType::Audio(AudioSettings { required: true });
+        let rtt = Some(Duration::from_millis(20));
+
+        let mut meter =
+            TrackQualityMeter::new(&audio, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&audio_inbound(None, 100, 48_000, 0));
+        meter.add_inbound_rtp(&audio_inbound(None, 200, 96_000, 0));
+        assert_eq!(meter.calculate(rtt), Some(ConnectionQualityScore::High));
+
+        let mut meter =
+            TrackQualityMeter::new(&audio, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&audio_inbound(None, 100, 48_000, 0));
+        meter.add_inbound_rtp(&audio_inbound(None, 200, 96_000, 9_600));
+        assert_eq!(meter.calculate(rtt), Some(ConnectionQualityScore::Poor));
+    }
+
+    #[test]
+    fn video_track_freezes_and_framerate() {
+        let camera = video_type(MediaSourceKind::Device);
+        let display = video_type(MediaSourceKind::Display);
+
+        let mut meter =
+            TrackQualityMeter::new(&camera, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&video_inbound(100, 0, 0, 30));
+        meter.add_inbound_rtp(&video_inbound(130, 0, 0, 30));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::High));
+
+        meter.add_inbound_rtp(&video_inbound(160, 0, 1, 30));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::Low));
+
+        meter.add_inbound_rtp(&video_inbound(190, 0, 2, 30));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::Poor));
+
+        let mut meter =
+            TrackQualityMeter::new(&camera, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&video_inbound(100, 0, 0, 30));
+        meter.add_inbound_rtp(&video_inbound(200, 3, 0, 30));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::Medium),);
+
+        let mut meter =
+            TrackQualityMeter::new(&camera, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&video_inbound(100, 0, 0, 5));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::Low));
+
+        let mut meter =
+            TrackQualityMeter::new(&display, STATS_TTL, Clock::default());
+        meter.add_inbound_rtp(&video_inbound(100, 0, 0, 5));
+        assert_eq!(meter.calculate(None), Some(ConnectionQualityScore::High));
+    }
+
+    #[test]
+    fn scores_received_tracks() {
+        let audio = Rc::new(MediaTrack::new(
+            TrackId(1),
+            MediaType::Audio(AudioSettings { required: true }),
+        ));
+        audio.set_mid(String::from("0"));
+        let video = Rc::new(MediaTrack::new(
+            TrackId(2),
+            video_type(MediaSourceKind::Device),
+        ));
+        let mut receivers = HashMap::new();
+        receivers.insert(audio.id(), audio);
+        receivers.insert(video.id(), video);
+
+        let mut peer = PeerMetric {
+            id: PeerId(0),
+            member_id: MemberId::from("member"),
+            partner_peer: Weak::new(),
+            quality_meter: QualityMeter::new(STATS_TTL, Clock::default()),
+            receivers,
+            tracks_meters: HashMap::new(),
+            last_quality_score: ConnectionQualityScore::Poor,
+            last_tracks_quality_scores: Vec::new(),
+            connection_state: PeerConnectionState::Connected,
+        };
+        peer.add_inbound_rtp(&audio_inbound(Some("0"), 100, 48_000, 0));
+        peer.add_inbound_rtp(&audio_inbound(Some("unknown"), 100, 48_000, 0));
+        peer.add_inbound_rtp(&video_inbound(100, 0, 0, 30));
+        peer.add_inbound_rtp(&video_inbound(130, 0, 2, 30));
+
+        assert_eq!(
+            peer.calculate_tracks(),
+            vec![
+                TrackQualityScore {
+                    track_id: TrackId(1),
+                    kind: TrackMediaKind::Audio,
+                    source_kind: MediaSourceKind::Device,
+                    quality_score: ConnectionQualityScore::High,
+                },
+                TrackQualityScore {
+                    track_id: TrackId(2),
+                    kind: TrackMediaKind::Video,
+                    source_kind: MediaSourceKind::Device,
+                    quality_score: ConnectionQualityScore::Poor,
+                },
+            ],
+        );
+
+        peer.connection_state = PeerConnectionState::Disconnected;
+        assert!(peer
+            .calculate_tracks()
+            .iter()
+            .all(|s| s.quality_score == ConnectionQualityScore::Poor));
+    }
 }
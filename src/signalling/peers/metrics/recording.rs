@@ -0,0 +1,696 @@
+//! Recording of the [`RtcStatsHandler`]s inputs and their replaying.
+//!
+//! [`StatsRecorder`] writes everything provided to [`PeerMetricsService`] of a
+//! `Room` into a [`StatsRecordsSink`], so [`StatsReplayer`] may feed it back
+//! to a new [`PeerMetricsService`] later, reproducing the
+//! [`PeersMetricsEvent`]s.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead as _, BufReader, Read},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::{
+    stream::{self, LocalBoxStream},
+    FutureExt as _, StreamExt as _,
+};
+use medea_client_api_proto::{
+    stats::RtcStat, MediaType, MemberId, PeerConnectionState, PeerId, RoomId,
+    TrackId, TrackUpdate,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conf,
+    log::prelude::*,
+    media::{peer::PeerUpdatesSubscriber, MediaTrack, Peer, PeerStateMachine},
+    signalling::peers::{
+        metrics::{
+            Clock, PeerMetricsService, PeersMetricsEvent, PeersStats,
+            RtcStatsHandler, RtcStatsHandlersRegistry,
+        },
+        PeerTrafficWatcher,
+    },
+    utils::JsonLinesSink,
+};
+
+/// Single recorded input of [`PeerMetricsService`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StatsRecord {
+    /// Time of this [`StatsRecord`] being recorded.
+    pub at: DateTime<Utc>,
+
+    /// ID of the `Room` owning the [`Peer`].
+    pub room_id: RoomId,
+
+    /// ID of the `Member` owning the [`Peer`].
+    pub member_id: MemberId,
+
+    /// ID of the [`Peer`] this [`StatsRecord`] is about.
+    pub peer_id: PeerId,
+
+    /// Recorded input itself.
+    pub event: StatsRecordEvent,
+}
+
+/// Input of [`PeerMetricsService`] recorded in a [`StatsRecord`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum StatsRecordEvent {
+    /// [`Peer`] was registered via [`RtcStatsHandler::register_peer`].
+    PeerRegistered {
+        /// ID of the partner [`Peer`].
+        partner_peer_id: PeerId,
+
+        /// ID of the `Member` owning the partner [`Peer`].
+        partner_member_id: MemberId,
+
+        /// Send tracks of the [`Peer`].
+        senders: Vec<RecordedTrack>,
+
+        /// Receive tracks of the [`Peer`].
+        receivers: Vec<RecordedTrack>,
+    },
+
+    /// Tracks of [`Peer`] were updated via [`RtcStatsHandler::update_peer`].
+    PeerUpdated {
+        /// Send tracks of the [`Peer`].
+        senders: Vec<RecordedTrack>,
+
+        /// Receive tracks of the [`Peer`].
+        receivers: Vec<RecordedTrack>,
+    },
+
+    /// [`Peer`] was unregistered via [`RtcStatsHandler::unregister_peers`].
+    PeerUnregistered,
+
+    /// [`PeerConnectionState`] of [`Peer`] was reported by client.
+    PeerConnectionState(PeerConnectionState),
+
+    /// [`RtcStat`]s of [`Peer`] were reported by client.
+    RtcStats(Vec<RtcStat>),
+}
+
+/// Recorded [`MediaTrack`] of a [`Peer`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecordedTrack {
+    /// ID of the [`MediaTrack`].
+    pub id: TrackId,
+
+    /// [`MediaType`] of the [`MediaTrack`].
+    pub media_type: MediaType,
+
+    /// [`mid`] of the [`MediaTrack`], if known.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    pub mid: Option<String>,
+
+    /// Indicator whether transceiver of the [`MediaTrack`] is enabled.
+    pub enabled: bool,
+}
+
+impl From<&MediaTrack> for RecordedTrack {
+    fn from(track: &MediaTrack) -> Self {
+        Self {
+            id: track.id(),
+            media_type: track.media_type().clone(),
+            mid: track.mid(),
+            enabled: track.is_transceiver_enabled(),
+        }
+    }
+}
+
+impl From<&RecordedTrack> for Rc<MediaTrack> {
+    fn from(recorded: &RecordedTrack) -> Self {
+        let track = MediaTrack::new(recorded.id, recorded.media_type.clone());
+        if let Some(mid) = recorded.mid.clone() {
+            track.set_mid(mid);
+        }
+        track.set_transceiver_enabled(recorded.enabled);
+        Rc::new(track)
+    }
+}
+
+/// Sink appending [`StatsRecord`]s to a gzip-compressed file as JSON lines.
+///
+/// Does nothing if no file is configured.
+/// Each [`StatsRecord`] is written as a separate gzip member, so everything
+/// recorded before a media server crash remains readable.
+#[derive(Clone, Debug, Default)]
+pub struct StatsRecordsSink(JsonLinesSink);
+
+impl StatsRecordsSink {
+    /// Opens the file configured in the provided [`conf::StatsRecording`] for
+    /// appending.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the configured file cannot be opened or created.
+    pub fn new(cf: &conf::StatsRecording) -> io::Result<Self> {
+        JsonLinesSink::file(cf.file()).map(|sink| Self(sink.gzipped()))
+    }
+
+    /// Indicates whether this [`StatsRecordsSink`] writes [`StatsRecord`]s
+    /// anywhere.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_enabled()
+    }
+
+    /// Appends the provided [`StatsRecord`] as a single compressed JSON line.
+    ///
+    /// Failures are logged, since losing a single [`StatsRecord`] should not
+    /// affect the `Room`.
+    pub fn write(&self, record: &StatsRecord) {
+        if let Err(e) = self.0.write(record) {
+            error!("Failed to write StatsRecord: {}", e);
+        }
+    }
+
+    /// Registers [`StatsRecorder`] writing to this [`StatsRecordsSink`] in the
+    /// provided [`RtcStatsHandlersRegistry`], if this [`StatsRecordsSink`] is
+    /// enabled.
+    pub fn register_in(&self, registry: &mut RtcStatsHandlersRegistry) {
+        if self.is_enabled() {
+            let sink = self.clone();
+            registry.register(move |room_id| {
+                Box::new(StatsRecorder::new(room_id.clone(), sink.clone()))
+            });
+        }
+    }
+}
+
+/// Reads [`StatsRecord`]s written by [`StatsRecordsSink`] from the provided
+/// gzip-compressed JSON lines.
+///
+/// Truncated last gzip member (e.g. a record being written at the moment) is
+/// considered as the regular end of the compressed data.
+pub fn read_records<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = io::Result<StatsRecord>> {
+    BufReader::new(MultiGzDecoder::new(reader))
+        .lines()
+        .take_while(|line| {
+            !matches!(line, Err(e) if e.kind() == io::ErrorKind::UnexpectedEof)
+        })
+        .filter(|line| !matches!(line, Ok(l) if l.is_empty()))
+        .map(|line| {
+            line.and_then(|l| serde_json::from_str(&l).map_err(Into::into))
+        })
+}
+
+/// [`RtcStatsHandler`] recording all its inputs as [`StatsRecord`]s.
+#[derive(Debug)]
+pub struct StatsRecorder {
+    /// ID of the `Room` which this [`StatsRecorder`] is created for.
+    room_id: RoomId,
+
+    /// [`StatsRecordsSink`] which [`StatsRecord`]s are written to.
+    sink: StatsRecordsSink,
+
+    /// `Member`s owning the registered [`Peer`]s.
+    members: HashMap<PeerId, MemberId>,
+}
+
+impl StatsRecorder {
+    /// Creates new [`StatsRecorder`] for the `Room` with the provided
+    /// [`RoomId`].
+    #[inline]
+    #[must_use]
+    pub fn new(room_id: RoomId, sink: StatsRecordsSink) -> Self {
+        Self {
+            room_id,
+            sink,
+            members: HashMap::new(),
+        }
+    }
+
+    /// Writes the provided [`StatsRecordEvent`] of the [`Peer`] with the
+    /// provided [`PeerId`], if this [`Peer`] is registered.
+    fn record(&self, peer_id: PeerId, event: StatsRecordEvent) {
+        if let Some(member_id) = self.members.get(&peer_id) {
+            self.sink.write(&StatsRecord {
+                at: Utc::now(),
+                room_id: self.room_id.clone(),
+                member_id: member_id.clone(),
+                peer_id,
+                event,
+            });
+        }
+    }
+}
+
+/// Returns [`RecordedTrack`]s of the provided [`MediaTrack`]s ordered by
+/// their IDs.
+fn recorded_tracks<'a, I>(tracks: I) -> Vec<RecordedTrack>
+where
+    I: IntoIterator<Item = &'a Rc<MediaTrack>>,
+{
+    let mut tracks: Vec<RecordedTrack> = tracks
+        .into_iter()
+        .map(|track| RecordedTrack::from(track.as_ref()))
+        .collect();
+    tracks.sort_by_key(|track| track.id.0);
+    tracks
+}
+
+impl RtcStatsHandler for StatsRecorder {
+    fn register_peer(&mut self, peer: &PeerStateMachine) {
+        self.members.insert(peer.id(), peer.member_id());
+        self.record(
+            peer.id(),
+            StatsRecordEvent::PeerRegistered {
+                partner_peer_id: peer.partner_peer_id(),
+                partner_member_id: peer.partner_member_id(),
+                senders: recorded_tracks(peer.senders().values()),
+                receivers: recorded_tracks(peer.receivers().values()),
+            },
+        );
+    }
+
+    fn unregister_peers(&mut self, peers_ids: &[PeerId]) {
+        for peer_id in peers_ids {
+            self.record(*peer_id, StatsRecordEvent::PeerUnregistered);
+            self.members.remove(peer_id);
+        }
+    }
+
+    fn update_peer(&mut self, peer: &PeerStateMachine) {
+        self.record(
+            peer.id(),
+            StatsRecordEvent::PeerUpdated {
+                senders: recorded_tracks(peer.senders().values()),
+                receivers: recorded_tracks(peer.receivers().values()),
+            },
+        );
+    }
+
+    fn check(&mut self) {}
+
+    fn add_stats(&mut self, peer_id: PeerId, stats: &[RtcStat]) {
+        self.record(peer_id, StatsRecordEvent::RtcStats(stats.to_vec()));
+    }
+
+    fn update_peer_connection_state(
+        &mut self,
+        peer_id: PeerId,
+        state: PeerConnectionState,
+    ) {
+        self.record(peer_id, StatsRecordEvent::PeerConnectionState(state));
+    }
+
+    fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+        stream::empty().boxed_local()
+    }
+}
+
+/// [`PeerUpdatesSubscriber`] of the restored [`Peer`]s, which ignores all the
+/// updates, since restored [`Peer`]s are never negotiated.
+#[derive(Debug)]
+struct IgnoredPeerUpdates;
+
+impl PeerUpdatesSubscriber for IgnoredPeerUpdates {
+    fn negotiation_needed(&self, _: PeerId) {}
+
+    fn force_update(&self, _: PeerId, _: Vec<TrackUpdate>) {}
+}
+
+/// Replayed `Room` of a [`StatsReplayer`].
+struct ReplayedRoom {
+    /// [`PeerMetricsService`] which [`StatsRecord`]s are fed to.
+    metrics: PeerMetricsService,
+
+    /// Manual [`Clock`] of the [`ReplayedRoom::metrics`], driven by the
+    /// [`StatsRecord::at`] time.
+    clock: Clock,
+
+    /// Recorded time of the next [`RtcStatsHandler::check`] call.
+    next_check_at: SystemTime,
+
+    /// Subscription to the [`PeersMetricsEvent`]s of the
+    /// [`ReplayedRoom::metrics`].
+    events: LocalBoxStream<'static, PeersMetricsEvent>,
+
+    /// Partners of the registered [`Peer`]s.
+    partners: HashMap<PeerId, (PeerId, MemberId)>,
+}
+
+/// Feeder of [`StatsRecord`]s to [`PeerMetricsService`]s, creating one for
+/// each recorded `Room`.
+///
+/// [`PeerMetricsService`]s observe the recorded time of [`StatsRecord`]s, and
+/// [`RtcStatsHandler::check`] is called each [`StatsReplayer::CHECK_INTERVAL`]
+/// of the recorded time, as a `Room` does.
+pub struct StatsReplayer {
+    /// [`PeerTrafficWatcher`] used by the created [`PeerMetricsService`]s.
+    peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+
+    /// [`conf::Media`] used by the created [`PeerMetricsService`]s.
+    media_conf: conf::Media,
+
+    /// Replayed `Room`s.
+    rooms: HashMap<RoomId, ReplayedRoom>,
+}
+
+impl StatsReplayer {
+    /// Interval of [`RtcStatsHandler::check`] calls in the recorded time.
+    pub const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Creates new [`StatsReplayer`].
+    #[must_use]
+    pub fn new(
+        peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+        media_conf: conf::Media,
+    ) -> Self {
+        Self {
+            peers_traffic_watcher,
+            media_conf,
+            rooms: HashMap::new(),
+        }
+    }
+
+    /// Feeds the provided [`StatsRecord`] to the [`PeerMetricsService`] of its
+    /// `Room`, returning all the [`PeersMetricsEvent`]s emitted in response
+    /// and on [`RtcStatsHandler::check`]s passed since the previous
+    /// [`StatsRecord`].
+    pub fn replay(&mut self, record: StatsRecord) -> Vec<PeersMetricsEvent> {
+        let StatsRecord {
+            at,
+            room_id,
+            member_id,
+            peer_id,
+            event,
+        } = record;
+        let at = SystemTime::from(at);
+        let peers_traffic_watcher = &self.peers_traffic_watcher;
+        let media_conf = &self.media_conf;
+        let room = self.rooms.entry(room_id.clone()).or_insert_with(|| {
+            let clock = Clock::manual(at);
+            let mut metrics = PeerMetricsService::new(
+                room_id,
+                Arc::clone(peers_traffic_watcher),
+                media_conf.max_lag,
+                PeersStats::default(),
+                &RtcStatsHandlersRegistry::default(),
+                clock.clone(),
+            );
+            let events = metrics.subscribe();
+            ReplayedRoom {
+                metrics,
+                clock,
+                next_check_at: at + Self::CHECK_INTERVAL,
+                events,
+                partners: HashMap::new(),
+            }
+        });
+
+        while room.next_check_at <= at {
+            room.clock.advance_to(room.next_check_at);
+            room.metrics.check();
+            room.next_check_at += Self::CHECK_INTERVAL;
+        }
+        room.clock.advance_to(at);
+
+        match event {
+            StatsRecordEvent::PeerRegistered {
+                partner_peer_id,
+                partner_member_id,
+                senders,
+                receivers,
+            } => {
+                room.partners
+                    .insert(peer_id, (partner_peer_id, partner_member_id));
+                if let Some(peer) =
+                    room.restore_peer(peer_id, member_id, &senders, &receivers)
+                {
+                    room.metrics.register_peer(&peer);
+                }
+            }
+            StatsRecordEvent::PeerUpdated { senders, receivers } => {
+                if let Some(peer) =
+                    room.restore_peer(peer_id, member_id, &senders, &receivers)
+                {
+                    room.metrics.update_peer(&peer);
+                }
+            }
+            StatsRecordEvent::PeerUnregistered => {
+                room.partners.remove(&peer_id);
+                room.metrics.unregister_peers(&[peer_id]);
+            }
+            StatsRecordEvent::PeerConnectionState(state) => {
+                room.metrics.update_peer_connection_state(peer_id, state);
+            }
+            StatsRecordEvent::RtcStats(stats) => {
+                room.metrics.add_stats(peer_id, &stats);
+            }
+        }
+
+        let mut events = Vec::new();
+        while let Some(Some(event)) = room.events.next().now_or_never() {
+            events.push(event);
+        }
+        events
+    }
+}
+
+impl ReplayedRoom {
+    /// Restores [`Peer`] with the provided [`PeerId`] and [`RecordedTrack`]s.
+    ///
+    /// Returns [`None`] if this [`Peer`] wasn't registered.
+    fn restore_peer(
+        &self,
+        peer_id: PeerId,
+        member_id: MemberId,
+        senders: &[RecordedTrack],
+        receivers: &[RecordedTrack],
+    ) -> Option<PeerStateMachine> {
+        let (partner_peer_id, partner_member_id) =
+            self.partners.get(&peer_id)?.clone();
+        let peer = Peer::new(
+            peer_id,
+            member_id,
+            partner_peer_id,
+            partner_member_id,
+            false,
+            Rc::new(IgnoredPeerUpdates),
+        )
+        .with_tracks(
+            senders.iter().map(Into::into).collect(),
+            receivers.iter().map(Into::into).collect(),
+        );
+        Some(peer.into())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{fs::File, sync::Arc, time::SystemTime};
+
+    use medea_client_api_proto::{
+        self as proto,
+        stats::{
+            RtcOutboundRtpStreamMediaType, RtcOutboundRtpStreamStats, RtcStat,
+            RtcStatsType, StatId,
+        },
+        AudioSettings, MemberId, PeerConnectionState, PeerId, TrackId,
+    };
+
+    use crate::{
+        api::control::callback::{MediaDirection, MediaType},
+        conf,
+        media::peer::tests::test_peer_from_peer_tracks,
+        signalling::peers::{
+            metrics::{PeersMetricsEvent, RtcStatsHandler},
+            traffic_watcher::MockPeerTrafficWatcher,
+        },
+    };
+
+    use super::{
+        read_records, RecordedTrack, StatsRecord, StatsRecordEvent,
+        StatsRecorder, StatsRecordsSink, StatsReplayer,
+    };
+
+    /// Returns [`StatsRecord`] of the `Peer` with the provided [`PeerId`] in
+    /// the `test` `Room`.
+    fn record(peer_id: u32, event: StatsRecordEvent) -> StatsRecord {
+        StatsRecord {
+            at: chrono::Utc::now(),
+            room_id: "test".into(),
+            member_id: MemberId::from(format!("member-{}", peer_id)),
+            peer_id: PeerId(peer_id),
+            event,
+        }
+    }
+
+    /// Checks that [`StatsRecord`]s written by [`StatsRecorder`] can be read
+    /// back while the [`StatsRecordsSink`] is still open.
+    #[test]
+    fn reads_recorded_inputs() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let sink = StatsRecordsSink::new(&conf::StatsRecording {
+            file: file.path().to_string_lossy().into_owned(),
+        })
+        .unwrap();
+        let mut recorder = StatsRecorder::new("test".into(), sink);
+
+        let peer = test_peer_from_peer_tracks(1, 1, 1, 1);
+        recorder.register_peer(&peer);
+        recorder.add_stats(PeerId(1), &[]);
+        recorder.update_peer_connection_state(
+            PeerId(1),
+            PeerConnectionState::Connected,
+        );
+        recorder.add_stats(PeerId(3), &[]);
+        recorder.unregister_peers(&[PeerId(1)]);
+        recorder.add_stats(PeerId(1), &[]);
+
+        let records: Vec<_> = read_records(File::open(file.path()).unwrap())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 4);
+        assert!(records.iter().all(|r| r.peer_id == PeerId(1)
+            && r.member_id == MemberId::from("test-member")));
+        if let StatsRecordEvent::PeerRegistered {
+            partner_peer_id,
+            senders,
+            receivers,
+            ..
+        } = &records[0].event
+        {
+            assert_eq!(*partner_peer_id, PeerId(2));
+            assert_eq!(senders.len(), 2);
+            assert_eq!(receivers.len(), 2);
+        } else {
+            panic!("unexpected StatsRecord: {:?}", records[0]);
+        }
+        assert_eq!(records[1].event, StatsRecordEvent::RtcStats(Vec::new()));
+        assert_eq!(
+            records[2].event,
+            StatsRecordEvent::PeerConnectionState(
+                PeerConnectionState::Connected
+            ),
+        );
+        assert_eq!(records[3].event, StatsRecordEvent::PeerUnregistered);
+    }
+
+    /// Checks that [`StatsReplayer`] reproduces [`PeersMetricsEvent`]s from
+    /// the replayed [`StatsRecord`]s.
+    #[test]
+    fn replays_recorded_inputs() {
+        let mut replayer = StatsReplayer::new(
+            Arc::new(MockPeerTrafficWatcher::new()),
+            conf::Media::default(),
+        );
+        let registered = |partner_peer_id| StatsRecordEvent::PeerRegistered {
+            partner_peer_id: PeerId(partner_peer_id),
+            partner_member_id: MemberId::from(format!(
+                "member-{}",
+                partner_peer_id
+            )),
+            senders: Vec::new(),
+            receivers: Vec::new(),
+        };
+        let state = StatsRecordEvent::PeerConnectionState;
+
+        let mut events = Vec::new();
+        for record in vec![
+            record(1, registered(2)),
+            record(2, registered(1)),
+            record(1, state(PeerConnectionState::Connected)),
+            record(2, state(PeerConnectionState::Connected)),
+            record(1, state(PeerConnectionState::Failed)),
+            record(2, state(PeerConnectionState::Failed)),
+        ] {
+            events.extend(replayer.replay(record));
+        }
+
+        assert!(events.contains(&PeersMetricsEvent::PeerConnectionFailed {
+            peer_id: PeerId(2),
+            failures: 1,
+        }));
+    }
+
+    /// Checks that [`StatsReplayer`] detects stopped traffic by the recorded
+    /// time of [`StatsRecord`]s, rather than by the wall-clock time.
+    #[test]
+    fn replays_traffic_stop_by_recorded_time() {
+        let mut watcher = MockPeerTrafficWatcher::new();
+        watcher.expect_traffic_flows().return_const(());
+        watcher.expect_traffic_stopped().return_const(());
+        let mut replayer =
+            StatsReplayer::new(Arc::new(watcher), conf::Media::default());
+
+        let started_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        let at = |secs| started_at + chrono::Duration::seconds(secs);
+        let record_at = |secs, event| StatsRecord {
+            at: at(secs),
+            ..record(1, event)
+        };
+        let audio = RecordedTrack {
+            id: TrackId(1),
+            media_type: proto::MediaType::Audio(AudioSettings {
+                required: true,
+            }),
+            mid: None,
+            enabled: true,
+        };
+        let audio_sent = RtcStat {
+            id: StatId("audio".into()),
+            timestamp: SystemTime::from(at(0)).into(),
+            stats: RtcStatsType::OutboundRtp(Box::new(
+                RtcOutboundRtpStreamStats {
+                    track_id: None,
+                    media_type: RtcOutboundRtpStreamMediaType::Audio {
+                        total_samples_sent: None,
+                        voice_activity_flag: None,
+                    },
+                    packets_sent: 100,
+                    bytes_sent: 0,
+                    media_source_id: None,
+                },
+            )),
+        };
+
+        let mut events = Vec::new();
+        for record in vec![
+            record_at(
+                0,
+                StatsRecordEvent::PeerRegistered {
+                    partner_peer_id: PeerId(2),
+                    partner_member_id: MemberId::from("member-2"),
+                    senders: vec![audio],
+                    receivers: Vec::new(),
+                },
+            ),
+            record_at(0, StatsRecordEvent::RtcStats(vec![audio_sent])),
+            record_at(
+                10,
+                StatsRecordEvent::PeerConnectionState(
+                    PeerConnectionState::Connected,
+                ),
+            ),
+        ] {
+            events.extend(replayer.replay(record));
+        }
+        let no_traffic = PeersMetricsEvent::NoTrafficFlow {
+            peer_id: PeerId(1),
+            was_flowing_at: at(0),
+            media_type: MediaType::Audio,
+            direction: MediaDirection::Publish,
+        };
+        assert!(!events.contains(&no_traffic));
+
+        events.extend(replayer.replay(record_at(
+            20,
+            StatsRecordEvent::PeerConnectionState(
+                PeerConnectionState::Connected,
+            ),
+        )));
+        assert!(events.contains(&no_traffic));
+    }
+}
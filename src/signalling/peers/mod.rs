@@ -9,17 +9,18 @@ mod traffic_watcher;
 
 use std::{
     cell::{Cell, RefCell},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     rc::Rc,
     sync::Arc,
+    time::SystemTime,
 };
 
 use derive_more::Display;
 use futures::{future, Stream};
 use medea_client_api_proto::{
-    state, stats::RtcStat, Incrementable, MemberId, PeerConnectionState,
-    PeerId, RoomId, TrackId,
+    state, stats::RtcStat, ConnectionQualityScore, Incrementable, MemberId,
+    PeerConnectionState, PeerId, RoomId, TrackId,
 };
 
 use crate::{
@@ -31,14 +32,20 @@ use crate::{
             webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
             Endpoint,
         },
-        peers::metrics::{PeerMetricsService, RtcStatsHandler},
+        journal::RoomJournal,
+        peers::metrics::{Clock, PeerMetricsService},
         room::RoomError,
     },
-    turn::{TurnAuthService, UnreachablePolicy},
+    turn::{IceUser, RelayUsageRepository, TurnAuthService, UnreachablePolicy},
 };
 
 pub use self::{
-    metrics::{PeersMetricsEvent, PeersMetricsEventHandler},
+    metrics::{
+        read_records, EventSender, PeerStats, PeersMetricsEvent,
+        PeersMetricsEventHandler, PeersStats, RecordedTrack, RtcStatsHandler,
+        RtcStatsHandlerFactory, RtcStatsHandlersRegistry, StatsRecord,
+        StatsRecordEvent, StatsRecorder, StatsRecordsSink, StatsReplayer,
+    },
     traffic_watcher::{
         build_peers_traffic_watcher, FlowMetricSource,
         PeerConnectionStateEventsHandler, PeerTrafficWatcher,
@@ -59,6 +66,13 @@ pub struct PeersService {
     /// [`IceUser`]: crate::turn::ice_user::IceUser
     turn_service: Arc<dyn TurnAuthService>,
 
+    /// Traffic relayed through TURN servers, which [`Peer`]s are registered
+    /// in along with [`Member`]s owning them.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    /// [`Peer`]: crate::media::peer::Peer
+    relay_usage: RelayUsageRepository,
+
     /// [`Peer`]s of [`Member`]s in this [`Room`].
     ///
     /// [`Member`]: crate::signalling::elements::Member
@@ -66,6 +80,13 @@ pub struct PeersService {
     /// [`Room`]: crate::signalling::Room
     peers: PeerRepository,
 
+    /// [`PeerId`]s of the [`Peer`]s whose [`IceUser`]s are being rotated at
+    /// the moment.
+    ///
+    /// [`IceUser`]: crate::turn::ice_user::IceUser
+    /// [`Peer`]: crate::media::peer::Peer
+    rotating_ice_users: RefCell<HashSet<PeerId>>,
+
     /// Count of [`Peer`]s in this [`Room`].
     ///
     /// [`Peer`]: crate::media::peer::Peer
@@ -87,9 +108,22 @@ pub struct PeersService {
     /// [`Room`]: crate::signalling::room::Room
     peer_metrics_service: RefCell<Box<dyn RtcStatsHandler>>,
 
+    /// Latest [`PeerStats`] of [`Peer`]s, collected by
+    /// [`PeersService::peer_metrics_service`].
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    peers_stats: PeersStats,
+
     /// Subscriber to the events which indicates that negotiation process
     /// should be started for a some [`Peer`].
     negotiation_sub: Rc<dyn PeerUpdatesSubscriber>,
+
+    /// Journal of the [`Room`] activity, recording state transitions of
+    /// [`Peer`]s.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    /// [`Room`]: crate::signalling::room::Room
+    journal: RoomJournal,
 }
 
 /// Simple ID counter.
@@ -122,17 +156,24 @@ impl PeersService {
     /// [`RoomId`].
     ///
     /// [`Room`]: crate::signalling::room::Room
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         room_id: RoomId,
         turn_service: Arc<dyn TurnAuthService>,
+        relay_usage: RelayUsageRepository,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
         media_conf: &conf::Media,
+        stats_handlers: &RtcStatsHandlersRegistry,
         negotiation_sub: Rc<dyn PeerUpdatesSubscriber>,
+        journal: RoomJournal,
     ) -> Rc<Self> {
+        let peers_stats = PeersStats::default();
         Rc::new(Self {
             room_id: room_id.clone(),
             turn_service,
+            relay_usage,
             peers: PeerRepository::default(),
+            rotating_ice_users: RefCell::default(),
             peers_count: Counter::default(),
             tracks_count: Counter::default(),
             peers_traffic_watcher: Arc::clone(&peers_traffic_watcher),
@@ -141,17 +182,25 @@ impl PeersService {
                     room_id,
                     peers_traffic_watcher,
                     media_conf.max_lag,
+                    peers_stats.clone(),
+                    stats_handlers,
+                    Clock::default(),
                 ),
             )),
+            peers_stats,
             negotiation_sub,
+            journal,
         })
     }
 
-    /// Store [`Peer`] in [`Room`].
+    /// Store [`Peer`] in [`Room`], recording its state into the journal of
+    /// the [`Room`].
     ///
     /// [`Room`]: crate::signalling::Room
     #[inline]
     pub fn add_peer<S: Into<PeerStateMachine>>(&self, peer: S) {
+        let peer = peer.into();
+        self.journal.peer_state(&peer);
         self.peers.add_peer(peer)
     }
 
@@ -450,8 +499,8 @@ impl PeersService {
                     sink_peer.add_endpoint(&sink.into());
                     src_peer.add_endpoint(&src.into());
 
-                    self.peers.add_peer(src_peer);
-                    self.peers.add_peer(sink_peer);
+                    self.add_peer(src_peer);
+                    self.add_peer(sink_peer);
 
                     future::try_join_all(register_peer_tasks)
                         .await
@@ -463,8 +512,9 @@ impl PeersService {
         }
     }
 
-    /// Creates and sets [`IceUser`], registers [`Peer`] in
-    /// [`PeerTrafficWatcher`].
+    /// Creates and sets [`IceUser`] with STUN/TURN servers selected by the
+    /// region of the [`Endpoint`]'s owner, registers [`Peer`] in
+    /// [`RelayUsageRepository`] and [`PeerTrafficWatcher`].
     ///
     /// [`IceUser`]: crate::turn::ice_user::IceUser
     async fn peer_post_construct(
@@ -472,10 +522,21 @@ impl PeersService {
         peer_id: PeerId,
         endpoint: &Endpoint,
     ) -> Result<(), RoomError> {
+        let owner = endpoint.owner();
         let ice_user = self
             .turn_service
-            .create(self.room_id.clone(), peer_id, UnreachablePolicy::ReturnErr)
+            .create(
+                self.room_id.clone(),
+                peer_id,
+                owner.ice_region(),
+                UnreachablePolicy::ReturnErr,
+            )
             .await?;
+        self.relay_usage.register_peer(
+            self.room_id.clone(),
+            peer_id,
+            owner.id(),
+        );
 
         self.peers
             .map_peer_by_id_mut(peer_id, move |p| p.set_ice_user(ice_user))?;
@@ -494,6 +555,154 @@ impl PeersService {
         }
     }
 
+    /// Returns [`Peer`]s whose [`IceUser`]s should be rotated at the moment,
+    /// along with [`MemberId`]s of their owners, and marks them as being
+    /// rotated.
+    ///
+    /// [`Peer`]s being rotated already are skipped.
+    pub(super) fn start_ice_users_rotation(&self) -> Vec<(PeerId, MemberId)> {
+        let now = SystemTime::now();
+        let mut rotating = self.rotating_ice_users.borrow_mut();
+        self.peers
+            .0
+            .borrow()
+            .values()
+            .filter(|peer| {
+                peer.ice_user_expiry()
+                    .map_or(false, |e| e.is_rotation_due(now))
+            })
+            .filter(|peer| rotating.insert(peer.id()))
+            .map(|peer| (peer.id(), peer.member_id()))
+            .collect()
+    }
+
+    /// Creates fresh [`IceUser`] with STUN/TURN servers selected by the
+    /// provided `region` for the [`Peer`] with the provided [`PeerId`], and
+    /// returns the replaced one.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::TurnServiceErr`] if [`IceUser`] creation fails.
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if the [`Peer`] was removed
+    /// meanwhile.
+    pub(super) async fn rotate_ice_user(
+        &self,
+        peer_id: PeerId,
+        region: Option<String>,
+    ) -> Result<Option<IceUser>, RoomError> {
+        let ice_user = self
+            .turn_service
+            .create(
+                self.room_id.clone(),
+                peer_id,
+                region,
+                UnreachablePolicy::ReturnErr,
+            )
+            .await;
+        self.rotating_ice_users.borrow_mut().remove(&peer_id);
+
+        let ice_user = ice_user?;
+        self.peers
+            .map_peer_by_id_mut(peer_id, move |p| p.set_ice_user(ice_user))
+    }
+
+    /// Switches the [`Peer`] with the provided [`PeerId`] and its partner
+    /// [`Peer`] to forcibly relayed media.
+    ///
+    /// Returns [`PeerId`] and [`MemberId`] of the partner [`Peer`], or [`None`]
+    /// if the [`Peer`] is forcibly relayed already.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if any of [`Peer`]s doesn't
+    /// exist in [`PeerRepository`].
+    pub(super) fn fall_back_to_relay(
+        &self,
+        peer_id: PeerId,
+    ) -> Result<Option<(PeerId, MemberId)>, RoomError> {
+        let partner = self.peers.map_peer_by_id_mut(peer_id, |peer| {
+            if peer.is_force_relayed() {
+                return None;
+            }
+            peer.fall_back_to_relay();
+            Some((peer.partner_peer_id(), peer.partner_member_id()))
+        })?;
+        if let Some((partner_peer_id, _)) = &partner {
+            self.peers.map_peer_by_id_mut(
+                *partner_peer_id,
+                |partner_peer| {
+                    partner_peer.fall_back_to_relay();
+                },
+            )?;
+        }
+        Ok(partner)
+    }
+
+    /// Rolls back SDP negotiation of the [`Peer`] with the provided [`PeerId`]
+    /// and its partner [`Peer`], transitioning both to [`Stable`] state.
+    ///
+    /// Returns [`PeerId`] of the partner [`Peer`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if any of [`Peer`]s doesn't
+    /// exist in [`PeerRepository`].
+    ///
+    /// [`Stable`]: crate::media::peer::Stable
+    pub(super) fn rollback_negotiation(
+        &self,
+        peer_id: PeerId,
+    ) -> Result<PeerId, RoomError> {
+        let peer = self.peers.take(peer_id)?;
+        let partner_peer_id = peer.partner_peer_id();
+        self.add_peer(peer.rollback_negotiation());
+
+        let partner_peer = self.peers.take(partner_peer_id)?;
+        self.add_peer(partner_peer.rollback_negotiation());
+
+        Ok(partner_peer_id)
+    }
+
+    /// Returns [`PeerId`]s of [`Peer`]s owned by the provided [`MemberId`],
+    /// which were switched to forcibly relayed media after their direct
+    /// connection kept failing.
+    pub(super) fn relay_fallback_peers(
+        &self,
+        member_id: &MemberId,
+    ) -> Vec<PeerId> {
+        self.peers
+            .0
+            .borrow()
+            .values()
+            .filter(|peer| {
+                peer.is_relay_fallback() && &peer.member_id() == member_id
+            })
+            .map(PeerStateMachine::id)
+            .collect()
+    }
+
+    /// Returns latest [`PeerStats`] of [`Peer`]s owned by the provided
+    /// [`MemberId`].
+    #[inline]
+    pub(super) fn member_peers_stats(
+        &self,
+        member_id: &MemberId,
+    ) -> HashMap<PeerId, PeerStats> {
+        self.peers_stats.member_peers_stats(member_id)
+    }
+
+    /// Records the provided [`ConnectionQualityScore`] in the [`PeerStats`]
+    /// history of the [`Peer`] with the provided [`PeerId`].
+    #[inline]
+    pub(super) fn record_quality_score(
+        &self,
+        peer_id: PeerId,
+        score: ConnectionQualityScore,
+    ) {
+        self.peers_stats.record_quality_score(peer_id, score);
+    }
+
     /// Updates [`PeerMetricsService`] tracks of the [`Peer`] with provided
     /// [`PeerId`].
     ///
@@ -826,12 +1035,16 @@ mod tests {
             Rc::new(Self {
                 room_id,
                 turn_service,
+                relay_usage: RelayUsageRepository::default(),
                 peers: PeerRepository::default(),
                 peers_count: Counter::default(),
                 tracks_count: Counter::default(),
                 peers_traffic_watcher,
                 peer_metrics_service: RefCell::new(peer_metrics_service),
+                peers_stats: PeersStats::default(),
                 negotiation_sub,
+                rotating_ice_users: RefCell::default(),
+                journal: RoomJournal::default(),
             })
         }
     }
@@ -9,10 +9,7 @@ use medea_client_api_proto::{
     PeerId, PeerMetrics, TrackId, TrackPatchCommand,
 };
 
-use crate::{
-    log::prelude::*,
-    media::{Peer, PeerStateMachine, WaitLocalSdp, WaitRemoteSdp},
-};
+use crate::media::{Peer, WaitLocalSdp, WaitRemoteSdp};
 
 use super::{Room, RoomError};
 
@@ -34,6 +31,9 @@ impl CommandHandler for Room {
     /// [`WaitRemoteSdp`], partners [`Peer`] state must be [`Stable`] and will
     /// be changed to [`WaitLocalSdp`].
     ///
+    /// Clears [`IceCandidate`]s of the partner [`Peer`] if the provided
+    /// [`Peer`] restarts ICE.
+    ///
     /// [`Stable`]: crate::media::peer::Stable
     fn on_make_sdp_offer(
         &mut self,
@@ -44,9 +44,12 @@ impl CommandHandler for Room {
     ) -> Self::Output {
         let mut from_peer: Peer<WaitLocalSdp> =
             self.peers.take_inner_peer(from_peer_id)?;
-        let to_peer: Peer<WaitRemoteSdp> =
+        let mut to_peer: Peer<WaitRemoteSdp> =
             self.peers.take_inner_peer(from_peer.partner_peer_id())?;
 
+        if from_peer.is_ice_restart() {
+            to_peer.clear_ice_candidates();
+        }
         from_peer.set_mids(mids)?;
         from_peer.update_senders_statuses(senders_statuses);
 
@@ -77,6 +80,7 @@ impl CommandHandler for Room {
             }
         };
 
+        self.sdp_offer_made(from_peer_id);
         self.peers.add_peer(from_peer);
         self.peers.add_peer(to_peer);
 
@@ -116,6 +120,7 @@ impl CommandHandler for Room {
         let to_peer = to_peer.set_remote_answer(sdp_answer.clone());
 
         let to_member_id = to_peer.member_id();
+        self.negotiations.remove(&to_peer.id());
         let event = Event::SdpAnswerMade {
             peer_id: to_peer.id(),
             sdp_answer: sdp_answer.clone(),
@@ -141,6 +146,10 @@ impl CommandHandler for Room {
     ///
     /// Adds [`IceCandidate`] to the [`Peer`].
     ///
+    /// Drops [`IceCandidate`] violating [`IceCandidatePolicy`] of this
+    /// [`Room`], logging and counting the violation.
+    ///
+    /// [`IceCandidatePolicy`]: crate::media::IceCandidatePolicy
     /// [`Stable`]: crate::media::peer::Stable
     fn on_set_ice_candidate(
         &mut self,
@@ -149,13 +158,49 @@ impl CommandHandler for Room {
     ) -> Self::Output {
         // TODO: add E2E test
         if candidate.candidate.is_empty() {
-            warn!("Empty candidate from Peer: {}, ignoring", from_peer_id);
+            self.log_error(format!(
+                "Empty ICE candidate of Peer [id = {}] is ignored",
+                from_peer_id,
+            ));
+            return Ok(());
+        }
+
+        let (to_peer_id, from_member_id, is_force_relayed) =
+            self.peers.map_peer_by_id(from_peer_id, |from_peer| {
+                (
+                    from_peer.partner_peer_id(),
+                    from_peer.member_id(),
+                    from_peer.is_force_relayed(),
+                )
+            })?;
+        let candidates_count =
+            self.peers.map_peer_by_id(to_peer_id, |to_peer| {
+                to_peer.ice_candidates().len()
+            })?;
+        let is_external = self
+            .members
+            .get_member_by_id(&from_member_id)?
+            .is_external();
+        if let Err(violation) = self.ice_policy.check(
+            &candidate.candidate,
+            is_external,
+            is_force_relayed,
+            candidates_count,
+        ) {
+            let rejected = {
+                let count =
+                    self.rejected_ice_candidates.entry(violation).or_default();
+                *count += 1;
+                *count
+            };
+            self.log_error(format!(
+                "Dropped ICE candidate of Peer [id = {}]: {} (dropped {} \
+                 times)",
+                from_peer_id, violation, rejected,
+            ));
             return Ok(());
         }
 
-        let to_peer_id = self
-            .peers
-            .map_peer_by_id(from_peer_id, PeerStateMachine::partner_peer_id)?;
         let to_member_id =
             self.peers.map_peer_by_id_mut(to_peer_id, |to_peer| {
                 to_peer.add_ice_candidate(candidate.clone());
@@ -27,9 +27,11 @@ use crate::{
         elements::{
             endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
             member::MemberError,
+            Member,
         },
         room::ActFuture,
     },
+    tracing::{Span, SpanContext},
 };
 
 use super::{Room, RoomError};
@@ -38,7 +40,11 @@ impl Room {
     /// Deletes [`Member`] from this [`Room`] by [`MemberId`].
     ///
     /// [`Member`]: crate::signalling::elements::Member
-    fn delete_member(&mut self, member_id: &MemberId, ctx: &mut Context<Self>) {
+    pub(super) fn delete_member(
+        &mut self,
+        member_id: &MemberId,
+        ctx: &mut Context<Self>,
+    ) {
         debug!(
             "Deleting Member [id = {}] in Room [id = {}].",
             member_id, self.id
@@ -52,6 +58,7 @@ impl Room {
                 ctx,
             );
             self.members.delete_member(member_id);
+            self.relay_usage.remove_member(&self.id, member_id);
             debug!(
                 "Member [id = {}] deleted from Room [id = {}].",
                 member_id, self.id
@@ -121,6 +128,9 @@ impl Room {
         spec: &WebRtcPublishEndpointSpec,
     ) -> Result<(), RoomError> {
         let member = self.members.get_member(&member_id)?;
+        if member.srcs().is_empty() {
+            self.ensure_publishers_capacity(self.publishers_count(false))?;
+        }
 
         let is_member_have_this_src_id =
             member.get_src_by_id(&publish_id).is_some();
@@ -230,11 +240,11 @@ impl Room {
                     ctx.spawn(this.init_member_connections(&member).map(
                         move |res, this, ctx| {
                             if let Err(e) = res {
-                                error!(
-                                    "Failed to interconnect Members, because \
+                                this.log_error(format!(
+                                    "Failed to interconnect Member [id = {}]: \
                                      {}",
-                                    e,
-                                );
+                                    member_id, e,
+                                ));
                                 this.disconnect_member(
                                     &member_id,
                                     CloseReason::InternalError,
@@ -277,17 +287,75 @@ impl Room {
     }
 }
 
+impl Room {
+    /// Serializes the provided [`Member`] into [`proto::Member`] along with
+    /// its current Client API connection status.
+    fn serialize_member(&self, member: Member) -> proto::Member {
+        let connected = self.members.member_has_connection(&member.id());
+        let usage = self.relay_usage.member_usage(&self.id, &member.id());
+        let peers_usage =
+            self.relay_usage.member_peers_usage(&self.id, &member.id());
+        let relay_fallback_peers =
+            self.peers.relay_fallback_peers(&member.id());
+        let peers_stats = self.peers.member_peers_stats(&member.id());
+        let mut proto: proto::Member = member.into();
+        proto.connected = connected;
+        proto.relay_usage = Some(usage.into());
+        proto.peers_relay_usage = peers_usage
+            .into_iter()
+            .map(|(id, usage)| (u64::from(id.0), usage.into()))
+            .collect();
+        proto.relay_fallback_peers = relay_fallback_peers
+            .into_iter()
+            .map(|id| u64::from(id.0))
+            .collect();
+        proto.peers_stats = peers_stats
+            .into_iter()
+            .map(|(id, stats)| (u64::from(id.0), stats.into()))
+            .collect();
+        proto
+    }
+}
+
 impl Into<proto::Room> for &Room {
     fn into(self) -> proto::Room {
         let pipeline = self
             .members
             .members()
             .into_iter()
-            .map(|(id, member)| (id.to_string(), member.into()))
+            .map(|(id, member)| {
+                let member = self.serialize_member(member);
+                (
+                    id.to_string(),
+                    proto::room::Element {
+                        el: Some(proto::room::element::El::Member(member)),
+                    },
+                )
+            })
             .collect();
         proto::Room {
             id: self.id().to_string(),
             pipeline,
+            empty_timeout: self.empty_timeout.map(Into::into),
+            max_duration: self.max_duration.map(Into::into),
+            on_close: self
+                .on_close
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            max_members: self.max_members.unwrap_or_default() as u32,
+            max_publishers: self.max_publishers.unwrap_or_default() as u32,
+            rejected_ice_candidates: self
+                .rejected_ice_candidates
+                .iter()
+                .map(|(violation, count)| (violation.name().to_owned(), *count))
+                .collect(),
+            negotiation_timeouts: self
+                .negotiation_timeouts
+                .iter()
+                .map(|(stage, count)| (stage.name().to_owned(), *count))
+                .collect(),
+            recreated_peers: self.recreated_peers,
         }
     }
 }
@@ -300,6 +368,19 @@ impl Into<proto::Element> for &Room {
     }
 }
 
+/// Message for dumping [`Room::journal`] as JSON array of its entries.
+#[derive(Message)]
+#[rtype(result = "String")]
+pub struct DumpJournal;
+
+impl Handler<DumpJournal> for Room {
+    type Result = String;
+
+    fn handle(&mut self, _: DumpJournal, _: &mut Self::Context) -> String {
+        self.journal.to_json()
+    }
+}
+
 // TODO: Tightly coupled with protobuf.
 //       We should name this method GetElements, that will return some
 //       intermediate DTO, that will be serialized at the caller side.
@@ -337,7 +418,13 @@ impl Handler<SerializeProto> for Room {
                 StatefulFid::Member(member_fid) => {
                     let member =
                         self.members.get_member(member_fid.member_id())?;
-                    serialized.insert(fid, member.into());
+                    let member = self.serialize_member(member);
+                    serialized.insert(
+                        fid,
+                        proto::Element {
+                            el: Some(proto::element::El::Member(member)),
+                        },
+                    );
                 }
                 StatefulFid::Endpoint(endpoint_fid) => {
                     let member =
@@ -354,15 +441,19 @@ impl Handler<SerializeProto> for Room {
     }
 }
 
-/// Signal for deleting elements from this [`Room`].
+/// Signal for deleting elements from this [`Room`], along with
+/// [`SpanContext`] of the traced operation requested this deletion.
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
-pub struct Delete(pub Vec<StatefulFid>);
+pub struct Delete(pub Vec<StatefulFid>, pub Option<SpanContext>);
 
 impl Handler<Delete> for Room {
     type Result = ();
 
     fn handle(&mut self, msg: Delete, ctx: &mut Self::Context) {
+        let span =
+            Span::with_parent("room.delete", msg.1).attr("room.id", &self.id);
+        let _entered = span.enter();
         let mut member_ids = Vec::new();
         let mut endpoint_ids = Vec::new();
         for id in msg.0 {
@@ -374,6 +465,8 @@ impl Handler<Delete> for Room {
                     endpoint_ids.push(endpoint_fid);
                 }
                 StatefulFid::Room(_) => {
+                    self.journal
+                        .error("Room itself cannot be deleted from the Room");
                     warn!("Found Fid<IsRoomId> while deleting __from__ Room.");
                 }
             }
@@ -388,10 +481,11 @@ impl Handler<Delete> for Room {
     }
 }
 
-/// Signal for creating new `Member` in this [`Room`].
+/// Signal for creating new `Member` in this [`Room`], along with
+/// [`SpanContext`] of the traced operation requested this creation.
 #[derive(Message, Debug)]
 #[rtype(result = "Result<(), RoomError>")]
-pub struct CreateMember(pub MemberId, pub MemberSpec);
+pub struct CreateMember(pub MemberId, pub MemberSpec, pub Option<SpanContext>);
 
 impl Handler<CreateMember> for Room {
     type Result = Result<(), RoomError>;
@@ -401,11 +495,95 @@ impl Handler<CreateMember> for Room {
         msg: CreateMember,
         _: &mut Self::Context,
     ) -> Self::Result {
-        self.members.create_member(msg.0.clone(), &msg.1)?;
-        debug!(
-            "Member [id = {}] created in Room [id = {}].",
-            msg.0, self.id
-        );
+        let mut span = Span::with_parent("room.create_member", msg.2)
+            .attr("room.id", &self.id)
+            .attr("member.id", &msg.0);
+        let _entered = span.enter();
+        if let Err(e) = self.create_member(&msg.0, &msg.1) {
+            span.set_error(&e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+impl Room {
+    /// Creates new `Member` with the provided [`MemberId`] and
+    /// [`MemberSpec`], ensuring capacity limits of this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if capacity limits of this [`Room`] are reached, or the
+    /// [`MemberSpec`] is invalid.
+    fn create_member(
+        &mut self,
+        id: &MemberId,
+        spec: &MemberSpec,
+    ) -> Result<(), RoomError> {
+        if self.members.get_member_by_id(id).is_err() {
+            self.ensure_members_capacity(self.members.members().len())?;
+            if spec.publish_endpoints().next().is_some() {
+                self.ensure_publishers_capacity(self.publishers_count(false))?;
+            }
+        }
+        self.members.create_member(id.clone(), spec)?;
+        debug!("Member [id = {}] created in Room [id = {}].", id, self.id);
+        Ok(())
+    }
+}
+
+/// Signal for updating `Member`s of this [`Room`] according to its changed
+/// spec.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct UpdateMembers {
+    /// IDs of `Member`s to be deleted.
+    pub deleted: Vec<MemberId>,
+
+    /// Specs of `Member`s to be created.
+    pub created: HashMap<MemberId, MemberSpec>,
+}
+
+impl Handler<UpdateMembers> for Room {
+    type Result = Result<(), RoomError>;
+
+    /// Deletes `Member`s and then creates new ones.
+    ///
+    /// `Member`s are created in such order that the `Member`s publishing
+    /// media are created before the `Member`s playing it.
+    fn handle(
+        &mut self,
+        msg: UpdateMembers,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        for member_id in &msg.deleted {
+            self.delete_member(member_id, ctx);
+        }
+
+        let mut pending = msg.created;
+        while !pending.is_empty() {
+            let mut ready: Vec<_> = pending
+                .iter()
+                .filter(|(_, spec)| {
+                    spec.play_endpoints().all(|(_, play)| {
+                        self.members
+                            .get_member_by_id(&play.src.member_id)
+                            .is_ok()
+                    })
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            if ready.is_empty() {
+                // Sources of the rest Members are missing, so creation will
+                // fail with the appropriate error.
+                ready = pending.keys().cloned().collect();
+            }
+            for member_id in ready {
+                let spec = pending.remove(&member_id).unwrap();
+                self.create_member(&member_id, &spec)?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -417,6 +595,9 @@ pub struct CreateEndpoint {
     pub member_id: MemberId,
     pub endpoint_id: EndpointId,
     pub spec: EndpointSpec,
+
+    /// [`SpanContext`] of the traced operation requested this creation.
+    pub trace: Option<SpanContext>,
 }
 
 impl Handler<CreateEndpoint> for Room {
@@ -427,6 +608,11 @@ impl Handler<CreateEndpoint> for Room {
         msg: CreateEndpoint,
         _: &mut Self::Context,
     ) -> Self::Result {
+        let span = Span::with_parent("room.create_endpoint", msg.trace)
+            .attr("room.id", &self.id)
+            .attr("member.id", &msg.member_id)
+            .attr("endpoint.id", &msg.endpoint_id);
+        let _entered = span.enter();
         match msg.spec {
             EndpointSpec::WebRtcPlay(endpoint) => {
                 match self.create_sink_endpoint(
@@ -3,14 +3,23 @@
 
 mod command_handler;
 mod dynamic_api;
+mod negotiation;
 mod peer_events_handler;
 mod rpc_server;
 
-use std::{pin::Pin, rc::Rc, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::PathBuf,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use actix::{
-    Actor, ActorFuture, Addr, AsyncContext as _, AtomicResponse, Context,
-    Handler, MailboxError, WrapFuture as _,
+    Actor, ActorFuture, Addr, Arbiter, AsyncContext as _, AtomicResponse,
+    Context, Handler, MailboxError, Recipient, WrapFuture as _,
 };
 use derive_more::{Display, From};
 use failure::Fail;
@@ -22,27 +31,38 @@ use medea_client_api_proto::{
 use crate::{
     api::control::{
         callback::{
-            CallbackClientError, CallbackClientFactoryImpl, CallbackService,
-            OnLeaveEvent, OnLeaveReason,
+            CallbackClientError, CallbackClientFactoryImpl, CallbackEvent,
+            CallbackService, CallbackUrl, OnCallRecordEvent, OnLeaveEvent,
+            OnLeaveReason, OnMemberRemovedEvent,
         },
-        refs::{Fid, StatefulFid, ToEndpoint, ToMember},
+        refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
         room::RoomSpec,
     },
+    cdr::{CallRecord, CallRecorder, CdrSink},
+    conf,
     log::prelude::*,
-    media::{peer::PeerUpdatesSubscriber, Peer, PeerError, Stable},
+    media::{
+        ice_candidate::PolicyViolation, peer::PeerUpdatesSubscriber,
+        IceCandidatePolicy, Peer, PeerError, Stable,
+    },
     shutdown::ShutdownGracefully,
     signalling::{
         elements::{member::MemberError, Member, MembersLoadError},
+        journal::RoomJournal,
         participants::{ParticipantService, ParticipantServiceErr},
         peers::{PeerTrafficWatcher, PeersService},
+        room_service::RoomEmptinessChanged,
     },
-    turn::TurnServiceErr,
+    turn::{IceUser, RelayUsageRepository, TurnServiceErr},
     utils::actix_try_join_all,
     AppContext,
 };
 
+use self::negotiation::{Negotiation, NegotiationStage};
+
 pub use dynamic_api::{
-    Close, CreateEndpoint, CreateMember, Delete, SerializeProto,
+    Close, CreateEndpoint, CreateMember, Delete, DumpJournal, SerializeProto,
+    UpdateMembers,
 };
 
 /// Ergonomic type alias for using [`ActorFuture`] for [`Room`].
@@ -111,6 +131,16 @@ pub enum RoomError {
     /// Failed to send callback via [`CallbackService`]
     #[display(fmt = "CallbackService errored in Room: {}", _0)]
     CallbackClientError(CallbackClientError),
+
+    /// [`Room`] has reached its limit of [`Member`]s.
+    #[display(fmt = "Room has reached its limit of {} Members", _0)]
+    #[from(ignore)]
+    MembersLimitReached(usize),
+
+    /// [`Room`] has reached its limit of publishing [`Member`]s.
+    #[display(fmt = "Room has reached its limit of {} publishing Members", _0)]
+    #[from(ignore)]
+    PublishersLimitReached(usize),
 }
 
 /// Media server room with its [`Member`]s.
@@ -123,6 +153,9 @@ pub struct Room {
     /// [`CallbackEvent`]: crate::api::control::callback::CallbackEvent
     callbacks: CallbackService<CallbackClientFactoryImpl>,
 
+    /// Traffic relayed through TURN servers for [`Member`]s of this [`Room`].
+    relay_usage: RelayUsageRepository,
+
     /// [`Member`]s and associated [`RpcConnection`]s of this [`Room`], handles
     /// [`RpcConnection`] authorization, establishment, message sending.
     ///
@@ -131,6 +164,82 @@ pub struct Room {
 
     /// [`Peer`]s of [`Member`]s in this [`Room`].
     peers: Rc<PeersService>,
+
+    /// Timeout of this [`Room`] staying without any connected [`Member`]s,
+    /// after which it's removed automatically.
+    empty_timeout: Option<Duration>,
+
+    /// Maximum duration of this [`Room`] existence, after which it's removed
+    /// automatically.
+    max_duration: Option<Duration>,
+
+    /// URL to which `OnRoomClosed` Control API callback will be sent.
+    on_close: Option<CallbackUrl>,
+
+    /// Subscriber to the [`RoomEmptinessChanged`] notifications of this
+    /// [`Room`].
+    ///
+    /// Used by [`RoomService`] to schedule auto-removal of this [`Room`].
+    ///
+    /// [`RoomService`]: crate::signalling::room_service::RoomService
+    emptiness_subscriber: Option<Recipient<RoomEmptinessChanged>>,
+
+    /// Indicator whether this [`Room`] had no connected [`Member`]s at the
+    /// moment of the last [`RoomEmptinessChanged`] notification.
+    is_empty: bool,
+
+    /// Maximum number of [`Member`]s in this [`Room`].
+    max_members: Option<usize>,
+
+    /// Maximum number of publishing [`Member`]s in this [`Room`].
+    max_publishers: Option<usize>,
+
+    /// Policies of ICE candidates forwarded between [`Peer`]s of this
+    /// [`Room`].
+    ice_policy: IceCandidatePolicy,
+
+    /// Number of ICE candidates rejected in this [`Room`] by
+    /// [`Room::ice_policy`], grouped by [`PolicyViolation`]s.
+    rejected_ice_candidates: HashMap<PolicyViolation, u64>,
+
+    /// Number of failed ICE restarts between [`Peer`]s of this [`Room`],
+    /// after which they are switched to forcibly relayed media.
+    relay_fallback_after: Option<u32>,
+
+    /// Recorder of call detail records of this [`Room`].
+    call_recorder: CallRecorder,
+
+    /// Sink of call detail records of this [`Room`].
+    cdr: CdrSink,
+
+    /// Indicator whether call detail records of this [`Room`] should be sent
+    /// as `OnCallRecord` Control API callbacks.
+    cdr_callbacks: bool,
+
+    /// SDP negotiations in progress, keyed by IDs of the offerer [`Peer`]s.
+    negotiations: HashMap<PeerId, Negotiation>,
+
+    /// Deadlines of SDP negotiations between [`Peer`]s of this [`Room`].
+    negotiation_conf: conf::Negotiation,
+
+    /// Number of SDP negotiations timed out in this [`Room`], grouped by
+    /// [`NegotiationStage`]s they timed out on.
+    negotiation_timeouts: HashMap<NegotiationStage, u64>,
+
+    /// Number of [`Peer`] pairs recreated in this [`Room`] after their SDP
+    /// negotiations kept timing out.
+    recreated_peers: u64,
+
+    /// Journal of this [`Room`] activity.
+    journal: RoomJournal,
+
+    /// Directory which [`Room::journal`] is flushed to, if this [`Room`] is
+    /// closed after errors.
+    journal_dir: PathBuf,
+
+    /// Regions of [`Member`]s resolved from the IP addresses they're
+    /// connected from.
+    client_regions: Vec<conf::turn::ClientRegion>,
 }
 
 impl Room {
@@ -142,32 +251,84 @@ impl Room {
     ///
     /// Errors with [`RoomError::BadRoomSpec`] if [`RoomSpec`] transformation
     /// fails.
+    ///
+    /// Errors with [`RoomError::MembersLimitReached`] or
+    /// [`RoomError::PublishersLimitReached`] if [`Member`]s of the provided
+    /// [`RoomSpec`] exceed capacity limits set by this [`RoomSpec`]. Default
+    /// limits of the [`Room`] are not checked here.
     pub fn start(
         room_spec: &RoomSpec,
         context: &AppContext,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+        emptiness_subscriber: Option<Recipient<RoomEmptinessChanged>>,
     ) -> Result<Addr<Self>, RoomError> {
         // 16 is the default actix address channel capacity.
         let (_, rx) = actix::dev::channel::channel(16);
 
         let ctx = Context::with_receiver(rx);
+        let journal = RoomJournal::new(context.config.journal.capacity);
         let this = Self {
             id: room_spec.id().clone(),
             peers: PeersService::new(
                 room_spec.id().clone(),
                 context.turn_service.clone(),
+                context.relay_usage.clone(),
                 peers_traffic_watcher,
                 &context.config.media,
+                &context.stats_handlers,
                 Rc::new(ctx.address().downgrade())
                     as Rc<dyn PeerUpdatesSubscriber>,
+                journal.clone(),
             ),
-            members: ParticipantService::new(room_spec, context)?,
+            members: ParticipantService::new(
+                room_spec,
+                context,
+                journal.clone(),
+            )?,
             callbacks: context.callbacks.clone(),
+            relay_usage: context.relay_usage.clone(),
+            empty_timeout: room_spec.empty_timeout,
+            max_duration: room_spec.max_duration,
+            on_close: room_spec.on_close.clone(),
+            emptiness_subscriber,
+            is_empty: true,
+            max_members: room_spec
+                .max_members
+                .or_else(|| context.config.limits.max_members_per_room()),
+            max_publishers: room_spec
+                .max_publishers
+                .or_else(|| context.config.limits.max_publishers_per_room()),
+            ice_policy: IceCandidatePolicy::new(context.config.ice.clone()),
+            rejected_ice_candidates: HashMap::new(),
+            relay_fallback_after: context.config.ice.relay_fallback_after(),
+            call_recorder: CallRecorder::new(room_spec.id().clone()),
+            cdr: context.cdr.clone(),
+            cdr_callbacks: context.config.cdr.callbacks,
+            negotiations: HashMap::new(),
+            negotiation_conf: context.config.negotiation.clone(),
+            negotiation_timeouts: HashMap::new(),
+            recreated_peers: 0,
+            journal,
+            journal_dir: PathBuf::from(&context.config.journal.dir),
+            client_regions: context.config.turn.client_regions.clone(),
         };
+        this.ensure_spec_capacity(
+            room_spec.max_members,
+            room_spec.max_publishers,
+        )?;
 
         Ok(ctx.run(this))
     }
 
+    /// Returns region of a [`Member`] connected from the provided `ip`
+    /// according to the configured [`conf::turn::ClientRegion`]s.
+    fn client_region(&self, ip: IpAddr) -> Option<String> {
+        self.client_regions
+            .iter()
+            .find(|region| region.contains(ip))
+            .map(|region| region.name.clone())
+    }
+
     /// Returns [`state::Room`] for the provided [`MemberId`].
     #[inline]
     #[must_use]
@@ -182,6 +343,195 @@ impl Room {
         &self.id
     }
 
+    /// Sends [`RoomEmptinessChanged`] notification to the
+    /// [`Room::emptiness_subscriber`] if this [`Room`] became empty or
+    /// non-empty since the last notification.
+    ///
+    /// [`Member`]s waiting for reconnection are considered connected.
+    fn report_emptiness(&mut self) {
+        let is_empty = !self.members.has_connections();
+        if self.is_empty == is_empty {
+            return;
+        }
+        self.is_empty = is_empty;
+
+        if let Some(subscriber) = &self.emptiness_subscriber {
+            if let Err(e) = subscriber.do_send(RoomEmptinessChanged {
+                room_id: self.id.clone(),
+                is_empty,
+            }) {
+                self.log_error(format!(
+                    "Failed to notify about Room emptiness: {}",
+                    e,
+                ));
+            }
+        }
+    }
+
+    /// Checks whether one more [`Member`] fits into this [`Room`] having
+    /// the provided number of [`Member`]s.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::MembersLimitReached`] if [`Room::max_members`]
+    /// limit is reached.
+    fn ensure_members_capacity(&self, count: usize) -> Result<(), RoomError> {
+        match self.max_members {
+            Some(max) if count >= max => {
+                Err(RoomError::MembersLimitReached(max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether one more publishing [`Member`] fits into this [`Room`]
+    /// having the provided number of publishing [`Member`]s.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PublishersLimitReached`] if
+    /// [`Room::max_publishers`] limit is reached.
+    fn ensure_publishers_capacity(
+        &self,
+        count: usize,
+    ) -> Result<(), RoomError> {
+        match self.max_publishers {
+            Some(max) if count >= max => {
+                Err(RoomError::PublishersLimitReached(max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether all the [`Member`]s created from the [`RoomSpec`] of this
+    /// [`Room`] fit into the provided capacity limits.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::MembersLimitReached`] if there are more than
+    /// `max_members` [`Member`]s in this [`Room`].
+    ///
+    /// Errors with [`RoomError::PublishersLimitReached`] if there are more
+    /// than `max_publishers` publishing [`Member`]s in this [`Room`].
+    fn ensure_spec_capacity(
+        &self,
+        max_members: Option<usize>,
+        max_publishers: Option<usize>,
+    ) -> Result<(), RoomError> {
+        match max_members {
+            Some(max) if self.members.members().len() > max => {
+                return Err(RoomError::MembersLimitReached(max));
+            }
+            _ => {}
+        }
+        match max_publishers {
+            Some(max) if self.publishers_count(false) > max => {
+                Err(RoomError::PublishersLimitReached(max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns number of publishing [`Member`]s in this [`Room`].
+    ///
+    /// Only connected [`Member`]s are counted if `only_connected` is `true`.
+    fn publishers_count(&self, only_connected: bool) -> usize {
+        self.members
+            .members()
+            .values()
+            .filter(|member| !member.srcs().is_empty())
+            .filter(|member| {
+                !only_connected
+                    || self.members.member_has_connection(&member.id())
+            })
+            .count()
+    }
+
+    /// Checks whether the provided [`Member`] is able to join this [`Room`]
+    /// without exceeding its capacity limits.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::MembersLimitReached`] if there are too many
+    /// connected [`Member`]s in this [`Room`].
+    ///
+    /// Errors with [`RoomError::PublishersLimitReached`] if the provided
+    /// [`Member`] publishes and there are too many connected publishing
+    /// [`Member`]s in this [`Room`].
+    fn ensure_can_join(&self, member: &Member) -> Result<(), RoomError> {
+        let connected = self
+            .members
+            .members()
+            .keys()
+            .filter(|id| self.members.member_has_connection(id))
+            .count();
+        self.ensure_members_capacity(connected)?;
+        if !member.srcs().is_empty() {
+            self.ensure_publishers_capacity(self.publishers_count(true))?;
+        }
+        Ok(())
+    }
+
+    /// Removes the [`Member`] which has left this [`Room`] if it's configured
+    /// to be removed on leave, and sends [`OnMemberRemovedEvent`] to its
+    /// `on_leave` URL.
+    fn remove_member_on_leave(
+        &mut self,
+        member_id: &MemberId,
+        ctx: &mut Context<Self>,
+    ) {
+        let member = match self.members.get_member_by_id(member_id) {
+            Ok(member) if member.is_remove_on_leave() => member,
+            _ => return,
+        };
+
+        self.delete_member(member_id, ctx);
+        info!(
+            "Member [id = {}] removed from Room [id = {}] after leaving.",
+            member_id, self.id,
+        );
+
+        if let Some(url) = member.get_on_leave() {
+            self.send_callback(
+                url,
+                member.get_fid().into(),
+                OnMemberRemovedEvent,
+            );
+        }
+    }
+
+    /// Sends [Control API] callback with the provided event to the provided
+    /// [`CallbackUrl`], recording it into [`Room::journal`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn send_callback<T: Into<CallbackEvent>>(
+        &self,
+        url: CallbackUrl,
+        fid: StatefulFid,
+        event: T,
+    ) {
+        let event = event.into();
+        self.journal.callback(&fid, &event);
+        let callbacks = self.callbacks.clone();
+        let journal = self.journal.clone();
+        let room_id = self.id.clone();
+        Arbiter::spawn(async move {
+            if let Err(e) = callbacks.send(url, fid.clone(), event).await {
+                log_error(
+                    &journal,
+                    &room_id,
+                    format!("Failed to send callback of {}: {}", fid, e),
+                );
+            }
+        });
+    }
+
+    /// Logs the provided error `message` and records it into
+    /// [`Room::journal`].
+    fn log_error(&self, message: String) {
+        log_error(&self.journal, &self.id, message);
+    }
+
     /// Sends [`Event::PeersRemoved`] to [`Member`].
     fn send_peers_removed(
         &self,
@@ -281,6 +631,13 @@ impl Room {
             "Peers {:?} removed for member [id = {}].",
             peers_id, member_id
         );
+        self.call_recorder.peers_removed(&peers_id);
+        self.journal.peers_removed(&peers_id);
+        for peer_id in &peers_id {
+            if let Some(mut negotiation) = self.negotiations.remove(peer_id) {
+                negotiation.fail("Peer removed before negotiation finished");
+            }
+        }
         if let Ok(member) = self.members.get_member_by_id(&member_id) {
             member.peers_removed(&peers_id);
             let _ = self.send_peers_removed(member_id, peers_id);
@@ -294,7 +651,10 @@ impl Room {
     /// # Errors
     ///
     /// Errors if [`Peer`] lookup fails, or it is not in [`Stable`] state.
-    fn send_tracks_applied(&self, peer_id: PeerId) -> Result<(), RoomError> {
+    fn send_tracks_applied(
+        &mut self,
+        peer_id: PeerId,
+    ) -> Result<(), RoomError> {
         let peer: Peer<Stable> = self.peers.take_inner_peer(peer_id)?;
         let partner_peer: Peer<Stable> =
             self.peers.take_inner_peer(peer.partner_peer_id())?;
@@ -304,6 +664,7 @@ impl Room {
 
         let updates = peer.get_updates();
         let member_id = peer.member_id();
+        self.start_negotiation(&peer, &partner_peer, true);
 
         self.peers.add_peer(peer);
         self.peers.add_peer(partner_peer);
@@ -340,18 +701,164 @@ impl Room {
         self.members
             .close_member_connection(&member_id, ws_close_reason, ctx);
 
+        self.record_member_left(member_id, on_leave_reason);
+
         if let Ok(member) = self.members.get_member_by_id(member_id) {
             if let (Some(url), Some(reason)) =
                 (member.get_on_leave(), on_leave_reason)
             {
-                self.callbacks.do_send(
+                let usage = self.relay_usage.member_usage(&self.id, member_id);
+                self.send_callback(
+                    url,
+                    member.get_fid().into(),
+                    OnLeaveEvent::new(reason, usage),
+                );
+            }
+        }
+
+        self.report_emptiness();
+    }
+
+    /// Finishes recording of the session of the [`Member`] with the provided
+    /// [`MemberId`], writes its [`MemberRecord`] to [`Room::cdr`] and sends it
+    /// to `on_metrics` URL of the [`Member`] if [`Room::cdr_callbacks`] are
+    /// enabled.
+    ///
+    /// [`MemberRecord`]: crate::cdr::MemberRecord
+    fn record_member_left(
+        &mut self,
+        member_id: &MemberId,
+        reason: Option<OnLeaveReason>,
+    ) {
+        let usage = self.relay_usage.member_usage(&self.id, member_id);
+        let peers_usage =
+            self.relay_usage.member_peers_usage(&self.id, member_id);
+        let record = match self.call_recorder.member_left(
+            member_id,
+            reason,
+            usage,
+            &peers_usage,
+        ) {
+            Some(record) => CallRecord::Member(record),
+            None => return,
+        };
+
+        self.cdr.write(&record);
+        if !self.cdr_callbacks {
+            return;
+        }
+        if let Ok(member) = self.members.get_member_by_id(member_id) {
+            if let Some(url) = member.get_on_metrics() {
+                self.send_callback(
                     url,
                     member.get_fid().into(),
-                    OnLeaveEvent::new(reason),
+                    OnCallRecordEvent(record),
                 );
             }
         }
     }
+
+    /// Issues fresh [`IceUser`]s for the [`Peer`]s whose credentials are
+    /// about to expire.
+    ///
+    /// Fresh [`IceServer`]s are pushed to the client with
+    /// [`Event::IceServersUpdated`] and ICE restart is scheduled, so the
+    /// `Peer` switches to them before the replaced [`IceUser`] expires and is
+    /// cleaned up.
+    ///
+    /// [`IceServer`]: medea_client_api_proto::IceServer
+    /// [`IceUser`]: crate::turn::IceUser
+    fn rotate_ice_users(&mut self, ctx: &mut Context<Self>) {
+        for (peer_id, member_id) in self.peers.start_ice_users_rotation() {
+            let region = self
+                .members
+                .get_member_by_id(&member_id)
+                .ok()
+                .and_then(|member| member.ice_region());
+            let peers = Rc::clone(&self.peers);
+            ctx.spawn(
+                async move { peers.rotate_ice_user(peer_id, region).await }
+                    .into_actor(self)
+                    .map(move |res, this: &mut Self, ctx| {
+                        let old_user = match res {
+                            Ok(old_user) => old_user,
+                            Err(e) => {
+                                this.log_error(format!(
+                                    "Failed to rotate IceUser of Peer [id = \
+                                     {}]: {}",
+                                    peer_id, e,
+                                ));
+                                return;
+                            }
+                        };
+                        if let Some(expiry) =
+                            old_user.as_ref().and_then(IceUser::expiry)
+                        {
+                            let expires_in = expiry
+                                .expires_at
+                                .duration_since(SystemTime::now())
+                                .unwrap_or_default();
+                            ctx.run_later(expires_in, move |_, _| {
+                                drop(old_user);
+                            });
+                        }
+                        if let Err(e) = this
+                            .send_ice_servers_updated(peer_id, member_id, true)
+                        {
+                            this.log_error(format!(
+                                "Failed to push rotated ICE servers of Peer \
+                                 [id = {}]: {}",
+                                peer_id, e,
+                            ));
+                        }
+                    }),
+            );
+        }
+    }
+
+    /// Sends [`Event::IceServersUpdated`] with the current [`IceServer`]s of
+    /// the [`Peer`] with the provided [`PeerId`] to its owner, and schedules
+    /// ICE restart of this [`Peer`] if `restart_ice` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if the [`Peer`] was removed.
+    ///
+    /// Errors with [`RoomError::NoTurnCredentials`] if the [`Peer`] has no
+    /// [`IceUser`].
+    ///
+    /// Errors if the owner of the [`Peer`] is not connected at the moment, so
+    /// it will receive the fresh [`IceServer`]s on reconnection only.
+    ///
+    /// [`IceServer`]: medea_client_api_proto::IceServer
+    /// [`IceUser`]: crate::turn::IceUser
+    fn send_ice_servers_updated(
+        &mut self,
+        peer_id: PeerId,
+        member_id: MemberId,
+        restart_ice: bool,
+    ) -> Result<(), RoomError> {
+        let (ice_servers, force_relay) =
+            self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                if restart_ice {
+                    peer.as_changes_scheduler().restart_ice();
+                }
+                (peer.ice_servers_list(), peer.is_force_relayed())
+            })?;
+        let ice_servers = ice_servers
+            .ok_or_else(|| RoomError::NoTurnCredentials(member_id.clone()))?;
+        let sent = self.members.send_event_to_member(
+            member_id,
+            Event::IceServersUpdated {
+                peer_id,
+                ice_servers,
+                force_relay,
+            },
+        );
+        self.peers.commit_scheduled_changes(peer_id)?;
+
+        sent
+    }
 }
 
 /// [`Actor`] implementation that provides an ergonomic way
@@ -364,8 +871,46 @@ impl Actor for Room {
         ctx.run_interval(Duration::from_secs(5), |this, _| {
             this.peers.check_peers();
         });
+        ctx.run_interval(Duration::from_secs(5), |this, ctx| {
+            this.rotate_ice_users(ctx);
+        });
+        ctx.run_interval(Duration::from_secs(1), |this, ctx| {
+            this.check_negotiation_deadlines(ctx);
+        });
         ctx.add_stream(self.peers.subscribe_to_metrics_events());
     }
+
+    fn stopped(&mut self, _: &mut Self::Context) {
+        for member_id in self.call_recorder.joined_members() {
+            self.record_member_left(&member_id, None);
+        }
+        let record = CallRecord::Room(self.call_recorder.room_closed());
+        self.cdr.write(&record);
+        if let (true, Some(url)) = (self.cdr_callbacks, self.on_close.clone()) {
+            self.send_callback(
+                url,
+                Fid::<ToRoom>::new(self.id.clone()).into(),
+                OnCallRecordEvent(record),
+            );
+        }
+
+        self.relay_usage.remove_room(&self.id);
+
+        if self.journal.has_errors() {
+            match self.journal.flush(&self.journal_dir, &self.id) {
+                Ok(path) => info!(
+                    "Journal of Room [id = {}] closed after errors is written \
+                     to {}",
+                    self.id,
+                    path.display(),
+                ),
+                Err(e) => error!(
+                    "Failed to write journal of Room [id = {}]: {}",
+                    self.id, e,
+                ),
+            }
+        }
+    }
 }
 
 impl Handler<ShutdownGracefully> for Room {
@@ -393,15 +938,36 @@ impl Handler<ShutdownGracefully> for Room {
                 self.members.member_has_connection(&member.id())
             })
             .for_each(|(member, on_leave)| {
-                self.callbacks.do_send(
+                let usage =
+                    self.relay_usage.member_usage(&self.id, &member.id());
+                self.send_callback(
                     on_leave,
                     member.get_fid().into(),
-                    OnLeaveEvent::new(OnLeaveReason::ServerShutdown),
+                    OnLeaveEvent::new(OnLeaveReason::ServerShutdown, usage),
                 );
             });
+        let connected: Vec<_> = self
+            .call_recorder
+            .joined_members()
+            .into_iter()
+            .filter(|id| self.members.member_has_connection(id))
+            .collect();
+        for member_id in connected {
+            self.record_member_left(
+                &member_id,
+                Some(OnLeaveReason::ServerShutdown),
+            );
+        }
 
         AtomicResponse::new(Box::pin(
             self.members.drop_connections(ctx).into_actor(self),
         ))
     }
 }
+
+/// Logs the provided error `message` of the [`Room`] with the provided
+/// [`RoomId`] and records it into the provided [`RoomJournal`].
+fn log_error(journal: &RoomJournal, room_id: &RoomId, message: String) {
+    error!("Room [id = {}]: {}", room_id, message);
+    journal.error(message);
+}
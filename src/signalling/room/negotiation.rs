@@ -0,0 +1,504 @@
+//! Tracking of SDP negotiations between [`Peer`]s of a [`Room`] and recovery
+//! of the ones stuck for too long.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use actix::{ActorFuture as _, AsyncContext as _, Context};
+use derive_more::Display;
+use medea_client_api_proto::PeerId;
+
+use crate::{log::prelude::*, media::Peer, tracing::Span};
+
+use super::{Room, RoomError};
+
+/// Stage of SDP negotiation between [`Peer`]s.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum NegotiationStage {
+    /// Offerer [`Peer`] is making its SDP offer.
+    #[display(fmt = "SDP offer")]
+    Offer,
+
+    /// Answerer [`Peer`] is making its SDP answer.
+    #[display(fmt = "SDP answer")]
+    Answer,
+}
+
+impl NegotiationStage {
+    /// Returns `snake_case` name of this [`NegotiationStage`].
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Offer => "offer",
+            Self::Answer => "answer",
+        }
+    }
+}
+
+/// SDP negotiation between [`Peer`]s in progress.
+#[derive(Debug)]
+pub struct Negotiation {
+    /// [`Span`] of this [`Negotiation`], exported once it's dropped.
+    span: Span,
+
+    /// Current [`NegotiationStage`] of this [`Negotiation`].
+    stage: NegotiationStage,
+
+    /// Time before which the current [`NegotiationStage`] should be passed.
+    ///
+    /// [`None`] if it's awaited forever.
+    deadline: Option<Instant>,
+
+    /// Number of negotiations between the same [`Peer`]s timed out in a row
+    /// before this [`Negotiation`].
+    timeouts: u32,
+}
+
+impl Negotiation {
+    /// Marks this [`Negotiation`] as failed with the provided `reason`.
+    pub fn fail<E: fmt::Display>(&mut self, reason: E) {
+        self.span.set_error(reason);
+    }
+
+    /// Returns deadline of the provided [`NegotiationStage`] started right
+    /// now, if the provided `timeout` is set.
+    fn deadline(timeout: Option<Duration>) -> Option<Instant> {
+        timeout.map(|timeout| Instant::now() + timeout)
+    }
+}
+
+impl Room {
+    /// Starts [`Negotiation`] between the provided `offerer` and `answerer`
+    /// [`Peer`]s, which is finished once the `answerer` makes its SDP answer.
+    ///
+    /// The `offerer` should make its SDP offer before
+    /// [`conf::Negotiation::offer_timeout`] passes.
+    ///
+    /// [`conf::Negotiation::offer_timeout`]:
+    /// crate::conf::Negotiation::offer_timeout
+    pub(super) fn start_negotiation<O, A>(
+        &mut self,
+        offerer: &Peer<O>,
+        answerer: &Peer<A>,
+        is_renegotiation: bool,
+    ) {
+        let span = Span::new("room.negotiation")
+            .attr("room.id", &self.id)
+            .attr("offerer.member.id", offerer.member_id())
+            .attr("offerer.peer.id", offerer.id())
+            .attr("answerer.member.id", answerer.member_id())
+            .attr("answerer.peer.id", answerer.id())
+            .attr("renegotiation", is_renegotiation);
+        self.negotiations.insert(
+            offerer.id(),
+            Negotiation {
+                span,
+                stage: NegotiationStage::Offer,
+                deadline: Negotiation::deadline(
+                    self.negotiation_conf.offer_timeout(),
+                ),
+                timeouts: 0,
+            },
+        );
+    }
+
+    /// Moves [`Negotiation`] started by the offerer [`Peer`] with the provided
+    /// [`PeerId`] to [`NegotiationStage::Answer`].
+    ///
+    /// The answerer should make its SDP answer before
+    /// [`conf::Negotiation::answer_timeout`] passes.
+    ///
+    /// [`conf::Negotiation::answer_timeout`]:
+    /// crate::conf::Negotiation::answer_timeout
+    pub(super) fn sdp_offer_made(&mut self, offerer_id: PeerId) {
+        let answer_timeout = self.negotiation_conf.answer_timeout();
+        if let Some(negotiation) = self.negotiations.get_mut(&offerer_id) {
+            negotiation.stage = NegotiationStage::Answer;
+            negotiation.deadline = Negotiation::deadline(answer_timeout);
+        }
+    }
+
+    /// Recovers [`Negotiation`]s which haven't passed their current
+    /// [`NegotiationStage`] in time.
+    pub(super) fn check_negotiation_deadlines(
+        &mut self,
+        ctx: &mut Context<Self>,
+    ) {
+        let now = Instant::now();
+        let timed_out: Vec<_> = self
+            .negotiations
+            .iter()
+            .filter(|(_, n)| n.deadline.map_or(false, |d| d <= now))
+            .map(|(offerer_id, _)| *offerer_id)
+            .collect();
+        for offerer_id in timed_out {
+            if let Err(e) = self.on_negotiation_timeout(offerer_id, ctx) {
+                self.log_error(format!(
+                    "Failed to recover timed out negotiation of Peer [id = \
+                     {}]: {}",
+                    offerer_id, e,
+                ));
+            }
+        }
+    }
+
+    /// Rolls back timed out [`Negotiation`] started by the offerer [`Peer`]
+    /// with the provided [`PeerId`], and restarts it with swapped roles.
+    ///
+    /// [`Peer`]s are removed and recreated instead, if their negotiations
+    /// kept timing out [`conf::Negotiation::recreate_after`] times in a row.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of [`Peer`]s doesn't exist, or negotiation cannot be
+    /// restarted.
+    ///
+    /// [`conf::Negotiation::recreate_after`]:
+    /// crate::conf::Negotiation::recreate_after
+    fn on_negotiation_timeout(
+        &mut self,
+        offerer_id: PeerId,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), RoomError> {
+        let mut negotiation = match self.negotiations.remove(&offerer_id) {
+            Some(negotiation) => negotiation,
+            None => return Ok(()),
+        };
+        let stage = negotiation.stage;
+        let timeouts = negotiation.timeouts + 1;
+        negotiation.fail(format!("{} timed out", stage));
+        let total = {
+            let count = self.negotiation_timeouts.entry(stage).or_default();
+            *count += 1;
+            *count
+        };
+
+        let answerer_id = self.peers.rollback_negotiation(offerer_id)?;
+        self.log_error(format!(
+            "{} in negotiation of Peers [id = {}, {}] timed out {} times in a \
+             row (timed out {} times in total), Peers are rolled back to \
+             Stable",
+            stage, offerer_id, answerer_id, timeouts, total,
+        ));
+
+        if self
+            .negotiation_conf
+            .recreate_after()
+            .map_or(false, |after| timeouts >= after)
+        {
+            return self.recreate_peers(offerer_id, ctx);
+        }
+
+        let is_known_to_remote = self
+            .peers
+            .map_peer_by_id(answerer_id, |p| p.is_known_to_remote())?;
+        if is_known_to_remote {
+            self.send_tracks_applied(answerer_id)?;
+        } else {
+            self.send_peer_created(answerer_id)?;
+        }
+        if let Some(negotiation) = self.negotiations.get_mut(&answerer_id) {
+            negotiation.timeouts = timeouts;
+        }
+        info!(
+            "Negotiation of Peers [id = {}, {}] in Room [id = {}] restarted \
+             with Peer [id = {}] as offerer",
+            offerer_id, answerer_id, self.id, answerer_id,
+        );
+
+        Ok(())
+    }
+
+    /// Removes the [`Peer`] with the provided [`PeerId`] along with its
+    /// partner [`Peer`], and interconnects their [`Member`]s from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of [`Peer`]s or [`Member`]s doesn't exist.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn recreate_peers(
+        &mut self,
+        peer_id: PeerId,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), RoomError> {
+        let (member_id, partner_member_id) =
+            self.peers.map_peer_by_id(peer_id, |p| {
+                (p.member_id(), p.partner_member_id())
+            })?;
+        let member = self.members.get_member_by_id(&member_id)?;
+        let partner = self.members.get_member_by_id(&partner_member_id)?;
+
+        for (member_id, peers) in
+            self.peers.remove_peers(&member_id, &[peer_id])
+        {
+            self.member_peers_removed(
+                peers.into_iter().map(|p| p.id()).collect(),
+                member_id,
+            );
+        }
+        self.recreated_peers += 1;
+        info!(
+            "Peers between Members [id = {}, {}] in Room [id = {}] are \
+             recreated after timed out negotiations (recreated {} times in \
+             total)",
+            member_id, partner_member_id, self.id, self.recreated_peers,
+        );
+
+        ctx.spawn(self.connect_members(&member, &partner).map(
+            move |res, this, _| {
+                if let Err(e) = res {
+                    this.log_error(format!(
+                        "Failed to recreate Peers between Members [id = {}, \
+                         {}]: {}",
+                        member_id, partner_member_id, e,
+                    ));
+                }
+            },
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{convert::TryFrom as _, time::Duration};
+
+    use actix::Addr;
+    use futures::{
+        channel::mpsc, future, stream::LocalBoxStream, StreamExt as _,
+    };
+    use medea_client_api_proto::{
+        Command, Credential, Event, MemberId, NegotiationRole,
+    };
+    use medea_control_api_proto::grpc::api as proto;
+    use tokio::time::timeout;
+
+    use crate::{
+        api::{
+            client::rpc_connection::{CommandMessage, MockRpcConnection},
+            control::{
+                refs::{Fid, StatefulFid, ToRoom},
+                RoomSpec, RootElement,
+            },
+            RpcServer as _,
+        },
+        conf::{self, Conf},
+        signalling::peers::build_peers_traffic_watcher,
+        AppContext,
+    };
+
+    use super::{super::SerializeProto, Room};
+
+    /// Starts [`Room`] from the `pub-sub-video-call` spec with the provided
+    /// [`conf::Negotiation`], connects both of its `Member`s, and returns
+    /// [`Event`]s sent to them.
+    async fn start_room(
+        negotiation: conf::Negotiation,
+    ) -> (Addr<Room>, LocalBoxStream<'static, (MemberId, Event)>) {
+        let mut conf = Conf::default();
+        conf.negotiation = negotiation;
+        let app =
+            AppContext::new(conf, crate::turn::new_turn_auth_service_mock());
+        let spec: RootElement = serde_yaml::from_str(include_str!(
+            "../../../tests/specs/pub-sub-video-call.yml"
+        ))
+        .unwrap();
+        let room = Room::start(
+            &RoomSpec::try_from(&spec).unwrap(),
+            &app,
+            build_peers_traffic_watcher(&app.config.media),
+            None,
+        )
+        .unwrap();
+
+        let (tx, rx) = mpsc::unbounded();
+        for member_id in &["caller", "responder"] {
+            let member_id = MemberId::from(*member_id);
+            let tx = tx.clone();
+            let id = member_id.clone();
+            let mut connection = MockRpcConnection::new();
+            connection.expect_send_event().returning(move |_, event| {
+                let _ = tx.unbounded_send((id.clone(), event));
+            });
+            connection
+                .expect_close()
+                .returning(|_, _| Box::pin(future::ready(())));
+            room.connection_established(
+                member_id,
+                Credential::from("test"),
+                None,
+                Box::new(connection),
+            )
+            .await
+            .unwrap();
+        }
+
+        (room, rx.boxed_local())
+    }
+
+    /// Awaits the next [`Event`] matching the provided predicate, along with
+    /// ID of the `Member` it's sent to.
+    async fn next_event<F>(
+        events: &mut LocalBoxStream<'static, (MemberId, Event)>,
+        mut predicate: F,
+    ) -> (MemberId, Event)
+    where
+        F: FnMut(&MemberId, &Event) -> bool,
+    {
+        timeout(Duration::from_secs(5), async {
+            while let Some((member_id, event)) = events.next().await {
+                if predicate(&member_id, &event) {
+                    return (member_id, event);
+                }
+            }
+            panic!("Events stream ended")
+        })
+        .await
+        .expect("Expected Event was not sent")
+    }
+
+    /// Awaits [`Event::PeerCreated`] sent to the offerer.
+    async fn offerer_created(
+        events: &mut LocalBoxStream<'static, (MemberId, Event)>,
+    ) -> (MemberId, Event) {
+        next_event(events, |_, event| {
+            matches!(
+                event,
+                Event::PeerCreated {
+                    negotiation_role: NegotiationRole::Offerer,
+                    ..
+                }
+            )
+        })
+        .await
+    }
+
+    /// Returns [`proto::Room`] with metrics of the provided [`Room`].
+    async fn room_metrics(room: &Addr<Room>) -> proto::Room {
+        let fid = StatefulFid::from(Fid::<ToRoom>::new(
+            "pub-sub-video-call".to_owned().into(),
+        ));
+        let mut elements = room
+            .send(SerializeProto(vec![fid.clone()]))
+            .await
+            .unwrap()
+            .unwrap();
+        match elements.remove(&fid).unwrap().el.unwrap() {
+            proto::element::El::Room(room) => room,
+            _ => unreachable!(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn rolls_back_timed_out_offer_with_swapped_roles() {
+        let (room, mut events) = start_room(conf::Negotiation {
+            offer_timeout: Duration::from_millis(100),
+            recreate_after: 0,
+            ..conf::Negotiation::default()
+        })
+        .await;
+
+        let (offerer, _) = offerer_created(&mut events).await;
+        let (new_offerer, _) = offerer_created(&mut events).await;
+        assert_ne!(offerer, new_offerer);
+
+        let metrics = room_metrics(&room).await;
+        assert_eq!(metrics.negotiation_timeouts.get("offer"), Some(&1));
+        assert_eq!(metrics.negotiation_timeouts.get("answer"), None);
+        assert_eq!(metrics.recreated_peers, 0);
+    }
+
+    #[actix_rt::test]
+    async fn rolls_back_timed_out_answer_with_swapped_roles() {
+        let (room, mut events) = start_room(conf::Negotiation {
+            offer_timeout: Duration::from_secs(60),
+            answer_timeout: Duration::from_millis(100),
+            recreate_after: 0,
+        })
+        .await;
+
+        let (offerer, event) = offerer_created(&mut events).await;
+        let (peer_id, tracks) = match event {
+            Event::PeerCreated {
+                peer_id, tracks, ..
+            } => (peer_id, tracks),
+            _ => unreachable!(),
+        };
+        room.do_send(CommandMessage::new(
+            offerer.clone(),
+            Command::MakeSdpOffer {
+                peer_id,
+                sdp_offer: "offer".to_owned(),
+                mids: tracks
+                    .iter()
+                    .enumerate()
+                    .map(|(mid, track)| (track.id, mid.to_string()))
+                    .collect(),
+                transceivers_statuses: tracks
+                    .iter()
+                    .map(|track| (track.id, true))
+                    .collect(),
+            },
+        ));
+        let (answerer, _) = next_event(&mut events, |_, event| {
+            matches!(
+                event,
+                Event::PeerCreated {
+                    negotiation_role: NegotiationRole::Answerer(_),
+                    ..
+                }
+            )
+        })
+        .await;
+        assert_ne!(offerer, answerer);
+
+        let (new_offerer, _) = offerer_created(&mut events).await;
+        assert_eq!(new_offerer, answerer);
+
+        let metrics = room_metrics(&room).await;
+        assert_eq!(metrics.negotiation_timeouts.get("offer"), None);
+        assert_eq!(metrics.negotiation_timeouts.get("answer"), Some(&1));
+        assert_eq!(metrics.recreated_peers, 0);
+    }
+
+    #[actix_rt::test]
+    async fn recreates_peers_after_timed_out_negotiations() {
+        let (room, mut events) = start_room(conf::Negotiation {
+            offer_timeout: Duration::from_millis(100),
+            recreate_after: 1,
+            ..conf::Negotiation::default()
+        })
+        .await;
+
+        let (offerer, created) = offerer_created(&mut events).await;
+        let old_peer_id = match created {
+            Event::PeerCreated { peer_id, .. } => peer_id,
+            _ => unreachable!(),
+        };
+        let (_, removed) = next_event(&mut events, |member_id, event| {
+            *member_id == offerer && matches!(event, Event::PeersRemoved { .. })
+        })
+        .await;
+        match removed {
+            Event::PeersRemoved { peer_ids } => {
+                assert!(peer_ids.contains(&old_peer_id));
+            }
+            _ => unreachable!(),
+        }
+        let (_, recreated) = offerer_created(&mut events).await;
+        match recreated {
+            Event::PeerCreated { peer_id, .. } => {
+                assert_ne!(peer_id, old_peer_id);
+            }
+            _ => unreachable!(),
+        }
+
+        let metrics = room_metrics(&room).await;
+        assert_eq!(metrics.negotiation_timeouts.get("offer"), Some(&1));
+        assert_eq!(metrics.recreated_peers, 1);
+    }
+}
@@ -1,14 +1,18 @@
 //! [`PeerConnectionStateEventsHandler`] implementation for [`Room`].
 
+use std::collections::HashMap;
+
 use actix::{Handler, Message, StreamHandler, WeakAddr};
 use chrono::{DateTime, Utc};
 use medea_client_api_proto::{
-    ConnectionQualityScore, Event, MemberId, NegotiationRole, PeerId,
-    TrackUpdate,
+    CongestionDirection, CongestionSeverity, ConnectionQualityScore, Event,
+    MemberId, NegotiationRole, PeerId, TrackQualityScore, TrackUpdate,
 };
 
 use crate::{
-    api::control::callback::{MediaDirection, MediaType},
+    api::control::callback::{
+        MediaDirection, MediaType, OnNetworkCongestionEvent, OnPeerMetricsEvent,
+    },
     log::prelude::*,
     media::{peer::PeerUpdatesSubscriber, Peer, PeerStateMachine, Stable},
     signalling::{
@@ -26,7 +30,12 @@ impl Room {
     /// will be changed to a [`WaitLocalSdp`] state.
     ///
     /// [`WaitLocalSdp`]: crate::media::peer::WaitLocalSdp
-    fn send_peer_created(&self, peer_id: PeerId) -> Result<(), RoomError> {
+    ///
+    /// Starts recording of both [`Peer`]s in [`Room::call_recorder`].
+    pub(super) fn send_peer_created(
+        &mut self,
+        peer_id: PeerId,
+    ) -> Result<(), RoomError> {
         let peer: Peer<Stable> = self.peers.take_inner_peer(peer_id)?;
         let partner_peer: Peer<Stable> =
             self.peers.take_inner_peer(peer.partner_peer_id())?;
@@ -46,6 +55,18 @@ impl Room {
             force_relay: peer.is_force_relayed(),
         };
 
+        self.call_recorder.peer_started(
+            peer.id(),
+            member_id.clone(),
+            partner_peer.member_id(),
+        );
+        self.call_recorder.peer_started(
+            partner_peer.id(),
+            partner_peer.member_id(),
+            member_id.clone(),
+        );
+        self.start_negotiation(&peer, &partner_peer, false);
+
         self.peers.add_peer(peer);
         self.peers.add_peer(partner_peer);
 
@@ -76,7 +97,10 @@ impl StreamHandler<PeersMetricsEvent> for Room {
     /// [`PeersMetricsEventHandler`] implementation.
     fn handle(&mut self, event: PeersMetricsEvent, _: &mut Self::Context) {
         if let Err(err) = event.dispatch_with(self) {
-            error!("Error handling PeersMetricsEvent: {:?}", err);
+            self.log_error(format!(
+                "Failed to handle Peers metrics event: {}",
+                err
+            ));
         }
     }
 }
@@ -84,46 +108,108 @@ impl StreamHandler<PeersMetricsEvent> for Room {
 impl PeersMetricsEventHandler for Room {
     type Output = Result<(), RoomError>;
 
-    /// Does nothing atm.
+    /// Records stopped traffic in [`Room::call_recorder`].
     fn on_no_traffic_flow(
         &mut self,
-        _: PeerId,
-        _: DateTime<Utc>,
-        _: MediaType,
-        _: MediaDirection,
+        peer_id: PeerId,
+        was_flowing_at: DateTime<Utc>,
+        media_type: MediaType,
+        direction: MediaDirection,
     ) -> Self::Output {
+        self.call_recorder.traffic_stopped(
+            peer_id,
+            media_type,
+            direction,
+            was_flowing_at,
+        );
         Ok(())
     }
 
-    /// Does nothing atm.
+    /// Records restored traffic in [`Room::call_recorder`].
     fn on_traffic_flows(
         &mut self,
-        _: PeerId,
-        _: MediaType,
-        _: MediaDirection,
+        peer_id: PeerId,
+        media_type: MediaType,
+        direction: MediaDirection,
     ) -> Self::Output {
+        self.call_recorder
+            .traffic_flows(peer_id, media_type, direction);
         Ok(())
     }
 
-    /// Sends received [`ConnectionQualityScore`] to member.
+    /// Records received [`ConnectionQualityScore`] in the [`Peer`]'s history
+    /// and [`Room::call_recorder`], and sends it to member along with the
+    /// [`TrackQualityScore`]s of its received tracks.
     fn on_quality_meter_update(
         &mut self,
+        peer_id: PeerId,
         member_id: MemberId,
         partner_member_id: MemberId,
         quality_score: ConnectionQualityScore,
+        tracks_quality_scores: Vec<TrackQualityScore>,
     ) -> Self::Output {
+        self.peers.record_quality_score(peer_id, quality_score);
+        self.call_recorder
+            .quality_score_updated(peer_id, quality_score);
         self.members.send_event_to_member(
             member_id,
             Event::ConnectionQualityUpdated {
                 partner_member_id,
                 quality_score,
+                tracks_quality_scores,
             },
         )
     }
 
     /// Schedules ICE restart and commits scheduled changes.
-    fn on_peer_connection_failed(&mut self, peer_id: PeerId) -> Self::Output {
-        debug!("PeerConnection failed [peer_id = {}].", peer_id);
+    ///
+    /// Switches the failed [`Peer`] pair to forcibly relayed media, if its ICE
+    /// restarts kept failing more than [`Room::relay_fallback_after`] times,
+    /// and sends [`Event::IceServersUpdated`] to both its [`Member`]s.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn on_peer_connection_failed(
+        &mut self,
+        peer_id: PeerId,
+        failures: u32,
+    ) -> Self::Output {
+        debug!(
+            "PeerConnection failed [peer_id = {}, failures = {}].",
+            peer_id, failures,
+        );
+
+        self.call_recorder.ice_restarted(peer_id);
+
+        let failed_restarts = failures.saturating_sub(1);
+        if self
+            .relay_fallback_after
+            .map_or(false, |after| failed_restarts >= after)
+        {
+            if let Some((partner_peer_id, partner_member_id)) =
+                self.peers.fall_back_to_relay(peer_id)?
+            {
+                info!(
+                    "Peers [id = {}, {}] in Room [id = {}] are switched to \
+                     relayed media after {} failed ICE restarts",
+                    peer_id, partner_peer_id, self.id, failed_restarts,
+                );
+                if let Err(e) = self.send_ice_servers_updated(
+                    partner_peer_id,
+                    partner_member_id,
+                    false,
+                ) {
+                    self.log_error(format!(
+                        "Failed to push relayed ICE servers of Peer [id = \
+                         {}]: {}",
+                        partner_peer_id, e,
+                    ));
+                }
+                let member_id =
+                    self.peers.map_peer_by_id(peer_id, |p| p.member_id())?;
+                return self.send_ice_servers_updated(peer_id, member_id, true);
+            }
+        }
+
         self.peers.map_peer_by_id_mut(peer_id, |peer| {
             peer.as_changes_scheduler().restart_ice();
         })?;
@@ -131,6 +217,68 @@ impl PeersMetricsEventHandler for Room {
 
         Ok(())
     }
+
+    /// Sends [`Event::NetworkCongestion`] to the [`Member`] owning the
+    /// [`Peer`] with the provided [`PeerId`], and [`OnNetworkCongestionEvent`]
+    /// to its `on_metrics` URL.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn on_network_congestion(
+        &mut self,
+        peer_id: PeerId,
+        direction: CongestionDirection,
+        severity: CongestionSeverity,
+    ) -> Self::Output {
+        let member_id =
+            self.peers.map_peer_by_id(peer_id, |p| p.member_id())?;
+        let member = self.members.get_member_by_id(&member_id)?;
+        if let Some(url) = member.get_on_metrics() {
+            self.send_callback(
+                url,
+                member.get_fid().into(),
+                OnNetworkCongestionEvent {
+                    peer_id,
+                    direction,
+                    severity,
+                },
+            );
+        }
+        self.members.send_event_to_member(
+            member_id,
+            Event::NetworkCongestion {
+                peer_id,
+                direction,
+                severity,
+            },
+        )
+    }
+
+    /// Sends [`OnPeerMetricsEvent`] to `on_metrics` URL of the [`Member`]
+    /// owning the [`Peer`] with the provided [`PeerId`].
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn on_custom(
+        &mut self,
+        peer_id: PeerId,
+        name: String,
+        data: HashMap<String, String>,
+    ) -> Self::Output {
+        let member_id =
+            self.peers.map_peer_by_id(peer_id, |p| p.member_id())?;
+        let member = self.members.get_member_by_id(&member_id)?;
+        if let Some(url) = member.get_on_metrics() {
+            self.send_callback(
+                url,
+                member.get_fid().into(),
+                OnPeerMetricsEvent {
+                    peer_id,
+                    name,
+                    data,
+                },
+            );
+        }
+        Ok(())
+    }
 }
 
 /// Message which indicates that `Peer` with provided [`PeerId`] has started.
@@ -1,5 +1,7 @@
 //! Implementation of the [`RpcServer`] and related [`Command`]s and functions.
 
+use std::net::IpAddr;
+
 use actix::{
     fut::{self, Either},
     ActorFuture, Addr, Handler, WrapFuture,
@@ -26,6 +28,7 @@ use crate::{
     log::prelude::*,
     media::PeerStateMachine,
     signalling::room::RoomError,
+    tracing::Span,
 };
 
 use super::{ActFuture, Room};
@@ -102,12 +105,14 @@ impl RpcServer for Addr<Room> {
         &self,
         member_id: MemberId,
         credentials: Credential,
+        client_ip: Option<IpAddr>,
         connection: Box<dyn RpcConnection>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>
     {
         self.send(RpcConnectionEstablished {
             member_id,
             credentials,
+            client_ip,
             connection,
         })
         .map(|r| {
@@ -160,22 +165,32 @@ impl Handler<CommandMessage> for Room {
         ctx: &mut Self::Context,
     ) -> Self::Result {
         if let Err(err) = self.validate_command(&msg) {
-            warn!(
-                "Ignoring Command from Member [{}] that failed validation \
-                 cause: {}",
-                msg.member_id, err
-            );
+            self.log_error(format!(
+                "Ignoring Command from Member [id = {}] that failed \
+                 validation: {}",
+                msg.member_id, err,
+            ));
             return;
         }
 
         let member_id = msg.member_id;
         let command = msg.command;
+        if traced_command_name(&command).is_some() {
+            self.journal.command(&member_id, &command);
+        }
+        let mut span =
+            traced_command_name(&command).map_or_else(Span::default, |name| {
+                Span::new(name)
+                    .attr("room.id", &self.id)
+                    .attr("member.id", &member_id)
+            });
+        let _entered = span.enter();
         if let Err(err) = command.dispatch_with(self) {
-            error!(
-                "Error when handling command from Member [id = {}] in Room \
-                 [id = {}]: {}",
-                member_id, self.id, err
-            );
+            span.set_error(&err);
+            self.log_error(format!(
+                "Failed to handle command from Member [id = {}]: {}",
+                member_id, err,
+            ));
             self.disconnect_member(
                 &member_id,
                 CloseReason::InternalError,
@@ -186,6 +201,24 @@ impl Handler<CommandMessage> for Room {
     }
 }
 
+/// Returns name of the [`Span`] tracing handling of the provided [`Command`].
+///
+/// [`None`] if the [`Command`] is too frequent to be traced.
+fn traced_command_name(command: &Command) -> Option<&'static str> {
+    use Command as C;
+
+    match command {
+        C::MakeSdpOffer { .. } => Some("room.make_sdp_offer"),
+        C::MakeSdpAnswer { .. } => Some("room.make_sdp_answer"),
+        C::SetIceCandidate { .. } => Some("room.set_ice_candidate"),
+        C::UpdateTracks { .. } => Some("room.update_tracks"),
+        C::AddPeerConnectionMetrics { .. }
+        | C::LeaveRoom { .. }
+        | C::JoinRoom { .. }
+        | C::SynchronizeMe { .. } => None,
+    }
+}
+
 impl Handler<Synchronize> for Room {
     type Result = ();
 
@@ -203,7 +236,10 @@ impl Handler<Synchronize> for Room {
             msg.0.clone(),
             Event::StateSynchronized { state },
         ) {
-            error!("Failed to synchronize Member [id = {}]: {:?}", msg.0, e);
+            self.log_error(format!(
+                "Failed to synchronize Member [id = {}]: {}",
+                msg.0, e,
+            ));
         }
     }
 }
@@ -233,11 +269,25 @@ impl Handler<RpcConnectionEstablished> for Room {
         let member = actix_try!(self
             .members
             .get_member_by_id_and_credentials(&member_id, &credentials));
+        member.set_client_region(
+            msg.client_ip.and_then(|ip| self.client_region(ip)),
+        );
 
         let is_reconnect = self.members.member_has_connection(&member_id);
+        if !is_reconnect {
+            if let Err(e) = self.ensure_can_join(&member) {
+                self.log_error(format!(
+                    "Member [id = {}] can't join Room: {}",
+                    member_id, e,
+                ));
+                return Box::pin(fut::err(e));
+            }
+        }
 
         let maybe_send_on_join = match (member.get_on_join(), is_reconnect) {
             (Some(callback_url), false) => future::Either::Left({
+                self.journal
+                    .callback(&member.get_fid().into(), &OnJoinEvent.into());
                 let callback_service = self.callbacks.clone();
                 async move {
                     callback_service
@@ -263,10 +313,21 @@ impl Handler<RpcConnectionEstablished> for Room {
                             .err_into()
                             .into_actor(this),
                     ),
-                    Err(err) => Either::Right(fut::err(err)),
+                    Err(err) => {
+                        this.log_error(format!(
+                            "Failed to send OnJoin callback of Member [id = \
+                             {}]: {}",
+                            member_id, err,
+                        ));
+                        Either::Right(fut::err(err))
+                    }
                 })
-                .then(|res, this, _| match res {
+                .then(move |res, this, _| match res {
                     Ok(member) => {
+                        if !is_reconnect {
+                            this.call_recorder.member_joined(member.id());
+                        }
+                        this.report_emptiness();
                         Either::Left(this.init_member_connections(&member).map(
                             move |res, _, _| {
                                 res.map(move |_| RpcConnectionSettings {
@@ -318,6 +379,7 @@ impl Handler<RpcConnectionClosed> for Room {
                     Some(on_leave),
                     ctx,
                 );
+                self.remove_member_on_leave(&msg.member_id, ctx);
             }
         }
     }
@@ -325,7 +387,7 @@ impl Handler<RpcConnectionClosed> for Room {
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, path::PathBuf};
 
     use medea_client_api_proto::{IceCandidate, RoomId};
 
@@ -335,9 +397,11 @@ mod test {
         api::control::{
             member::Credential, pipeline::Pipeline, MemberSpec, RoomSpec,
         },
+        cdr::CallRecorder,
         conf::{self, Conf},
-        media::peer::tests::dummy_negotiation_sub_mock,
+        media::{peer::tests::dummy_negotiation_sub_mock, IceCandidatePolicy},
         signalling::{
+            journal::RoomJournal,
             participants::ParticipantService,
             peers::{build_peers_traffic_watcher, PeersService},
         },
@@ -348,6 +412,11 @@ mod test {
         let room_spec = RoomSpec {
             id: RoomId::from("test"),
             pipeline: Pipeline::new(HashMap::new()),
+            empty_timeout: None,
+            max_duration: None,
+            on_close: None,
+            max_members: None,
+            max_publishers: None,
         };
         let context = AppContext::new(
             Conf::default(),
@@ -359,12 +428,41 @@ mod test {
             peers: PeersService::new(
                 room_spec.id().clone(),
                 context.turn_service.clone(),
+                context.relay_usage.clone(),
                 build_peers_traffic_watcher(&conf::Media::default()),
                 &context.config.media,
+                &context.stats_handlers,
                 dummy_negotiation_sub_mock(),
+                RoomJournal::default(),
             ),
-            members: ParticipantService::new(&room_spec, &context).unwrap(),
+            members: ParticipantService::new(
+                &room_spec,
+                &context,
+                RoomJournal::default(),
+            )
+            .unwrap(),
             callbacks: context.callbacks.clone(),
+            relay_usage: context.relay_usage.clone(),
+            empty_timeout: None,
+            max_duration: None,
+            on_close: None,
+            emptiness_subscriber: None,
+            is_empty: true,
+            max_members: None,
+            max_publishers: None,
+            ice_policy: IceCandidatePolicy::default(),
+            rejected_ice_candidates: HashMap::new(),
+            relay_fallback_after: None,
+            call_recorder: CallRecorder::new(room_spec.id().clone()),
+            cdr: context.cdr.clone(),
+            cdr_callbacks: false,
+            negotiations: HashMap::new(),
+            negotiation_conf: conf::Negotiation::default(),
+            negotiation_timeouts: HashMap::new(),
+            recreated_peers: 0,
+            journal: RoomJournal::default(),
+            journal_dir: PathBuf::new(),
+            client_regions: Vec::new(),
         }
     }
 
@@ -380,6 +478,10 @@ mod test {
             None,
             None,
             None,
+            None,
+            false,
+            None,
+            false,
         );
 
         room.members
@@ -421,6 +523,10 @@ mod test {
             None,
             None,
             None,
+            None,
+            false,
+            None,
+            false,
         );
 
         room.members
@@ -450,8 +556,51 @@ mod test {
         ));
     }
 
+    #[actix_rt::test]
+    async fn resolves_member_region_by_client_ip() {
+        let mut room = empty_room();
+        room.client_regions = vec![conf::turn::ClientRegion {
+            name: String::from("eu"),
+            networks: vec!["10.0.0.0/8".parse().unwrap()],
+        }];
+        for (id, region) in &[("member1", None), ("member2", Some("us"))] {
+            let spec = MemberSpec::new(
+                Pipeline::new(HashMap::new()),
+                Credential::Plain(String::from("test")),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+                region.map(ToOwned::to_owned),
+                false,
+            );
+            room.members
+                .create_member(MemberId::from(*id), &spec)
+                .unwrap();
+        }
+        let connect = |room: &Room, id: &str, ip: &str| {
+            let member =
+                room.members.get_member_by_id(&MemberId::from(id)).unwrap();
+            member.set_client_region(room.client_region(ip.parse().unwrap()));
+            member.ice_region()
+        };
+
+        assert_eq!(
+            connect(&room, "member1", "10.1.2.3").as_deref(),
+            Some("eu")
+        );
+        assert_eq!(connect(&room, "member1", "11.1.2.3"), None);
+        assert_eq!(
+            connect(&room, "member2", "10.1.2.3").as_deref(),
+            Some("us")
+        );
+    }
+
     mod callbacks {
-        use std::convert::TryFrom;
+        use std::{convert::TryFrom, time::Duration};
 
         use actix::Addr;
         use medea_client_api_proto::{
@@ -471,6 +620,7 @@ mod test {
                     url::CallbackUrl,
                 },
                 member::Credential,
+                refs::{Fid, ToRoom},
                 RoomElement,
             },
         };
@@ -497,13 +647,22 @@ mod test {
                 credentials: Credential::Plain(String::from("test")),
                 on_leave,
                 on_join,
+                on_metrics: None,
                 idle_timeout: None,
                 reconnect_timeout: None,
                 ping_interval: None,
+                remove_on_leave: false,
+                region: None,
+                external: false,
             };
             RoomSpec {
                 id: RoomId::from("test"),
                 pipeline: Pipeline::new(hashmap! {id => member}),
+                empty_timeout: None,
+                max_duration: None,
+                on_close: None,
+                max_members: None,
+                max_publishers: None,
             }
         }
 
@@ -519,6 +678,7 @@ mod test {
                 &room_spec(with_on_join, with_on_leave),
                 &app_ctx,
                 build_peers_traffic_watcher(&app_ctx.config.media),
+                None,
             )
             .unwrap();
 
@@ -546,6 +706,7 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -578,6 +739,7 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(rpc_connection),
                 )
                 .await
@@ -585,6 +747,7 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -603,6 +766,7 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -625,6 +789,7 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -644,11 +809,34 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
                 .unwrap_err();
             }
         }
+
+        #[actix_rt::test]
+        #[serial]
+        async fn failed_callback_is_journaled() {
+            let mut room = empty_room();
+            room.journal = RoomJournal::new(10);
+
+            room.send_callback(
+                CallbackUrl::try_from(String::from("grpc://127.0.0.1:9099"))
+                    .unwrap(),
+                Fid::<ToRoom>::new(room.id.clone()).into(),
+                OnJoinEvent,
+            );
+            for _ in 0..50 {
+                if room.journal.has_errors() {
+                    break;
+                }
+                tokio::time::delay_for(Duration::from_millis(100)).await;
+            }
+
+            assert!(room.journal.has_errors());
+        }
     }
 }
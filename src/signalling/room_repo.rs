@@ -67,6 +67,13 @@ impl RoomRepository {
     pub fn contains_room_with_id(&self, id: &RoomId) -> bool {
         self.rooms.lock().unwrap().contains_key(id)
     }
+
+    /// Returns number of [`Room`]s in [`RoomRepository`].
+    #[inline]
+    #[must_use]
+    pub fn rooms_count(&self) -> usize {
+        self.rooms.lock().unwrap().len()
+    }
 }
 
 impl RpcServerRepository for RoomRepository {
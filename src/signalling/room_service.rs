@@ -1,9 +1,16 @@
 //! Service which provides CRUD actions for [`Room`].
 
-use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use actix::{
-    Actor, Addr, Context, Handler, MailboxError, Message, ResponseFuture,
+    Actor, ActorFuture as _, Addr, AsyncContext as _, Context, Handler,
+    MailboxError, Message, ResponseFuture, SpawnHandle, WrapFuture as _,
 };
 use derive_more::Display;
 use failure::Fail;
@@ -16,8 +23,9 @@ use redis::RedisError;
 
 use crate::{
     api::control::{
+        callback::{CallbackUrl, OnRoomClosedEvent, OnRoomClosedReason},
         endpoints::EndpointSpec,
-        load_static_specs_from_dir,
+        list_static_spec_files, load_from_file,
         member::Credential,
         refs::{Fid, StatefulFid, ToMember, ToRoom},
         EndpointId, LoadStaticControlSpecsError, MemberSpec, RoomSpec,
@@ -28,12 +36,14 @@ use crate::{
     signalling::{
         peers::{build_peers_traffic_watcher, PeerTrafficWatcher},
         room::{
-            Close, CreateEndpoint, CreateMember, Delete, RoomError,
-            SerializeProto,
+            Close, CreateEndpoint, CreateMember, Delete, DumpJournal,
+            RoomError, SerializeProto, UpdateMembers,
         },
         room_repo::RoomRepository,
         Room,
     },
+    tracing::{Span, SpanContext},
+    turn::coturn_metrics::CoturnMetricsService,
     AppContext,
 };
 
@@ -84,6 +94,10 @@ pub enum RoomServiceError {
         _1
     )]
     NotSameRoomIds(RoomId, RoomId),
+
+    /// Media server has reached its limit of [`Room`]s.
+    #[display(fmt = "Media server has reached its limit of {} Rooms", _0)]
+    RoomsLimitReached(usize),
 }
 
 impl From<RoomError> for RoomServiceError {
@@ -98,6 +112,34 @@ impl From<LoadStaticControlSpecsError> for RoomServiceError {
     }
 }
 
+/// State of a static [Control API] spec file watched by [`RoomService`].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug)]
+struct StaticSpec {
+    /// Last seen modification time of the spec file.
+    modified: SystemTime,
+
+    /// Last [`RoomSpec`] successfully applied from the spec file.
+    spec: Option<RoomSpec>,
+}
+
+/// Scheduled auto-removal tasks of a [`Room`].
+#[derive(Debug, Default)]
+struct RoomAutoRemoval {
+    /// [`RoomSpec::empty_timeout`] of the [`Room`].
+    empty_timeout: Option<Duration>,
+
+    /// URL to which `OnRoomClosed` Control API callback will be sent.
+    on_close: Option<CallbackUrl>,
+
+    /// Task closing the [`Room`] once its `empty_timeout` is reached.
+    empty_task: Option<SpawnHandle>,
+
+    /// Task closing the [`Room`] once its `max_duration` is reached.
+    max_duration_task: Option<SpawnHandle>,
+}
+
 /// Service for controlling [`Room`]s.
 pub struct RoomService {
     /// Repository that stores [`Room`]s addresses.
@@ -119,6 +161,20 @@ pub struct RoomService {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     static_specs_dir: String,
 
+    /// Interval of checking static [Control API] specs directory for changes.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    static_specs_watch_interval: Duration,
+
+    /// Static [Control API] spec files which [`Room`]s were started from.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    static_specs: HashMap<PathBuf, StaticSpec>,
+
+    /// Handle of the task watching static specs directory for changes, if it's
+    /// running.
+    static_specs_watcher: Option<SpawnHandle>,
+
     /// Public URL of server. Address for exposed [Client API].
     ///
     /// [Client API]: https://tinyurl.com/yx9thsnr
@@ -126,12 +182,21 @@ pub struct RoomService {
 
     /// [`PeerTrafficWatcher`] for all [`Room`]s of this [`RoomService`].
     peer_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
-    /* TODO: Enable in https://github.com/instrumentisto/medea/pull/91
-     * /// Service which is responsible for processing [`Peer`]'s metrics
-     * received /// from Coturn.
-     * ///
-     * /// [`Peer`]: crate::media::peer::Peer
-     * _coturn_metrics: Addr<CoturnMetricsService>, */
+
+    /// Scheduled auto-removal tasks of the [`Room`]s having auto-removal
+    /// rules.
+    auto_removals: HashMap<RoomId, RoomAutoRemoval>,
+
+    // TODO: Feed `PeerTrafficWatcher` with Coturn metrics in
+    //       https://github.com/instrumentisto/medea/pull/91
+    /// Service which is responsible for processing [`Peer`]'s metrics
+    /// received from Coturn.
+    ///
+    /// Started only if relay usage accounting is enabled, and used for it
+    /// only.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    _coturn_metrics: Option<Addr<CoturnMetricsService>>,
 }
 
 impl RoomService {
@@ -147,29 +212,177 @@ impl RoomService {
     ) -> Result<Self, RedisError> {
         let peer_traffic_watcher =
             build_peers_traffic_watcher(&app.config.media);
+        let coturn_metrics = if app.config.turn.relay_usage {
+            Some(
+                CoturnMetricsService::new(
+                    &app.config.turn,
+                    None,
+                    app.relay_usage.clone(),
+                )?
+                .start(),
+            )
+        } else {
+            None
+        };
         Ok(Self {
-            // TODO: Enable in https://github.com/instrumentisto/medea/pull/91
-            // _coturn_metrics: CoturnMetricsService::new(
-            //     &app.config.turn,
-            //     peer_traffic_watcher.clone(),
-            // )?
-            // .start(),
+            _coturn_metrics: coturn_metrics,
             static_specs_dir: app.config.control.static_specs_dir.clone(),
+            static_specs_watch_interval: app
+                .config
+                .control
+                .static_specs_watch_interval,
+            static_specs: HashMap::new(),
+            static_specs_watcher: None,
             public_url: app.config.server.client.http.public_url.clone(),
             peer_traffic_watcher,
+            auto_removals: HashMap::new(),
             room_repo,
             app,
             graceful_shutdown,
         })
     }
 
+    /// Starts new [`Room`] from the provided [`RoomSpec`], adds it to the
+    /// [`RoomRepository`] and schedules its auto-removal (if required).
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::RoomAlreadyExists`] if [`Room`] with
+    /// the same [`RoomId`] already exists.
+    ///
+    /// Errors with [`RoomServiceError::RoomError`] if [`Room`] fails to start.
+    fn start_room(
+        &mut self,
+        spec: &RoomSpec,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), RoomServiceError> {
+        if self.room_repo.contains_room_with_id(spec.id()) {
+            return Err(RoomServiceError::RoomAlreadyExists(
+                Fid::<ToRoom>::new(spec.id().clone()),
+            ));
+        }
+        if let Some(max_rooms) = self.app.config.limits.max_rooms() {
+            if self.room_repo.rooms_count() >= max_rooms {
+                return Err(RoomServiceError::RoomsLimitReached(max_rooms));
+            }
+        }
+
+        let room = Room::start(
+            spec,
+            &self.app,
+            self.peer_traffic_watcher.clone(),
+            Some(ctx.address().recipient()),
+        )?;
+
+        shutdown::subscribe(
+            &self.graceful_shutdown,
+            room.clone().recipient(),
+            shutdown::Priority(2),
+        );
+
+        debug!("New Room [id = {}] started.", spec.id());
+        self.room_repo.add(spec.id().clone(), room);
+
+        if spec.empty_timeout.is_some() || spec.max_duration.is_some() {
+            let mut auto_removal = RoomAutoRemoval {
+                empty_timeout: spec.empty_timeout,
+                on_close: spec.on_close.clone(),
+                ..RoomAutoRemoval::default()
+            };
+            // Newly created Room has no connected Members yet.
+            auto_removal.empty_task = spec.empty_timeout.map(|timeout| {
+                Self::schedule_auto_close(
+                    spec.id().clone(),
+                    timeout,
+                    OnRoomClosedReason::Empty,
+                    ctx,
+                )
+            });
+            auto_removal.max_duration_task =
+                spec.max_duration.map(|max_duration| {
+                    Self::schedule_auto_close(
+                        spec.id().clone(),
+                        max_duration,
+                        OnRoomClosedReason::MaxDuration,
+                        ctx,
+                    )
+                });
+            self.auto_removals.insert(spec.id().clone(), auto_removal);
+        }
+
+        Ok(())
+    }
+
+    /// Schedules closing of the [`Room`] with the provided [`RoomId`] after
+    /// the provided `timeout` because of the provided [`OnRoomClosedReason`].
+    fn schedule_auto_close(
+        room_id: RoomId,
+        timeout: Duration,
+        reason: OnRoomClosedReason,
+        ctx: &mut Context<Self>,
+    ) -> SpawnHandle {
+        ctx.run_later(timeout, move |this, ctx| {
+            this.auto_close_room(room_id, reason, ctx);
+        })
+    }
+
+    /// Closes the [`Room`] with the provided [`RoomId`] according to its
+    /// auto-removal rules and emits `OnRoomClosed` Control API callback.
+    fn auto_close_room(
+        &mut self,
+        room_id: RoomId,
+        reason: OnRoomClosedReason,
+        ctx: &mut Context<Self>,
+    ) {
+        info!(
+            "Closing Room [id = {}] automatically, reason: {:?}",
+            room_id, reason,
+        );
+        let on_close = self
+            .auto_removals
+            .get(&room_id)
+            .and_then(|r| r.on_close.clone());
+
+        let callbacks = self.app.callbacks.clone();
+        let fid = Fid::<ToRoom>::new(room_id.clone());
+        let close_room =
+            self.close_room(room_id, ctx).map(move |res| match res {
+                Ok(_) => {
+                    if let Some(url) = on_close {
+                        callbacks.do_send(
+                            url,
+                            fid.into(),
+                            OnRoomClosedEvent::new(reason),
+                        );
+                    }
+                }
+                Err(e) => error!(
+                    "Failed to close Room [id = {}] automatically: {:?}",
+                    fid, e,
+                ),
+            });
+        ctx.spawn(close_room.into_actor(self));
+    }
+
     /// Closes [`Room`] with provided [`RoomId`].
     ///
-    /// This is also deletes this [`Room`] from [`RoomRepository`].
+    /// This is also deletes this [`Room`] from [`RoomRepository`] and cancels
+    /// all its scheduled auto-removal tasks.
     fn close_room(
-        &self,
+        &mut self,
         id: RoomId,
+        ctx: &mut Context<Self>,
     ) -> LocalBoxFuture<'static, Result<(), MailboxError>> {
+        if let Some(auto_removal) = self.auto_removals.remove(&id) {
+            auto_removal
+                .empty_task
+                .into_iter()
+                .chain(auto_removal.max_duration_task)
+                .for_each(|task| {
+                    ctx.cancel_future(task);
+                });
+        }
+
         self.room_repo
             .get(&id)
             .map_or(future::ok(()).boxed_local(), |room| {
@@ -186,6 +399,292 @@ impl RoomService {
             })
     }
 
+    /// Checks whether `Member`s of the provided [`RoomSpec`] fit into the
+    /// default capacity limits of a [`Room`] (`limits.max_members_per_room`
+    /// and `limits.max_publishers_per_room`), unless they're overridden by
+    /// this [`RoomSpec`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError::TryFromElement`] if `Member`s of the
+    /// provided [`RoomSpec`] are malformed.
+    ///
+    /// Errors with [`RoomError::MembersLimitReached`] or
+    /// [`RoomError::PublishersLimitReached`] if `Member`s of the provided
+    /// [`RoomSpec`] exceed the limits.
+    fn ensure_default_capacity(
+        &self,
+        spec: &RoomSpec,
+    ) -> Result<(), RoomServiceError> {
+        let members =
+            spec.members().map_err(RoomServiceError::TryFromElement)?;
+        let limits = &self.app.config.limits;
+
+        let max_members =
+            spec.max_members.or_else(|| limits.max_members_per_room());
+        match max_members {
+            Some(max) if members.len() > max => {
+                return Err(RoomError::MembersLimitReached(max).into());
+            }
+            _ => {}
+        }
+        let max_publishers = spec
+            .max_publishers
+            .or_else(|| limits.max_publishers_per_room());
+        let publishers = members
+            .values()
+            .filter(|member| member.publish_endpoints().next().is_some())
+            .count();
+        match max_publishers {
+            Some(max) if publishers > max => {
+                Err(RoomError::PublishersLimitReached(max).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts new [`Room`] from the provided [`RoomSpec`] loaded from a static
+    /// [Control API] spec file.
+    ///
+    /// Unlike [`Room`]s created via [Control API], the default capacity limits
+    /// of a [`Room`] are not enforced for the static specs, so they're only
+    /// logged if exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError`] if [`Room`] fails to start.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn start_static_room(
+        &mut self,
+        spec: &RoomSpec,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), RoomServiceError> {
+        if let Err(e) = self.ensure_default_capacity(spec) {
+            warn!(
+                "Static spec of Room [id = {}] exceeds default limits, so \
+                 some of its Members may be unable to join: {}",
+                spec.id, e,
+            );
+        }
+        self.start_room(spec, ctx)
+    }
+
+    /// Synchronizes [`Room`]s with the static [Control API] spec files.
+    ///
+    /// [`Room`]s are started for the new spec files, updated for the changed
+    /// ones and closed for the removed ones. Spec files failed to be loaded
+    /// are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`LoadStaticControlSpecsError`] if static specs directory
+    /// cannot be read.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn sync_static_specs(
+        &mut self,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), LoadStaticControlSpecsError> {
+        let files = list_static_spec_files(&self.static_specs_dir)?;
+
+        let removed: Vec<_> = self
+            .static_specs
+            .keys()
+            .filter(|path| !files.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            if let Some(StaticSpec {
+                spec: Some(spec), ..
+            }) = self.static_specs.remove(&path)
+            {
+                info!(
+                    "Static spec {} removed, closing Room [id = {}].",
+                    path.display(),
+                    spec.id,
+                );
+                self.spawn_close_room(spec.id, ctx);
+            }
+        }
+
+        for (path, modified) in files {
+            let old = match self.static_specs.get_mut(&path) {
+                Some(static_spec) if static_spec.modified == modified => {
+                    continue;
+                }
+                Some(static_spec) => {
+                    static_spec.modified = modified;
+                    static_spec.spec.clone()
+                }
+                None => {
+                    self.static_specs.insert(
+                        path.clone(),
+                        StaticSpec {
+                            modified,
+                            spec: None,
+                        },
+                    );
+                    None
+                }
+            };
+
+            let new = match load_from_file(&path) {
+                Ok(spec) => spec,
+                Err(e) => {
+                    error!(
+                        "Failed to load static spec {}: {}",
+                        path.display(),
+                        e,
+                    );
+                    continue;
+                }
+            };
+            if old.as_ref() == Some(&new) {
+                continue;
+            }
+
+            if let Err(e) = self.apply_static_spec(path.clone(), old, new, ctx)
+            {
+                error!("Failed to apply static spec {}: {}", path.display(), e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the `new` [`RoomSpec`] loaded from a static [Control API] spec
+    /// file, which previously contained the `old` [`RoomSpec`].
+    ///
+    /// Changes in `Member`s are applied to the running [`Room`] in place,
+    /// while any other changes recreate the [`Room`].
+    ///
+    /// The `new` [`RoomSpec`] is remembered for the spec file at the provided
+    /// `path` only once the [`Room`] is actually running it. If `Member`s
+    /// fail to be updated in place, the [`Room`] is recreated.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomServiceError`] if new [`Room`] cannot be started.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    fn apply_static_spec(
+        &mut self,
+        path: PathBuf,
+        old: Option<RoomSpec>,
+        new: RoomSpec,
+        ctx: &mut Context<Self>,
+    ) -> Result<(), RoomServiceError> {
+        let old = match old {
+            Some(old) if self.room_repo.contains_room_with_id(&old.id) => old,
+            _ => {
+                self.start_static_room(&new, ctx)?;
+                info!("Room [id = {}] started from static spec.", new.id);
+                self.store_static_spec(&path, Some(new));
+                return Ok(());
+            }
+        };
+
+        let only_members_changed = RoomSpec {
+            pipeline: new.pipeline.clone(),
+            ..old.clone()
+        } == new;
+        if !only_members_changed {
+            info!("Recreating Room [id = {}] from static spec.", new.id);
+            self.spawn_recreate_room(old.id, path, new, ctx);
+            return Ok(());
+        }
+
+        let (deleted, created) = old.members_diff(&new);
+        let mut members =
+            new.members().map_err(RoomServiceError::TryFromElement)?;
+        let update = UpdateMembers {
+            deleted: deleted.into_iter().collect(),
+            created: created
+                .into_iter()
+                .filter_map(|id| members.remove_entry(&id))
+                .collect(),
+        };
+        info!(
+            "Updating Members of Room [id = {}] from static spec.",
+            new.id
+        );
+        if let Some(room) = self.room_repo.get(&new.id) {
+            ctx.spawn(room.send(update).into_actor(self).map(
+                move |res, this, ctx| {
+                    let err = match res {
+                        Ok(Ok(_)) => {
+                            this.store_static_spec(&path, Some(new));
+                            return;
+                        }
+                        Ok(Err(e)) => e.to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    // Some of the Members may be updated already, so the Room
+                    // is recreated to not leave it running neither the old
+                    // spec nor the new one.
+                    error!(
+                        "Failed to update Members of Room [id = {}], \
+                         recreating it: {}",
+                        new.id, err,
+                    );
+                    this.spawn_recreate_room(new.id.clone(), path, new, ctx);
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Closes the [`Room`] with the provided `old_id` and starts it again from
+    /// the provided [`RoomSpec`] loaded from the static spec file at the
+    /// provided `path` in background.
+    ///
+    /// No [`RoomSpec`] is remembered for the spec file if the [`Room`] fails
+    /// to be started.
+    fn spawn_recreate_room(
+        &mut self,
+        old_id: RoomId,
+        path: PathBuf,
+        new: RoomSpec,
+        ctx: &mut Context<Self>,
+    ) {
+        let close_room = self.close_room(old_id.clone(), ctx);
+        ctx.spawn(close_room.into_actor(self).map(move |res, this, ctx| {
+            if let Err(e) = res {
+                error!("Failed to close Room [id = {}]: {}", old_id, e);
+            }
+            match this.start_static_room(&new, ctx) {
+                Ok(_) => this.store_static_spec(&path, Some(new)),
+                Err(e) => {
+                    error!("Failed to start Room [id = {}]: {}", new.id, e);
+                    this.store_static_spec(&path, None);
+                }
+            }
+        }));
+    }
+
+    /// Remembers the provided [`RoomSpec`] as the last one applied from the
+    /// static spec file at the provided `path`.
+    ///
+    /// [`None`] means that no [`Room`] is running the spec file at the moment.
+    fn store_static_spec(&mut self, path: &Path, spec: Option<RoomSpec>) {
+        if let Some(static_spec) = self.static_specs.get_mut(path) {
+            static_spec.spec = spec;
+        }
+    }
+
+    /// Closes [`Room`] with provided [`RoomId`] in background, logging any
+    /// errors.
+    fn spawn_close_room(&mut self, id: RoomId, ctx: &mut Context<Self>) {
+        let close_room = self.close_room(id.clone(), ctx).map(move |res| {
+            if let Err(e) = res {
+                error!("Failed to close Room [id = {}]: {}", id, e);
+            }
+        });
+        ctx.spawn(close_room.into_actor(self));
+    }
+
     /// Returns [Control API] sid based on provided arguments and
     /// `MEDEA_SERVER__CLIENT__HTTP__PUBLIC_URL` config value.
     ///
@@ -226,37 +725,46 @@ pub struct StartStaticRooms;
 impl Handler<StartStaticRooms> for RoomService {
     type Result = Result<(), RoomServiceError>;
 
+    /// Starts [`Room`]s from all static specs and begins watching static specs
+    /// directory for changes (if enabled and not watching already).
     fn handle(
         &mut self,
         _: StartStaticRooms,
-        _: &mut Self::Context,
+        ctx: &mut Self::Context,
     ) -> Self::Result {
-        let room_specs = load_static_specs_from_dir(&self.static_specs_dir)?;
-
-        for spec in room_specs {
-            if self.room_repo.contains_room_with_id(spec.id()) {
-                return Err(RoomServiceError::RoomAlreadyExists(
-                    Fid::<ToRoom>::new(spec.id),
-                ));
-            }
+        if self.static_specs_watcher.is_none()
+            && self.static_specs_watch_interval > Duration::from_secs(0)
+        {
+            self.static_specs_watcher = Some(ctx.run_interval(
+                self.static_specs_watch_interval,
+                |this, ctx| {
+                    if let Err(e) = this.sync_static_specs(ctx) {
+                        debug!("Failed to sync static specs: {}", e);
+                    }
+                },
+            ));
+        }
 
-            let room_id = spec.id().clone();
+        Ok(self.sync_static_specs(ctx)?)
+    }
+}
 
-            let room = Room::start(
-                &spec,
-                &self.app,
-                self.peer_traffic_watcher.clone(),
-            )?;
+/// Signal for synchronizing [`Room`]s with the static specs right away.
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct SyncStaticSpecs;
 
-            shutdown::subscribe(
-                &self.graceful_shutdown,
-                room.clone().recipient(),
-                shutdown::Priority(2),
-            );
+impl Handler<SyncStaticSpecs> for RoomService {
+    type Result = Result<(), RoomServiceError>;
 
-            self.room_repo.add(room_id, room);
-        }
-        Ok(())
+    /// Starts, updates and closes [`Room`]s according to the changes in static
+    /// specs directory.
+    fn handle(
+        &mut self,
+        _: SyncStaticSpecs,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        Ok(self.sync_static_specs(ctx)?)
     }
 }
 
@@ -273,6 +781,9 @@ pub struct CreateRoom {
     ///
     /// [Control API]: https://tinyurl.com/yxsqplq7
     pub spec: RoomSpec,
+
+    /// [`SpanContext`] of the traced operation requested this creation.
+    pub trace: Option<SpanContext>,
 }
 
 impl Handler<CreateRoom> for RoomService {
@@ -281,9 +792,12 @@ impl Handler<CreateRoom> for RoomService {
     fn handle(
         &mut self,
         msg: CreateRoom,
-        _: &mut Self::Context,
+        ctx: &mut Self::Context,
     ) -> Self::Result {
         let room_spec = msg.spec;
+        let span = Span::with_parent("room_service.create_room", msg.trace)
+            .attr("room.id", room_spec.id());
+        let _entered = span.enter();
         let sid = match room_spec.members() {
             Ok(members) => members
                 .iter()
@@ -299,26 +813,8 @@ impl Handler<CreateRoom> for RoomService {
             Err(e) => return Err(RoomServiceError::TryFromElement(e)),
         };
 
-        if self.room_repo.get(&room_spec.id).is_some() {
-            return Err(RoomServiceError::RoomAlreadyExists(
-                Fid::<ToRoom>::new(room_spec.id),
-            ));
-        }
-
-        let room_addr = Room::start(
-            &room_spec,
-            &self.app,
-            self.peer_traffic_watcher.clone(),
-        )?;
-
-        shutdown::subscribe(
-            &self.graceful_shutdown,
-            room_addr.clone().recipient(),
-            shutdown::Priority(2),
-        );
-
-        debug!("New Room [id = {}] started.", room_spec.id);
-        self.room_repo.add(room_spec.id, room_addr);
+        self.ensure_default_capacity(&room_spec)?;
+        self.start_room(&room_spec, ctx)?;
 
         Ok(sid)
     }
@@ -333,6 +829,9 @@ pub struct CreateMemberInRoom {
     pub id: MemberId,
     pub parent_fid: Fid<ToRoom>,
     pub spec: MemberSpec,
+
+    /// [`SpanContext`] of the traced operation requested this creation.
+    pub trace: Option<SpanContext>,
 }
 
 impl Handler<CreateMemberInRoom> for RoomService {
@@ -347,6 +846,9 @@ impl Handler<CreateMemberInRoom> for RoomService {
         let id = msg.id;
         let spec = msg.spec;
         let sid = self.get_sid(&room_id, &id, spec.credentials());
+        let span = Span::with_parent("room_service.create_member", msg.trace)
+            .attr("room.id", &room_id)
+            .attr("member.id", &id);
 
         self.room_repo.get(&room_id).map_or_else(
             || {
@@ -358,7 +860,7 @@ impl Handler<CreateMemberInRoom> for RoomService {
             |room| {
                 async move {
                     let id_str = id.to_string();
-                    room.send(CreateMember(id, spec))
+                    room.send(CreateMember(id, spec, span.context()))
                         .await
                         .map_err(RoomServiceError::RoomMailboxErr)??;
                     Ok(hashmap! {id_str => sid})
@@ -378,6 +880,9 @@ pub struct CreateEndpointInRoom {
     pub id: EndpointId,
     pub parent_fid: Fid<ToMember>,
     pub spec: EndpointSpec,
+
+    /// [`SpanContext`] of the traced operation requested this creation.
+    pub trace: Option<SpanContext>,
 }
 
 impl Handler<CreateEndpointInRoom> for RoomService {
@@ -391,6 +896,10 @@ impl Handler<CreateEndpointInRoom> for RoomService {
         let (room_id, member_id) = msg.parent_fid.take_all();
         let endpoint_id = msg.id;
         let spec = msg.spec;
+        let span = Span::with_parent("room_service.create_endpoint", msg.trace)
+            .attr("room.id", &room_id)
+            .attr("member.id", &member_id)
+            .attr("endpoint.id", &endpoint_id);
 
         self.room_repo.get(&room_id).map_or_else(
             || {
@@ -405,6 +914,7 @@ impl Handler<CreateEndpointInRoom> for RoomService {
                         member_id,
                         endpoint_id,
                         spec,
+                        trace: span.context(),
                     })
                     .await
                     .map_err(RoomServiceError::RoomMailboxErr)??;
@@ -432,6 +942,7 @@ impl DeleteElements<Unvalidated> {
     pub fn new() -> Self {
         Self {
             fids: Vec::new(),
+            trace: None,
             _validation_state: PhantomData,
         }
     }
@@ -441,6 +952,11 @@ impl DeleteElements<Unvalidated> {
         self.fids.push(fid)
     }
 
+    /// Sets [`SpanContext`] of the traced operation requested this deletion.
+    pub fn set_trace(&mut self, trace: Option<SpanContext>) {
+        self.trace = trace;
+    }
+
     /// Validates request. It must have at least one fid, all fids must share
     /// same [`RoomId`].
     ///
@@ -468,6 +984,7 @@ impl DeleteElements<Unvalidated> {
 
         Ok(DeleteElements {
             fids: self.fids,
+            trace: self.trace,
             _validation_state: PhantomData,
         })
     }
@@ -491,6 +1008,7 @@ impl DeleteElements<Unvalidated> {
 #[rtype(result = "Result<(), RoomServiceError>")]
 pub struct DeleteElements<T> {
     fids: Vec<StatefulFid>,
+    trace: Option<SpanContext>,
     _validation_state: PhantomData<T>,
 }
 
@@ -503,8 +1021,18 @@ impl Handler<DeleteElements<Validated>> for RoomService {
     fn handle(
         &mut self,
         msg: DeleteElements<Validated>,
-        _: &mut Self::Context,
+        ctx: &mut Self::Context,
     ) -> Self::Result {
+        let span = Span::with_parent("room_service.delete_elements", msg.trace)
+            .attr(
+                "fids",
+                msg.fids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        let _entered = span.enter();
         let mut deletes_from_room: Vec<StatefulFid> = Vec::new();
 
         // TODO: use Vec::drain_filter when it will be in stable
@@ -513,7 +1041,7 @@ impl Handler<DeleteElements<Validated>> for RoomService {
                 .into_iter()
                 .filter_map(|fid| {
                     if let StatefulFid::Room(room_id) = fid {
-                        Some(self.close_room(room_id.take_room_id()))
+                        Some(self.close_room(room_id.take_room_id(), ctx))
                     } else {
                         deletes_from_room.push(fid);
                         None
@@ -532,7 +1060,7 @@ impl Handler<DeleteElements<Validated>> for RoomService {
             self.room_repo.get(&room_id).map_or_else(
                 || future::ok(()).boxed_local(),
                 |room| {
-                    room.send(Delete(deletes_from_room))
+                    room.send(Delete(deletes_from_room, span.context()))
                         .map_ok(|_| ())
                         .map_err(RoomServiceError::RoomMailboxErr)
                         .err_into()
@@ -545,6 +1073,56 @@ impl Handler<DeleteElements<Validated>> for RoomService {
     }
 }
 
+/// Notification of a [`Room`] about becoming empty (having no connected
+/// `Member`s) or non-empty.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RoomEmptinessChanged {
+    /// [`RoomId`] of the [`Room`] which emptiness has changed.
+    pub room_id: RoomId,
+
+    /// Indicator whether the [`Room`] has no connected `Member`s now.
+    pub is_empty: bool,
+}
+
+impl Handler<RoomEmptinessChanged> for RoomService {
+    type Result = ();
+
+    /// Schedules closing of the empty [`Room`] after its `empty_timeout`, or
+    /// cancels the scheduled closing once some `Member` connects to it.
+    fn handle(
+        &mut self,
+        msg: RoomEmptinessChanged,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let auto_removal = if let Some(auto_removal) =
+            self.auto_removals.get_mut(&msg.room_id)
+        {
+            auto_removal
+        } else {
+            return;
+        };
+
+        if let Some(task) = auto_removal.empty_task.take() {
+            ctx.cancel_future(task);
+        }
+        if msg.is_empty {
+            if let Some(timeout) = auto_removal.empty_timeout {
+                debug!(
+                    "Room [id = {}] became empty, it will be closed in {:?}.",
+                    msg.room_id, timeout,
+                );
+                auto_removal.empty_task = Some(Self::schedule_auto_close(
+                    msg.room_id,
+                    timeout,
+                    OnRoomClosedReason::Empty,
+                    ctx,
+                ));
+            }
+        }
+    }
+}
+
 /// Serialized to protobuf `Element`s which will be returned from [`Get`] on
 /// success result.
 type SerializedElements = HashMap<StatefulFid, proto::Element>;
@@ -597,6 +1175,53 @@ impl Handler<Get> for RoomService {
     }
 }
 
+/// Message which returns journals of [`Room`]s, serialized as JSON arrays of
+/// their entries, by the provided [`Fid`]s.
+///
+/// [`Fid`]s of `Member`s and `Endpoint`s refer to journals of their
+/// [`Room`]s.
+#[derive(Message)]
+#[rtype(result = "Result<HashMap<RoomId, String>, RoomServiceError>")]
+pub struct GetJournals(pub Vec<StatefulFid>);
+
+impl Handler<GetJournals> for RoomService {
+    type Result =
+        ResponseFuture<Result<HashMap<RoomId, String>, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: GetJournals,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let mut rooms = HashMap::new();
+        for fid in msg.0 {
+            let room_id = fid.room_id();
+            if rooms.contains_key(room_id) {
+                continue;
+            }
+            if let Some(room) = self.room_repo.get(room_id) {
+                rooms.insert(room_id.clone(), room);
+            } else {
+                return future::err(RoomServiceError::RoomNotFound(fid.into()))
+                    .boxed_local();
+            }
+        }
+
+        async {
+            let mut journals = HashMap::new();
+            for (room_id, room) in rooms {
+                let journal = room
+                    .send(DumpJournal)
+                    .await
+                    .map_err(RoomServiceError::RoomMailboxErr)?;
+                journals.insert(room_id, journal);
+            }
+            Ok(journals)
+        }
+        .boxed_local()
+    }
+}
+
 #[cfg(test)]
 mod delete_elements_validation_specs {
     use std::convert::TryFrom as _;
@@ -694,16 +1319,27 @@ mod room_service_specs {
     /// Returns [`AppContext`] with default [`Conf`] and mocked
     /// [`TurnAuthService`].
     fn app_ctx() -> AppContext {
+        app_ctx_with_conf(Conf::default())
+    }
+
+    /// Returns [`AppContext`] with the provided [`Conf`] and mocked
+    /// [`TurnAuthService`].
+    fn app_ctx_with_conf(conf: Conf) -> AppContext {
         let turn_service = crate::turn::new_turn_auth_service_mock();
-        AppContext::new(Conf::default(), turn_service)
+        AppContext::new(conf, turn_service)
     }
 
     /// Returns [`Addr`] to [`RoomService`].
     fn room_service(room_repo: RoomRepository) -> Addr<RoomService> {
-        let conf = Conf::default();
-        let shutdown_timeout = conf.shutdown.timeout;
+        room_service_with_app(room_repo, app_ctx())
+    }
 
-        let app = app_ctx();
+    /// Returns [`Addr`] to [`RoomService`] with the provided [`AppContext`].
+    fn room_service_with_app(
+        room_repo: RoomRepository,
+        app: AppContext,
+    ) -> Addr<RoomService> {
+        let shutdown_timeout = app.config.shutdown.timeout;
         let graceful_shutdown = GracefulShutdown::new(shutdown_timeout).start();
 
         RoomService::new(room_repo, app, graceful_shutdown)
@@ -753,7 +1389,7 @@ mod room_service_specs {
 
         test_for_create!(
             room_service,
-            CreateRoom { spec },
+            CreateRoom { spec, trace: None },
             caller_fid,
             |member_el| {
                 assert_eq!(member_el.get_member().get_pipeline().len(), 1);
@@ -777,6 +1413,7 @@ mod room_service_specs {
             &spec,
             &app_ctx(),
             build_peers_traffic_watcher(&conf::Media::default()),
+            None,
         )
         .unwrap();
         let room_service = room_service(RoomRepository::from(hashmap!(
@@ -796,6 +1433,7 @@ mod room_service_specs {
                 id: member_id,
                 spec: member_spec,
                 parent_fid: member_parent_fid,
+                trace: None,
             },
             member_full_id,
             |member_el| {
@@ -825,6 +1463,7 @@ mod room_service_specs {
             &spec,
             &app_ctx(),
             build_peers_traffic_watcher(&conf::Media::default()),
+            None,
         )
         .unwrap();
         let room_service = room_service(RoomRepository::from(hashmap!(
@@ -845,6 +1484,7 @@ mod room_service_specs {
                 id: endpoint_id,
                 spec: endpoint_spec,
                 parent_fid: endpoint_parent_fid,
+                trace: None,
             },
             endpoint_full_id,
             |endpoint_el| {
@@ -892,6 +1532,7 @@ mod room_service_specs {
             &room_spec(),
             &app_ctx(),
             build_peers_traffic_watcher(&conf::Media::default()),
+            None,
         )
         .unwrap();
         let room_service = room_service(RoomRepository::from(hashmap!(
@@ -913,6 +1554,7 @@ mod room_service_specs {
             &room_spec(),
             &app_ctx(),
             build_peers_traffic_watcher(&conf::Media::default()),
+            None,
         )
         .unwrap();
         let room_service = room_service(RoomRepository::from(hashmap!(
@@ -935,6 +1577,7 @@ mod room_service_specs {
             &room_spec(),
             &app_ctx(),
             build_peers_traffic_watcher(&conf::Media::default()),
+            None,
         )
         .unwrap();
         let room_service = room_service(RoomRepository::from(hashmap!(
@@ -943,4 +1586,451 @@ mod room_service_specs {
 
         test_for_delete_and_get(room_service, endpoint_fid).await;
     }
+
+    /// Checks that [`Room`] is closed by [`RoomService`] once the provided
+    /// [`RoomSpec`] lifetime rule is reached.
+    async fn test_room_auto_removal(spec: RoomSpec) {
+        let room_id = spec.id.clone();
+        let room_fid = StatefulFid::from(Fid::<ToRoom>::new(room_id));
+        let room_service = room_service(RoomRepository::new());
+
+        room_service
+            .send(CreateRoom { spec, trace: None })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(room_service
+            .send(Get(vec![room_fid.clone()]))
+            .await
+            .unwrap()
+            .is_ok());
+
+        tokio::time::delay_for(Duration::from_millis(300)).await;
+
+        assert!(room_service
+            .send(Get(vec![room_fid]))
+            .await
+            .unwrap()
+            .is_err());
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn room_closed_on_empty_timeout() {
+        let mut spec = room_spec();
+        spec.empty_timeout = Some(Duration::from_millis(50));
+
+        test_room_auto_removal(spec).await;
+    }
+
+    #[actix_rt::test]
+    async fn room_closed_on_max_duration() {
+        let mut spec = room_spec();
+        spec.max_duration = Some(Duration::from_millis(50));
+
+        test_room_auto_removal(spec).await;
+    }
+
+    #[actix_rt::test]
+    async fn rooms_limit_is_enforced() {
+        let mut conf = Conf::default();
+        conf.limits.max_rooms = 1;
+        let room_service = room_service_with_app(
+            RoomRepository::new(),
+            app_ctx_with_conf(conf),
+        );
+
+        let spec = room_spec();
+        let mut another_spec = room_spec();
+        another_spec.id = "another-room".to_string().into();
+
+        room_service
+            .send(CreateRoom { spec, trace: None })
+            .await
+            .unwrap()
+            .unwrap();
+        match room_service
+            .send(CreateRoom {
+                spec: another_spec,
+                trace: None,
+            })
+            .await
+        {
+            Ok(Err(RoomServiceError::RoomsLimitReached(1))) => (),
+            _ => panic!("Expected RoomsLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn members_limit_is_enforced() {
+        let mut spec = room_spec();
+        spec.max_members = Some(2);
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&"responder".to_string().into())
+            .unwrap()
+            .clone();
+
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+            None,
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::from(hashmap!(
+            room_id.clone() => room,
+        )));
+
+        let res = room_service
+            .send(CreateMemberInRoom {
+                id: "test-member".to_string().into(),
+                spec: member_spec,
+                parent_fid: Fid::<ToRoom>::new(room_id),
+                trace: None,
+            })
+            .await
+            .unwrap();
+        match res {
+            Err(RoomServiceError::RoomError(
+                RoomError::MembersLimitReached(2),
+            )) => (),
+            _ => panic!("Expected MembersLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn members_limit_is_enforced_on_spec_update() {
+        let mut spec = room_spec();
+        spec.max_members = Some(2);
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&"responder".to_string().into())
+            .unwrap()
+            .clone();
+
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+            None,
+        )
+        .unwrap();
+
+        let res = room
+            .send(UpdateMembers {
+                deleted: Vec::new(),
+                created: hashmap!(
+                    "test-member".to_string().into() => member_spec,
+                ),
+            })
+            .await
+            .unwrap();
+        match res {
+            Err(RoomError::MembersLimitReached(2)) => (),
+            _ => panic!("Expected MembersLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn members_limit_is_enforced_on_room_start() {
+        let mut spec = room_spec();
+        spec.max_members = Some(1);
+
+        let res = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+            None,
+        );
+        match res {
+            Err(RoomError::MembersLimitReached(1)) => (),
+            _ => panic!("Expected MembersLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn default_members_limit_is_enforced_on_room_creation() {
+        let mut conf = Conf::default();
+        conf.limits.max_members_per_room = 1;
+        let room_service = room_service_with_app(
+            RoomRepository::new(),
+            app_ctx_with_conf(conf),
+        );
+
+        let res = room_service
+            .send(CreateRoom {
+                spec: room_spec(),
+                trace: None,
+            })
+            .await
+            .unwrap();
+        match res {
+            Err(RoomServiceError::RoomError(
+                RoomError::MembersLimitReached(1),
+            )) => (),
+            _ => panic!("Expected MembersLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn default_members_limit_is_not_enforced_on_static_specs() {
+        let specs_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            specs_dir.path().join("room.yml"),
+            include_str!("../../tests/specs/pub-sub-video-call.yml"),
+        )
+        .unwrap();
+        let mut conf = Conf::default();
+        conf.limits.max_members_per_room = 1;
+
+        let room_service =
+            static_room_service_with_conf(specs_dir.path(), conf);
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+
+        assert!(room_exists(&room_service, "pub-sub-video-call").await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn publishers_limit_is_enforced() {
+        let mut conf = Conf::default();
+        conf.limits.max_publishers_per_room = 1;
+        let app = app_ctx_with_conf(conf);
+
+        let spec = room_spec();
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&"caller".to_string().into())
+            .unwrap()
+            .clone();
+
+        let room_id: RoomId = "pub-sub-video-call".to_string().into();
+        let room = Room::start(
+            &spec,
+            &app,
+            build_peers_traffic_watcher(&conf::Media::default()),
+            None,
+        )
+        .unwrap();
+        let room_service = room_service_with_app(
+            RoomRepository::from(hashmap!(room_id.clone() => room)),
+            app,
+        );
+
+        let res = room_service
+            .send(CreateMemberInRoom {
+                id: "test-member".to_string().into(),
+                spec: member_spec,
+                parent_fid: Fid::<ToRoom>::new(room_id),
+                trace: None,
+            })
+            .await
+            .unwrap();
+        match res {
+            Err(RoomServiceError::RoomError(
+                RoomError::PublishersLimitReached(1),
+            )) => (),
+            _ => panic!("Expected PublishersLimitReached error"),
+        }
+
+        actix::System::current().stop();
+    }
+
+    /// Returns [`Addr`] to [`RoomService`] watching the provided static specs
+    /// directory.
+    fn static_room_service(specs_dir: &std::path::Path) -> Addr<RoomService> {
+        static_room_service_with_conf(specs_dir, Conf::default())
+    }
+
+    /// Returns [`Addr`] to [`RoomService`] with the provided [`Conf`] watching
+    /// the provided static specs directory.
+    fn static_room_service_with_conf(
+        specs_dir: &std::path::Path,
+        mut conf: Conf,
+    ) -> Addr<RoomService> {
+        conf.control.static_specs_dir = specs_dir.to_str().unwrap().to_owned();
+        room_service_with_app(RoomRepository::new(), app_ctx_with_conf(conf))
+    }
+
+    /// Checks whether [`Room`] with the provided [`RoomId`] exists in the
+    /// provided [`RoomService`].
+    async fn room_exists(room_service: &Addr<RoomService>, id: &str) -> bool {
+        let fid = StatefulFid::from(Fid::<ToRoom>::new(id.to_string().into()));
+        room_service.send(Get(vec![fid])).await.unwrap().is_ok()
+    }
+
+    /// Checks whether `Member` with the provided ID exists in the `room`
+    /// [`Room`] of the provided [`RoomService`].
+    async fn member_exists(room_service: &Addr<RoomService>, id: &str) -> bool {
+        let fid = StatefulFid::try_from(format!("room/{}", id)).unwrap();
+        room_service.send(Get(vec![fid])).await.unwrap().is_ok()
+    }
+
+    #[actix_rt::test]
+    async fn syncs_static_specs_of_all_formats() {
+        let specs_dir = tempfile::tempdir().unwrap();
+        let yaml_spec =
+            include_str!("../../tests/specs/pub-sub-video-call.yml");
+        let yaml_path = specs_dir.path().join("yaml-room.yml");
+        std::fs::write(&yaml_path, yaml_spec).unwrap();
+        std::fs::write(
+            specs_dir.path().join("json-room.json"),
+            r#"{"kind": "Room", "id": "json-room", "spec": {"pipeline": {
+                "member": {"kind": "Member", "credentials": {"plain": "test"},
+                           "spec": {"pipeline": {}}}
+            }}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            specs_dir.path().join("toml-room.toml"),
+            r#"
+                kind = "Room"
+                id = "toml-room"
+                [spec.pipeline.member]
+                kind = "Member"
+                credentials = { plain = "test" }
+                spec = { pipeline = {} }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(specs_dir.path().join("broken.yml"), "kind: Foo")
+            .unwrap();
+        std::fs::write(specs_dir.path().join("ignored.txt"), "not a spec")
+            .unwrap();
+
+        let room_service = static_room_service(specs_dir.path());
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+
+        assert!(room_exists(&room_service, "pub-sub-video-call").await);
+        assert!(room_exists(&room_service, "json-room").await);
+        assert!(room_exists(&room_service, "toml-room").await);
+
+        std::fs::remove_file(&yaml_path).unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert!(!room_exists(&room_service, "pub-sub-video-call").await);
+        assert!(room_exists(&room_service, "json-room").await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn applies_changed_static_spec() {
+        let specs_dir = tempfile::tempdir().unwrap();
+        let spec_path = specs_dir.path().join("room.yml");
+        let spec = include_str!("../../tests/specs/pub-sub-video-call.yml");
+        let caller_only = spec.split("    responder:").next().unwrap();
+        std::fs::write(&spec_path, caller_only).unwrap();
+
+        let room_service = static_room_service(specs_dir.path());
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+        let responder_fid = StatefulFid::try_from(
+            "pub-sub-video-call/responder/play".to_string(),
+        )
+        .unwrap();
+        assert!(room_service
+            .send(Get(vec![responder_fid.clone()]))
+            .await
+            .unwrap()
+            .is_err());
+
+        std::fs::write(&spec_path, spec).unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        assert!(room_service
+            .send(Get(vec![responder_fid]))
+            .await
+            .unwrap()
+            .is_ok());
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn restarts_room_after_failed_static_spec_recreation() {
+        const SPEC: &str = r#"{"kind": "Room", "id": "room", %LIMIT%
+            "spec": {"pipeline": {
+                "alice": {"kind": "Member", "credentials": {"plain": "test"},
+                          "spec": {"pipeline": {}}},
+                "bob": {"kind": "Member", "credentials": {"plain": "test"},
+                        "spec": {"pipeline": {}}}
+            }}}"#;
+        let specs_dir = tempfile::tempdir().unwrap();
+        let spec_path = specs_dir.path().join("room.json");
+        std::fs::write(&spec_path, SPEC.replace("%LIMIT%", "")).unwrap();
+
+        let room_service = static_room_service(specs_dir.path());
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+        assert!(room_exists(&room_service, "room").await);
+
+        std::fs::write(
+            &spec_path,
+            SPEC.replace("%LIMIT%", r#""max_members": 1,"#),
+        )
+        .unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+        assert!(!room_exists(&room_service, "room").await);
+
+        std::fs::write(&spec_path, SPEC.replace("%LIMIT%", "")).unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        assert!(room_exists(&room_service, "room").await);
+
+        actix::System::current().stop();
+    }
+
+    #[actix_rt::test]
+    async fn recreates_room_after_failed_static_spec_members_update() {
+        const SPEC: &str = r#"{"kind": "Room", "id": "room", "max_members": 2,
+            "spec": {"pipeline": {%MEMBERS%}}}"#;
+        const MEMBER: &str = r#""kind": "Member",
+            "credentials": {"plain": "test"}, "spec": {"pipeline": {}}"#;
+        let members = |ids: &[&str]| {
+            let members: Vec<_> = ids
+                .iter()
+                .map(|id| format!(r#""{}": {{{}}}"#, id, MEMBER))
+                .collect();
+            SPEC.replace("%MEMBERS%", &members.join(","))
+        };
+        let specs_dir = tempfile::tempdir().unwrap();
+        let spec_path = specs_dir.path().join("room.json");
+        std::fs::write(&spec_path, members(&["alice", "bob"])).unwrap();
+
+        let room_service = static_room_service(specs_dir.path());
+        room_service.send(StartStaticRooms).await.unwrap().unwrap();
+        assert!(member_exists(&room_service, "bob").await);
+
+        std::fs::write(&spec_path, members(&["alice", "carol", "dave"]))
+            .unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+        assert!(!room_exists(&room_service, "room").await);
+
+        std::fs::write(&spec_path, members(&["alice", "carol"])).unwrap();
+        room_service.send(SyncStaticSpecs).await.unwrap().unwrap();
+        assert!(member_exists(&room_service, "alice").await);
+        assert!(member_exists(&room_service, "carol").await);
+        assert!(!member_exists(&room_service, "bob").await);
+
+        actix::System::current().stop();
+    }
 }
@@ -0,0 +1,226 @@
+//! Exporter of [`Span`]s to [OpenTelemetry] collector via [OTLP]/HTTP.
+//!
+//! [`Span`]: crate::tracing::Span
+//! [OpenTelemetry]: https://opentelemetry.io
+//! [OTLP]: https://opentelemetry.io/docs/specs/otlp
+
+use std::{
+    mem,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use actix::Arbiter;
+use actix_web::client::Client;
+use futures::{channel::mpsc, stream, StreamExt as _};
+use serde_json::{json, Value};
+
+use crate::{
+    conf,
+    log::prelude::*,
+    tracing::{SpanData, SpanKind},
+};
+
+/// Exporter of finished [`Span`]s to [OTLP]/HTTP endpoint in JSON encoding.
+///
+/// [`Span`]: crate::tracing::Span
+/// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+#[derive(Debug)]
+pub struct OtlpExporter {
+    /// URL of [OTLP]/HTTP traces endpoint.
+    ///
+    /// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+    endpoint: String,
+
+    /// Name of this service reported in exported spans.
+    service_name: String,
+
+    /// Interval of exporting collected spans.
+    export_interval: Duration,
+
+    /// Maximum number of spans exported at once.
+    max_batch_size: usize,
+
+    /// Timeout of a single export request.
+    export_timeout: Duration,
+}
+
+impl OtlpExporter {
+    /// Creates new [`OtlpExporter`] exporting to the provided `endpoint`.
+    #[must_use]
+    pub fn new(endpoint: String, cf: &conf::Tracing) -> Self {
+        Self {
+            endpoint,
+            service_name: cf.service_name.clone(),
+            export_interval: cf.export_interval,
+            max_batch_size: cf.max_batch_size.max(1),
+            export_timeout: cf.export_timeout,
+        }
+    }
+
+    /// Spawns exporting of [`SpanData`]s received from the provided `spans`
+    /// channel in the current [`Arbiter`].
+    ///
+    /// Spans are exported in batches every [`OtlpExporter::export_interval`]
+    /// or once [`OtlpExporter::max_batch_size`] of them is collected. Failed
+    /// batches are logged and dropped.
+    pub fn spawn(self, spans: mpsc::UnboundedReceiver<SpanData>) {
+        Arbiter::spawn(async move {
+            let client = Client::default();
+            let ticks =
+                tokio::time::interval(self.export_interval).map(|_| None);
+            let mut events = stream::select(spans.map(Some), ticks);
+
+            let mut batch = Vec::new();
+            while let Some(event) = events.next().await {
+                if let Some(span) = event {
+                    batch.push(span);
+                    if batch.len() < self.max_batch_size {
+                        continue;
+                    }
+                }
+                if !batch.is_empty() {
+                    self.export(&client, mem::take(&mut batch)).await;
+                }
+            }
+        });
+    }
+
+    /// Sends the provided `batch` of [`SpanData`]s to the
+    /// [`OtlpExporter::endpoint`].
+    async fn export(&self, client: &Client, batch: Vec<SpanData>) {
+        let count = batch.len();
+        let res = client
+            .post(&self.endpoint)
+            .timeout(self.export_timeout)
+            .send_json(&self.encode(batch))
+            .await;
+        match res {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => warn!(
+                "Collector rejected {} tracing spans with status {}",
+                count,
+                resp.status(),
+            ),
+            Err(e) => warn!("Failed to export {} tracing spans: {}", count, e),
+        }
+    }
+
+    /// Encodes the provided [`SpanData`]s as [OTLP] JSON
+    /// `ExportTraceServiceRequest`.
+    ///
+    /// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+    fn encode(&self, batch: Vec<SpanData>) -> Value {
+        let spans: Vec<_> = batch.into_iter().map(encode_span).collect();
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [
+                        attribute("service.name", &self.service_name),
+                    ],
+                },
+                "scopeSpans": [{
+                    "scope": {
+                        "name": "medea",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+}
+
+/// Encodes the provided [`SpanData`] as [OTLP] JSON `Span`.
+///
+/// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+fn encode_span(span: SpanData) -> Value {
+    let kind = match span.kind {
+        SpanKind::Internal => 1,
+        SpanKind::Server => 2,
+        SpanKind::Client => 3,
+    };
+    let status = span
+        .error
+        .map_or_else(|| json!({}), |e| json!({"code": 2, "message": e}));
+    json!({
+        "traceId": span.trace_id.to_string(),
+        "spanId": span.span_id.to_string(),
+        "parentSpanId": span
+            .parent_span_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        "name": span.name,
+        "kind": kind,
+        "startTimeUnixNano": unix_nanos(span.started_at),
+        "endTimeUnixNano": unix_nanos(span.ended_at),
+        "attributes": span
+            .attributes
+            .iter()
+            .map(|(key, value)| attribute(key, value))
+            .collect::<Vec<_>>(),
+        "status": status,
+    })
+}
+
+/// Encodes [OTLP] JSON `KeyValue` attribute with a string value.
+///
+/// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+fn attribute(key: &str, value: &str) -> Value {
+    json!({"key": key, "value": {"stringValue": value}})
+}
+
+/// Returns nanoseconds since UNIX epoch of the provided [`SystemTime`] as a
+/// string, as 64-bit integers are encoded in [OTLP] JSON.
+///
+/// [OTLP]: https://opentelemetry.io/docs/specs/otlp
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::tracing::{SpanId, TraceId};
+
+    use super::*;
+
+    #[test]
+    fn encodes_otlp_json() {
+        let exporter = OtlpExporter::new(
+            "http://127.0.0.1:4318/v1/traces".into(),
+            &conf::Tracing::default(),
+        );
+        let started_at = UNIX_EPOCH + Duration::from_secs(1);
+        let span = SpanData {
+            trace_id: TraceId([1; 16]),
+            span_id: SpanId([2; 8]),
+            parent_span_id: Some(SpanId([3; 8])),
+            name: "control_api.create",
+            kind: SpanKind::Server,
+            started_at,
+            ended_at: started_at + Duration::from_millis(5),
+            attributes: vec![("fid", "room".into())],
+            error: Some("Room already exists.".into()),
+        };
+
+        let json = exporter.encode(vec![span]);
+
+        let resource = &json["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "medea",
+        );
+        let span = &resource["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "01".repeat(16));
+        assert_eq!(span["spanId"], "02".repeat(8));
+        assert_eq!(span["parentSpanId"], "03".repeat(8));
+        assert_eq!(span["kind"], 2);
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "1005000000");
+        assert_eq!(span["attributes"][0]["key"], "fid");
+        assert_eq!(span["attributes"][0]["value"]["stringValue"], "room");
+        assert_eq!(span["status"]["code"], 2);
+    }
+}
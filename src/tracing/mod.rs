@@ -0,0 +1,408 @@
+//! Distributed tracing of [Control API] requests, `Room`s operations, SDP
+//! negotiations and [Control API] callbacks.
+//!
+//! [`Span`]s are exported to [OpenTelemetry] collector via [OTLP], while
+//! their [`SpanContext`]s are propagated as [W3C Trace Context].
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+//! [OpenTelemetry]: https://opentelemetry.io
+//! [OTLP]: https://opentelemetry.io/docs/specs/otlp
+//! [W3C Trace Context]: https://www.w3.org/TR/trace-context
+
+mod exporter;
+
+use std::{
+    cell::Cell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::SystemTime,
+};
+
+use futures::channel::mpsc;
+
+use crate::{conf, log::prelude::*};
+
+#[doc(inline)]
+pub use self::exporter::OtlpExporter;
+
+/// Name of gRPC metadata and HTTP header carrying [`SpanContext`].
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Indicator whether tracing is enabled.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// Sender of finished [`Span`]s to the running [`OtlpExporter`].
+    static ref SPANS_TX: Mutex<Option<mpsc::UnboundedSender<SpanData>>> =
+        Mutex::new(None);
+}
+
+thread_local! {
+    /// [`SpanContext`] of the [`Span`] entered on the current thread.
+    static CURRENT: Cell<Option<SpanContext>> = Cell::new(None);
+}
+
+/// Starts [`OtlpExporter`] in the current [`Arbiter`], enabling tracing, if
+/// it's configured in the provided [`conf::Tracing`].
+///
+/// [`Arbiter`]: actix::Arbiter
+pub fn init(cf: &conf::Tracing) {
+    if let Some(endpoint) = cf.endpoint() {
+        let (tx, rx) = mpsc::unbounded();
+        OtlpExporter::new(endpoint.to_owned(), cf).spawn(rx);
+        *SPANS_TX.lock().unwrap() = Some(tx);
+        ENABLED.store(true, Ordering::Relaxed);
+        info!("Tracing spans are exported to {}", endpoint);
+    }
+}
+
+/// Indicates whether tracing is enabled.
+#[inline]
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns [`SpanContext`] of the [`Span`] entered on the current thread.
+#[inline]
+#[must_use]
+pub fn current() -> Option<SpanContext> {
+    CURRENT.with(Cell::get)
+}
+
+/// ID of a trace.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TraceId(pub [u8; 16]);
+
+impl fmt::Display for TraceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// ID of a [`Span`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SpanId(pub [u8; 8]);
+
+impl fmt::Display for SpanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// Identity of a [`Span`] propagated between services and actors, so the
+/// [`Span`]s started by them are joined into a single trace.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SpanContext {
+    /// ID of the trace the [`Span`] belongs to.
+    pub trace_id: TraceId,
+
+    /// ID of the [`Span`].
+    pub span_id: SpanId,
+}
+
+impl SpanContext {
+    /// Parses [`SpanContext`] from the provided [`TRACEPARENT`] header value.
+    ///
+    /// Returns [`None`] if the value is malformed or contains all-zero IDs.
+    #[must_use]
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        if version.len() != 2 || version == "ff" || flags.len() != 2 {
+            return None;
+        }
+
+        let mut cx = Self {
+            trace_id: TraceId([0; 16]),
+            span_id: SpanId([0; 8]),
+        };
+        decode_hex(trace_id, &mut cx.trace_id.0)?;
+        decode_hex(span_id, &mut cx.span_id.0)?;
+        if cx.trace_id.0 == [0; 16] || cx.span_id.0 == [0; 8] {
+            return None;
+        }
+        Some(cx)
+    }
+
+    /// Returns [`TRACEPARENT`] header value of this sampled [`SpanContext`].
+    #[must_use]
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+}
+
+/// Decodes the provided lowercase `hex` string into the provided `out` bytes.
+///
+/// Returns [`None`] if `hex` has wrong length or contains non-hex characters.
+fn decode_hex(hex: &str, out: &mut [u8]) -> Option<()> {
+    if hex.len() != out.len() * 2 {
+        return None;
+    }
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(())
+}
+
+/// Kind of a [`Span`], describing its relationship with remote services.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpanKind {
+    /// Operation internal to this service.
+    Internal,
+
+    /// Handling of a request from a remote client.
+    Server,
+
+    /// Request to a remote service.
+    Client,
+}
+
+/// Traced operation, which is finished and exported once dropped.
+///
+/// Does nothing if tracing is disabled. [`Default`] [`Span`] does nothing
+/// either.
+#[derive(Debug, Default)]
+pub struct Span(Option<SpanData>);
+
+impl Span {
+    /// Starts new [`Span`] as a child of the [`Span`] entered on the current
+    /// thread, or as a root of a new trace if there is no such one.
+    #[inline]
+    #[must_use]
+    pub fn new(name: &'static str) -> Self {
+        Self::with_parent(name, current())
+    }
+
+    /// Starts new [`Span`] as a child of the [`Span`] entered on the current
+    /// thread.
+    ///
+    /// Does nothing if there is no such [`Span`], so frequent operations are
+    /// traced only as parts of already traced ones.
+    #[inline]
+    #[must_use]
+    pub fn child(name: &'static str) -> Self {
+        match current() {
+            Some(parent) => Self::with_parent(name, Some(parent)),
+            None => Self(None),
+        }
+    }
+
+    /// Starts new [`Span`] as a child of the provided `parent`, or as a root
+    /// of a new trace if it's [`None`].
+    #[must_use]
+    pub fn with_parent(
+        name: &'static str,
+        parent: Option<SpanContext>,
+    ) -> Self {
+        if !is_enabled() {
+            return Self(None);
+        }
+        Self(Some(SpanData {
+            trace_id: parent
+                .map_or_else(|| TraceId(rand::random()), |p| p.trace_id),
+            span_id: SpanId(rand::random()),
+            parent_span_id: parent.map(|p| p.span_id),
+            name,
+            kind: SpanKind::Internal,
+            started_at: SystemTime::now(),
+            ended_at: SystemTime::now(),
+            attributes: Vec::new(),
+            error: None,
+        }))
+    }
+
+    /// Sets [`SpanKind`] of this [`Span`].
+    #[inline]
+    #[must_use]
+    pub fn kind(mut self, kind: SpanKind) -> Self {
+        if let Some(data) = &mut self.0 {
+            data.kind = kind;
+        }
+        self
+    }
+
+    /// Adds attribute with the provided `key` and `value` to this [`Span`].
+    #[inline]
+    #[must_use]
+    pub fn attr<V: fmt::Display>(
+        mut self,
+        key: &'static str,
+        value: V,
+    ) -> Self {
+        self.set_attr(key, value);
+        self
+    }
+
+    /// Adds attribute with the provided `key` and `value` to this [`Span`].
+    pub fn set_attr<V: fmt::Display>(&mut self, key: &'static str, value: V) {
+        if let Some(data) = &mut self.0 {
+            data.attributes.push((key, value.to_string()));
+        }
+    }
+
+    /// Marks this [`Span`] as failed with the provided error `message`.
+    pub fn set_error<E: fmt::Display>(&mut self, message: E) {
+        if let Some(data) = &mut self.0 {
+            data.error = Some(message.to_string());
+        }
+    }
+
+    /// Returns [`SpanContext`] of this [`Span`] to be propagated.
+    ///
+    /// [`None`] if tracing is disabled.
+    #[inline]
+    #[must_use]
+    pub fn context(&self) -> Option<SpanContext> {
+        self.0.as_ref().map(|data| SpanContext {
+            trace_id: data.trace_id,
+            span_id: data.span_id,
+        })
+    }
+
+    /// Enters this [`Span`] on the current thread, so it becomes a parent of
+    /// [`Span`]s started until the returned [`Entered`] guard is dropped.
+    #[must_use]
+    pub fn enter(&self) -> Entered {
+        Entered(
+            self.context()
+                .map(|cx| CURRENT.with(|current| current.replace(Some(cx)))),
+        )
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(mut data) = self.0.take() {
+            data.ended_at = SystemTime::now();
+            if let Some(tx) = &*SPANS_TX.lock().unwrap() {
+                let _ = tx.unbounded_send(data);
+            }
+        }
+    }
+}
+
+/// Guard of an entered [`Span`], which restores the previously entered one
+/// on drop.
+///
+/// Holds [`SpanContext`] entered before, or [`None`] if nothing has been
+/// entered by this guard.
+#[derive(Debug)]
+pub struct Entered(Option<Option<SpanContext>>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0 {
+            CURRENT.with(|current| current.set(previous));
+        }
+    }
+}
+
+/// Data of a finished [`Span`] to be exported.
+#[derive(Clone, Debug)]
+pub struct SpanData {
+    /// ID of the trace this [`Span`] belongs to.
+    pub trace_id: TraceId,
+
+    /// ID of this [`Span`].
+    pub span_id: SpanId,
+
+    /// ID of the parent [`Span`], if any.
+    pub parent_span_id: Option<SpanId>,
+
+    /// Name of the traced operation.
+    pub name: &'static str,
+
+    /// [`SpanKind`] of this [`Span`].
+    pub kind: SpanKind,
+
+    /// Time of this [`Span`] starting.
+    pub started_at: SystemTime,
+
+    /// Time of this [`Span`] ending.
+    pub ended_at: SystemTime,
+
+    /// Attributes of this [`Span`].
+    pub attributes: Vec<(&'static str, String)>,
+
+    /// Error the traced operation failed with, if any.
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    /// Guard enabling tracing until it's dropped.
+    struct Enabled;
+
+    impl Enabled {
+        /// Enables tracing until the returned [`Enabled`] is dropped.
+        fn guard() -> Self {
+            ENABLED.store(true, Ordering::Relaxed);
+            Self
+        }
+    }
+
+    impl Drop for Enabled {
+        fn drop(&mut self) {
+            ENABLED.store(false, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn parses_traceparent() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let cx = SpanContext::from_traceparent(value).unwrap();
+
+        assert_eq!(cx.trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(cx.span_id.to_string(), "00f067aa0ba902b7");
+        assert_eq!(cx.to_traceparent(), value);
+    }
+
+    #[test]
+    fn entered_span_is_parent_of_new_ones() {
+        let enabled = Enabled::guard();
+        let remote = SpanContext::from_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        );
+
+        let span = Span::with_parent("parent", remote);
+        let cx = span.context().unwrap();
+        assert_eq!(cx.trace_id, remote.unwrap().trace_id);
+        {
+            let _entered = span.enter();
+            assert_eq!(current(), Some(cx));
+
+            let child = Span::child("child");
+            let child_data = child.0.as_ref().unwrap();
+            assert_eq!(child_data.trace_id, cx.trace_id);
+            assert_eq!(child_data.parent_span_id, Some(cx.span_id));
+        }
+        assert_eq!(current(), None);
+        assert_eq!(Span::child("orphan").context(), None);
+
+        drop(enabled);
+        assert!(!is_enabled());
+    }
+
+    #[test]
+    fn rejects_malformed_traceparent() {
+        for value in &[
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902bz-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ] {
+            assert_eq!(SpanContext::from_traceparent(value), None, "{}", value);
+        }
+    }
+}
@@ -120,10 +120,38 @@ impl CoturnEvent {
         use CoturnEventParseError as E;
 
         let channel: String = msg.get_channel().map_err(E::NoChannelInfo)?;
+        let payload: String = msg.get_payload().map_err(E::WrongPayloadType)?;
+
+        Self::from_channel(&channel, &payload)
+    }
+
+    /// Tries to parse [`CoturnEvent`] from the provided Redis `channel` name
+    /// and its `payload`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CoturnEvent::parse()`] does.
+    fn from_channel(
+        channel: &str,
+        payload: &str,
+    ) -> Result<Self, CoturnEventParseError> {
+        use CoturnEventParseError as E;
+
         let mut channel_splitted = channel.split('/').skip(4);
 
         let (room_id, peer_id) = {
             let user = channel_splitted.next().ok_or(E::NoUserInfo)?;
+            // Usernames generated in `use-auth-secret` mode are prefixed with
+            // their expiration timestamp: `{expiry}:{room}_{peer}`.
+            let user = match user.find(':') {
+                Some(i)
+                    if i > 0
+                        && user[..i].bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    &user[i + 1..]
+                }
+                _ => user,
+            };
             let mut user_splitted = user.split('_');
             let room_id = RoomId::from(
                 user_splitted.next().ok_or(E::NoMemberId)?.to_string(),
@@ -148,12 +176,7 @@ impl CoturnEvent {
             .map_err(E::FailedAllocationIdParsing)?;
         let event_type = channel_splitted.next().ok_or(E::NoEventType)?;
 
-        let event = CoturnAllocationEvent::parse(
-            event_type,
-            msg.get_payload::<String>()
-                .map_err(E::WrongPayloadType)?
-                .as_str(),
-        )?;
+        let event = CoturnAllocationEvent::parse(event_type, payload)?;
 
         Ok(CoturnEvent {
             event,
@@ -324,3 +347,35 @@ impl Traffic {
         })
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn parses_plain_username() {
+        let event = CoturnEvent::from_channel(
+            "turn/realm/medea/user/room-1_3/allocation/42/status",
+            "new lifetime=600",
+        )
+        .unwrap();
+
+        assert_eq!(event.room_id, RoomId::from("room-1"));
+        assert_eq!(event.peer_id, PeerId(3));
+        assert_eq!(event.allocation_id, 42);
+        assert!(matches!(event.event, CoturnAllocationEvent::New { .. }));
+    }
+
+    #[test]
+    fn parses_turn_rest_api_username() {
+        let event = CoturnEvent::from_channel(
+            "turn/realm/medea/user/1700000000:room-1_3/allocation/42/status",
+            "new lifetime=600",
+        )
+        .unwrap();
+
+        assert_eq!(event.room_id, RoomId::from("room-1"));
+        assert_eq!(event.peer_id, PeerId(3));
+        assert_eq!(event.allocation_id, 42);
+    }
+}
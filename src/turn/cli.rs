@@ -3,14 +3,17 @@
 //! [Coturn]: https://github.com/coturn/coturn
 //! [Telnet]: https://en.wikipedia.org/wiki/Telnet
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use bytes::Bytes;
 use deadpool::managed::PoolConfig;
 use derive_more::{Display, From};
 use failure::Fail;
 use medea_coturn_telnet_client::{
-    pool::{Error as PoolError, Manager as PoolManager, Pool},
+    pool::{
+        Backoff, CircuitState, Error as PoolError, Manager as PoolManager,
+        Pool, SessionsDeletion, Status,
+    },
     CoturnTelnetError,
 };
 
@@ -45,42 +48,107 @@ impl CoturnTelnetClient {
         addr: (H, u16),
         pass: P,
         pool_config: PoolConfig,
+        backoff: Backoff,
     ) -> Self {
-        Self(Pool::from_config(
-            PoolManager::new(addr.0, addr.1, pass),
-            pool_config,
-        ))
+        Self(
+            Pool::from_config(
+                PoolManager::new(addr.0, addr.1, pass),
+                pool_config,
+            )
+            .with_backoff(backoff),
+        )
     }
 
     /// Forcibly closes sessions on [Coturn] server by the provided
     /// [`IceUsername`]s.
     ///
-    /// # Errors
+    /// Doesn't stop on failures, but reports them in the returned
+    /// [`SessionsDeletion`]. Fails fast while [Coturn] is considered
+    /// unavailable.
     ///
-    /// When:
-    /// - establishing connection with [Coturn] fails;
-    /// - retrieving all `users`' sessions from [Coturn] fails;
-    /// - deleting all retrieved `users`' sessions fails.
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub async fn delete_sessions(
+        &self,
+        users: &[IceUsername],
+    ) -> SessionsDeletion {
+        self.0
+            .delete_sessions(users.iter().map(ToString::to_string))
+            .await
+    }
+
+    /// Returns health [`Status`] of connections with [Coturn] server.
     ///
     /// [Coturn]: https://github.com/coturn/coturn
+    #[inline]
+    #[must_use]
+    pub fn status(&self) -> Status {
+        self.0.status()
+    }
+}
+
+/// Health of connections with [Coturn] server admin interface, determining
+/// status of `/health` endpoint of Client API HTTP server.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CoturnCliHealth {
+    /// Maximum number of connections in the pool.
+    pub max_size: usize,
+
+    /// Current number of connections in the pool.
+    pub size: usize,
+
+    /// Number of idle connections in the pool.
+    pub available: usize,
+
+    /// Number of requests waiting for a connection.
+    pub waiting: usize,
+
+    /// Total number of failed attempts to establish connection.
+    pub failed_connects: u64,
+
+    /// Total number of connections which failed to be recycled.
+    pub failed_recycles: u64,
+
+    /// Total number of connections rejected by [Coturn] server because of
+    /// wrong password.
     ///
-    /// # Errors
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub auth_failures: u64,
+
+    /// Delay before the next reconnection attempt, if the circuit breaker is
+    /// open, so requests to [Coturn] server fail fast.
     ///
-    /// With [`CoturnCliError::PoolError`] if could not get or establish new
-    /// connection in pool.
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub retry_in: Option<Duration>,
+}
+
+impl CoturnCliHealth {
+    /// Indicates whether connections with [Coturn] server are available.
     ///
-    /// With [`CoturnCliError::CliError`] in case of unexpected protocol error.
-    pub async fn delete_sessions(
-        &self,
-        users: &[IceUsername],
-    ) -> Result<(), CoturnCliError> {
-        let mut conn = self.0.get().await?;
-        for u in users {
-            let sessions =
-                conn.print_sessions(u.to_string()).await?.into_iter();
-            conn.delete_sessions(sessions).await?;
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[inline]
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.retry_in.is_none()
+    }
+}
+
+impl From<Status> for CoturnCliHealth {
+    fn from(status: Status) -> Self {
+        Self {
+            max_size: status.max_size,
+            size: status.size,
+            available: status.available,
+            waiting: status.waiting,
+            failed_connects: status.failed_connects,
+            failed_recycles: status.failed_recycles,
+            auth_failures: status.auth_failures,
+            retry_in: match status.circuit {
+                CircuitState::Closed => None,
+                CircuitState::Open { retry_in } => Some(retry_in),
+            },
         }
-        Ok(())
     }
 }
 
@@ -88,7 +156,7 @@ impl fmt::Debug for CoturnTelnetClient {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("CoturnTelnetClient")
-            .field("pool", &self.0.status())
+            .field("pool", &self.0)
             .finish()
     }
 }
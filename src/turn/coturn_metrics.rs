@@ -13,7 +13,7 @@ use actix::{
     fut::Either, Actor, ActorFuture, AsyncContext, StreamHandler, WrapFuture,
 };
 use futures::{channel::mpsc, StreamExt as _};
-use redis::{ConnectionInfo, RedisError};
+use redis::RedisError;
 
 use crate::{
     log::prelude::*,
@@ -22,7 +22,8 @@ use crate::{
 
 use super::{
     allocation_event::{CoturnAllocationEvent, CoturnEvent},
-    CoturnUsername,
+    redis_client::RedisClient,
+    CoturnUsername, RelayUsageRepository,
 };
 
 /// Channel pattern used to subscribe to all allocation events published by
@@ -40,10 +41,15 @@ pub type ActFuture<O = ()> =
 #[derive(Debug)]
 pub struct CoturnMetricsService {
     /// [`PeerTrafficWatcher`] which will be notified of all traffic events.
-    peer_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+    ///
+    /// [`None`] if traffic events are used for relay usage accounting only.
+    peer_traffic_watcher: Option<Arc<dyn PeerTrafficWatcher>>,
+
+    /// Repository accounting traffic relayed through Coturn.
+    relay_usage: RelayUsageRepository,
 
     /// Redis client with which Coturn stat updates are received.
-    client: redis::Client,
+    client: RedisClient,
 
     /// Count of allocations for each [`CoturnUsername`] (which acts as a key).
     allocations_count: HashMap<CoturnUsername, u64>,
@@ -57,14 +63,16 @@ impl CoturnMetricsService {
     /// [`RedisError`] can be returned if some basic check on the URL is failed.
     pub fn new(
         cf: &crate::conf::turn::Turn,
-        peer_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+        peer_traffic_watcher: Option<Arc<dyn PeerTrafficWatcher>>,
+        relay_usage: RelayUsageRepository,
     ) -> Result<Self, RedisError> {
-        let client = redis::Client::open(ConnectionInfo::from(&cf.db.redis))?;
+        let client = RedisClient::new(&cf.db.redis)?;
 
         Ok(Self {
             client,
             allocations_count: HashMap::new(),
             peer_traffic_watcher,
+            relay_usage,
         })
     }
 
@@ -79,7 +87,7 @@ impl CoturnMetricsService {
 
         Box::pin(
             async move {
-                let conn = client.get_async_connection().await?;
+                let conn = client.connect().await?;
                 let mut pubsub = conn.into_pubsub();
                 pubsub.psubscribe(ALLOCATIONS_CHANNEL_PATTERN).await?;
 
@@ -146,6 +154,12 @@ impl StreamHandler<redis::Msg> for CoturnMetricsService {
             }
         };
 
+        self.relay_usage.record(&event);
+
+        let peer_traffic_watcher = match &self.peer_traffic_watcher {
+            Some(watcher) => watcher,
+            None => return,
+        };
         let username = CoturnUsername {
             room_id: event.room_id.clone(),
             peer_id: event.peer_id,
@@ -159,7 +173,7 @@ impl StreamHandler<redis::Msg> for CoturnMetricsService {
                 let is_traffic_really_going =
                     traffic.sent_packets + traffic.received_packets > 10;
                 if is_traffic_really_going {
-                    self.peer_traffic_watcher.traffic_flows(
+                    peer_traffic_watcher.traffic_flows(
                         event.room_id,
                         event.peer_id,
                         FlowMetricSource::Coturn,
@@ -169,7 +183,7 @@ impl StreamHandler<redis::Msg> for CoturnMetricsService {
             CoturnAllocationEvent::Deleted => {
                 *allocations_count -= 1;
                 if *allocations_count == 0 {
-                    self.peer_traffic_watcher.traffic_stopped(
+                    peer_traffic_watcher.traffic_stopped(
                         event.room_id,
                         event.peer_id,
                         Instant::now(),
@@ -2,12 +2,17 @@
 //!
 //! [Coturn]: https://github.com/coturn/coturn
 
-use std::mem;
+use std::{
+    mem,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use crypto::{hmac::Hmac, mac::Mac as _, sha1::Sha1};
 use derive_more::{AsRef, Display, From, Into};
 use medea_client_api_proto::{IceServer, PeerId, RoomId};
 
 use crate::{
+    conf::turn::Server,
     log::prelude as log,
     utils::{generate_token, MpscOneshotSender},
 };
@@ -15,7 +20,7 @@ use crate::{
 /// Username for authorization on [Coturn] server.
 ///
 /// [Coturn]: https://github.com/coturn/coturn
-#[derive(AsRef, Clone, Debug, Display, Eq, From, Into, PartialEq)]
+#[derive(AsRef, Clone, Debug, Display, Eq, From, Hash, Into, PartialEq)]
 #[as_ref(forward)]
 pub struct IceUsername(String);
 
@@ -25,6 +30,40 @@ impl IceUsername {
     fn new(room_id: &RoomId, peer_id: PeerId) -> Self {
         Self(format!("{}_{}", room_id, peer_id))
     }
+
+    /// Returns new time-limited [`IceUsername`] for the provided [`RoomId`]
+    /// and [`PeerId`] in `{expiry}:{room_id}_{peer_id}` format of
+    /// [TURN REST API].
+    ///
+    /// [TURN REST API]: https://tools.ietf.org/html/draft-uberti-behave-turn-rest-00
+    #[must_use]
+    fn with_expiry(
+        room_id: &RoomId,
+        peer_id: PeerId,
+        expires_at: SystemTime,
+    ) -> Self {
+        let expiry = expires_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self(format!("{}:{}", expiry, Self::new(room_id, peer_id)))
+    }
+
+    /// Returns new [`IceUsername`] for the provided [`RoomId`] and [`PeerId`]
+    /// in `{room_id}_{peer_id}_{expiry}` format, so credentials rotated for
+    /// the same `Peer` don't clash.
+    #[must_use]
+    fn with_suffix(
+        room_id: &RoomId,
+        peer_id: PeerId,
+        expires_at: SystemTime,
+    ) -> Self {
+        let expiry = expires_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self(format!("{}_{}", Self::new(room_id, peer_id), expiry))
+    }
 }
 
 /// Password for authorization on [Coturn] server.
@@ -45,13 +84,58 @@ impl IcePassword {
     pub fn generate() -> Self {
         Self(generate_token(Self::LENGTH))
     }
+
+    /// Generates [`IcePassword`] for the provided [`IceUsername`] as
+    /// `base64(hmac-sha1(secret, username))`, which is verified by TURN server
+    /// knowing the same `secret`.
+    #[must_use]
+    pub fn sign(secret: &str, username: &IceUsername) -> Self {
+        let mut hmac = Hmac::new(Sha1::new(), secret.as_bytes());
+        hmac.input(username.0.as_bytes());
+        Self(base64::encode(hmac.result().code()))
+    }
+}
+
+/// Lifetime of time-limited [`IceUser`]'s credentials.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Expiry {
+    /// Time when credentials expire.
+    pub expires_at: SystemTime,
+
+    /// Time before [`Expiry::expires_at`] when fresh credentials should be
+    /// issued instead.
+    pub rotate_before: Duration,
+}
+
+impl Expiry {
+    /// Returns [`Expiry`] of credentials issued now and living for the
+    /// provided `ttl`, or [`None`] if `ttl` is zero, meaning that credentials
+    /// never expire.
+    #[must_use]
+    pub fn after(ttl: Duration, rotate_before: Duration) -> Option<Self> {
+        if ttl == Duration::from_secs(0) {
+            return None;
+        }
+        Some(Self {
+            expires_at: SystemTime::now() + ttl,
+            rotate_before,
+        })
+    }
+
+    /// Indicates whether fresh credentials should be issued at the provided
+    /// moment.
+    #[inline]
+    #[must_use]
+    pub fn is_rotation_due(&self, now: SystemTime) -> bool {
+        now + self.rotate_before >= self.expires_at
+    }
 }
 
 /// Credentials on Turn server.
 #[derive(Debug)]
 pub struct IceUser {
-    /// Address of Turn server.
-    address: String,
+    /// STUN/TURN servers these credentials are provided for.
+    servers: Vec<Server>,
 
     /// Username for authorization.
     username: IceUsername,
@@ -59,39 +143,97 @@ pub struct IceUser {
     /// Password for authorization.
     pass: IcePassword,
 
+    /// Lifetime of these credentials.
+    ///
+    /// [`None`] if they never expire.
+    expiry: Option<Expiry>,
+
     /// Sender into which [`IceUsername`] is sent in [`Drop`] implementation.
     ///
-    /// [`None`] if [`IceUser`] is static.
+    /// [`None`] if [`IceUser`] requires no cleanup.
     on_drop: Option<MpscOneshotSender<IceUsername>>,
 }
 
 impl IceUser {
     /// Builds a new non-static [`IceUser`].
-    #[inline]
+    ///
+    /// Time-limited [`IceUser`] gets its expiry suffixed to its
+    /// [`IceUsername`], so it doesn't clash with the rotated ones.
     #[must_use]
     pub fn new_non_static(
-        address: String,
+        servers: Vec<Server>,
         room_id: &RoomId,
         peer_id: PeerId,
         pass: IcePassword,
+        expiry: Option<Expiry>,
         on_drop: MpscOneshotSender<IceUsername>,
     ) -> Self {
+        let mut user =
+            Self::new_unregistered(servers, room_id, peer_id, pass, expiry);
+        user.on_drop = Some(on_drop);
+        user
+    }
+
+    /// Builds a new non-static [`IceUser`], which credentials are registered
+    /// nowhere, so it requires no cleanup.
+    #[must_use]
+    pub fn new_unregistered(
+        servers: Vec<Server>,
+        room_id: &RoomId,
+        peer_id: PeerId,
+        pass: IcePassword,
+        expiry: Option<Expiry>,
+    ) -> Self {
+        let username = match expiry {
+            Some(e) => IceUsername::with_suffix(room_id, peer_id, e.expires_at),
+            None => IceUsername::new(room_id, peer_id),
+        };
         Self {
-            address,
-            username: IceUsername::new(&room_id, peer_id),
+            servers,
+            username,
             pass,
-            on_drop: Some(on_drop),
+            expiry,
+            on_drop: None,
+        }
+    }
+
+    /// Builds a new [`IceUser`] with time-limited credentials signed with the
+    /// provided `secret` shared with TURN server.
+    ///
+    /// Such [`IceUser`] requires no cleanup, as TURN server rejects its
+    /// credentials once [`Expiry::expires_at`] is reached.
+    #[must_use]
+    pub fn new_ephemeral(
+        servers: Vec<Server>,
+        room_id: &RoomId,
+        peer_id: PeerId,
+        expiry: Expiry,
+        secret: &str,
+    ) -> Self {
+        let username =
+            IceUsername::with_expiry(room_id, peer_id, expiry.expires_at);
+        Self {
+            servers,
+            pass: IcePassword::sign(secret, &username),
+            username,
+            expiry: Some(expiry),
+            on_drop: None,
         }
     }
 
     /// Build a new static [`IceUser`].
     #[inline]
     #[must_use]
-    pub fn new_static(address: String, username: String, pass: String) -> Self {
+    pub fn new_static(
+        servers: Vec<Server>,
+        username: String,
+        pass: String,
+    ) -> Self {
         Self {
-            address,
+            servers,
             username: IceUsername(username),
             pass: IcePassword(pass),
+            expiry: None,
             on_drop: None,
         }
     }
@@ -99,22 +241,35 @@ impl IceUser {
     /// Builds a list of [`IceServer`]s of this [`IceUser`].
     #[must_use]
     pub fn servers_list(&self) -> Vec<IceServer> {
-        let stun_url = vec![format!("stun:{}", self.address)];
-        let stun = IceServer {
-            urls: stun_url,
-            username: None,
-            credential: None,
-        };
-        let turn_urls = vec![
-            format!("turn:{}", self.address),
-            format!("turn:{}?transport=tcp", self.address),
-        ];
-        let turn = IceServer {
-            urls: turn_urls,
-            username: Some(self.username.to_string()),
-            credential: Some(self.pass.to_string()),
-        };
-        vec![stun, turn]
+        let mut list = Vec::new();
+        for server in &self.servers {
+            let address = format!("{}:{}", server.host, server.port);
+            if server.stun {
+                list.push(IceServer {
+                    urls: vec![format!("stun:{}", address)],
+                    username: None,
+                    credential: None,
+                });
+            }
+            if server.turn {
+                let mut urls = vec![
+                    format!("turn:{}", address),
+                    format!("turn:{}?transport=tcp", address),
+                ];
+                if server.tls_port != 0 {
+                    urls.push(format!(
+                        "turns:{}:{}?transport=tcp",
+                        server.host, server.tls_port,
+                    ));
+                }
+                list.push(IceServer {
+                    urls,
+                    username: Some(self.username.to_string()),
+                    credential: Some(self.pass.to_string()),
+                });
+            }
+        }
+        list
     }
 
     /// Returns [`IceUsername`] of this [`IceUser`].
@@ -130,6 +285,14 @@ impl IceUser {
     pub fn pass(&self) -> &IcePassword {
         &self.pass
     }
+
+    /// Returns [`Expiry`] of this [`IceUser`], if its credentials are
+    /// time-limited.
+    #[inline]
+    #[must_use]
+    pub fn expiry(&self) -> Option<Expiry> {
+        self.expiry
+    }
 }
 
 impl Drop for IceUser {
@@ -154,10 +317,11 @@ mod spec {
         let (tx, mut rx) = mpsc::unbounded();
 
         let user = IceUser::new_non_static(
-            String::new(),
+            Vec::new(),
             &RoomId::from("foobar"),
             PeerId(0),
             IcePassword::generate(),
+            None,
             MpscOneshotSender::from(tx),
         );
         let user_name = user.username.clone();
@@ -167,4 +331,82 @@ mod spec {
         assert_eq!(rx.next().await.unwrap(), user_name);
         assert!(rx.next().await.is_none());
     }
+
+    #[test]
+    fn signs_ephemeral_credentials() {
+        let user = IceUser::new_ephemeral(
+            Vec::new(),
+            &RoomId::from("room"),
+            PeerId(1),
+            Expiry {
+                expires_at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+                rotate_before: Duration::from_secs(60),
+            },
+            "secret",
+        );
+
+        assert_eq!(user.user().to_string(), "1700000000:room_1");
+        assert_eq!(user.pass().to_string(), "FofDQmJej1Leb3rh7DdRZyxhGLY=");
+    }
+
+    #[test]
+    fn suffixes_expiring_usernames() {
+        let (tx, _rx) = futures::channel::mpsc::unbounded();
+        let expiry = Expiry {
+            expires_at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            rotate_before: Duration::from_secs(60),
+        };
+
+        let user = IceUser::new_non_static(
+            Vec::new(),
+            &RoomId::from("room"),
+            PeerId(1),
+            IcePassword::generate(),
+            Some(expiry),
+            MpscOneshotSender::from(tx),
+        );
+
+        assert_eq!(user.user().to_string(), "room_1_1700000000");
+        assert!(!expiry
+            .is_rotation_due(UNIX_EPOCH + Duration::from_secs(1_699_999_900)));
+        assert!(expiry
+            .is_rotation_due(UNIX_EPOCH + Duration::from_secs(1_699_999_940)));
+    }
+
+    #[test]
+    fn lists_servers_by_their_kinds() {
+        let user = IceUser::new_static(
+            vec![
+                Server {
+                    host: "turn.example.com".into(),
+                    tls_port: 5349,
+                    stun: false,
+                    ..Server::default()
+                },
+                Server {
+                    host: "stun.example.com".into(),
+                    port: 19302,
+                    turn: false,
+                    ..Server::default()
+                },
+            ],
+            "user".into(),
+            "pass".into(),
+        );
+
+        let list = user.servers_list();
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            list[0].urls,
+            [
+                "turn:turn.example.com:3478",
+                "turn:turn.example.com:3478?transport=tcp",
+                "turns:turn.example.com:5349?transport=tcp",
+            ]
+        );
+        assert_eq!(list[0].username.as_deref(), Some("user"));
+        assert_eq!(list[1].urls, ["stun:stun.example.com:19302"]);
+        assert!(list[1].credential.is_none());
+    }
 }
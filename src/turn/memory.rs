@@ -0,0 +1,108 @@
+//! [`TurnAuthService`] generating TURN credentials without registering them
+//! anywhere.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use medea_client_api_proto::{PeerId, RoomId};
+
+use super::{
+    ice_user::{Expiry, IcePassword},
+    IceServers, IceUser, TurnAuthService, TurnServiceErr, UnreachablePolicy,
+};
+
+/// [`TurnAuthService`] which generates random credentials of [`IceUser`]s
+/// without registering them in [Coturn]'s database, so TURN server is unable
+/// to verify them.
+///
+/// Requires neither [Redis] nor [Coturn] to be running, so is intended for
+/// single-node development setups only: with STUN servers, or with TURN
+/// servers not authenticating users (like [Coturn] with `no-auth` option).
+///
+/// [Coturn]: https://github.com/coturn/coturn
+/// [Redis]: https://redis.io
+#[derive(Debug)]
+pub struct MemoryService {
+    /// STUN/TURN servers provided to clients.
+    servers: Arc<IceServers>,
+
+    /// Lifetime of the generated credentials.
+    ///
+    /// Zero means that they never expire.
+    ttl: Duration,
+
+    /// Time before credentials expiry when fresh ones should be issued.
+    rotate_before: Duration,
+}
+
+impl MemoryService {
+    /// Creates new [`MemoryService`] generating credentials for the provided
+    /// [`IceServers`], which live for the provided `ttl`.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        servers: Arc<IceServers>,
+        ttl: Duration,
+        rotate_before: Duration,
+    ) -> Self {
+        Self {
+            servers,
+            ttl,
+            rotate_before,
+        }
+    }
+}
+
+#[async_trait]
+impl TurnAuthService for MemoryService {
+    /// Generates [`IceUser`] with selected STUN/TURN servers, provided
+    /// [`RoomId`], [`PeerId`] and random password.
+    ///
+    /// Never fails, so the provided [`UnreachablePolicy`] is ignored.
+    async fn create(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        region: Option<String>,
+        _: UnreachablePolicy,
+    ) -> Result<IceUser, TurnServiceErr> {
+        Ok(IceUser::new_unregistered(
+            self.servers.select(region.as_deref()),
+            &room_id,
+            peer_id,
+            IcePassword::generate(),
+            Expiry::after(self.ttl, self.rotate_before),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::conf;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn generates_random_passwords() {
+        let cf = conf::Turn::default();
+        let service = MemoryService::new(
+            Arc::new(IceServers::new(&cf)),
+            cf.ttl,
+            cf.rotate_before,
+        );
+
+        let create = |peer_id| {
+            service.create(
+                RoomId::from("room"),
+                PeerId(peer_id),
+                None,
+                UnreachablePolicy::ReturnErr,
+            )
+        };
+        let first = create(1).await.unwrap();
+        let second = create(2).await.unwrap();
+
+        assert_ne!(first.user(), second.user());
+        assert_ne!(first.pass().to_string(), second.pass().to_string());
+    }
+}
@@ -6,7 +6,12 @@ pub mod allocation_event;
 pub mod cli;
 pub mod coturn_metrics;
 pub mod ice_user;
+pub mod memory;
+pub mod redis_client;
+pub mod relay_usage;
 pub mod repo;
+pub mod secret;
+pub mod servers;
 pub mod service;
 
 use derive_more::Display;
@@ -15,6 +20,10 @@ use medea_client_api_proto::{PeerId, RoomId};
 #[doc(inline)]
 pub use self::{
     ice_user::{IceUser, IceUsername},
+    memory::MemoryService,
+    relay_usage::{RelayUsage, RelayUsageRepository},
+    secret::SecretService,
+    servers::IceServers,
     service::{
         new_turn_auth_service, TurnAuthService, TurnServiceErr,
         UnreachablePolicy,
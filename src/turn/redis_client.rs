@@ -0,0 +1,197 @@
+//! [Redis] client connecting to [Coturn]'s database server, optionally
+//! discovered via [Redis Sentinel].
+//!
+//! [Coturn]: https://github.com/coturn/coturn
+//! [Redis]: https://redis.io
+//! [Redis Sentinel]: https://redis.io/topics/sentinel
+
+use async_trait::async_trait;
+use deadpool::managed::{self, RecycleError, RecycleResult};
+use redis::{
+    aio::Connection, cmd, Client, ConnectionAddr, ConnectionInfo, ErrorKind,
+    FromRedisValue, RedisError, RedisResult, Value,
+};
+
+use crate::{conf, log::prelude as log};
+
+/// Pool of connections with [Redis] database server.
+///
+/// [Redis]: https://redis.io
+pub type Pool = managed::Pool<Connection, RedisError>;
+
+/// Error of getting connection from [`Pool`].
+pub type PoolError = managed::PoolError<RedisError>;
+
+/// [Redis] client which resolves the current master via [Redis Sentinel] on
+/// each connection, if Sentinel is configured.
+///
+/// [Redis]: https://redis.io
+/// [Redis Sentinel]: https://redis.io/topics/sentinel
+#[derive(Clone, Debug)]
+pub struct RedisClient {
+    /// Settings of connections with Redis database server.
+    ///
+    /// Its address is replaced with the discovered master's one if
+    /// [`RedisClient::sentinel`] is used.
+    info: ConnectionInfo,
+
+    /// [Redis Sentinel] to discover the current master with.
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    sentinel: Option<Sentinel>,
+}
+
+impl RedisClient {
+    /// Creates new [`RedisClient`] with the provided settings.
+    ///
+    /// # Errors
+    ///
+    /// Errors if [Redis Sentinel] nodes are misconfigured.
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    pub fn new(cf: &conf::turn::Redis) -> RedisResult<Self> {
+        let info = ConnectionInfo::from(cf);
+        let sentinel = if cf.sentinel.is_enabled() {
+            let nodes = cf.sentinel.nodes().map_err(|e| {
+                RedisError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid port of Sentinel node",
+                    e.to_string(),
+                ))
+            })?;
+            if nodes.is_empty() {
+                return Err(RedisError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "No Sentinel nodes specified",
+                )));
+            }
+            Some(Sentinel {
+                master_name: cf.sentinel.master_name.to_string(),
+                nodes: nodes
+                    .into_iter()
+                    .map(|(host, port)| ConnectionInfo {
+                        addr: Box::new(ConnectionAddr::Tcp(host, port)),
+                        db: 0,
+                        username: None,
+                        passwd: Some(cf.sentinel.pass.to_string())
+                            .filter(|p| !p.is_empty()),
+                    })
+                    .collect(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self { info, sentinel })
+    }
+
+    /// Establishes new connection with Redis database server, discovering
+    /// the current master first if [Redis Sentinel] is used.
+    ///
+    /// # Errors
+    ///
+    /// Errors if none of Sentinels knows the master, or connection cannot be
+    /// established.
+    ///
+    /// [Redis Sentinel]: https://redis.io/topics/sentinel
+    pub async fn connect(&self) -> RedisResult<Connection> {
+        let mut info = self.info.clone();
+        if let Some(sentinel) = &self.sentinel {
+            let (host, port) = sentinel.master_addr().await?;
+            info.addr = Box::new(ConnectionAddr::Tcp(host, port));
+        }
+        Client::open(info)?.get_async_connection().await
+    }
+
+    /// Creates new [`Pool`] of connections established by this
+    /// [`RedisClient`].
+    #[must_use]
+    pub fn into_pool(self, cf: conf::turn::PoolConfig) -> Pool {
+        Pool::from_config(Manager(self), cf.into())
+    }
+}
+
+/// [Redis Sentinel] nodes monitoring the master of Redis database server.
+///
+/// [Redis Sentinel]: https://redis.io/topics/sentinel
+#[derive(Clone, Debug)]
+struct Sentinel {
+    /// Name of the monitored master.
+    master_name: String,
+
+    /// Settings of connections with Sentinel nodes.
+    nodes: Vec<ConnectionInfo>,
+}
+
+impl Sentinel {
+    /// Asks Sentinel nodes one by one for the address of the current master,
+    /// returning the first answer.
+    async fn master_addr(&self) -> RedisResult<(String, u16)> {
+        let mut last_err = None;
+        for node in &self.nodes {
+            let res: RedisResult<Option<(String, u16)>> = async {
+                let mut conn =
+                    Client::open(node.clone())?.get_async_connection().await?;
+                cmd("SENTINEL")
+                    .arg("get-master-addr-by-name")
+                    .arg(&self.master_name)
+                    .query_async(&mut conn)
+                    .await
+            }
+            .await;
+            match res {
+                Ok(Some(addr)) => return Ok(addr),
+                Ok(None) => {
+                    last_err = Some(RedisError::from((
+                        ErrorKind::ResponseError,
+                        "Sentinel doesn't know the master",
+                        format!("{} at {}", self.master_name, node.addr),
+                    )));
+                }
+                Err(e) => {
+                    log::warn!("Failed to ask Sentinel {}: {}", node.addr, e);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            RedisError::from((
+                ErrorKind::InvalidClientConfig,
+                "No Sentinel nodes specified",
+            ))
+        }))
+    }
+}
+
+/// [`managed::Manager`] of [`Pool`] connections.
+///
+/// When [Redis Sentinel] is used, connections to a server which is not the
+/// master anymore are not recycled, so the [`Pool`] follows failovers.
+///
+/// [Redis Sentinel]: https://redis.io/topics/sentinel
+struct Manager(RedisClient);
+
+#[async_trait]
+impl managed::Manager<Connection, RedisError> for Manager {
+    async fn create(&self) -> RedisResult<Connection> {
+        self.0.connect().await
+    }
+
+    async fn recycle(
+        &self,
+        conn: &mut Connection,
+    ) -> RecycleResult<RedisError> {
+        if self.0.sentinel.is_none() {
+            cmd("PING").query_async::<_, ()>(conn).await?;
+            return Ok(());
+        }
+
+        let role: Vec<Value> = cmd("ROLE").query_async(conn).await?;
+        match role.first().map(String::from_redis_value).transpose()? {
+            Some(role) if role == "master" => Ok(()),
+            _ => Err(RecycleError::Message(
+                "Redis server is not the master anymore".into(),
+            )),
+        }
+    }
+}
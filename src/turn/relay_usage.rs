@@ -0,0 +1,337 @@
+//! Accounting of traffic relayed through [TURN] servers.
+//!
+//! [TURN]: https://webrtcglossary.com/turn/
+
+use std::{
+    collections::HashMap,
+    ops::{Add, AddAssign},
+    sync::{Arc, Mutex},
+};
+
+use medea_client_api_proto::{MemberId, PeerId, RoomId};
+use medea_control_api_proto::grpc::{api as proto, callback as callback_proto};
+use serde::Serialize;
+
+use super::allocation_event::{CoturnAllocationEvent, CoturnEvent, Traffic};
+
+/// Traffic relayed through [TURN] servers.
+///
+/// [TURN]: https://webrtcglossary.com/turn/
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct RelayUsage {
+    /// Number of bytes sent by [TURN] servers to client.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn/
+    pub sent_bytes: u64,
+
+    /// Number of bytes received by [TURN] servers from client.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn/
+    pub received_bytes: u64,
+
+    /// Number of packets sent by [TURN] servers to client.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn/
+    pub sent_packets: u64,
+
+    /// Number of packets received by [TURN] servers from client.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn/
+    pub received_packets: u64,
+}
+
+impl From<Traffic> for RelayUsage {
+    #[inline]
+    fn from(traffic: Traffic) -> Self {
+        Self {
+            sent_bytes: traffic.sent_bytes,
+            received_bytes: traffic.received_bytes,
+            sent_packets: traffic.sent_packets,
+            received_packets: traffic.received_packets,
+        }
+    }
+}
+
+impl Add for RelayUsage {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            sent_bytes: self.sent_bytes + rhs.sent_bytes,
+            received_bytes: self.received_bytes + rhs.received_bytes,
+            sent_packets: self.sent_packets + rhs.sent_packets,
+            received_packets: self.received_packets + rhs.received_packets,
+        }
+    }
+}
+
+impl AddAssign for RelayUsage {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Into<proto::RelayUsage> for RelayUsage {
+    fn into(self) -> proto::RelayUsage {
+        proto::RelayUsage {
+            sent_bytes: self.sent_bytes,
+            received_bytes: self.received_bytes,
+            sent_packets: self.sent_packets,
+            received_packets: self.received_packets,
+        }
+    }
+}
+
+impl Into<callback_proto::RelayUsage> for RelayUsage {
+    fn into(self) -> callback_proto::RelayUsage {
+        callback_proto::RelayUsage {
+            sent_bytes: self.sent_bytes,
+            received_bytes: self.received_bytes,
+            sent_packets: self.sent_packets,
+            received_packets: self.received_packets,
+        }
+    }
+}
+
+/// Relay usage of a single `Peer`.
+#[derive(Debug)]
+struct PeerRelayUsage {
+    /// ID of the `Member` owning the `Peer`.
+    member_id: MemberId,
+
+    /// [`RelayUsage`] of each allocation of the `Peer`, keyed by allocation
+    /// ID.
+    allocations: HashMap<u64, RelayUsage>,
+}
+
+impl PeerRelayUsage {
+    /// Returns [`RelayUsage`] of all the allocations of the `Peer`.
+    fn total(&self) -> RelayUsage {
+        self.allocations
+            .values()
+            .fold(RelayUsage::default(), |total, usage| total + *usage)
+    }
+}
+
+/// Storage of [`RelayUsage`] accumulated from [Coturn] allocation events for
+/// each `Peer`, attributed to `Member`s owning them.
+///
+/// Usage is kept after `Peer`s are removed, so `Member`'s totals include all
+/// its `Peer`s ever created, until the `Member` (or its `Room`) is removed.
+///
+/// This struct can be cloned and transferred across thread boundaries.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Clone, Debug, Default)]
+pub struct RelayUsageRepository(
+    Arc<Mutex<HashMap<RoomId, HashMap<PeerId, PeerRelayUsage>>>>,
+);
+
+impl RelayUsageRepository {
+    /// Registers `Peer` owned by the provided `Member`, so its relay usage is
+    /// accounted.
+    pub fn register_peer(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        member_id: MemberId,
+    ) {
+        let _ = self.0.lock().unwrap().entry(room_id).or_default().insert(
+            peer_id,
+            PeerRelayUsage {
+                member_id,
+                allocations: HashMap::new(),
+            },
+        );
+    }
+
+    /// Accounts the provided [`CoturnEvent`].
+    ///
+    /// [`CoturnAllocationEvent::Traffic`] is added to the allocation's usage,
+    /// while [`CoturnAllocationEvent::TotalTraffic`] replaces it, as it's
+    /// authoritative. Events of unregistered `Peer`s are ignored.
+    pub fn record(&self, event: &CoturnEvent) {
+        let mut rooms = self.0.lock().unwrap();
+        let peer = match rooms
+            .get_mut(&event.room_id)
+            .and_then(|peers| peers.get_mut(&event.peer_id))
+        {
+            Some(peer) => peer,
+            None => return,
+        };
+        let allocation =
+            peer.allocations.entry(event.allocation_id).or_default();
+        match event.event {
+            CoturnAllocationEvent::Traffic { traffic } => {
+                *allocation += traffic.into();
+            }
+            CoturnAllocationEvent::TotalTraffic { traffic } => {
+                *allocation = traffic.into();
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns [`RelayUsage`] of each `Peer` of the provided `Member`.
+    #[must_use]
+    pub fn member_peers_usage(
+        &self,
+        room_id: &RoomId,
+        member_id: &MemberId,
+    ) -> HashMap<PeerId, RelayUsage> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(room_id)
+            .map(|peers| {
+                peers
+                    .iter()
+                    .filter(|(_, peer)| &peer.member_id == member_id)
+                    .map(|(id, peer)| (*id, peer.total()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns total [`RelayUsage`] of all the `Peer`s of the provided
+    /// `Member`.
+    #[must_use]
+    pub fn member_usage(
+        &self,
+        room_id: &RoomId,
+        member_id: &MemberId,
+    ) -> RelayUsage {
+        self.member_peers_usage(room_id, member_id)
+            .values()
+            .fold(RelayUsage::default(), |total, usage| total + *usage)
+    }
+
+    /// Forgets relay usage of the provided `Member`.
+    pub fn remove_member(&self, room_id: &RoomId, member_id: &MemberId) {
+        if let Some(peers) = self.0.lock().unwrap().get_mut(room_id) {
+            peers.retain(|_, peer| &peer.member_id != member_id);
+        }
+    }
+
+    /// Forgets relay usage of all the `Member`s of the provided `Room`.
+    pub fn remove_room(&self, room_id: &RoomId) {
+        let _ = self.0.lock().unwrap().remove(room_id);
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    /// Returns [`CoturnEvent`] of the provided `Peer`'s allocation.
+    fn event(
+        peer_id: u32,
+        allocation_id: u64,
+        event: CoturnAllocationEvent,
+    ) -> CoturnEvent {
+        CoturnEvent {
+            event,
+            room_id: RoomId::from("room"),
+            peer_id: PeerId(peer_id),
+            allocation_id,
+        }
+    }
+
+    /// Returns [`Traffic`] with the provided bytes and packets counts.
+    fn traffic(bytes: u64, packets: u64) -> Traffic {
+        Traffic {
+            received_packets: packets,
+            received_bytes: bytes,
+            sent_packets: packets,
+            sent_bytes: bytes,
+        }
+    }
+
+    #[test]
+    fn accumulates_usage_by_members() {
+        use CoturnAllocationEvent::{Deleted, TotalTraffic, Traffic};
+
+        let repo = RelayUsageRepository::default();
+        let room_id = RoomId::from("room");
+        let (alice, bob) = (MemberId::from("alice"), MemberId::from("bob"));
+        repo.register_peer(room_id.clone(), PeerId(1), alice.clone());
+        repo.register_peer(room_id.clone(), PeerId(2), alice.clone());
+        repo.register_peer(room_id.clone(), PeerId(3), bob.clone());
+
+        for e in vec![
+            event(
+                1,
+                10,
+                Traffic {
+                    traffic: traffic(100, 1),
+                },
+            ),
+            event(
+                1,
+                10,
+                Traffic {
+                    traffic: traffic(50, 1),
+                },
+            ),
+            event(
+                1,
+                11,
+                Traffic {
+                    traffic: traffic(10, 1),
+                },
+            ),
+            event(
+                2,
+                20,
+                Traffic {
+                    traffic: traffic(30, 3),
+                },
+            ),
+            event(
+                3,
+                30,
+                TotalTraffic {
+                    traffic: traffic(500, 5),
+                },
+            ),
+            event(3, 30, Deleted),
+            event(
+                4,
+                40,
+                Traffic {
+                    traffic: traffic(1000, 10),
+                },
+            ),
+        ] {
+            repo.record(&e);
+        }
+
+        let peers = repo.member_peers_usage(&room_id, &alice);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[&PeerId(1)].sent_bytes, 160);
+        assert_eq!(peers[&PeerId(2)].received_packets, 3);
+        assert_eq!(repo.member_usage(&room_id, &alice).received_bytes, 190);
+        assert_eq!(repo.member_usage(&room_id, &bob).sent_bytes, 500);
+
+        repo.record(&event(
+            1,
+            10,
+            TotalTraffic {
+                traffic: traffic(200, 2),
+            },
+        ));
+
+        assert_eq!(repo.member_usage(&room_id, &alice).sent_bytes, 240);
+
+        repo.remove_member(&room_id, &alice);
+
+        assert_eq!(repo.member_usage(&room_id, &alice), RelayUsage::default());
+        assert_eq!(repo.member_usage(&room_id, &bob).sent_packets, 5);
+
+        repo.remove_room(&room_id);
+
+        assert_eq!(repo.member_usage(&room_id, &bob), RelayUsage::default());
+    }
+}
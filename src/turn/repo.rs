@@ -1,18 +1,20 @@
 //! Abstraction over remote Redis database used to store Turn server
 //! credentials.
 
-use std::{fmt, time::Duration};
+use std::fmt;
 
 use crypto::{digest::Digest, md5::Md5};
-use deadpool::managed::{PoolConfig, Timeouts};
-use deadpool_redis::{cmd, Pool, PoolError};
 use derive_more::{Display, From};
 use failure::Fail;
-use redis::{IntoConnectionInfo, RedisError};
+use redis::{cmd, RedisError};
 
 use crate::{
+    conf,
     log::prelude as log,
-    turn::{IceUser, IceUsername},
+    turn::{
+        redis_client::{Pool, PoolError, RedisClient},
+        IceUser, IceUsername,
+    },
 };
 
 /// Medea's [Coturn] realm name.
@@ -35,25 +37,13 @@ pub enum TurnDatabaseErr {
 pub struct TurnDatabase(Pool);
 
 impl TurnDatabase {
-    /// Creates new [`TurnDatabase`].
+    /// Creates new [`TurnDatabase`] with the provided settings.
     ///
     /// # Errors
     ///
-    /// Errors if authentication in Redis fails.
-    pub fn new<S: IntoConnectionInfo + Clone>(
-        conn_timeout: Duration,
-        conn_info: S,
-    ) -> Result<Self, TurnDatabaseErr> {
-        let manager = deadpool_redis::Manager::new(conn_info)?;
-        let config = PoolConfig {
-            max_size: 16, // TODO: configure via conf
-            timeouts: Timeouts {
-                wait: None,
-                create: Some(conn_timeout),
-                recycle: None,
-            },
-        };
-        Ok(Self(Pool::from_config(manager, config)))
+    /// Errors if the provided settings are invalid.
+    pub fn new(cf: &conf::turn::Redis) -> Result<Self, TurnDatabaseErr> {
+        Ok(Self(RedisClient::new(cf)?.into_pool(cf.pool)))
     }
 
     /// Inserts provided [`IceUser`] into remote Redis database.
@@ -72,7 +62,7 @@ impl TurnDatabase {
         Ok(cmd("SET")
             .arg(key)
             .arg(value)
-            .query_async(&mut conn)
+            .query_async(&mut *conn)
             .await?)
     }
 
@@ -97,7 +87,7 @@ impl TurnDatabase {
         let keys: Vec<_> = users.iter().map(IceUsername::redis_key).collect();
 
         let mut conn = self.0.get().await?;
-        Ok(cmd("DEL").arg(keys).query_async(&mut conn).await?)
+        Ok(cmd("DEL").arg(keys).query_async(&mut *conn).await?)
     }
 }
 
@@ -0,0 +1,126 @@
+//! [`TurnAuthService`] generating time-limited credentials with a secret shared
+//! with [TURN] server, as described in [TURN REST API].
+//!
+//! [TURN]: https://webrtcglossary.com/turn/
+//! [TURN REST API]: https://tools.ietf.org/html/draft-uberti-behave-turn-rest-00
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use medea_client_api_proto::{PeerId, RoomId};
+
+use crate::conf;
+
+use super::{
+    ice_user::Expiry, IceServers, IceUser, TurnAuthService, TurnServiceErr,
+    UnreachablePolicy,
+};
+
+/// [`TurnAuthService`] which signs credentials with a secret shared with
+/// [TURN] server (`use-auth-secret` mode of [Coturn]).
+///
+/// Doesn't require any storage, as [TURN] server validates credentials on its
+/// own and rejects them once they expire.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+/// [TURN]: https://webrtcglossary.com/turn/
+#[derive(Debug)]
+pub struct SecretService {
+    /// STUN/TURN servers provided to clients.
+    servers: Arc<IceServers>,
+
+    /// Secret shared with [TURN] server.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn/
+    secret: String,
+
+    /// Lifetime of the generated credentials.
+    ttl: Duration,
+
+    /// Time before credentials expiry when fresh ones should be issued.
+    rotate_before: Duration,
+}
+
+impl SecretService {
+    /// Creates new [`SecretService`] with the provided settings, generating
+    /// credentials for the provided [`IceServers`].
+    #[must_use]
+    pub fn new(cf: &conf::Turn, servers: Arc<IceServers>) -> Self {
+        Self {
+            servers,
+            secret: cf.secret.key.to_string(),
+            ttl: cf.secret.ttl,
+            rotate_before: cf.rotate_before,
+        }
+    }
+}
+
+#[async_trait]
+impl TurnAuthService for SecretService {
+    /// Generates [`IceUser`] with credentials expiring after the configured
+    /// TTL.
+    ///
+    /// Never fails, so the provided [`UnreachablePolicy`] is ignored.
+    async fn create(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        region: Option<String>,
+        _: UnreachablePolicy,
+    ) -> Result<IceUser, TurnServiceErr> {
+        Ok(IceUser::new_ephemeral(
+            self.servers.select(region.as_deref()),
+            &room_id,
+            peer_id,
+            Expiry {
+                expires_at: SystemTime::now() + self.ttl,
+                rotate_before: self.rotate_before,
+            },
+            &self.secret,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::UNIX_EPOCH;
+
+    use crate::turn::ice_user::IcePassword;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn creates_expiring_users() {
+        let mut cf = conf::Turn::default();
+        cf.secret.key = "secret".into();
+        cf.secret.ttl = Duration::from_secs(600);
+        let service = SecretService::new(&cf, Arc::new(IceServers::new(&cf)));
+
+        let user = service
+            .create(
+                RoomId::from("room"),
+                PeerId(1),
+                None,
+                UnreachablePolicy::ReturnErr,
+            )
+            .await
+            .unwrap();
+
+        let username = user.user().to_string();
+        let (expiry, name) = username.split_at(username.find(':').unwrap());
+        assert_eq!(name, ":room_1");
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let expiry: u64 = expiry.parse().unwrap();
+        assert!(expiry > now + 590 && expiry <= now + 600);
+        assert_eq!(
+            user.pass().to_string(),
+            IcePassword::sign("secret", user.user()).to_string(),
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Pool of STUN/TURN servers provided to clients.
+
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::future;
+use tokio::{
+    net::{self, UdpSocket},
+    time,
+};
+
+use crate::{
+    conf::{
+        self,
+        turn::{Selection, Server},
+    },
+    log::prelude as log,
+};
+
+/// Type of STUN Binding request message.
+const BINDING_REQUEST: u16 = 0x0001;
+
+/// Type of STUN Binding success response message.
+const BINDING_SUCCESS: u16 = 0x0101;
+
+/// Type of STUN Binding error response message.
+const BINDING_ERROR: u16 = 0x0111;
+
+/// Magic cookie which every STUN message contains, as defined in
+/// [RFC 5389, Section 6](https://tools.ietf.org/html/rfc5389#section-6).
+const MAGIC_COOKIE: u32 = 0x2112_A442;
+
+/// [`Server`] along with its availability.
+#[derive(Debug)]
+struct Entry {
+    /// Settings of this [`Server`].
+    server: Server,
+
+    /// Indicator whether this [`Server`] passed its last health check.
+    alive: AtomicBool,
+}
+
+/// Configured STUN/TURN [`Server`]s, which are selected for `Peer`s according
+/// to the configured [`Selection`] strategy.
+///
+/// [`Server`]s failing health checks are skipped, unless all of the candidate
+/// [`Server`]s are dead.
+#[derive(Debug)]
+pub struct IceServers {
+    /// All the configured [`Server`]s.
+    entries: Vec<Entry>,
+
+    /// Strategy of selecting [`Server`]s.
+    selection: Selection,
+
+    /// Index of the [`Server`] to be selected next in
+    /// [`Selection::RoundRobin`] strategy.
+    next: AtomicUsize,
+}
+
+impl IceServers {
+    /// Creates new [`IceServers`] from the provided settings, considering all
+    /// the [`Server`]s alive.
+    #[must_use]
+    pub fn new(cf: &conf::Turn) -> Self {
+        Self {
+            entries: cf
+                .servers()
+                .into_iter()
+                .map(|server| Entry {
+                    server,
+                    alive: AtomicBool::new(true),
+                })
+                .collect(),
+            selection: cf.selection,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Selects [`Server`]s for a `Peer` of a `Member` from the provided
+    /// `region`.
+    #[must_use]
+    pub fn select(&self, region: Option<&str>) -> Vec<Server> {
+        let alive: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.alive.load(Ordering::Relaxed))
+            .collect();
+        let candidates = if alive.is_empty() {
+            self.entries.iter().collect()
+        } else {
+            alive
+        };
+
+        let selected = match self.selection {
+            Selection::All => candidates,
+            Selection::RoundRobin => {
+                let i = self.next.fetch_add(1, Ordering::Relaxed);
+                vec![candidates[i % candidates.len()]]
+            }
+            Selection::Region => {
+                let in_region = |region: Option<&str>| -> Vec<&Entry> {
+                    candidates
+                        .iter()
+                        .filter(|e| e.server.region.as_deref() == region)
+                        .copied()
+                        .collect()
+                };
+                Some(in_region(region))
+                    .filter(|s| region.is_some() && !s.is_empty())
+                    .or_else(|| Some(in_region(None)).filter(|s| !s.is_empty()))
+                    .unwrap_or(candidates)
+            }
+        };
+        selected.into_iter().map(|e| e.server.clone()).collect()
+    }
+
+    /// Checks availability of all the [`Server`]s by sending them a STUN
+    /// Binding request via UDP and awaiting a response for the provided
+    /// `timeout`.
+    pub async fn check_health(&self, timeout: Duration) {
+        future::join_all(self.entries.iter().map(|entry| async move {
+            let binding = stun_binding(&entry.server.host, entry.server.port);
+            let alive =
+                matches!(time::timeout(timeout, binding).await, Ok(Ok(())));
+            if entry.alive.swap(alive, Ordering::Relaxed) != alive {
+                if alive {
+                    log::info!(
+                        "STUN/TURN server {}:{} is alive again",
+                        entry.server.host,
+                        entry.server.port,
+                    );
+                } else {
+                    log::warn!(
+                        "STUN/TURN server {}:{} is unreachable, skipping it",
+                        entry.server.host,
+                        entry.server.port,
+                    );
+                }
+            }
+        }))
+        .await;
+    }
+
+    /// Spawns a task checking availability of the [`Server`]s with the
+    /// provided settings, unless health checking is disabled.
+    ///
+    /// The task stops once these [`IceServers`] are dropped.
+    pub fn spawn_health_check(self: &Arc<Self>, cf: conf::turn::HealthCheck) {
+        if cf.interval == Duration::from_secs(0) {
+            return;
+        }
+        let this = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = time::interval(cf.interval);
+            loop {
+                interval.tick().await;
+                match this.upgrade() {
+                    Some(this) => this.check_health(cf.timeout).await,
+                    None => break,
+                }
+            }
+        });
+    }
+}
+
+/// Sends a STUN Binding request to the provided `host` and `port` via UDP,
+/// resolving once any STUN Binding response for it is received.
+///
+/// # Errors
+///
+/// Errors if the `host` cannot be resolved, or the request cannot be sent, or
+/// the response cannot be received.
+async fn stun_binding(host: &str, port: u16) -> io::Result<()> {
+    let addr =
+        net::lookup_host((host, port))
+            .await?
+            .next()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "host has no IP addresses",
+                )
+            })?;
+    let local: SocketAddr = if addr.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let mut socket = UdpSocket::bind(local).await?;
+    socket.connect(addr).await?;
+
+    let transaction_id: [u8; 12] = rand::random();
+    let mut request = [0; 20];
+    request[..2].copy_from_slice(&BINDING_REQUEST.to_be_bytes());
+    request[4..8].copy_from_slice(&MAGIC_COOKIE.to_be_bytes());
+    request[8..].copy_from_slice(&transaction_id);
+    socket.send(&request).await?;
+
+    let mut response = [0; 576];
+    loop {
+        let len = socket.recv(&mut response).await?;
+        if len < 20
+            || response[4..8] != MAGIC_COOKIE.to_be_bytes()
+            || response[8..20] != transaction_id
+        {
+            continue;
+        }
+        let kind = u16::from_be_bytes([response[0], response[1]]);
+        if kind == BINDING_SUCCESS || kind == BINDING_ERROR {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    /// Returns [`conf::Turn`] with the provided [`Server`]s and [`Selection`].
+    fn conf(
+        servers: &[(&'static str, Option<&str>)],
+        selection: Selection,
+    ) -> conf::Turn {
+        conf::Turn {
+            servers: servers
+                .iter()
+                .map(|(host, region)| Server {
+                    host: (*host).into(),
+                    region: region.map(ToOwned::to_owned),
+                    ..Server::default()
+                })
+                .collect(),
+            selection,
+            ..conf::Turn::default()
+        }
+    }
+
+    /// Returns hosts of the provided [`Server`]s.
+    fn hosts(servers: Vec<Server>) -> Vec<String> {
+        servers.into_iter().map(|s| s.host.to_string()).collect()
+    }
+
+    #[test]
+    fn selects_all_servers() {
+        let servers =
+            IceServers::new(&conf(&[("a", None), ("b", None)], Selection::All));
+
+        assert_eq!(hosts(servers.select(None)), ["a", "b"]);
+    }
+
+    #[test]
+    fn rotates_servers() {
+        let servers = IceServers::new(&conf(
+            &[("a", None), ("b", None)],
+            Selection::RoundRobin,
+        ));
+
+        assert_eq!(hosts(servers.select(None)), ["a"]);
+        assert_eq!(hosts(servers.select(None)), ["b"]);
+        assert_eq!(hosts(servers.select(None)), ["a"]);
+    }
+
+    #[test]
+    fn selects_servers_by_region() {
+        let servers = IceServers::new(&conf(
+            &[("eu", Some("eu")), ("us", Some("us")), ("any", None)],
+            Selection::Region,
+        ));
+
+        assert_eq!(hosts(servers.select(Some("eu"))), ["eu"]);
+        assert_eq!(hosts(servers.select(Some("asia"))), ["any"]);
+        assert_eq!(hosts(servers.select(None)), ["any"]);
+    }
+
+    #[test]
+    fn skips_dead_servers() {
+        let servers = IceServers::new(&conf(
+            &[("eu1", Some("eu")), ("eu2", Some("eu")), ("us", Some("us"))],
+            Selection::Region,
+        ));
+        servers.entries[0].alive.store(false, Ordering::Relaxed);
+
+        assert_eq!(hosts(servers.select(Some("eu"))), ["eu2"]);
+
+        servers.entries[1].alive.store(false, Ordering::Relaxed);
+
+        assert_eq!(hosts(servers.select(Some("eu"))), ["us"]);
+
+        servers.entries[2].alive.store(false, Ordering::Relaxed);
+
+        assert_eq!(hosts(servers.select(Some("eu"))), ["eu1", "eu2"]);
+    }
+
+    /// Spawns a STUN server on a random local UDP port, answering Binding
+    /// requests with Binding success responses, and returns its port.
+    fn spawn_stun_server() -> u16 {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let mut buf = [0; 576];
+            while let Ok((len, from)) = socket.recv_from(&mut buf) {
+                if len < 20 {
+                    continue;
+                }
+                buf[..2].copy_from_slice(&BINDING_SUCCESS.to_be_bytes());
+                socket.send_to(&buf[..20], from).unwrap();
+            }
+        });
+        port
+    }
+
+    #[actix_rt::test]
+    async fn marks_unreachable_servers_dead() {
+        let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut cf = conf(&[("127.0.0.1", None)], Selection::All);
+        cf.servers[0].port = spawn_stun_server();
+        cf.servers.push(Server {
+            host: "127.0.0.1".into(),
+            port: silent.local_addr().unwrap().port(),
+            ..Server::default()
+        });
+        let servers = IceServers::new(&cf);
+
+        servers.check_health(Duration::from_millis(500)).await;
+
+        assert!(servers.entries[0].alive.load(Ordering::Relaxed));
+        assert!(!servers.entries[1].alive.load(Ordering::Relaxed));
+        assert_eq!(servers.select(None).len(), 1);
+    }
+}
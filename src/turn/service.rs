@@ -3,7 +3,7 @@
 //! [Coturn]: https://github.com/coturn/coturn
 //! [TURN]: https://webrtcglossary.com/turn/
 
-use std::{fmt, slice, sync::Arc};
+use std::{fmt, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use derive_more::{Display, From};
@@ -14,15 +14,15 @@ use futures::{
     StreamExt as _,
 };
 use medea_client_api_proto::{PeerId, RoomId};
-use redis::ConnectionInfo;
 
 use crate::{
     conf,
     log::prelude as log,
     turn::{
-        cli::{CoturnCliError, CoturnTelnetClient},
-        ice_user::{IcePassword, IceUsername},
+        cli::{CoturnCliError, CoturnCliHealth, CoturnTelnetClient},
+        ice_user::{Expiry, IcePassword, IceUsername},
         repo::{TurnDatabase, TurnDatabaseErr},
+        IceServers, MemoryService, SecretService,
     },
     utils::MpscOneshotSender,
 };
@@ -58,13 +58,23 @@ pub enum UnreachablePolicy {
 /// Manages Turn server credentials.
 #[async_trait]
 pub trait TurnAuthService: fmt::Debug + Send + Sync {
-    /// Generates and registers Turn credentials.
+    /// Generates and registers Turn credentials for STUN/TURN servers selected
+    /// for the provided `region`.
     async fn create(
         &self,
         room_id: RoomId,
         peer_id: PeerId,
+        region: Option<String>,
         policy: UnreachablePolicy,
     ) -> Result<IceUser, TurnServiceErr>;
+
+    /// Returns [`CoturnCliHealth`] of connections with [Coturn] server admin
+    /// interface, if they're used by this [`TurnAuthService`].
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    fn cli_health(&self) -> Option<CoturnCliHealth> {
+        None
+    }
 }
 
 /// [`TurnAuthService`] implementation backed by Redis database.
@@ -81,8 +91,8 @@ struct Service {
     /// TurnAuthRepo password.
     db_pass: String,
 
-    /// Turn server address.
-    turn_address: String,
+    /// STUN/TURN servers provided to clients.
+    servers: Arc<IceServers>,
 
     /// Turn server static user.
     turn_username: String,
@@ -90,6 +100,14 @@ struct Service {
     /// Turn server static user password.
     turn_password: String,
 
+    /// Lifetime of the generated credentials.
+    ///
+    /// Zero means that they never expire.
+    ttl: Duration,
+
+    /// Time before credentials expiry when fresh ones should be issued.
+    rotate_before: Duration,
+
     /// Channel sender signalling about an [`IseUser`] no longer being used and
     /// that it should be removed from [Coturn].
     ///
@@ -106,7 +124,7 @@ impl Service {
     /// Returns [`IceUser`] with static credentials.
     fn static_user(&self) -> IceUser {
         IceUser::new_static(
-            self.turn_address.clone(),
+            self.servers.select(None),
             self.turn_username.clone(),
             self.turn_password.clone(),
         )
@@ -115,21 +133,22 @@ impl Service {
 
 #[async_trait]
 impl TurnAuthService for Service {
-    /// Generates [`IceUser`] with saved Turn address, provided [`MemberId`] and
-    /// random password. Inserts created [`IceUser`] into [`TurnDatabase`].
-    ///
-    /// [`MemberId`]: medea_client_api_proto::MemberId
+    /// Generates [`IceUser`] with selected STUN/TURN servers, provided
+    /// [`RoomId`], [`PeerId`] and random password. Inserts created [`IceUser`]
+    /// into [`TurnDatabase`].
     async fn create(
         &self,
         room_id: RoomId,
         peer_id: PeerId,
+        region: Option<String>,
         policy: UnreachablePolicy,
     ) -> Result<IceUser, TurnServiceErr> {
         let ice_user = IceUser::new_non_static(
-            self.turn_address.clone(),
+            self.servers.select(region.as_deref()),
             &room_id,
             peer_id,
             IcePassword::generate(),
+            Expiry::after(self.ttl, self.rotate_before),
             self.drop_tx.clone(),
         );
 
@@ -141,6 +160,12 @@ impl TurnAuthService for Service {
             },
         }
     }
+
+    /// Returns [`CoturnCliHealth`] of [`Service::coturn_cli`].
+    #[inline]
+    fn cli_health(&self) -> Option<CoturnCliHealth> {
+        Some(self.coturn_cli.status().into())
+    }
 }
 
 impl Drop for Service {
@@ -150,7 +175,8 @@ impl Drop for Service {
     }
 }
 
-/// Create new instance [`TurnAuthService`].
+/// Create new instance [`TurnAuthService`] according to the configured
+/// [`conf::turn::AuthMode`] and [`conf::turn::DbKind`].
 ///
 /// # Errors
 ///
@@ -159,15 +185,27 @@ impl Drop for Service {
 pub fn new_turn_auth_service<'a>(
     cf: &conf::Turn,
 ) -> Result<Arc<dyn TurnAuthService + 'a>, TurnServiceErr> {
-    let turn_db = TurnDatabase::new(
-        cf.db.redis.connect_timeout,
-        ConnectionInfo::from(&cf.db.redis),
-    )?;
+    let servers = Arc::new(IceServers::new(cf));
+    servers.spawn_health_check(cf.health_check);
+
+    if cf.auth == conf::turn::AuthMode::Secret {
+        return Ok(Arc::new(SecretService::new(cf, servers)));
+    }
+    if cf.db.kind == conf::turn::DbKind::Memory {
+        return Ok(Arc::new(MemoryService::new(
+            servers,
+            cf.ttl,
+            cf.rotate_before,
+        )));
+    }
+
+    let turn_db = TurnDatabase::new(&cf.db.redis)?;
 
     let coturn_cli = CoturnTelnetClient::new(
         (cf.cli.host.clone(), cf.cli.port),
         cf.cli.pass.to_string(),
         cf.cli.pool.into(),
+        cf.cli.backoff.into(),
     );
 
     let (tx, mut rx) = mpsc::unbounded();
@@ -177,19 +215,30 @@ pub fn new_turn_auth_service<'a>(
         let cli = coturn_cli.clone();
         let (fut, handle) = future::abortable(async move {
             while let Some(user) = rx.next().await {
-                let users = slice::from_ref(&user);
-                if let Err(e) = db.remove(users).await {
+                // Batch all the users dropped at once (on `Room` closing, for
+                // example), so they are removed with a single connection.
+                let mut users = vec![user];
+                while let Ok(Some(user)) = rx.try_next() {
+                    users.push(user);
+                }
+
+                if let Err(e) = db.remove(&users).await {
                     log::warn!(
-                        "Failed to remove IceUser(name: {}) from Redis: {}",
-                        user,
+                        "Failed to remove {} IceUsers from Redis: {}",
+                        users.len(),
                         e,
                     );
                 }
-                if let Err(e) = cli.delete_sessions(users).await {
+                let deletion = cli.delete_sessions(&users).await;
+                if let Some((user, e)) = deletion.failed.first() {
                     log::warn!(
-                        "Failed to remove IceUser(name: {}) from Coturn: {}",
+                        "Failed to remove {} of {} IceUsers from Coturn \
+                         (IceUser(name: {}): {}), pool status: {:?}",
+                        deletion.failed.len(),
+                        users.len(),
                         user,
                         e,
+                        cli.status(),
                     );
                 }
             }
@@ -202,9 +251,11 @@ pub fn new_turn_auth_service<'a>(
         turn_db,
         coturn_cli,
         db_pass: cf.db.redis.pass.to_string(),
-        turn_address: cf.addr(),
+        servers,
         turn_username: cf.user.to_string(),
         turn_password: cf.pass.to_string(),
+        ttl: cf.ttl,
+        rotate_before: cf.rotate_before,
         drop_tx: MpscOneshotSender::from(tx),
         users_cleanup_task,
     };
@@ -229,10 +280,15 @@ pub mod test {
             &self,
             _: RoomId,
             _: PeerId,
+            _: Option<String>,
             _: UnreachablePolicy,
         ) -> Result<IceUser, TurnServiceErr> {
             Ok(IceUser::new_static(
-                "5.5.5.5:1234".parse().unwrap(),
+                vec![conf::turn::Server {
+                    host: "5.5.5.5".into(),
+                    port: 1234,
+                    ..conf::turn::Server::default()
+                }],
                 "username".into(),
                 "password".into(),
             ))
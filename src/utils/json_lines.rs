@@ -0,0 +1,266 @@
+//! Sink of records serialized as JSON lines.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+
+/// Sink appending records to a [`Destination`] as JSON lines.
+///
+/// Does nothing if no [`Destination`] is configured.
+#[derive(Clone, Debug, Default)]
+pub struct JsonLinesSink {
+    /// [`Destination`] of the written records.
+    dest: Option<Arc<Mutex<Destination>>>,
+
+    /// Indicator whether each written line is compressed as a separate gzip
+    /// member.
+    gzip: bool,
+}
+
+impl JsonLinesSink {
+    /// Creates new [`JsonLinesSink`] writing records to the stdout.
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::with(Destination::Stdout)
+    }
+
+    /// Opens the file at the provided `path` for appending, if any, and
+    /// creates new [`JsonLinesSink`] writing records to it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the file cannot be opened or created.
+    pub fn file<P: Into<PathBuf>>(path: Option<P>) -> io::Result<Self> {
+        path.map_or_else(
+            || Ok(Self::default()),
+            |path| Self::rotating_file(path.into(), 0, 0),
+        )
+    }
+
+    /// Opens the file at the provided `path` for appending, and creates new
+    /// [`JsonLinesSink`] writing records to it.
+    ///
+    /// The file is rotated once it exceeds the provided `rotation_size`,
+    /// keeping the provided number of rotated files. `0` `rotation_size`
+    /// disables rotation.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the file cannot be opened or created.
+    pub fn rotating_file(
+        path: PathBuf,
+        rotation_size: u64,
+        keep: usize,
+    ) -> io::Result<Self> {
+        RotatingFile::open(path, rotation_size, keep)
+            .map(|file| Self::with(Destination::File(file)))
+    }
+
+    /// Makes this [`JsonLinesSink`] compress each written line as a separate
+    /// gzip member, so the written data remains a valid gzip stream.
+    #[inline]
+    #[must_use]
+    pub fn gzipped(mut self) -> Self {
+        self.gzip = true;
+        self
+    }
+
+    /// Indicates whether this [`JsonLinesSink`] writes records anywhere.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.dest.is_some()
+    }
+
+    /// Appends the provided `record` as a single JSON line.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the `record` cannot be serialized or written.
+    pub fn write<T: Serialize>(&self, record: &T) -> io::Result<()> {
+        let dest = if let Some(dest) = &self.dest {
+            dest
+        } else {
+            return Ok(());
+        };
+
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        if self.gzip {
+            let mut encoder =
+                GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&line)?;
+            line = encoder.finish()?;
+        }
+        match &mut *dest.lock().unwrap() {
+            Destination::Stdout => io::stdout().write_all(&line),
+            Destination::File(file) => file.write(&line),
+        }
+    }
+
+    /// Creates new [`JsonLinesSink`] writing records to the provided
+    /// [`Destination`].
+    fn with(dest: Destination) -> Self {
+        Self {
+            dest: Some(Arc::new(Mutex::new(dest))),
+            gzip: false,
+        }
+    }
+}
+
+/// Destination of records written by [`JsonLinesSink`].
+#[derive(Debug)]
+enum Destination {
+    /// Records are written to the stdout.
+    Stdout,
+
+    /// Records are appended to the [`RotatingFile`].
+    File(RotatingFile),
+}
+
+/// File rotated once it exceeds the configured size.
+#[derive(Debug)]
+struct RotatingFile {
+    /// Path of the file.
+    path: PathBuf,
+
+    /// Currently opened file.
+    file: File,
+
+    /// Current size of the file.
+    size: u64,
+
+    /// Size exceeding which the file is rotated. `0` disables rotation.
+    rotation_size: u64,
+
+    /// Number of rotated files to keep.
+    keep: usize,
+}
+
+impl RotatingFile {
+    /// Opens the file at the provided `path` for appending.
+    fn open(
+        path: PathBuf,
+        rotation_size: u64,
+        keep: usize,
+    ) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            rotation_size,
+            keep,
+        })
+    }
+
+    /// Appends the provided `data` to the file, rotating it beforehand if it
+    /// would exceed [`RotatingFile::rotation_size`].
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        if self.rotation_size > 0
+            && self.size > 0
+            && self.size + data.len() as u64 > self.rotation_size
+        {
+            self.rotate()?;
+        }
+        self.file.write_all(data)?;
+        self.size += data.len() as u64;
+        Ok(())
+    }
+
+    /// Shifts the rotated files, moves the current file to `<path>.1` and
+    /// opens a new empty one.
+    ///
+    /// The oldest rotated file is removed if there are more than
+    /// [`RotatingFile::keep`] ones.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.keep == 0 {
+            remove_if_exists(&self.path)?;
+        } else {
+            remove_if_exists(&self.rotated(self.keep))?;
+            for i in (1..self.keep).rev() {
+                let from = self.rotated(i);
+                if from.exists() {
+                    fs::rename(from, self.rotated(i + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Returns path of the `n`th rotated file.
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        path.into()
+    }
+}
+
+/// Removes the file at the provided `path`, if it exists.
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::io::Read as _;
+
+    use flate2::read::MultiGzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.jsonl");
+        let sink = JsonLinesSink::file(Some(&path)).unwrap();
+
+        sink.write(&"first").unwrap();
+        sink.write(&"second").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "\"first\"\n\"second\"\n",
+        );
+    }
+
+    #[test]
+    fn appends_gzipped_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.jsonl.gz");
+        let sink = JsonLinesSink::file(Some(&path)).unwrap().gzipped();
+
+        sink.write(&1).unwrap();
+        sink.write(&2).unwrap();
+
+        let mut lines = String::new();
+        MultiGzDecoder::new(File::open(&path).unwrap())
+            .read_to_string(&mut lines)
+            .unwrap();
+        assert_eq!(lines, "1\n2\n");
+    }
+
+    #[test]
+    fn does_nothing_without_destination() {
+        let sink = JsonLinesSink::file(None::<PathBuf>).unwrap();
+
+        assert!(!sink.is_enabled());
+        sink.write(&"ignored").unwrap();
+    }
+}
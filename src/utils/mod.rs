@@ -1,6 +1,7 @@
 //! Helper utils used in project.
 
 mod actix_try_join_all;
+mod json_lines;
 
 use std::time::Instant;
 
@@ -9,7 +10,9 @@ use derive_more::From;
 use futures::channel::mpsc::{TrySendError, UnboundedSender};
 use rand::{distributions::Alphanumeric, Rng};
 
-pub use self::actix_try_join_all::actix_try_join_all;
+pub use self::{
+    actix_try_join_all::actix_try_join_all, json_lines::JsonLinesSink,
+};
 
 /// Creates new [`HashMap`] from a list of key-value pairs.
 ///
@@ -154,6 +154,22 @@ pub struct Room {
 
     #[builder(default = "HashMap::new()")]
     members: HashMap<String, Member>,
+
+    #[builder(default = "None")]
+    empty_timeout: Option<Duration>,
+
+    #[builder(default = "None")]
+    max_duration: Option<Duration>,
+
+    #[builder(default = "None")]
+    #[builder(setter(strip_option))]
+    on_close: Option<String>,
+
+    #[builder(default = "0")]
+    max_members: u32,
+
+    #[builder(default = "0")]
+    max_publishers: u32,
 }
 
 impl Room {
@@ -175,6 +191,14 @@ impl Room {
         let grpc_room = proto::Room {
             id: self.id,
             pipeline: members,
+            empty_timeout: self.empty_timeout.map(Into::into),
+            max_duration: self.max_duration.map(Into::into),
+            on_close: self.on_close.unwrap_or_default(),
+            max_members: self.max_members,
+            max_publishers: self.max_publishers,
+            rejected_ice_candidates: HashMap::new(),
+            negotiation_timeouts: HashMap::new(),
+            recreated_peers: 0,
         };
 
         proto::CreateRequest {
@@ -217,6 +241,8 @@ pub struct Member {
     idle_timeout: Option<Duration>,
     #[builder(default = "None")]
     reconnect_timeout: Option<Duration>,
+    #[builder(default = "false")]
+    remove_on_leave: bool,
 }
 
 impl Into<proto::Member> for Member {
@@ -232,10 +258,19 @@ impl Into<proto::Member> for Member {
             pipeline,
             on_leave: self.on_leave.unwrap_or_default(),
             on_join: self.on_join.unwrap_or_default(),
+            on_metrics: String::new(),
             credentials: self.credentials,
             ping_interval: self.ping_interval.map(Into::into),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
+            remove_on_leave: self.remove_on_leave,
+            connected: false,
+            region: String::new(),
+            external: false,
+            relay_usage: None,
+            peers_relay_usage: HashMap::new(),
+            relay_fallback_peers: Vec::new(),
+            peers_stats: HashMap::new(),
         }
     }
 }
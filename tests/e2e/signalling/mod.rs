@@ -443,9 +443,13 @@ impl StreamHandler<Result<Frame, WsProtocolError>> for TestMember {
                             | Event::SdpAnswerMade { peer_id, .. }
                             | Event::IceCandidateDiscovered {
                                 peer_id, ..
-                            } => assert!(self.known_peers.contains(peer_id)),
+                            }
+                            | Event::NetworkCongestion { peer_id, .. } => {
+                                assert!(self.known_peers.contains(peer_id))
+                            }
                             Event::PeersRemoved { .. }
                             | Event::ConnectionQualityUpdated { .. }
+                            | Event::IceServersUpdated { .. }
                             | Event::RoomJoined { .. }
                             | Event::StateSynchronized { .. }
                             | Event::RoomLeft { .. } => (),